@@ -0,0 +1,105 @@
+use cached::proc_macro::cached;
+use moon_error::MoonError;
+use moon_lang::LockfileDependencyVersions;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use rustc_hash::FxHashMap;
+use serde_json::Value;
+use starbase_utils::fs;
+use std::path::PathBuf;
+use yarn_lock_parser::{parse_str, Entry};
+
+static TRAILING_COMMA: Lazy<Regex> = Lazy::new(|| Regex::new(r",(\s*[\]}])").unwrap());
+
+/// Split a `name@version` specifier, taking scoped package names into account.
+fn split_package_specifier(specifier: &str) -> Option<(&str, &str)> {
+    let index = specifier.rfind('@').filter(|index| *index > 0)?;
+
+    Some((&specifier[0..index], &specifier[index + 1..]))
+}
+
+/// Parse the contents of a text-based `bun.lock` file into a map of package names
+/// to their integrity hashes (or versions when not available). The lockfile is JSON
+/// with trailing commas, where each package is an array of `[specifier, registry, info, integrity]`.
+pub fn parse_bun_lock(content: &str) -> Result<LockfileDependencyVersions, MoonError> {
+    let mut deps: LockfileDependencyVersions = FxHashMap::default();
+
+    let json: Value = serde_json::from_str(&TRAILING_COMMA.replace_all(content, "$1"))
+        .map_err(|e| MoonError::Generic(format!("Failed to parse lockfile: {e}")))?;
+
+    let Some(packages) = json.get("packages").and_then(|packages| packages.as_object()) else {
+        return Ok(deps);
+    };
+
+    for package in packages.values() {
+        let Some(specifier) = package.get(0).and_then(|value| value.as_str()) else {
+            continue;
+        };
+
+        let Some((name, version)) = split_package_specifier(specifier) else {
+            continue;
+        };
+
+        // Workspace and local packages are not resolved from a registry, so we will skip them
+        if version.starts_with("workspace:")
+            || version.starts_with("link:")
+            || version.starts_with("file:")
+        {
+            continue;
+        }
+
+        let integrity = package
+            .get(3)
+            .and_then(|value| value.as_str())
+            .filter(|value| !value.is_empty());
+
+        deps.entry(name.to_owned())
+            .or_default()
+            .push(integrity.unwrap_or(version).to_owned());
+    }
+
+    Ok(deps)
+}
+
+/// Parse the Yarn v1 compatible output of `bun bun.lockb`, which is the only
+/// way to read the contents of the binary lockfile.
+pub fn parse_bun_lockb_output(content: &str) -> Result<LockfileDependencyVersions, MoonError> {
+    let mut deps: LockfileDependencyVersions = FxHashMap::default();
+
+    // Bun includes an additional hash comment in the header, which the
+    // parser does not expect, so replace the header with the standard one
+    let content = format!(
+        "# yarn lockfile v1\n\n\n{}",
+        content
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim_start()
+    );
+
+    let entries: Vec<Entry> = parse_str(&content)
+        .map_err(|e| MoonError::Generic(format!("Failed to parse lockfile: {e}")))?;
+
+    for entry in entries {
+        // All workspace dependencies have empty integrities, so we will skip them
+        if entry.integrity.is_empty() {
+            continue;
+        }
+
+        deps.entry(entry.name.to_owned())
+            .or_default()
+            .push(entry.integrity.to_owned());
+    }
+
+    Ok(deps)
+}
+
+#[cached(result)]
+pub fn load_lockfile_dependencies(path: PathBuf) -> Result<LockfileDependencyVersions, MoonError> {
+    if !path.exists() {
+        return Ok(FxHashMap::default());
+    }
+
+    parse_bun_lock(&fs::read_file(path)?)
+}
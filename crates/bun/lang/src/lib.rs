@@ -1,5 +1,9 @@
+pub mod bun_lock;
+
 use moon_lang::{DependencyManager, Language};
 
+pub use moon_lang::LockfileDependencyVersions;
+
 pub const BUN: Language = Language {
     binary: "bun",
     file_exts: &["js", "jsx", "cjs", "mjs", "ts", "tsx", "cts", "mts"],
@@ -15,3 +19,6 @@ pub const BUN_INSTALL: DependencyManager = DependencyManager {
     lockfile: "bun.lockb",
     manifest: "package.json",
 };
+
+/// The text-based lockfile that replaces the binary `bun.lockb` in newer Bun versions.
+pub const BUN_TEXT_LOCKFILE: &str = "bun.lock";
@@ -0,0 +1,80 @@
+use moon_bun_lang::bun_lock::*;
+use moon_test_utils::create_sandbox;
+
+#[test]
+fn loads_packages_from_text_lockfile() {
+    let sandbox = create_sandbox("bun");
+    let deps = load_lockfile_dependencies(sandbox.path().join("bun.lock")).unwrap();
+
+    assert_eq!(deps.len(), 5);
+    assert_eq!(
+        deps.get("is-odd").unwrap(),
+        &vec!["sha512-CQpnWPrDwmP1+SMHXZhtLtJv90yiyVfluGsX5iNCVkrhQtU3TQHsUWPG9wkdk9Lgd5yNpAg9jQEo90CBaXgWMA==".to_owned()]
+    );
+}
+
+#[test]
+fn supports_scoped_packages() {
+    let sandbox = create_sandbox("bun");
+    let deps = load_lockfile_dependencies(sandbox.path().join("bun.lock")).unwrap();
+
+    assert!(deps.contains_key("@types/bun"));
+}
+
+#[test]
+fn skips_workspace_packages() {
+    let sandbox = create_sandbox("bun");
+    let deps = load_lockfile_dependencies(sandbox.path().join("bun.lock")).unwrap();
+
+    assert!(!deps.contains_key("@example/app"));
+    assert!(!deps.contains_key("@example/utils"));
+}
+
+#[test]
+fn falls_back_to_version_without_integrity() {
+    let deps = parse_bun_lock(
+        r#"{ "lockfileVersion": 0, "packages": { "local": ["local@1.2.3", "", {},], }, }"#,
+    )
+    .unwrap();
+
+    assert_eq!(deps.get("local").unwrap(), &vec!["1.2.3".to_owned()]);
+}
+
+#[test]
+fn returns_empty_if_missing() {
+    let sandbox = create_sandbox("bun");
+    let deps = load_lockfile_dependencies(sandbox.path().join("missing/bun.lock")).unwrap();
+
+    assert!(deps.is_empty());
+}
+
+#[test]
+fn parses_binary_lockfile_output() {
+    let deps = parse_bun_lockb_output(
+        r#"# THIS IS AN AUTOGENERATED FILE. DO NOT EDIT THIS FILE DIRECTLY.
+# yarn lockfile v1
+# bun ./bun.lockb --hash: 3A9C1B8D7F0E2A4C-1d1f2c3e4b5a6978-8E7D6C5B4A392817-0F1E2D3C4B5A6978
+
+
+"@example/utils@workspace:packages/utils":
+  version "0.0.0"
+
+is-number@^6.0.0:
+  version "6.0.0"
+  resolved "https://registry.npmjs.org/is-number/-/is-number-6.0.0.tgz"
+  integrity sha512-Wu1VHeILBK8KAWJUAiSZQX94GmOE45Rg6/538fKwiloUu21KncEkYGPqob2oSZ5mUT73vLGrHQjKw3KMPwfDzg==
+
+is-odd@^3.0.1:
+  version "3.0.1"
+  resolved "https://registry.npmjs.org/is-odd/-/is-odd-3.0.1.tgz"
+  integrity sha512-CQpnWPrDwmP1+SMHXZhtLtJv90yiyVfluGsX5iNCVkrhQtU3TQHsUWPG9wkdk9Lgd5yNpAg9jQEo90CBaXgWMA==
+  dependencies:
+    is-number "^6.0.0"
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(deps.len(), 2);
+    assert!(deps.contains_key("is-number"));
+    assert!(!deps.contains_key("@example/utils"));
+}
@@ -0,0 +1,5 @@
+mod platform;
+mod target_hasher;
+
+pub use platform::BunPlatform;
+pub use target_hasher::BunTargetHasher;
@@ -0,0 +1,473 @@
+use crate::target_hasher::BunTargetHasher;
+use moon_action_context::ActionContext;
+use moon_bun_lang::{BUN, BUN_INSTALL, BUN_TEXT_LOCKFILE};
+use moon_bun_tool::BunTool;
+use moon_common::Id;
+use moon_config::{
+    BunConfig, Config, DependencyConfig, DependencyScope, DependencySource, HasherConfig,
+    HasherOptimization, PlatformType, ProjectConfig, ProjectsAliasesMap, ProjectsSourcesMap,
+    TaskConfig, TasksConfigsMap, TypeScriptConfig,
+};
+use moon_error::MoonError;
+use moon_hasher::{DepsHasher, HashSet};
+use moon_lang::has_vendor_installed_dependencies;
+use moon_logger::{debug, warn};
+use moon_node_lang::node::{find_package_bin, get_package_manager_workspaces, BinFile};
+use moon_node_lang::PackageJson;
+use moon_node_platform::infer_tasks_from_scripts;
+use moon_platform::{Platform, Runtime, Version};
+use moon_process::Command;
+use moon_project::Project;
+use moon_task::Task;
+use moon_terminal::{print_checkpoint, Checkpoint};
+use moon_tool::{get_path_env_var, DependencyManager, Tool, ToolError, ToolManager};
+use moon_typescript_platform::TypeScriptTargetHasher;
+use moon_utils::{async_trait, is_ci, is_test_env};
+use proto::{Installable, Proto};
+use rustc_hash::FxHashMap;
+use starbase_styles::color;
+use starbase_utils::glob::GlobSet;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+const LOG_TARGET: &str = "moon:bun-platform";
+
+#[derive(Debug)]
+pub struct BunPlatform {
+    pub config: BunConfig,
+
+    package_names: FxHashMap<String, Id>,
+
+    toolchain: ToolManager<BunTool>,
+
+    typescript_config: Option<TypeScriptConfig>,
+
+    workspace_root: PathBuf,
+}
+
+impl BunPlatform {
+    pub fn new(
+        config: &BunConfig,
+        typescript_config: &Option<TypeScriptConfig>,
+        workspace_root: &Path,
+    ) -> Self {
+        BunPlatform {
+            config: config.to_owned(),
+            package_names: FxHashMap::default(),
+            toolchain: ToolManager::new(Runtime::Bun(Version::new_global())),
+            typescript_config: typescript_config.to_owned(),
+            workspace_root: workspace_root.to_path_buf(),
+        }
+    }
+
+    /// Return the lockfile name in use. Newer Bun versions write a text-based
+    /// `bun.lock`, while older versions write the binary `bun.lockb`.
+    pub fn get_lock_filename(&self) -> &'static str {
+        if self.workspace_root.join(BUN_TEXT_LOCKFILE).exists() {
+            BUN_TEXT_LOCKFILE
+        } else {
+            BUN_INSTALL.lockfile
+        }
+    }
+}
+
+#[async_trait]
+impl Platform for BunPlatform {
+    fn get_type(&self) -> PlatformType {
+        PlatformType::Bun
+    }
+
+    fn get_runtime_from_config(&self, project_config: Option<&ProjectConfig>) -> Runtime {
+        if let Some(config) = &project_config {
+            if let Some(bun_config) = &config.toolchain.bun {
+                if let Some(version) = &bun_config.version {
+                    return Runtime::Bun(Version::new_override(version));
+                }
+            }
+        }
+
+        if let Some(version) = &self.config.version {
+            return Runtime::Bun(Version::new(version));
+        }
+
+        // Global
+        Runtime::Bun(Version::new_global())
+    }
+
+    fn matches(&self, platform: &PlatformType, runtime: Option<&Runtime>) -> bool {
+        if matches!(platform, PlatformType::Bun) {
+            return true;
+        }
+
+        if let Some(runtime) = &runtime {
+            return matches!(runtime, Runtime::Bun(_));
+        }
+
+        false
+    }
+
+    // PROJECT GRAPH
+
+    fn is_project_in_dependency_workspace(&self, project: &Project) -> Result<bool, MoonError> {
+        let mut in_workspace = false;
+
+        // Root package is always considered within the workspace
+        if project.root == self.workspace_root {
+            return Ok(true);
+        }
+
+        if let Some(globs) = get_package_manager_workspaces(self.workspace_root.to_owned())? {
+            in_workspace = GlobSet::new(&globs)?.matches(&project.source);
+        }
+
+        Ok(in_workspace)
+    }
+
+    fn load_project_graph_aliases(
+        &mut self,
+        projects_map: &ProjectsSourcesMap,
+        aliases_map: &mut ProjectsAliasesMap,
+    ) -> Result<(), MoonError> {
+        debug!(
+            target: LOG_TARGET,
+            "Loading names (aliases) from project {}'s",
+            color::file(BUN_INSTALL.manifest)
+        );
+
+        for (project_id, project_source) in projects_map {
+            let Some(package_json) = PackageJson::read(self.workspace_root.join(project_source))? else {
+                continue;
+            };
+
+            let Some(alias) = package_json.name else {
+                continue;
+            };
+
+            self.package_names
+                .insert(alias.clone(), project_id.to_owned());
+
+            if let Some(existing_source) = projects_map.get(&alias) {
+                if existing_source != project_source {
+                    warn!(
+                        target: LOG_TARGET,
+                        "A project already exists with the ID {} ({}), skipping alias of the same name ({})",
+                        color::id(&alias),
+                        color::file(existing_source),
+                        color::file(project_source)
+                    );
+
+                    continue;
+                }
+            }
+
+            if let Some(existing_id) = aliases_map.get(&alias) {
+                warn!(
+                    target: LOG_TARGET,
+                    "A project already exists with the alias {} (for ID {}), skipping conflicting alias (from {})",
+                    color::id(alias),
+                    color::id(existing_id),
+                    color::file(project_source)
+                );
+
+                continue;
+            }
+
+            aliases_map.insert(alias, project_id.to_owned());
+        }
+
+        Ok(())
+    }
+
+    fn load_project_implicit_dependencies(
+        &self,
+        project: &Project,
+        _aliases_map: &ProjectsAliasesMap,
+    ) -> Result<Vec<DependencyConfig>, MoonError> {
+        let mut implicit_deps = vec![];
+
+        debug!(
+            target: LOG_TARGET,
+            "Scanning {} for implicit dependency relations",
+            color::id(&project.id),
+        );
+
+        let Some(package_json) = PackageJson::read(&project.root)? else {
+            return Ok(implicit_deps);
+        };
+
+        for (deps, scope) in [
+            (&package_json.dependencies, DependencyScope::Production),
+            (&package_json.dev_dependencies, DependencyScope::Development),
+            (&package_json.peer_dependencies, DependencyScope::Peer),
+        ] {
+            let Some(deps) = deps else {
+                continue;
+            };
+
+            for dep_name in deps.keys() {
+                if let Some(dep_project_id) = self.package_names.get(dep_name) {
+                    implicit_deps.push(DependencyConfig {
+                        id: dep_project_id.to_owned(),
+                        scope,
+                        source: DependencySource::Implicit,
+                        via: Some(dep_name.clone()),
+                    });
+                }
+            }
+        }
+
+        Ok(implicit_deps)
+    }
+
+    fn load_project_tasks(&self, project: &Project) -> Result<TasksConfigsMap, MoonError> {
+        let mut tasks = BTreeMap::new();
+
+        if !self.config.infer_tasks_from_scripts {
+            return Ok(tasks);
+        }
+
+        debug!(
+            target: LOG_TARGET,
+            "Inferring {} tasks from {}",
+            color::id(&project.id),
+            color::file(BUN_INSTALL.manifest)
+        );
+
+        if let Some(package_json) = PackageJson::read(&project.root)? {
+            for (id, partial_task) in
+                infer_tasks_from_scripts(&project.id, &package_json, PlatformType::Bun)
+                    .map_err(|e| MoonError::Generic(e.to_string()))?
+            {
+                tasks.insert(id, TaskConfig::from_partial(partial_task));
+            }
+        }
+
+        Ok(tasks)
+    }
+
+    // TOOLCHAIN
+
+    fn is_toolchain_enabled(&self) -> Result<bool, ToolError> {
+        Ok(self.config.version.is_some())
+    }
+
+    fn get_tool(&self) -> Result<Box<&dyn Tool>, ToolError> {
+        let tool = self.toolchain.get()?;
+
+        Ok(Box::new(tool))
+    }
+
+    fn get_tool_for_version(&self, version: Version) -> Result<Box<&dyn Tool>, ToolError> {
+        let tool = self.toolchain.get_for_version(&version)?;
+
+        Ok(Box::new(tool))
+    }
+
+    fn get_dependency_configs(&self) -> Result<Option<(String, String)>, ToolError> {
+        Ok(Some((
+            self.get_lock_filename().to_owned(),
+            BUN_INSTALL.manifest.to_owned(),
+        )))
+    }
+
+    async fn setup_toolchain(&mut self) -> Result<(), ToolError> {
+        let version = match &self.config.version {
+            Some(v) => Version::new(v),
+            None => Version::new_global(),
+        };
+
+        let mut last_versions = FxHashMap::default();
+
+        if !self.toolchain.has(&version) {
+            self.toolchain.register(
+                &version,
+                BunTool::new(&Proto::new()?, &self.config, &version)?,
+            );
+        }
+
+        self.toolchain.setup(&version, &mut last_versions).await?;
+
+        Ok(())
+    }
+
+    async fn teardown_toolchain(&mut self) -> Result<(), ToolError> {
+        self.toolchain.teardown_all().await?;
+
+        Ok(())
+    }
+
+    // ACTIONS
+
+    async fn setup_tool(
+        &mut self,
+        _context: &ActionContext,
+        runtime: &Runtime,
+        last_versions: &mut FxHashMap<String, String>,
+    ) -> Result<u8, ToolError> {
+        let version = runtime.version();
+
+        if !self.toolchain.has(&version) {
+            self.toolchain.register(
+                &version,
+                BunTool::new(&Proto::new()?, &self.config, &version)?,
+            );
+        }
+
+        Ok(self.toolchain.setup(&version, last_versions).await?)
+    }
+
+    async fn install_deps(
+        &self,
+        _context: &ActionContext,
+        runtime: &Runtime,
+        working_dir: &Path,
+    ) -> Result<(), ToolError> {
+        // When in CI, we can avoid installing dependencies because
+        // we can assume they've already been installed before moon runs!
+        if is_ci() && has_vendor_installed_dependencies(working_dir, &BUN) {
+            warn!(
+                target: LOG_TARGET,
+                "In a CI environment and dependencies already exist, skipping install"
+            );
+
+            return Ok(());
+        }
+
+        let bun = self.toolchain.get_for_version(runtime.version())?;
+
+        debug!(target: LOG_TARGET, "Installing dependencies");
+
+        print_checkpoint("bun install", Checkpoint::Setup);
+
+        bun.install_dependencies(bun, working_dir, !is_test_env())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn hash_manifest_deps(
+        &self,
+        manifest_path: &Path,
+        hashset: &mut HashSet,
+        _hasher_config: &HasherConfig,
+    ) -> Result<(), ToolError> {
+        if let Ok(Some(package)) = PackageJson::read(manifest_path) {
+            let name = package.name.unwrap_or_else(|| "unknown".into());
+            let mut hasher = DepsHasher::new(name);
+
+            if let Some(peer_deps) = &package.peer_dependencies {
+                hasher.hash_deps(peer_deps);
+            }
+
+            if let Some(dev_deps) = &package.dev_dependencies {
+                hasher.hash_deps(dev_deps);
+            }
+
+            if let Some(deps) = &package.dependencies {
+                hasher.hash_deps(deps);
+            }
+
+            hashset.hash(hasher);
+        }
+
+        Ok(())
+    }
+
+    async fn hash_run_target(
+        &self,
+        project: &Project,
+        runtime: &Runtime,
+        hashset: &mut HashSet,
+        hasher_config: &HasherConfig,
+    ) -> Result<(), ToolError> {
+        let bun = self.toolchain.get_for_version(runtime.version()).ok();
+
+        let mut hasher = BunTargetHasher::new(bun.and_then(|bun| bun.config.version.clone()));
+
+        let resolved_dependencies = if matches!(
+            hasher_config.optimization,
+            HasherOptimization::Accuracy
+        ) && bun.is_some()
+        {
+            bun.unwrap()
+                .get_resolved_dependencies(&project.root)
+                .await?
+        } else {
+            FxHashMap::default()
+        };
+
+        if let Some(root_package) = PackageJson::read(&self.workspace_root)? {
+            hasher.hash_package_json(&root_package, &resolved_dependencies);
+        }
+
+        if let Some(package) = PackageJson::read(&project.root)? {
+            hasher.hash_package_json(&package, &resolved_dependencies);
+        }
+
+        hashset.hash(hasher);
+
+        if let Some(typescript_config) = &self.typescript_config {
+            let ts_hasher = TypeScriptTargetHasher::generate(
+                typescript_config,
+                &self.workspace_root,
+                &project.root,
+            )?;
+
+            hashset.hash(ts_hasher);
+        }
+
+        Ok(())
+    }
+
+    async fn create_run_target_command(
+        &self,
+        _context: &ActionContext,
+        project: &Project,
+        task: &Task,
+        runtime: &Runtime,
+        working_dir: &Path,
+    ) -> Result<Command, ToolError> {
+        let bun = if self.is_toolchain_enabled()? {
+            self.toolchain.get_for_version(runtime.version()).ok()
+        } else {
+            None
+        };
+
+        let bun_bin = match bun {
+            Some(bun) => bun.get_bin_path()?,
+            None => PathBuf::from(BUN.binary),
+        };
+
+        let mut command = Command::new(bun_bin);
+
+        match task.command.as_str() {
+            "bun" => {}
+            "bunx" => {
+                command.arg("x");
+            }
+            bin => match find_package_bin(&project.root, bin)? {
+                // Rust, Go
+                Some(BinFile::Binary(bin_path)) => {
+                    command = Command::new(bin_path);
+                }
+                // JavaScript
+                Some(BinFile::Script(bin_path)) => {
+                    command.arg(bin_path);
+                }
+                None => {
+                    command = Command::new(bin);
+                }
+            },
+        };
+
+        if let Some(bun) = bun {
+            if !bun.global {
+                command.env("PATH", get_path_env_var(&bun.tool.get_install_dir()?));
+            }
+        }
+
+        command.args(&task.args).envs(&task.env).cwd(working_dir);
+
+        Ok(command)
+    }
+}
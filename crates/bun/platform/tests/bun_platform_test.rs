@@ -0,0 +1,351 @@
+use moon::{generate_project_graph, load_workspace_from};
+use moon_action_context::ActionContext;
+use moon_bun_platform::BunPlatform;
+use moon_config::{
+    BunConfig, HasherConfig, HasherOptimization, PartialBunConfig, PartialToolchainConfig,
+    PartialWorkspaceConfig, PlatformType, TaskCommandArgs, WorkspaceProjects,
+};
+use moon_hasher::HashSet;
+use moon_platform::{Platform, Runtime, Version};
+use moon_process::Command;
+use moon_project::Project;
+use moon_project_graph::ProjectGraph;
+use moon_task::Task;
+use moon_test_utils::{create_sandbox, create_sandbox_with_config, Sandbox};
+use moon_utils::string_vec;
+use rustc_hash::FxHashMap;
+use std::path::{Path, PathBuf};
+
+fn create_platform(workspace_root: &Path) -> BunPlatform {
+    BunPlatform::new(&BunConfig::default(), &None, workspace_root)
+}
+
+fn create_project(id: &str, root: PathBuf, source: &str) -> Project {
+    Project {
+        id: id.into(),
+        root,
+        source: source.into(),
+        ..Project::default()
+    }
+}
+
+async fn create_target_command(platform: &BunPlatform, project: &Project, bin: &str) -> Command {
+    let task = Task {
+        command: bin.into(),
+        args: string_vec!["--check"],
+        platform: PlatformType::Bun,
+        ..Task::default()
+    };
+
+    platform
+        .create_run_target_command(
+            &ActionContext::default(),
+            project,
+            &task,
+            &Runtime::Bun(Version::new_global()),
+            &PathBuf::from("cwd"),
+        )
+        .await
+        .unwrap()
+}
+
+async fn get_bun_graph() -> (ProjectGraph, Sandbox) {
+    let workspace_config = PartialWorkspaceConfig {
+        projects: Some(WorkspaceProjects::Sources(FxHashMap::from_iter([
+            ("app".into(), "packages/app".to_owned()),
+            ("utils".into(), "packages/utils".to_owned()),
+            ("standalone".into(), "standalone".to_owned()),
+        ]))),
+        ..PartialWorkspaceConfig::default()
+    };
+
+    let toolchain_config = PartialToolchainConfig {
+        bun: Some(PartialBunConfig::default()),
+        ..PartialToolchainConfig::default()
+    };
+
+    let sandbox =
+        create_sandbox_with_config("bun", Some(workspace_config), Some(toolchain_config), None);
+
+    let mut workspace = load_workspace_from(sandbox.path()).await.unwrap();
+    let graph = generate_project_graph(&mut workspace).await.unwrap();
+
+    (graph, sandbox)
+}
+
+mod project_graph {
+    use super::*;
+    use moon_config::DependencyScope;
+
+    #[tokio::test]
+    async fn loads_package_names_as_aliases() {
+        let (graph, _sandbox) = get_bun_graph().await;
+
+        assert_eq!(
+            graph.aliases,
+            FxHashMap::from_iter([
+                ("@example/app".into(), "app".into()),
+                ("@example/utils".into(), "utils".into()),
+                ("standalone".into(), "standalone".into()),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn infers_dependencies_from_package_json() {
+        let (graph, _sandbox) = get_bun_graph().await;
+        let app = graph.get("app").unwrap();
+
+        assert_eq!(
+            app.dependencies
+                .keys()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>(),
+            vec!["utils".to_owned()]
+        );
+        assert_eq!(
+            app.dependencies.get("utils").unwrap().scope,
+            DependencyScope::Production
+        );
+    }
+}
+
+mod project_tasks {
+    use super::*;
+
+    #[test]
+    fn doesnt_infer_by_default() {
+        let sandbox = create_sandbox("bun");
+        let platform = create_platform(sandbox.path());
+
+        let tasks = platform
+            .load_project_tasks(&create_project(
+                "app",
+                sandbox.path().join("packages/app"),
+                "packages/app",
+            ))
+            .unwrap();
+
+        assert!(tasks.is_empty());
+    }
+
+    #[test]
+    fn infers_tasks_that_run_with_bun() {
+        let sandbox = create_sandbox("bun");
+        let platform = BunPlatform::new(
+            &BunConfig {
+                infer_tasks_from_scripts: true,
+                ..BunConfig::default()
+            },
+            &None,
+            sandbox.path(),
+        );
+
+        let tasks = platform
+            .load_project_tasks(&create_project(
+                "app",
+                sandbox.path().join("packages/app"),
+                "packages/app",
+            ))
+            .unwrap();
+
+        assert_eq!(
+            tasks.keys().map(|id| id.to_string()).collect::<Vec<_>>(),
+            string_vec!["build", "dev", "lint", "test"]
+        );
+
+        let build = tasks.get("build").unwrap();
+
+        assert_eq!(
+            build.command,
+            TaskCommandArgs::Sequence(string_vec!["bun", "run", "build"])
+        );
+        assert_eq!(build.platform, PlatformType::Bun);
+        assert!(tasks.get("dev").unwrap().local);
+    }
+}
+
+mod dependency_configs {
+    use super::*;
+
+    #[test]
+    fn uses_text_lockfile_if_exists() {
+        let sandbox = create_sandbox("bun");
+        let platform = create_platform(sandbox.path());
+
+        assert_eq!(
+            platform.get_dependency_configs().unwrap(),
+            Some(("bun.lock".into(), "package.json".into()))
+        );
+    }
+
+    #[test]
+    fn uses_binary_lockfile_otherwise() {
+        let platform = create_platform(&PathBuf::new());
+
+        assert_eq!(
+            platform.get_dependency_configs().unwrap(),
+            Some(("bun.lockb".into(), "package.json".into()))
+        );
+    }
+}
+
+mod dependency_workspace {
+    use super::*;
+
+    #[test]
+    fn root_is_in_workspace() {
+        let sandbox = create_sandbox("bun");
+        let platform = create_platform(sandbox.path());
+
+        assert!(platform
+            .is_project_in_dependency_workspace(&create_project(
+                "root",
+                sandbox.path().to_path_buf(),
+                "."
+            ))
+            .unwrap());
+    }
+
+    #[test]
+    fn package_in_workspaces_is_in_workspace() {
+        let sandbox = create_sandbox("bun");
+        let platform = create_platform(sandbox.path());
+
+        assert!(platform
+            .is_project_in_dependency_workspace(&create_project(
+                "app",
+                sandbox.path().join("packages/app"),
+                "packages/app"
+            ))
+            .unwrap());
+    }
+
+    #[test]
+    fn package_outside_workspaces_is_not_in_workspace() {
+        let sandbox = create_sandbox("bun");
+        let platform = create_platform(sandbox.path());
+
+        assert!(!platform
+            .is_project_in_dependency_workspace(&create_project(
+                "standalone",
+                sandbox.path().join("standalone"),
+                "standalone"
+            ))
+            .unwrap());
+    }
+}
+
+mod hash_run_target {
+    use super::*;
+
+    async fn hash_target(platform: &BunPlatform, project: &Project) -> String {
+        let mut hashset = HashSet::default();
+
+        platform
+            .hash_run_target(
+                project,
+                &Runtime::Bun(Version::new_global()),
+                &mut hashset,
+                &HasherConfig {
+                    optimization: HasherOptimization::Accuracy,
+                    ..HasherConfig::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        hashset.generate()
+    }
+
+    #[tokio::test]
+    async fn includes_project_dependencies() {
+        let sandbox = create_sandbox("bun");
+        let platform = create_platform(sandbox.path());
+
+        assert_ne!(
+            hash_target(
+                &platform,
+                &create_project("app", sandbox.path().join("packages/app"), "packages/app")
+            )
+            .await,
+            hash_target(
+                &platform,
+                &create_project(
+                    "utils",
+                    sandbox.path().join("packages/utils"),
+                    "packages/utils"
+                )
+            )
+            .await
+        );
+    }
+}
+
+mod target_command {
+    use super::*;
+
+    #[tokio::test]
+    async fn runs_bun_as_is() {
+        let sandbox = create_sandbox("bun");
+        let platform = create_platform(sandbox.path());
+        let command = create_target_command(
+            &platform,
+            &create_project("app", sandbox.path().join("packages/app"), "packages/app"),
+            "bun",
+        )
+        .await;
+
+        assert_eq!(command.bin, "bun");
+        assert_eq!(command.args, &["--check"]);
+    }
+
+    #[tokio::test]
+    async fn runs_bunx_through_bun() {
+        let sandbox = create_sandbox("bun");
+        let platform = create_platform(sandbox.path());
+        let command = create_target_command(
+            &platform,
+            &create_project("app", sandbox.path().join("packages/app"), "packages/app"),
+            "bunx",
+        )
+        .await;
+
+        assert_eq!(command.bin, "bun");
+        assert_eq!(command.args, &["x", "--check"]);
+    }
+
+    #[tokio::test]
+    async fn runs_package_bins_with_bun() {
+        let sandbox = create_sandbox("bun");
+        sandbox.create_file("node_modules/.bin/prettier", "#!/usr/bin/env node");
+
+        let platform = create_platform(sandbox.path());
+        let command = create_target_command(
+            &platform,
+            &create_project("app", sandbox.path().join("packages/app"), "packages/app"),
+            "prettier",
+        )
+        .await;
+
+        assert_eq!(command.bin, "bun");
+        assert_eq!(
+            PathBuf::from(&command.args[0]),
+            sandbox.path().join("node_modules/.bin/prettier")
+        );
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_command_if_not_a_package_bin() {
+        let sandbox = create_sandbox("bun");
+        let platform = create_platform(sandbox.path());
+        let command = create_target_command(
+            &platform,
+            &create_project("app", sandbox.path().join("packages/app"), "packages/app"),
+            "tsc",
+        )
+        .await;
+
+        assert_eq!(command.bin, "tsc");
+    }
+}
@@ -0,0 +1,208 @@
+use moon_bun_lang::{
+    bun_lock::{self, parse_bun_lockb_output},
+    LockfileDependencyVersions, BUN_INSTALL, BUN_TEXT_LOCKFILE,
+};
+use moon_config::BunConfig;
+use moon_logger::debug;
+use moon_platform_runtime::Version;
+use moon_process::{output_to_string, Command};
+use moon_terminal::{print_checkpoint, Checkpoint};
+use moon_tool::{get_path_env_var, DependencyManager, Tool, ToolError};
+use moon_utils::is_ci;
+use proto::{async_trait, bun::BunLanguage, Executable, Installable, Proto, Tool as ProtoTool};
+use rustc_hash::FxHashMap;
+use starbase_utils::fs;
+use std::env;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug)]
+pub struct BunTool {
+    pub config: BunConfig,
+
+    pub global: bool,
+
+    pub tool: BunLanguage,
+}
+
+impl BunTool {
+    pub fn new(proto: &Proto, config: &BunConfig, version: &Version) -> Result<BunTool, ToolError> {
+        let mut bun = BunTool {
+            config: config.to_owned(),
+            global: false,
+            tool: BunLanguage::new(proto),
+        };
+
+        if version.is_global() {
+            bun.global = true;
+            bun.config.version = None;
+        } else {
+            bun.config.version = Some(version.number.to_owned());
+        };
+
+        Ok(bun)
+    }
+}
+
+#[async_trait]
+impl Tool for BunTool {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn get_bin_path(&self) -> Result<PathBuf, ToolError> {
+        Ok(if self.global {
+            "bun".into()
+        } else {
+            self.tool.get_bin_path()?.to_path_buf()
+        })
+    }
+
+    async fn setup(
+        &mut self,
+        last_versions: &mut FxHashMap<String, String>,
+    ) -> Result<u8, ToolError> {
+        let mut installed = 0;
+
+        let Some(version) = &self.config.version else {
+            return Ok(installed);
+        };
+
+        if self.tool.is_setup(version).await? {
+            debug!("Bun has already been setup");
+
+            // When offline and the tool doesn't exist, fallback to the global binary
+        } else if proto::is_offline() {
+            debug!(
+                "No internet connection and Bun has not been setup, falling back to global binary in PATH"
+            );
+
+            self.global = true;
+
+            // Otherwise try and install the tool
+        } else {
+            let setup = match last_versions.get("bun") {
+                Some(last) => version != last,
+                None => true,
+            };
+
+            if setup || !self.tool.get_install_dir()?.exists() {
+                print_checkpoint(format!("installing bun v{version}"), Checkpoint::Setup);
+
+                if self.tool.setup(version).await? {
+                    last_versions.insert("bun".into(), version.to_string());
+                    installed += 1;
+                }
+            }
+        }
+
+        Ok(installed)
+    }
+
+    async fn teardown(&mut self) -> Result<(), ToolError> {
+        self.tool.teardown().await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DependencyManager<BunTool> for BunTool {
+    fn create_command(&self, _bun: &BunTool) -> Result<Command, ToolError> {
+        let mut cmd = Command::new(self.get_bin_path()?);
+
+        if !self.global {
+            cmd.env("PATH", get_path_env_var(&self.tool.get_install_dir()?));
+        }
+
+        Ok(cmd)
+    }
+
+    async fn dedupe_dependencies(
+        &self,
+        _bun: &BunTool,
+        _working_dir: &Path,
+        _log: bool,
+    ) -> Result<(), ToolError> {
+        // Not supported!
+
+        Ok(())
+    }
+
+    fn get_lock_filename(&self) -> String {
+        String::from(BUN_INSTALL.lockfile)
+    }
+
+    fn get_manifest_filename(&self) -> String {
+        String::from(BUN_INSTALL.manifest)
+    }
+
+    async fn get_resolved_dependencies(
+        &self,
+        project_root: &Path,
+    ) -> Result<LockfileDependencyVersions, ToolError> {
+        if let Some(lockfile_path) = fs::find_upwards(BUN_TEXT_LOCKFILE, project_root) {
+            return Ok(bun_lock::load_lockfile_dependencies(lockfile_path)?);
+        }
+
+        let Some(lockfile_path) = fs::find_upwards(BUN_INSTALL.lockfile, project_root) else {
+            return Ok(FxHashMap::default());
+        };
+
+        // The binary lockfile can only be read by Bun itself,
+        // which prints it in a Yarn v1 compatible format
+        let output = self
+            .create_command(self)?
+            .arg(BUN_INSTALL.lockfile)
+            .cwd(lockfile_path.parent().unwrap())
+            .create_async()
+            .exec_capture_output()
+            .await?;
+
+        Ok(parse_bun_lockb_output(&output_to_string(&output.stdout))?)
+    }
+
+    async fn install_dependencies(
+        &self,
+        _bun: &BunTool,
+        working_dir: &Path,
+        log: bool,
+    ) -> Result<(), ToolError> {
+        let mut args = vec!["install"];
+
+        if is_ci() {
+            args.push("--frozen-lockfile");
+        }
+
+        let mut cmd = self.create_command(self)?;
+
+        cmd.args(args).cwd(working_dir).set_print_command(log);
+
+        let mut cmd = cmd.create_async();
+
+        if env::var("MOON_TEST_HIDE_INSTALL_OUTPUT").is_ok() {
+            cmd.exec_capture_output().await?;
+        } else {
+            cmd.exec_stream_output().await?;
+        }
+
+        Ok(())
+    }
+
+    async fn install_focused_dependencies(
+        &self,
+        _bun: &BunTool,
+        _packages: &[String],
+        production_only: bool,
+    ) -> Result<(), ToolError> {
+        let mut cmd = self.create_command(self)?;
+        cmd.arg("install");
+
+        if production_only {
+            cmd.arg("--production");
+        }
+
+        cmd.create_async().exec_stream_output().await?;
+
+        Ok(())
+    }
+}
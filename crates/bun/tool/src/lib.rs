@@ -0,0 +1,3 @@
+mod bun_tool;
+
+pub use bun_tool::*;
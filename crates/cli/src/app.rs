@@ -6,6 +6,7 @@ use crate::enums::{CacheMode, LogLevel, TouchedStatus};
 use clap::{Parser, Subcommand};
 use clap_complete::Shell;
 use moon_action_context::ProfileType;
+use moon_action_pipeline::reporter::Reporter;
 use moon_common::Id;
 use moon_target::Target;
 use std::path::PathBuf;
@@ -15,6 +16,7 @@ pub const BIN_NAME: &str = if cfg!(windows) { "moon.exe" } else { "moon" };
 const HEADING_AFFECTED: &str = "Affected by changes";
 const HEADING_DEBUGGING: &str = "Debugging";
 const HEADING_PARALLELISM: &str = "Parallelism and distribution";
+const HEADING_REPORTING: &str = "Reporting";
 
 #[derive(Debug, Subcommand)]
 pub enum DockerCommands {
@@ -381,6 +383,12 @@ pub enum Commands {
         #[arg(long, help = "Force overwrite any existing files at the destination")]
         force: bool,
 
+        #[arg(
+            long,
+            help = "Fetch remote template locations again, instead of using the cache"
+        )]
+        refresh: bool,
+
         #[arg(long, help = "Create a new template")]
         template: bool,
 
@@ -472,6 +480,14 @@ pub enum Commands {
         )]
         update_cache: bool,
 
+        #[arg(
+            long,
+            short = 'w',
+            help = "Watch for file changes and re-run affected targets",
+            conflicts_with = "interactive"
+        )]
+        watch: bool,
+
         // Debugging
         #[arg(
             value_enum,
@@ -481,6 +497,21 @@ pub enum Commands {
         )]
         profile: Option<ProfileType>,
 
+        #[arg(
+            long,
+            help = "Record a timeline of the pipeline to a Chrome trace file",
+            help_heading = HEADING_DEBUGGING,
+        )]
+        trace: Option<PathBuf>,
+
+        // Reporting
+        #[arg(
+            long = "reporter",
+            help = "Report the results of ran targets (junit=<file>, github[=<file>])",
+            help_heading = HEADING_REPORTING,
+        )]
+        reporters: Vec<Reporter>,
+
         // Affected
         #[arg(
             long,
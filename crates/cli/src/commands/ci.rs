@@ -3,6 +3,7 @@ use ci_env::CiOutput;
 use itertools::Itertools;
 use moon::{build_dep_graph, generate_project_graph, load_workspace};
 use moon_action_context::ActionContext;
+use moon_action_pipeline::sharder::{Shard, Sharder};
 use moon_action_pipeline::Pipeline;
 use moon_dep_graph::DepGraph;
 use moon_logger::debug;
@@ -10,10 +11,12 @@ use moon_project_graph::ProjectGraph;
 use moon_target::Target;
 use moon_task::TouchedFilePaths;
 use moon_terminal::safe_exit;
+use moon_utils::time;
 use moon_workspace::Workspace;
 use rustc_hash::FxHashSet;
 use starbase::AppResult;
 use starbase_styles::color;
+use std::time::Duration;
 
 type TargetList = Vec<Target>;
 
@@ -108,40 +111,66 @@ fn gather_runnable_targets(
     Ok(targets)
 }
 
-/// Distribute targets across jobs if parallelism is enabled.
+/// Distribute targets across jobs if parallelism is enabled. Targets are
+/// balanced using historical durations from previous runs.
 fn distribute_targets_across_jobs(
     provider: &CiOutput,
     options: &CiOptions,
+    workspace: &Workspace,
+    project_graph: &ProjectGraph,
     targets: TargetList,
-) -> TargetList {
-    if options.job.is_none() || options.job_total.is_none() {
-        return targets;
+) -> AppResult<TargetList> {
+    let (Some(job_index), Some(job_total)) = (options.job, options.job_total) else {
+        return Ok(targets);
+    };
+
+    let mut sharder = Sharder::new();
+
+    // Prefer durations from the previous CI run, and fallback
+    // to the last known run of each target
+    sharder.load_report(&workspace.cache.dir.join("ciReport.json"));
+
+    for project in project_graph.get_all()? {
+        for task in project.tasks.values() {
+            for dep in &task.deps {
+                sharder.link(&task.target, dep);
+            }
+        }
     }
 
-    let job_index = options.job.unwrap_or_default();
-    let job_total = options.job_total.unwrap_or_default();
-    let batch_size = targets.len() / job_total;
-    let batched_targets;
+    for target in &targets {
+        if sharder.has_duration(target) {
+            continue;
+        }
+
+        let state = workspace.cache.cache_run_target_state(&target.id)?;
+
+        if state.last_run_duration > 0 {
+            sharder.set_duration(
+                target,
+                Duration::from_millis(state.last_run_duration as u64),
+            );
+        }
+    }
+
+    let mut shards = sharder.distribute(&targets, job_total);
+    let shard = if job_index < shards.len() {
+        shards.swap_remove(job_index)
+    } else {
+        Shard::default()
+    };
 
     print_header(provider, "Distributing targets across jobs");
     println!("Job index: {job_index}");
-    println!("Job total: {job_index}");
-    println!("Batch size: {batch_size}");
+    println!("Job total: {job_total}");
+    println!("Batch size: {}", shard.targets.len());
+    println!("Estimated duration: {}", time::elapsed(shard.duration));
     println!("Batched targets:");
 
-    if job_index == 0 {
-        batched_targets = targets[0..batch_size].to_vec();
-    } else if job_index == job_total - 1 {
-        batched_targets = targets[(batch_size * job_index)..].to_vec();
-    } else {
-        batched_targets =
-            targets[(batch_size * job_index)..(batch_size * (job_index + 1))].to_vec();
-    }
-
-    print_targets(&batched_targets);
+    print_targets(&shard.targets);
     print_footer(provider);
 
-    batched_targets
+    Ok(shard.targets)
 }
 
 /// Generate a dependency graph with the runnable targets.
@@ -194,7 +223,13 @@ pub async fn ci(options: CiOptions) -> AppResult {
         return Ok(());
     }
 
-    let targets = distribute_targets_across_jobs(&ci_provider, &options, targets);
+    let targets = distribute_targets_across_jobs(
+        &ci_provider,
+        &options,
+        &workspace,
+        &project_graph,
+        targets,
+    )?;
     let dep_graph = generate_dep_graph(&ci_provider, &workspace, &project_graph, &targets)?;
 
     // Process all tasks in the graph
@@ -108,7 +108,14 @@ pub async fn prune() -> AppResult {
                 )
                 .await?;
             }
-            PlatformType::Deno | PlatformType::System | PlatformType::Unknown => {}
+            PlatformType::Bun
+            | PlatformType::Deno
+            | PlatformType::Go
+            | PlatformType::Php
+            | PlatformType::Python
+            | PlatformType::Ruby
+            | PlatformType::System
+            | PlatformType::Unknown => {}
         }
     }
 
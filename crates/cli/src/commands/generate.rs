@@ -1,19 +1,24 @@
 use console::Term;
 use dialoguer::{theme::Theme, Confirm, Input, MultiSelect, Select};
-use miette::IntoDiagnostic;
-use moon::load_workspace;
-use moon_config::{TemplateVariable, TemplateVariableEnumValue};
+use miette::{miette, IntoDiagnostic};
+use moon::{build_dep_graph, generate_project_graph, load_workspace};
+use moon_action_pipeline::Pipeline;
+use moon_common::consts::{CONFIG_DIRNAME, CONFIG_WORKSPACE_FILENAME};
+use moon_config::{
+    add_workspace_project_source, TemplateVariable, TemplateVariableEnumValue, WorkspaceProjects,
+};
 use moon_error::MoonError;
 use moon_generator::{FileState, Generator, GeneratorError, Template, TemplateContext};
 use moon_logger::{debug, map_list, trace, warn};
 use moon_terminal::{create_theme, ExtendedTerm};
-use moon_utils::path;
+use moon_utils::{path, regex::clean_id};
 use rustc_hash::FxHashMap;
 use starbase::AppResult;
 use starbase_styles::color;
+use starbase_utils::fs;
 use std::env;
 use std::fmt::Display;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 const LOG_TARGET: &str = "moon:generate";
 
@@ -23,6 +28,7 @@ pub struct GenerateOptions {
     pub dest: Option<String>,
     pub dry_run: bool,
     pub force: bool,
+    pub refresh: bool,
     pub template: bool,
     pub vars: Vec<String>,
 }
@@ -337,6 +343,9 @@ pub async fn generate(name: String, options: GenerateOptions) -> AppResult {
         debug!(target: LOG_TARGET, "Running in DRY MODE");
     }
 
+    // Fetch remote template locations into the cache
+    generator.resolve_remote_templates(options.refresh).await?;
+
     // Create the template instance
     let mut template = generator.load_template(&name)?;
     let term = Term::buffered_stdout();
@@ -402,6 +411,12 @@ pub async fn generate(name: String, options: GenerateOptions) -> AppResult {
                 continue;
             }
 
+            // Merge with the previously generated file, preserving local changes
+            if file.is_three_way_merge() && generator.has_previous_render(file) {
+                file.state = FileState::Merge;
+                continue;
+            }
+
             // Merge files when applicable
             if file.is_mergeable().is_some() {
                 let operations = [
@@ -450,7 +465,7 @@ pub async fn generate(name: String, options: GenerateOptions) -> AppResult {
 
     term.line("")?;
 
-    for file in template.files {
+    for file in &template.files {
         term.line(format!(
             "{} {} {}",
             match &file.state {
@@ -475,5 +490,125 @@ pub async fn generate(name: String, options: GenerateOptions) -> AppResult {
     term.line("")?;
     term.flush_lines()?;
 
+    if options.dry_run {
+        return Ok(());
+    }
+
+    // Register the destination as a project in the workspace
+    let project_id = if template.config.register_project {
+        register_project(&workspace.root, &workspace.config.projects, &dest)?
+    } else {
+        None
+    };
+
+    // Run post-generate commands in the destination
+    generator
+        .run_post_generate(&template, &dest, &context)
+        .await?;
+
+    // Sync the new project so that it's ready to use
+    if let Some(project_id) = project_id {
+        sync_project(&project_id).await?;
+    }
+
+    Ok(())
+}
+
+fn register_project(
+    workspace_root: &Path,
+    projects: &WorkspaceProjects,
+    dest: &Path,
+) -> AppResult<Option<String>> {
+    let Ok(source) = dest.strip_prefix(workspace_root) else {
+        return Err(miette!(
+            "Unable to register project, as the destination {} is outside of the workspace.",
+            color::path(dest)
+        ));
+    };
+
+    let source = path::to_virtual_string(source)?;
+    let id = clean_id(&fs::file_name(dest));
+
+    let existing_sources = match projects {
+        WorkspaceProjects::Sources(sources) => sources,
+        WorkspaceProjects::Both { sources, .. } => sources,
+        WorkspaceProjects::Globs(_) => {
+            debug!(
+                target: LOG_TARGET,
+                "Projects are configured with globs, skipping project registration"
+            );
+
+            return Ok(None);
+        }
+    };
+
+    if existing_sources.contains_key(id.as_str()) {
+        warn!(
+            target: LOG_TARGET,
+            "Project {} has already been registered, skipping project registration",
+            color::id(&id)
+        );
+
+        return Ok(None);
+    }
+
+    let config_path = workspace_root
+        .join(CONFIG_DIRNAME)
+        .join(CONFIG_WORKSPACE_FILENAME);
+
+    debug!(
+        target: LOG_TARGET,
+        "Registering project {} ({}) in {}",
+        color::id(&id),
+        color::file(&source),
+        color::path(&config_path)
+    );
+
+    let content = if config_path.exists() {
+        fs::read_file(&config_path)?
+    } else {
+        String::new()
+    };
+
+    let term = Term::buffered_stdout();
+    let config_file = format!("{CONFIG_DIRNAME}/{CONFIG_WORKSPACE_FILENAME}");
+
+    let Some(content) = add_workspace_project_source(&content, &id, &source) else {
+        term.line(format!(
+            "Unable to register project {}, as its sources are not declared in {}. Add {} to register it manually.",
+            color::id(&id),
+            color::file(&config_file),
+            color::symbol(format!("{id}: '{source}'"))
+        ))?;
+        term.flush_lines()?;
+
+        return Ok(None);
+    };
+
+    fs::write_file(&config_path, content)?;
+
+    term.line(format!(
+        "Registered project {} in {}",
+        color::id(&id),
+        color::file(config_file)
+    ))?;
+    term.flush_lines()?;
+
+    Ok(Some(id))
+}
+
+async fn sync_project(id: &str) -> AppResult {
+    let mut workspace = load_workspace().await?;
+    let project_graph = generate_project_graph(&mut workspace).await?;
+    let mut dep_builder = build_dep_graph(&workspace, &project_graph);
+
+    dep_builder.sync_project(project_graph.get(id)?)?;
+
+    let dep_graph = dep_builder.build();
+    let mut pipeline = Pipeline::new(workspace, project_graph);
+    let results = pipeline.run(dep_graph, None).await?;
+
+    pipeline.render_results(&results)?;
+
     Ok(())
 }
@@ -2,7 +2,7 @@ use super::check_dirty_repo;
 use moon::{generate_project_graph, load_workspace};
 use moon_common::consts::CONFIG_PROJECT_FILENAME;
 use moon_common::Id;
-use moon_config::{DependencyScope, ProjectConfig, ProjectDependsOn};
+use moon_config::{DependencyScope, PlatformType, ProjectConfig, ProjectDependsOn};
 use moon_error::MoonError;
 use moon_logger::info;
 use moon_node_lang::package_json::{DepsSet, PackageJson};
@@ -58,8 +58,9 @@ pub async fn from_package_json(project_id: Id, skip_touched_files_check: bool) -
 
     PackageJson::sync(&project.root, |package_json| {
         // Create tasks from `package.json` scripts
-        for (task_id, task_config) in create_tasks_from_scripts(&project.id, package_json)
-            .map_err(|e| MoonError::Generic(e.to_string()))?
+        for (task_id, task_config) in
+            create_tasks_from_scripts(&project.id, package_json, PlatformType::Node)
+                .map_err(|e| MoonError::Generic(e.to_string()))?
         {
             partial_config
                 .tasks
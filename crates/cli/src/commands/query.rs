@@ -1,7 +1,7 @@
 pub use crate::queries::hash::query_hash;
 pub use crate::queries::hash_diff::{query_hash_diff, QueryHashDiffOptions};
 pub use crate::queries::projects::{
-    query_projects, QueryProjectsOptions, QueryProjectsResult, QueryTasksResult,
+    query_projects, query_tasks, QueryProjectsOptions, QueryProjectsResult, QueryTasksResult,
 };
 pub use crate::queries::touched_files::{
     query_touched_files, QueryTouchedFilesOptions, QueryTouchedFilesResult,
@@ -11,7 +11,6 @@ use is_terminal::IsTerminal;
 use miette::IntoDiagnostic;
 use moon::load_workspace;
 use moon_terminal::ExtendedTerm;
-use rustc_hash::FxHashMap;
 use starbase::AppResult;
 use starbase_styles::color;
 use std::io;
@@ -144,27 +143,32 @@ pub async fn touched_files(options: &mut QueryTouchedFilesOptions) -> AppResult
 
 pub async fn tasks(options: &QueryProjectsOptions) -> AppResult {
     let mut workspace = load_workspace().await?;
-    let projects = query_projects(&mut workspace, options).await?;
+    let tasks = query_tasks(&mut workspace, options).await?;
 
     // Write to stdout directly to avoid broken pipe panics
     let term = Term::buffered_stdout();
 
     if options.json {
         let result = QueryTasksResult {
-            tasks: FxHashMap::from_iter(projects.into_iter().map(|p| (p.id, p.tasks))),
+            tasks,
             options: options.to_owned(),
         };
 
         term.line(serde_json::to_string_pretty(&result).into_diagnostic()?)?;
-    } else if !projects.is_empty() {
-        for project in projects {
-            if project.tasks.is_empty() {
+    } else if !tasks.is_empty() {
+        let mut project_ids = tasks.keys().collect::<Vec<_>>();
+        project_ids.sort();
+
+        for project_id in project_ids {
+            let project_tasks = &tasks[project_id];
+
+            if project_tasks.is_empty() {
                 continue;
             }
 
-            term.line(&project.id)?;
+            term.line(project_id)?;
 
-            for (task_id, task) in &project.tasks {
+            for (task_id, task) in project_tasks {
                 term.line(format!("\t:{} | {}", task_id, task.command))?;
             }
         }
@@ -1,18 +1,38 @@
 use crate::enums::{CacheMode, TouchedStatus};
 use crate::queries::touched_files::{query_touched_files, QueryTouchedFilesOptions};
-use miette::miette;
+use miette::{miette, IntoDiagnostic};
 use moon::{build_dep_graph, generate_project_graph, load_workspace};
+use moon_action::ActionNode;
 use moon_action_context::{ActionContext, ProfileType};
+use moon_action_pipeline::reporter::Reporter;
 use moon_action_pipeline::Pipeline;
-use moon_logger::map_list;
+use moon_common::consts::CONFIG_DIRNAME;
+use moon_dep_graph::DepGraph;
+use moon_logger::{debug, map_list, trace, warn};
 use moon_project_graph::ProjectGraph;
+use moon_query::build_query;
+use moon_target::Target;
+use moon_task::TouchedFilePaths;
 use moon_utils::is_ci;
 use moon_workspace::Workspace;
-use rustc_hash::FxHashSet;
+use notify::{EventKind, RecursiveMode, Watcher};
+use rustc_hash::{FxHashMap, FxHashSet};
 use starbase::AppResult;
 use starbase_styles::color;
+use starbase_utils::glob;
+use std::collections::BTreeMap;
 use std::env;
+use std::path::{Path, PathBuf};
 use std::string::ToString;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+
+const LOG_TARGET: &str = "moon:run";
+
+// How long to wait for additional file changes before re-running,
+// so that a batch of writes (formatters, branch switches) only runs once.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(150);
 
 #[derive(Default)]
 pub struct RunOptions {
@@ -25,8 +45,11 @@ pub struct RunOptions {
     pub profile: Option<ProfileType>,
     pub query: Option<String>,
     pub remote: bool,
+    pub reporters: Vec<Reporter>,
     pub status: Vec<TouchedStatus>,
+    pub trace: Option<PathBuf>,
     pub update_cache: bool,
+    pub watch: bool,
 }
 
 pub fn is_local(options: &RunOptions) -> bool {
@@ -37,25 +60,38 @@ pub fn is_local(options: &RunOptions) -> bool {
     }
 }
 
-pub async fn run_target(
+/// Build the dependency graph and action context for the provided targets. When
+/// `watched_files` is provided, they will be used to determine affected targets,
+/// instead of querying the VCS for touched files.
+async fn prepare_run(
     target_ids: &[String],
-    options: RunOptions,
-    workspace: Workspace,
-    project_graph: ProjectGraph,
-) -> AppResult {
-    // Force cache to update using write-only mode
-    if options.update_cache {
-        env::set_var("MOON_CACHE", CacheMode::Write.to_string());
-    }
+    options: &RunOptions,
+    workspace: &Workspace,
+    project_graph: &ProjectGraph,
+    watched_files: Option<TouchedFilePaths>,
+) -> miette::Result<Option<(DepGraph, ActionContext)>> {
+    let is_watching = watched_files.is_some();
+    let should_run_affected = is_watching || !options.force && options.affected;
 
-    let should_run_affected = !options.force && options.affected;
+    let query = match &options.query {
+        Some(query_input) => Some(build_query(query_input)?),
+        None => None,
+    };
+    let query_requires_touched_files = query
+        .as_ref()
+        .map(|query| query.requires_touched_files())
+        .unwrap_or_default();
 
     // Always query for a touched files list as it'll be used by many actions
-    let touched_files = if !options.force && (options.affected || workspace.vcs.is_enabled()) {
+    let touched_files = if let Some(files) = watched_files {
+        files
+    } else if query_requires_touched_files
+        || !options.force && (options.affected || workspace.vcs.is_enabled())
+    {
         query_touched_files(
-            &workspace,
+            workspace,
             &mut QueryTouchedFilesOptions {
-                local: is_local(&options),
+                local: is_local(options),
                 status: options.status.clone(),
                 ..QueryTouchedFilesOptions::default()
             },
@@ -66,10 +102,14 @@ pub async fn run_target(
     };
 
     // Generate a dependency graph for all the targets that need to be ran
-    let mut dep_builder = build_dep_graph(&workspace, &project_graph);
+    let mut dep_builder = build_dep_graph(workspace, project_graph);
 
     if let Some(query_input) = &options.query {
         dep_builder.set_query(query_input)?;
+
+        if query_requires_touched_files {
+            dep_builder.set_query_touched_files(&touched_files);
+        }
     }
 
     // Run targets, optionally based on affected files
@@ -83,6 +123,11 @@ pub async fn run_target(
     )?;
 
     if primary_targets.is_empty() {
+        // Changed files may not affect any target, which is expected
+        if is_watching {
+            return Ok(None);
+        }
+
         let targets_list = map_list(target_ids, |id| color::label(id));
 
         if should_run_affected {
@@ -103,7 +148,7 @@ pub async fn run_target(
             println!("Using query {}", color::shell(query_input));
         }
 
-        return Ok(());
+        return Ok(None);
     }
 
     // Interactive can only run against 1 task
@@ -125,21 +170,45 @@ pub async fn run_target(
         affected_only: should_run_affected,
         initial_targets: FxHashSet::from_iter(target_ids.to_owned()),
         interactive: options.interactive,
-        passthrough_args: options.passthrough,
+        passthrough_args: options.passthrough.clone(),
         primary_targets: FxHashSet::from_iter(primary_targets),
-        profile: options.profile,
+        profile: options.profile.clone(),
         touched_files,
         workspace_root: workspace.root.clone(),
         ..ActionContext::default()
     };
 
-    let dep_graph = dep_builder.build();
+    Ok(Some((dep_builder.build(), context)))
+}
+
+async fn run_pipeline(
+    dep_graph: DepGraph,
+    context: ActionContext,
+    concurrency: Option<usize>,
+    trace: Option<PathBuf>,
+    reporters: Vec<Reporter>,
+    workspace: Workspace,
+    project_graph: ProjectGraph,
+) -> AppResult {
+    let trace = trace.map(|file| workspace.working_dir.join(file));
+    let reporters = reporters
+        .into_iter()
+        .map(|reporter| reporter.resolve(&workspace.working_dir))
+        .collect::<Vec<_>>();
     let mut pipeline = Pipeline::new(workspace, project_graph);
 
-    if let Some(concurrency) = options.concurrency {
+    if let Some(concurrency) = concurrency {
         pipeline.concurrency(concurrency);
     }
 
+    if let Some(trace) = trace {
+        pipeline.generate_trace(trace);
+    }
+
+    for reporter in reporters {
+        pipeline.add_reporter(reporter);
+    }
+
     let results = pipeline
         .bail_on_error()
         .generate_report("runReport.json")
@@ -151,11 +220,523 @@ pub async fn run_target(
     Ok(())
 }
 
+pub async fn run_target(
+    target_ids: &[String],
+    options: RunOptions,
+    workspace: Workspace,
+    project_graph: ProjectGraph,
+) -> AppResult {
+    // Force cache to update using write-only mode
+    if options.update_cache {
+        env::set_var("MOON_CACHE", CacheMode::Write.to_string());
+    }
+
+    if let Some((dep_graph, context)) =
+        prepare_run(target_ids, &options, &workspace, &project_graph, None).await?
+    {
+        run_pipeline(
+            dep_graph,
+            context,
+            options.concurrency,
+            options.trace,
+            options.reporters,
+            workspace,
+            project_graph,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Run a pipeline in the background while watching. Once it has completed successfully,
+/// the persistent targets waiting on it will be started. Errors are rendered instead of
+/// returned, so that a failing run does not exit the watcher.
+#[allow(clippy::too_many_arguments)]
+fn spawn_pipeline(
+    dep_graph: DepGraph,
+    context: ActionContext,
+    concurrency: Option<usize>,
+    trace: Option<PathBuf>,
+    reporters: Vec<Reporter>,
+    workspace: Workspace,
+    project_graph: ProjectGraph,
+    persistent_ready: Vec<oneshot::Sender<()>>,
+) -> JoinHandle<bool> {
+    tokio::spawn(async move {
+        if let Err(error) = run_pipeline(
+            dep_graph,
+            context,
+            concurrency,
+            trace,
+            reporters,
+            workspace,
+            project_graph,
+        )
+        .await
+        {
+            eprintln!("{error:?}");
+
+            return false;
+        }
+
+        for ready in persistent_ready {
+            let _ = ready.send(());
+        }
+
+        true
+    })
+}
+
+/// Run a persistent target in its own pipeline, once the pipeline running its
+/// dependencies has completed. Running targets hold onto the workspace, so each
+/// pipeline loads its own workspace and project graph.
+fn spawn_persistent_pipeline(
+    dep_graph: DepGraph,
+    context: ActionContext,
+    concurrency: Option<usize>,
+    reporters: Vec<Reporter>,
+    ready: oneshot::Receiver<()>,
+) -> JoinHandle<bool> {
+    tokio::spawn(async move {
+        if ready.await.is_err() {
+            return false;
+        }
+
+        let result: AppResult = async {
+            let mut workspace = load_workspace().await?;
+            let project_graph = generate_project_graph(&mut workspace).await?;
+
+            run_pipeline(
+                dep_graph,
+                context,
+                concurrency,
+                None,
+                reporters,
+                workspace,
+                project_graph,
+            )
+            .await
+        }
+        .await;
+
+        if let Err(error) = result {
+            eprintln!("{error:?}");
+
+            return false;
+        }
+
+        true
+    })
+}
+
+/// Convert the changed file paths into workspace relative paths, while filtering out
+/// files that are ignored by the VCS, are within moon's cache, or are task outputs
+/// (to avoid an infinite loop of re-runs).
+fn filter_watched_files(
+    changed_files: FxHashSet<PathBuf>,
+    workspace: &Workspace,
+    project_graph: &ProjectGraph,
+) -> miette::Result<TouchedFilePaths> {
+    let mut output_paths = FxHashSet::default();
+    let mut output_globs: Vec<String> = vec![];
+
+    for project in project_graph.get_all()? {
+        for task in project.tasks.values() {
+            output_paths.extend(task.output_paths.iter().cloned());
+            output_globs.extend(task.output_globs.iter().cloned());
+        }
+    }
+
+    let output_globset = glob::GlobSet::new(&output_globs).into_diagnostic()?;
+    let cache_dir = PathBuf::from(CONFIG_DIRNAME).join("cache");
+    let mut files = FxHashSet::default();
+
+    for file in changed_files {
+        let Ok(file) = file.strip_prefix(&workspace.root) else {
+            continue;
+        };
+
+        let is_output = output_paths.iter().any(|output| file.starts_with(output))
+            || output_globset.is_match(file);
+
+        if file.starts_with(&cache_dir)
+            || file.starts_with(".git")
+            || is_output
+            || workspace.vcs.is_ignored(&file.to_string_lossy())
+        {
+            continue;
+        }
+
+        files.insert(file.to_path_buf());
+    }
+
+    Ok(files)
+}
+
+/// Wait for a file to change, and then continue to collect changes
+/// until the debounce duration has passed without a new change.
+async fn wait_for_changes(receiver: &mut mpsc::UnboundedReceiver<PathBuf>) -> FxHashSet<PathBuf> {
+    let mut files = FxHashSet::default();
+
+    if let Some(file) = receiver.recv().await {
+        files.insert(file);
+    }
+
+    while let Ok(Some(file)) = tokio::time::timeout(WATCH_DEBOUNCE, receiver.recv()).await {
+        files.insert(file);
+    }
+
+    files
+}
+
+/// Determine the paths to watch, based on the inputs of all tasks that the targets may run,
+/// including their dependencies. Globs are watched from their static prefix, and files
+/// through their parent directory, as editors typically replace files when writing.
+fn collect_watch_paths(
+    target_ids: &[String],
+    options: &RunOptions,
+    workspace: &Workspace,
+    project_graph: &ProjectGraph,
+) -> miette::Result<BTreeMap<PathBuf, RecursiveMode>> {
+    let mut dep_builder = build_dep_graph(workspace, project_graph);
+    let primary_targets = dep_builder.run_targets_by_id(target_ids, None)?;
+
+    if options.dependents {
+        for target in &primary_targets {
+            dep_builder.run_dependents_for_target(target)?;
+        }
+    }
+
+    let mut paths = BTreeMap::new();
+
+    let mut add_path = |mut path: PathBuf, mut mode: RecursiveMode| {
+        // Watch the closest existing directory, so that new files are detected
+        while !path.exists() && path != workspace.root {
+            let Some(parent) = path.parent() else {
+                break;
+            };
+
+            path = parent.to_path_buf();
+            mode = RecursiveMode::NonRecursive;
+        }
+
+        let entry = paths.entry(path).or_insert(mode);
+
+        if mode == RecursiveMode::Recursive {
+            *entry = mode;
+        }
+    };
+
+    for node in dep_builder.build().get_nodes() {
+        let (ActionNode::RunTarget(_, target) | ActionNode::RunPersistentTarget(_, target)) = node else {
+            continue;
+        };
+
+        let Some(project_id) = &target.scope_id else {
+            continue;
+        };
+
+        let task = project_graph.get(project_id)?.get_task(&target.task_id)?;
+
+        for input in &task.input_paths {
+            let path = workspace.root.join(input);
+
+            if path.is_dir() {
+                add_path(path, RecursiveMode::Recursive);
+            } else if let Some(parent) = path.parent() {
+                add_path(parent.to_path_buf(), RecursiveMode::NonRecursive);
+            }
+        }
+
+        for input in &task.input_globs {
+            if input.starts_with('!') {
+                continue;
+            }
+
+            let prefix = Path::new(input)
+                .components()
+                .take_while(|part| !glob::is_glob(part.as_os_str().to_string_lossy()))
+                .collect::<PathBuf>();
+
+            add_path(workspace.root.join(prefix), RecursiveMode::Recursive);
+        }
+    }
+
+    // Remove paths that are already watched through a recursive parent
+    let recursive_paths = paths
+        .iter()
+        .filter(|(_, mode)| **mode == RecursiveMode::Recursive)
+        .map(|(path, _)| path.to_owned())
+        .collect::<Vec<_>>();
+
+    paths.retain(|path, _| {
+        !recursive_paths
+            .iter()
+            .any(|parent| path != parent && path.starts_with(parent))
+    });
+
+    Ok(paths)
+}
+
+fn create_watcher(
+    paths: &BTreeMap<PathBuf, RecursiveMode>,
+    sender: mpsc::UnboundedSender<PathBuf>,
+) -> miette::Result<notify::RecommendedWatcher> {
+    let mut watcher = notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+        if let Ok(event) = result {
+            if matches!(event.kind, EventKind::Access(_)) {
+                return;
+            }
+
+            for path in event.paths {
+                let _ = sender.send(path);
+            }
+        }
+    })
+    .into_diagnostic()?;
+
+    for (path, mode) in paths {
+        trace!(target: LOG_TARGET, "Watching {}", color::path(path));
+
+        if let Err(error) = watcher.watch(path, *mode) {
+            warn!(
+                target: LOG_TARGET,
+                "Failed to watch {}: {}",
+                color::path(path),
+                error
+            );
+        }
+    }
+
+    Ok(watcher)
+}
+
+/// Runs that were started while watching. Persistent targets are ran in their own pipelines,
+/// so that only the persistent targets affected by a change are restarted, and the others
+/// are left running. All other actions are ran together in a single pipeline.
+#[derive(Default)]
+struct WatchRuns {
+    failed: bool,
+    persistent: FxHashMap<Target, JoinHandle<bool>>,
+    pipeline: Option<JoinHandle<bool>>,
+}
+
+impl WatchRuns {
+    /// Return true if the previous pipeline is still running or has failed,
+    /// in which case its persistent targets have not been started.
+    async fn is_incomplete(&mut self) -> bool {
+        if let Some(handle) = &self.pipeline {
+            if !handle.is_finished() {
+                return true;
+            }
+
+            if let Some(handle) = self.pipeline.take() {
+                self.failed = !handle.await.unwrap_or_default();
+            }
+        }
+
+        self.failed
+    }
+
+    async fn start(
+        &mut self,
+        dep_graph: DepGraph,
+        context: ActionContext,
+        options: &RunOptions,
+        workspace: Workspace,
+        project_graph: ProjectGraph,
+    ) {
+        let (dep_graph, persistent_graphs) = dep_graph.split_persistent_targets();
+        let mut persistent_ready = vec![];
+
+        if let Some(handle) = self.pipeline.take() {
+            if !handle.is_finished() {
+                trace!(target: LOG_TARGET, "Aborting previous run");
+
+                handle.abort();
+            }
+
+            let _ = handle.await;
+        }
+
+        for (target, persistent_graph) in persistent_graphs {
+            if let Some(handle) = self.persistent.remove(&target) {
+                if !handle.is_finished() {
+                    trace!(
+                        target: LOG_TARGET,
+                        "Restarting persistent target {}",
+                        color::label(&target.id)
+                    );
+
+                    handle.abort();
+                }
+
+                let _ = handle.await;
+            }
+
+            let (sender, receiver) = oneshot::channel();
+
+            persistent_ready.push(sender);
+
+            self.persistent.insert(
+                target,
+                spawn_persistent_pipeline(
+                    persistent_graph,
+                    context.clone(),
+                    options.concurrency,
+                    options.reporters.clone(),
+                    receiver,
+                ),
+            );
+        }
+
+        self.failed = false;
+        self.pipeline = Some(spawn_pipeline(
+            dep_graph,
+            context,
+            options.concurrency,
+            options.trace.clone(),
+            options.reporters.clone(),
+            workspace,
+            project_graph,
+            persistent_ready,
+        ));
+    }
+}
+
+/// Run the targets, and then watch the inputs of their tasks for changes. When files have
+/// changed, re-run the targets that are affected by them. If the previous pipeline is still
+/// running, it will be aborted, and affected persistent targets will be restarted, instead
+/// of spawned alongside the old ones. Unaffected persistent targets continue to run.
+pub async fn run_target_with_watch(
+    target_ids: &[String],
+    options: RunOptions,
+    workspace: Workspace,
+    project_graph: ProjectGraph,
+) -> AppResult {
+    if options.update_cache {
+        env::set_var("MOON_CACHE", CacheMode::Write.to_string());
+    }
+
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    let mut watch_paths = collect_watch_paths(target_ids, &options, &workspace, &project_graph)?;
+    let mut _watcher = create_watcher(&watch_paths, sender.clone())?;
+    let mut runs = WatchRuns::default();
+    let mut pending_files = TouchedFilePaths::default();
+
+    if let Some((dep_graph, context)) =
+        prepare_run(target_ids, &options, &workspace, &project_graph, None).await?
+    {
+        runs.start(dep_graph, context, &options, workspace, project_graph)
+            .await;
+    }
+
+    println!(
+        "{}",
+        color::muted_light("Watching for changes... (press Ctrl+C to exit)")
+    );
+
+    loop {
+        let changed_files = wait_for_changes(&mut receiver).await;
+
+        // Reload the workspace on each change, as configs may have changed,
+        // but don't exit the watcher if they are currently invalid
+        let mut workspace = match load_workspace().await {
+            Ok(workspace) => workspace,
+            Err(error) => {
+                eprintln!("{error:?}");
+                continue;
+            }
+        };
+
+        let project_graph = match generate_project_graph(&mut workspace).await {
+            Ok(project_graph) => project_graph,
+            Err(error) => {
+                eprintln!("{error:?}");
+                continue;
+            }
+        };
+
+        // Task inputs may have changed along with the configs
+        match collect_watch_paths(target_ids, &options, &workspace, &project_graph) {
+            Ok(paths) => {
+                if paths != watch_paths {
+                    match create_watcher(&paths, sender.clone()) {
+                        Ok(watcher) => {
+                            _watcher = watcher;
+                            watch_paths = paths;
+                        }
+                        Err(error) => {
+                            eprintln!("{error:?}");
+                        }
+                    };
+                }
+            }
+            Err(error) => {
+                eprintln!("{error:?}");
+            }
+        };
+
+        let touched_files = match filter_watched_files(changed_files, &workspace, &project_graph) {
+            Ok(files) => files,
+            Err(error) => {
+                eprintln!("{error:?}");
+                continue;
+            }
+        };
+
+        if touched_files.is_empty() {
+            continue;
+        }
+
+        debug!(
+            target: LOG_TARGET,
+            "Detected changes to {}",
+            map_list(&touched_files.iter().collect::<Vec<_>>(), |f| color::path(
+                f
+            ))
+        );
+
+        // When the previous run didn't complete, include its files again,
+        // so that its targets (and waiting persistent targets) still run
+        if !runs.is_incomplete().await {
+            pending_files.clear();
+        }
+
+        pending_files.extend(touched_files);
+
+        let (dep_graph, context) = match prepare_run(
+            target_ids,
+            &options,
+            &workspace,
+            &project_graph,
+            Some(pending_files.clone()),
+        )
+        .await
+        {
+            Ok(Some(run)) => run,
+            Ok(None) => continue,
+            Err(error) => {
+                eprintln!("{error:?}");
+                continue;
+            }
+        };
+
+        runs.start(dep_graph, context, &options, workspace, project_graph)
+            .await;
+    }
+}
+
 pub async fn run(target_ids: &[String], options: RunOptions) -> AppResult {
     let mut workspace = load_workspace().await?;
     let project_graph = generate_project_graph(&mut workspace).await?;
 
-    run_target(target_ids, options, workspace, project_graph).await?;
+    if options.watch {
+        run_target_with_watch(target_ids, options, workspace, project_graph).await?;
+    } else {
+        run_target(target_ids, options, workspace, project_graph).await?;
+    }
 
     Ok(())
 }
@@ -149,6 +149,7 @@ pub async fn run_cli() -> AppResult {
             defaults,
             dry_run,
             force,
+            refresh,
             template,
             vars,
         } => {
@@ -159,6 +160,7 @@ pub async fn run_cli() -> AppResult {
                     dest,
                     dry_run,
                     force,
+                    refresh,
                     template,
                     vars,
                 },
@@ -284,9 +286,12 @@ pub async fn run_cli() -> AppResult {
             profile,
             query,
             remote,
+            reporters,
             status,
             targets,
+            trace,
             update_cache,
+            watch,
         } => {
             run(
                 &targets,
@@ -300,8 +305,11 @@ pub async fn run_cli() -> AppResult {
                     profile,
                     query,
                     remote,
+                    reporters,
                     status,
+                    trace,
                     update_cache,
+                    watch,
                 },
             )
             .await
@@ -107,16 +107,24 @@ pub async fn query_projects(
     debug!(target: LOG_TARGET, "Querying for projects");
 
     let project_graph = generate_project_graph(workspace).await?;
-    let touched_files = if options.affected {
+    let query = match &options.query {
+        Some(query_input) => Some(moon_query::build_query(query_input)?),
+        None => None,
+    };
+    let query_requires_touched_files = query
+        .as_ref()
+        .map(|query| query.requires_touched_files())
+        .unwrap_or_default();
+    let touched_files = if options.affected || query_requires_touched_files {
         load_touched_files(workspace).await?
     } else {
         FxHashSet::default()
     };
 
     // When a MQL input is provided, it takes full precedence over option args
-    if let Some(query) = &options.query {
+    if let Some(query) = &query {
         let projects = project_graph
-            .query(&moon_query::build_query(query)?)?
+            .query_with_touched_files(query, Some(&touched_files))?
             .into_iter()
             .filter_map(|project| {
                 if options.affected && !project.is_affected(&touched_files) {
@@ -197,3 +205,45 @@ pub async fn query_projects(
 
     Ok(projects)
 }
+
+pub async fn query_tasks(
+    workspace: &mut Workspace,
+    options: &QueryProjectsOptions,
+) -> AppResult<FxHashMap<Id, BTreeMap<Id, Task>>> {
+    let Some(query_input) = &options.query else {
+        return Ok(query_projects(workspace, options)
+            .await?
+            .into_iter()
+            .map(|project| (project.id, project.tasks))
+            .collect());
+    };
+
+    debug!(target: LOG_TARGET, "Querying for tasks");
+
+    let project_graph = generate_project_graph(workspace).await?;
+    let query = moon_query::build_query(query_input)?;
+    let touched_files = if options.affected || query.requires_touched_files() {
+        load_touched_files(workspace).await?
+    } else {
+        FxHashSet::default()
+    };
+
+    // Task fields are matched per task, so only the matching tasks are returned
+    let mut tasks = FxHashMap::default();
+
+    for (project, project_tasks) in project_graph.query_tasks(&query, Some(&touched_files))? {
+        if options.affected && !project.is_affected(&touched_files) {
+            continue;
+        }
+
+        tasks.insert(
+            project.id.clone(),
+            project_tasks
+                .into_iter()
+                .map(|task| (task.id.clone(), task.to_owned()))
+                .collect(),
+        );
+    }
+
+    Ok(tasks)
+}
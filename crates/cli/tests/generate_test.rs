@@ -1,5 +1,6 @@
+use moon_config::{PartialGeneratorConfig, PartialWorkspaceConfig, TemplateLocator};
 use moon_test_utils::{
-    assert_snapshot, create_sandbox_with_config, predicates::prelude::*, Sandbox,
+    assert_snapshot, create_sandbox, create_sandbox_with_config, predicates::prelude::*, Sandbox,
 };
 use std::fs;
 
@@ -233,9 +234,265 @@ fn supports_tera_twig_exts() {
     );
 }
 
+#[test]
+fn generates_files_from_remote_template() {
+    let remote = create_sandbox("generator/templates");
+    remote.enable_git();
+
+    let sandbox = create_sandbox_with_config(
+        "base",
+        Some(PartialWorkspaceConfig {
+            generator: Some(PartialGeneratorConfig {
+                templates: Some(vec![TemplateLocator::Git {
+                    remote_url: format!("file://{}", remote.path().display()),
+                    revision: "master".into(),
+                }]),
+            }),
+            ..PartialWorkspaceConfig::default()
+        }),
+        None,
+        None,
+    );
+
+    let assert = sandbox.run_moon(|cmd| {
+        cmd.arg("generate")
+            .arg("standard")
+            .arg("./test")
+            .arg("--refresh");
+    });
+
+    assert.success();
+
+    assert!(sandbox.path().join(".moon/cache/templates").exists());
+    assert!(sandbox.path().join("test/file.ts").exists());
+    assert!(sandbox.path().join("test/folder/nested-file.ts").exists());
+}
+
+#[test]
+fn generates_files_from_extended_templates() {
+    let sandbox = generate_sandbox();
+
+    let assert = sandbox.run_moon(|cmd| {
+        cmd.arg("generate")
+            .arg("extends-child")
+            .arg("./test")
+            .arg("--defaults");
+    });
+
+    assert.success();
+
+    assert_eq!(
+        fs::read_to_string(sandbox.path().join("test/src/index.ts")).unwrap(),
+        "export const child = true;\n"
+    );
+    assert_eq!(
+        fs::read_to_string(sandbox.path().join("test/src/base.ts")).unwrap(),
+        "export const base = true;\n"
+    );
+    assert_eq!(
+        fs::read_to_string(sandbox.path().join("test/package.json")).unwrap(),
+        "{\n  \"name\": \"child\",\n  \"private\": true,\n  \"scripts\": {\n    \"build\": \"tsc\"\n  }\n}"
+    );
+    assert!(!sandbox.path().join("test/partials").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn runs_post_generate_and_registers_project() {
+    let sandbox = generate_sandbox();
+
+    sandbox.create_file(".moon/workspace.yml", "projects:\n  root: '.'\n");
+
+    let assert = sandbox.run_moon(|cmd| {
+        cmd.arg("generate")
+            .arg("hooks")
+            .arg("./test")
+            .arg("--defaults");
+    });
+
+    let output = assert.output();
+
+    assert!(predicate::str::contains("Registered project test").eval(&output));
+
+    assert.success();
+    assert_eq!(
+        fs::read_to_string(sandbox.path().join("test/hook-output.txt")).unwrap(),
+        "hooks hooks\n"
+    );
+    assert!(
+        fs::read_to_string(sandbox.path().join(".moon/workspace.yml"))
+            .unwrap()
+            .contains("test: 'test'")
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn registers_project_without_reformatting_workspace_config() {
+    let sandbox = generate_sandbox();
+
+    sandbox.create_file("apps/app/moon.yml", "");
+    sandbox.create_file(
+        ".moon/workspace.yml",
+        "# Workspace settings\nprojects:\n    # Applications\n    app: \"apps/app\"\n\n# Version control\nvcs:\n  defaultBranch: master\n",
+    );
+
+    let assert = sandbox.run_moon(|cmd| {
+        cmd.arg("generate")
+            .arg("hooks")
+            .arg("./test")
+            .arg("--defaults");
+    });
+
+    assert.success();
+
+    assert_eq!(
+        fs::read_to_string(sandbox.path().join(".moon/workspace.yml")).unwrap(),
+        "# Workspace settings\nprojects:\n    # Applications\n    app: \"apps/app\"\n    test: 'test'\n\n# Version control\nvcs:\n  defaultBranch: master\n"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn prints_project_entry_when_sources_are_not_declared() {
+    let sandbox = generate_sandbox();
+
+    sandbox.create_file("shared/workspace.yml", "projects:\n  root: '.'\n");
+    sandbox.create_file(
+        ".moon/workspace.yml",
+        "extends: '../shared/workspace.yml'\n",
+    );
+
+    let assert = sandbox.run_moon(|cmd| {
+        cmd.arg("generate")
+            .arg("hooks")
+            .arg("./test")
+            .arg("--defaults");
+    });
+
+    let output = assert.output();
+
+    assert!(predicate::str::contains("Unable to register project test").eval(&output));
+    assert!(predicate::str::contains("test: 'test'").eval(&output));
+
+    assert.success();
+    assert_eq!(
+        fs::read_to_string(sandbox.path().join(".moon/workspace.yml")).unwrap(),
+        "extends: '../shared/workspace.yml'\n"
+    );
+}
+
+#[test]
+fn skips_post_generate_when_dry_run() {
+    let sandbox = generate_sandbox();
+
+    let assert = sandbox.run_moon(|cmd| {
+        cmd.arg("generate")
+            .arg("hooks")
+            .arg("./test")
+            .arg("--defaults")
+            .arg("--dryRun");
+    });
+
+    assert.success();
+
+    assert!(!sandbox.path().join("test/hook-output.txt").exists());
+    assert!(
+        !fs::read_to_string(sandbox.path().join(".moon/workspace.yml"))
+            .unwrap()
+            .contains("test: test")
+    );
+}
+
 mod frontmatter {
     use super::*;
 
+    #[test]
+    fn supports_conditions_and_loops() {
+        let sandbox = generate_sandbox();
+        sandbox.create_file(
+            "templates/loops/template.yml",
+            r#"
+title: Title
+description: Description
+variables:
+  services:
+    type: enum
+    multiple: true
+    default: auth
+    values: [auth, billing]
+    prompt: Services?
+  withTests:
+    type: boolean
+    default: false
+"#,
+        );
+        sandbox.create_file(
+            "templates/loops/handlers/[item].ts",
+            "---\nfor_each: services\n---\nexport const {{ item }} = true;\n",
+        );
+        sandbox.create_file(
+            "templates/loops/handlers.test.ts",
+            "---\nif: withTests\n---\ntest();\n",
+        );
+
+        sandbox
+            .run_moon(|cmd| {
+                cmd.arg("generate")
+                    .arg("loops")
+                    .arg("./test")
+                    .arg("--defaults");
+            })
+            .success();
+
+        assert_eq!(
+            fs::read_to_string(sandbox.path().join("test/handlers/auth.ts")).unwrap(),
+            "export const auth = true;\n"
+        );
+        assert!(!sandbox.path().join("test/handlers/billing.ts").exists());
+        assert!(!sandbox.path().join("test/handlers/[item].ts").exists());
+        assert!(!sandbox.path().join("test/handlers.test.ts").exists());
+    }
+
+    #[test]
+    fn three_way_merges_when_regenerating() {
+        let sandbox = generate_sandbox();
+        sandbox.create_file(
+            "templates/three-way/template.yml",
+            "title: Title\ndescription: Description",
+        );
+        sandbox.create_file(
+            "templates/three-way/file.txt",
+            "---\nmerge: three-way\n---\none\ntwo\n",
+        );
+
+        let generate = || {
+            sandbox
+                .run_moon(|cmd| {
+                    cmd.arg("generate")
+                        .arg("three-way")
+                        .arg("./test")
+                        .arg("--defaults");
+                })
+                .success();
+        };
+
+        generate();
+
+        sandbox.create_file("test/file.txt", "one\ntwo\nlocal\n");
+        sandbox.create_file(
+            "templates/three-way/file.txt",
+            "---\nmerge: three-way\n---\nONE\ntwo\n",
+        );
+
+        generate();
+
+        assert_eq!(
+            fs::read_to_string(sandbox.path().join("test/file.txt")).unwrap(),
+            "ONE\ntwo\nlocal\n"
+        );
+    }
+
     #[test]
     fn changes_dest_path() {
         let sandbox = generate_sandbox();
@@ -0,0 +1,91 @@
+use moon_cache::CacheEngine;
+use moon_config::{
+    OutputStoreLayout, PartialInheritedTasksConfig, PartialOutputStoreConfig, PartialRunnerConfig,
+    PartialWorkspaceConfig, WorkspaceProjects,
+};
+use moon_test_utils::{create_sandbox_with_config, predicates::prelude::*, Sandbox};
+use moon_utils::string_vec;
+use rustc_hash::FxHashMap;
+use std::fs;
+
+fn cas_sandbox() -> Sandbox {
+    let workspace_config = PartialWorkspaceConfig {
+        projects: Some(WorkspaceProjects::Sources(FxHashMap::from_iter([(
+            "unix".into(),
+            "unix".to_owned(),
+        )]))),
+        runner: Some(PartialRunnerConfig {
+            output_store: Some(PartialOutputStoreConfig {
+                layout: Some(OutputStoreLayout::Cas),
+                ..PartialOutputStoreConfig::default()
+            }),
+            ..PartialRunnerConfig::default()
+        }),
+        ..PartialWorkspaceConfig::default()
+    };
+
+    let tasks_config = PartialInheritedTasksConfig {
+        implicit_inputs: Some(string_vec![]),
+        ..PartialInheritedTasksConfig::default()
+    };
+
+    let sandbox =
+        create_sandbox_with_config("system", Some(workspace_config), None, Some(tasks_config));
+
+    sandbox.enable_git();
+    sandbox
+}
+
+fn extract_hash_from_run(sandbox: &Sandbox, target_id: &str) -> String {
+    CacheEngine::load(sandbox.path())
+        .unwrap()
+        .cache_run_target_state(target_id)
+        .unwrap()
+        .hash
+}
+
+#[cfg(not(windows))]
+#[test]
+fn stores_blobs_instead_of_archive() {
+    let sandbox = cas_sandbox();
+
+    sandbox
+        .run_moon(|cmd| {
+            cmd.arg("run").arg("unix:outputs");
+        })
+        .success();
+
+    let hash = extract_hash_from_run(&sandbox, "unix:outputs");
+    let outputs_dir = sandbox.path().join(".moon/cache/outputs");
+
+    assert!(outputs_dir.join(format!("{hash}.json")).exists());
+    assert!(outputs_dir.join("blobs").exists());
+    assert!(!outputs_dir.join(format!("{hash}.tar.gz")).exists());
+}
+
+#[cfg(not(windows))]
+#[test]
+fn hydrates_from_blobs() {
+    let sandbox = cas_sandbox();
+
+    sandbox.run_moon(|cmd| {
+        cmd.arg("run").arg("unix:outputs");
+    });
+
+    fs::remove_file(sandbox.path().join("unix/file.txt")).unwrap();
+    fs::remove_dir_all(sandbox.path().join("unix/folder")).unwrap();
+    fs::remove_file(
+        sandbox
+            .path()
+            .join(".moon/cache/states/unix/outputs/lastRun.json"),
+    )
+    .unwrap();
+
+    let assert = sandbox.run_moon(|cmd| {
+        cmd.arg("run").arg("unix:outputs");
+    });
+
+    assert!(predicate::str::contains("cached").eval(&assert.output()));
+    assert!(sandbox.path().join("unix/file.txt").exists());
+    assert!(sandbox.path().join("unix/folder/subfile.txt").exists());
+}
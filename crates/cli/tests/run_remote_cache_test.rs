@@ -0,0 +1,163 @@
+use httpmock::{prelude::*, Method::HEAD};
+use moon_cache::CacheEngine;
+use moon_config::{
+    PartialInheritedTasksConfig, PartialRemoteCacheConfig, PartialRunnerConfig,
+    PartialWorkspaceConfig, WorkspaceProjects,
+};
+use moon_test_utils::{create_sandbox_with_config, predicates::prelude::*, Sandbox};
+use moon_utils::string_vec;
+use rustc_hash::FxHashMap;
+use std::fs;
+
+fn sandbox(uri: String) -> Sandbox {
+    let workspace_config = PartialWorkspaceConfig {
+        projects: Some(WorkspaceProjects::Sources(FxHashMap::from_iter([(
+            "unix".into(),
+            "unix".to_owned(),
+        )]))),
+        runner: Some(PartialRunnerConfig {
+            remote_cache: Some(PartialRemoteCacheConfig {
+                url: Some(format!("{uri}/cache")),
+                ..PartialRemoteCacheConfig::default()
+            }),
+            ..PartialRunnerConfig::default()
+        }),
+        ..PartialWorkspaceConfig::default()
+    };
+
+    let tasks_config = PartialInheritedTasksConfig {
+        implicit_inputs: Some(string_vec![]),
+        ..PartialInheritedTasksConfig::default()
+    };
+
+    let sandbox =
+        create_sandbox_with_config("system", Some(workspace_config), None, Some(tasks_config));
+
+    sandbox.enable_git();
+    sandbox
+}
+
+fn extract_hash_from_run(sandbox: &Sandbox, target_id: &str) -> String {
+    CacheEngine::load(sandbox.path())
+        .unwrap()
+        .cache_run_target_state(target_id)
+        .unwrap()
+        .hash
+}
+
+#[cfg(not(windows))]
+#[test]
+fn uploads_archive_on_cache_miss() {
+    let server = MockServer::start();
+
+    let check = server.mock(|when, then| {
+        when.method(HEAD).path_contains("/cache/");
+        then.status(404);
+    });
+
+    let upload = server.mock(|when, then| {
+        when.method(PUT).path_contains("/cache/");
+        then.status(200);
+    });
+
+    let sandbox = sandbox(server.url(""));
+
+    let assert = sandbox.run_moon(|cmd| {
+        cmd.arg("run").arg("unix:outputs");
+    });
+
+    check.assert_hits(1);
+    upload.assert_hits(1);
+
+    assert.success();
+}
+
+#[cfg(not(windows))]
+#[test]
+fn doesnt_upload_when_not_writable() {
+    let server = MockServer::start();
+
+    server.mock(|when, then| {
+        when.method(HEAD).path_contains("/cache/");
+        then.status(404);
+    });
+
+    let upload = server.mock(|when, then| {
+        when.method(PUT).path_contains("/cache/");
+        then.status(200);
+    });
+
+    let sandbox = sandbox(server.url(""));
+
+    let assert = sandbox.run_moon(|cmd| {
+        cmd.arg("run").arg("unix:outputs").env("MOON_CACHE", "read");
+    });
+
+    upload.assert_hits(0);
+
+    assert.success();
+}
+
+#[cfg(not(windows))]
+#[test]
+fn hydrates_from_remote_archive() {
+    let server = MockServer::start();
+
+    let mut check = server.mock(|when, then| {
+        when.method(HEAD).path_contains("/cache/");
+        then.status(404);
+    });
+
+    let mut upload = server.mock(|when, then| {
+        when.method(PUT).path_contains("/cache/");
+        then.status(200);
+    });
+
+    let sandbox = sandbox(server.url(""));
+
+    sandbox.run_moon(|cmd| {
+        cmd.arg("run").arg("unix:outputs");
+    });
+
+    let hash = extract_hash_from_run(&sandbox, "unix:outputs");
+    let archive_path = sandbox
+        .path()
+        .join(".moon/cache/outputs")
+        .join(format!("{hash}.tar.gz"));
+    let archive = fs::read(&archive_path).unwrap();
+
+    // Remove everything locally so that only the remote can hydrate
+    fs::remove_file(&archive_path).unwrap();
+    fs::remove_file(sandbox.path().join("unix/file.txt")).unwrap();
+    fs::remove_dir_all(sandbox.path().join("unix/folder")).unwrap();
+    fs::remove_file(
+        sandbox
+            .path()
+            .join(".moon/cache/states/unix/outputs/lastRun.json"),
+    )
+    .unwrap();
+
+    check.delete();
+    upload.delete();
+
+    let check = server.mock(|when, then| {
+        when.method(HEAD).path(format!("/cache/{hash}"));
+        then.status(200);
+    });
+
+    let download = server.mock(|when, then| {
+        when.method(GET).path(format!("/cache/{hash}"));
+        then.status(200).body(archive);
+    });
+
+    let assert = sandbox.run_moon(|cmd| {
+        cmd.arg("run").arg("unix:outputs");
+    });
+
+    check.assert_hits(1);
+    download.assert_hits(1);
+
+    assert!(predicate::str::contains("cached from remote").eval(&assert.output()));
+    assert!(sandbox.path().join("unix/file.txt").exists());
+    assert!(sandbox.path().join("unix/folder/subfile.txt").exists());
+}
@@ -1,9 +1,14 @@
-use moon_config::{PartialInheritedTasksConfig, PartialWorkspaceConfig, WorkspaceProjects};
+use moon_config::{
+    PartialInheritedTasksConfig, PartialNotifierConfig, PartialSubscriberConfig,
+    PartialWorkspaceConfig, WorkspaceProjects,
+};
 use moon_test_utils::{
     assert_snapshot, create_sandbox_with_config, predicates::prelude::*, Sandbox,
 };
 use moon_utils::string_vec;
 use rustc_hash::FxHashMap;
+use std::fs;
+use std::time::{Duration, Instant};
 
 fn system_sandbox() -> Sandbox {
     let workspace_config = PartialWorkspaceConfig {
@@ -190,6 +195,28 @@ mod unix {
         assert_snapshot!(assert.output());
     }
 
+    #[test]
+    fn terminates_on_timeout() {
+        let sandbox = system_sandbox();
+
+        let assert = sandbox.run_moon(|cmd| {
+            cmd.arg("run").arg("unix:timeout");
+        });
+
+        assert_snapshot!(assert.output());
+    }
+
+    #[test]
+    fn retries_on_timeout() {
+        let sandbox = system_sandbox();
+
+        let assert = sandbox.run_moon(|cmd| {
+            cmd.arg("run").arg("unix:timeoutRetry");
+        });
+
+        assert_snapshot!(assert.output());
+    }
+
     #[test]
     fn can_run_many_targets() {
         let sandbox = system_sandbox();
@@ -208,6 +235,248 @@ mod unix {
         assert!(predicate::str::contains("unix:baz | baz").eval(&output));
     }
 
+    #[test]
+    fn generates_a_trace() {
+        let sandbox = system_sandbox();
+
+        sandbox.run_moon(|cmd| {
+            cmd.arg("run")
+                .arg("unix:foo")
+                .arg("unix:bar")
+                .arg("--trace")
+                .arg("trace.json");
+        });
+
+        let trace: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(sandbox.path().join("trace.json")).unwrap())
+                .unwrap();
+        let events = trace["traceEvents"].as_array().unwrap();
+        let names = events
+            .iter()
+            .map(|event| event["name"].as_str().unwrap())
+            .collect::<Vec<_>>();
+
+        assert!(names.contains(&"SetupSystemTool"));
+        assert!(names.contains(&"RunTarget(unix:foo)"));
+        assert!(names.contains(&"RunTarget(unix:bar)"));
+        assert!(names.contains(&"Attempt 1"));
+        assert!(names.contains(&"thread_name"));
+        assert!(events
+            .iter()
+            .all(|event| event["tid"].as_u64().unwrap() > 0));
+    }
+
+    #[test]
+    fn generates_junit_report() {
+        let sandbox = system_sandbox();
+
+        sandbox.run_moon(|cmd| {
+            cmd.arg("run")
+                .arg("unix:echo")
+                .arg("unix:exitNonZero")
+                .arg("--reporter")
+                .arg("junit=report.xml");
+        });
+
+        let report = fs::read_to_string(sandbox.path().join("report.xml")).unwrap();
+
+        assert!(report.contains(r#"<testsuite name="unix" tests="2" failures="1""#));
+        assert!(report.contains(r#"<testcase name="unix:echo" classname="unix""#));
+        assert!(report.contains(r#"<testcase name="unix:exitNonZero" classname="unix""#));
+        assert!(report.contains("stderr</failure>"));
+    }
+
+    #[test]
+    fn doesnt_report_logs_of_a_previous_run() {
+        let sandbox = system_sandbox();
+
+        sandbox.run_moon(|cmd| {
+            cmd.arg("run").arg("unix:exitNonZero");
+        });
+
+        // Fail before the process is spawned, so that logs aren't written
+        let config = fs::read_to_string(sandbox.path().join("unix/moon.yml")).unwrap();
+
+        sandbox.create_file(
+            "unix/moon.yml",
+            config.replacen(
+                "command: bash\n    args: ./exitNonZero.sh",
+                "command: unknown-binary-that-doesnt-exist\n    options:\n      shell: false",
+                1,
+            ),
+        );
+
+        sandbox.run_moon(|cmd| {
+            cmd.arg("run")
+                .arg("unix:exitNonZero")
+                .arg("--reporter")
+                .arg("junit=report.xml");
+        });
+
+        let report = fs::read_to_string(sandbox.path().join("report.xml")).unwrap();
+
+        assert!(report.contains(r#"<testsuite name="unix" tests="1" failures="1""#));
+        assert!(!report.contains("stderr</failure>"));
+    }
+
+    #[test]
+    fn generates_github_summary() {
+        let sandbox = system_sandbox();
+
+        sandbox.run_moon(|cmd| {
+            cmd.arg("run")
+                .arg("unix:echo")
+                .arg("--reporter")
+                .arg("github=summary.md");
+        });
+
+        let summary = fs::read_to_string(sandbox.path().join("summary.md")).unwrap();
+
+        assert!(summary.contains("| `unix:echo` | ✅ passed |"));
+    }
+
+    fn subscriber_sandbox(script: &str, timeout: Option<u64>) -> Sandbox {
+        let workspace_config = PartialWorkspaceConfig {
+            notifier: Some(PartialNotifierConfig {
+                subscribers: Some(vec![PartialSubscriberConfig {
+                    args: Some(string_vec!["subscriber.sh"]),
+                    command: Some("bash".into()),
+                    timeout,
+                    ..PartialSubscriberConfig::default()
+                }]),
+                ..PartialNotifierConfig::default()
+            }),
+            projects: Some(WorkspaceProjects::Sources(FxHashMap::from_iter([(
+                "unix".into(),
+                "unix".to_owned(),
+            )]))),
+            ..PartialWorkspaceConfig::default()
+        };
+
+        let sandbox = create_sandbox_with_config("system", Some(workspace_config), None, None);
+
+        sandbox.create_file("subscriber.sh", script);
+        sandbox.enable_git();
+        sandbox
+    }
+
+    #[test]
+    fn streams_events_to_subscribers() {
+        let sandbox = subscriber_sandbox(
+            r#"
+while read -r line; do
+  echo "$line" >> events.log
+  echo '{"flow":"continue"}'
+done
+"#,
+            None,
+        );
+
+        let assert = sandbox.run_moon(|cmd| {
+            cmd.arg("run").arg("unix:echo");
+        });
+
+        assert.success();
+
+        let events = fs::read_to_string(sandbox.path().join("events.log")).unwrap();
+        let types = events
+            .lines()
+            .map(|line| {
+                serde_json::from_str::<serde_json::Value>(line).unwrap()["type"]
+                    .as_str()
+                    .unwrap()
+                    .to_owned()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(types.first().unwrap(), "pipeline.started");
+        assert!(types.contains(&"target.ran".to_owned()));
+        assert_eq!(types.last().unwrap(), "pipeline.finished");
+    }
+
+    #[test]
+    fn disables_subscribers_that_dont_respond() {
+        let sandbox = subscriber_sandbox(
+            r#"
+while read -r line; do
+  echo "$line" >> events.log
+done
+"#,
+            Some(1),
+        );
+
+        let assert = sandbox.run_moon(|cmd| {
+            cmd.arg("run").arg("unix:echo");
+        });
+
+        assert
+            .success()
+            .stderr(predicate::str::contains("no response within 1 seconds"));
+
+        // Only the first event is sent before being disabled
+        let events = fs::read_to_string(sandbox.path().join("events.log")).unwrap();
+
+        assert_eq!(events.lines().count(), 1);
+    }
+
+    #[test]
+    fn kills_subscribers_that_dont_exit() {
+        let sandbox = subscriber_sandbox(
+            r#"
+while read -r line; do
+  echo '{"flow":"continue"}'
+done
+exec sleep 100
+"#,
+            Some(1),
+        );
+
+        let start = Instant::now();
+        let assert = sandbox.run_moon(|cmd| {
+            cmd.arg("run").arg("unix:echo");
+        });
+
+        assert
+            .success()
+            .stderr(predicate::str::contains("did not exit within 1 seconds"));
+
+        assert!(start.elapsed() < Duration::from_secs(30));
+    }
+
+    #[test]
+    fn subscribers_can_veto_a_run() {
+        let sandbox = subscriber_sandbox(
+            r#"
+while read -r line; do
+  echo '{"flow":"break"}'
+done
+"#,
+            None,
+        );
+
+        let assert = sandbox.run_moon(|cmd| {
+            cmd.arg("run").arg("unix:echo");
+        });
+
+        assert
+            .failure()
+            .stderr(predicate::str::contains("vetoed by a subscriber"));
+    }
+
+    #[test]
+    fn errors_for_unknown_reporter() {
+        let sandbox = system_sandbox();
+
+        let assert = sandbox.run_moon(|cmd| {
+            cmd.arg("run")
+                .arg("unix:echo")
+                .arg("--reporter")
+                .arg("unknown");
+        });
+
+        assert!(predicate::str::contains("unknown reporter \"unknown\"").eval(&assert.output()));
+    }
+
     #[test]
     fn supports_multi_commands_ampersand() {
         let sandbox = system_sandbox();
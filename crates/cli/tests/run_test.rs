@@ -1678,6 +1678,173 @@ mod affected {
     }
 }
 
+mod watch {
+    use super::*;
+    use moon_test_utils::{assert_cmd, create_sandbox};
+    use std::path::PathBuf;
+    use std::process::{Child, Command, Stdio};
+    use std::thread;
+    use std::time::Duration;
+
+    struct WatchProcess(Child, PathBuf);
+
+    impl Drop for WatchProcess {
+        fn drop(&mut self) {
+            let _ = self.0.kill();
+            let _ = self.0.wait();
+
+            // Persistent targets are orphaned when moon is killed
+            for name in ["app-dev", "server-dev"] {
+                if let Ok(pid) = fs::read_to_string(self.1.join(format!("{name}.pid"))) {
+                    let _ = Command::new("kill").arg(pid.trim()).status();
+                }
+            }
+        }
+    }
+
+    fn is_running(sandbox: &Sandbox, name: &str) -> bool {
+        let pid = fs::read_to_string(sandbox.path().join(format!("{name}.pid"))).unwrap();
+
+        Command::new("kill")
+            .arg("-0")
+            .arg(pid.trim())
+            .status()
+            .unwrap()
+            .success()
+    }
+
+    fn spawn_watch(sandbox: &Sandbox, targets: &[&str]) -> WatchProcess {
+        let child = Command::new(assert_cmd::cargo::cargo_bin("moon"))
+            .current_dir(sandbox.path())
+            .env("MOON_TEST", "true")
+            .env("STARBASE_TEST", "true")
+            .arg("run")
+            .args(targets)
+            .arg("--watch")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        WatchProcess(child, sandbox.path().to_path_buf())
+    }
+
+    fn count_runs(sandbox: &Sandbox, name: &str) -> usize {
+        fs::read_to_string(sandbox.path().join("runs.log"))
+            .unwrap_or_default()
+            .lines()
+            .filter(|line| *line == name)
+            .count()
+    }
+
+    fn wait_for_runs(sandbox: &Sandbox, name: &str, count: usize) {
+        for _ in 0..300 {
+            if count_runs(sandbox, name) >= count {
+                return;
+            }
+
+            thread::sleep(Duration::from_millis(100));
+        }
+
+        panic!(
+            "Expected {name} to run {count} times, ran {} times",
+            count_runs(sandbox, name)
+        );
+    }
+
+    #[test]
+    fn reruns_affected_targets_on_change() {
+        let sandbox = create_sandbox("watch");
+        let _process = spawn_watch(&sandbox, &["app:build", "server:dev"]);
+
+        wait_for_runs(&sandbox, "build", 1);
+        wait_for_runs(&sandbox, "server-dev", 1);
+
+        // Wait for the watcher to be ready
+        thread::sleep(Duration::from_secs(1));
+
+        sandbox.create_file("app/src/file.txt", "changed");
+
+        wait_for_runs(&sandbox, "build", 2);
+
+        assert_eq!(count_runs(&sandbox, "server-dev"), 1);
+    }
+
+    #[test]
+    fn restarts_only_affected_persistent_targets() {
+        let sandbox = create_sandbox("watch");
+        let _process = spawn_watch(&sandbox, &["app:dev", "server:dev"]);
+
+        wait_for_runs(&sandbox, "app-dev", 1);
+        wait_for_runs(&sandbox, "server-dev", 1);
+
+        thread::sleep(Duration::from_secs(1));
+
+        sandbox.create_file("server/src/file.txt", "changed");
+
+        wait_for_runs(&sandbox, "server-dev", 2);
+
+        sandbox.create_file("server/src/file.txt", "changed again");
+
+        wait_for_runs(&sandbox, "server-dev", 3);
+
+        assert_eq!(count_runs(&sandbox, "app-dev"), 1);
+        assert!(is_running(&sandbox, "app-dev"));
+    }
+
+    #[test]
+    fn ignores_files_that_are_not_inputs() {
+        let sandbox = create_sandbox("watch");
+        let _process = spawn_watch(&sandbox, &["app:build"]);
+
+        wait_for_runs(&sandbox, "build", 1);
+
+        thread::sleep(Duration::from_secs(1));
+
+        sandbox.create_file("app/README.md", "changed");
+        sandbox.create_file("server/src/file.txt", "changed");
+
+        thread::sleep(Duration::from_secs(2));
+
+        assert_eq!(count_runs(&sandbox, "build"), 1);
+
+        // Still watching after ignoring changes
+        sandbox.create_file("app/src/file.txt", "changed");
+
+        wait_for_runs(&sandbox, "build", 2);
+    }
+
+    #[test]
+    fn errors_with_interactive() {
+        let sandbox = cases_sandbox();
+
+        let assert = sandbox.run_moon(|cmd| {
+            cmd.arg("run")
+                .arg("base:standard")
+                .arg("--watch")
+                .arg("--interactive");
+        });
+
+        assert.failure().stderr(predicate::str::contains(
+            "cannot be used with '--interactive'",
+        ));
+    }
+
+    #[test]
+    fn errors_for_unknown_target_before_watching() {
+        let sandbox = cases_sandbox();
+        sandbox.enable_git();
+
+        let assert = sandbox.run_moon(|cmd| {
+            cmd.arg("run").arg("base:unknown").arg("--watch");
+        });
+
+        assert
+            .failure()
+            .stdout(predicate::str::contains("Watching for changes").not());
+    }
+}
+
 mod interactive {
     use super::*;
 
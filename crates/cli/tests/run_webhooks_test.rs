@@ -1,12 +1,15 @@
 use httpmock::prelude::*;
-use moon_config::PartialNotifierConfig;
+use moon_config::{PartialNotifierConfig, PartialWorkspaceConfig, WorkspaceProjects};
 use moon_test_utils::{create_sandbox_with_config, get_node_fixture_configs, Sandbox};
+use moon_utils::string_vec;
+use rustc_hash::FxHashMap;
 
 fn sandbox(uri: String) -> Sandbox {
     let (mut workspace_config, toolchain_config, tasks_config) = get_node_fixture_configs();
 
     workspace_config.notifier = Some(PartialNotifierConfig {
         webhook_url: Some(format!("{uri}/webhook")),
+        ..PartialNotifierConfig::default()
     });
 
     let sandbox = create_sandbox_with_config(
@@ -20,6 +23,22 @@ fn sandbox(uri: String) -> Sandbox {
     sandbox
 }
 
+fn system_sandbox(notifier_config: PartialNotifierConfig) -> Sandbox {
+    let workspace_config = PartialWorkspaceConfig {
+        notifier: Some(notifier_config),
+        projects: Some(WorkspaceProjects::Sources(FxHashMap::from_iter([(
+            "unix".into(),
+            "unix".to_owned(),
+        )]))),
+        ..PartialWorkspaceConfig::default()
+    };
+
+    let sandbox = create_sandbox_with_config("system", Some(workspace_config), None, None);
+
+    sandbox.enable_git();
+    sandbox
+}
+
 #[tokio::test]
 async fn sends_webhooks() {
     let server = MockServer::start();
@@ -80,7 +99,8 @@ async fn doesnt_send_webhooks_if_first_fails() {
         cmd.arg("run").arg("node:cjs");
     });
 
-    mock.assert_hits(1);
+    // Initial attempt + retries
+    mock.assert_hits(4);
 }
 
 #[tokio::test]
@@ -103,3 +123,148 @@ async fn all_webhooks_have_same_uuid() {
 
     mock.assert_hits(19);
 }
+
+#[cfg(not(windows))]
+mod delivery {
+    use super::*;
+
+    #[tokio::test]
+    async fn only_sends_filtered_events() {
+        let server = MockServer::start();
+
+        let finished = server.mock(|when, then| {
+            when.method(POST)
+                .path("/webhook")
+                .header("X-Moon-Event", "pipeline.finished")
+                .json_body_partial(r#"{"type":"pipeline.finished"}"#);
+            then.status(200);
+        });
+
+        let ran = server.mock(|when, then| {
+            when.method(POST)
+                .path("/webhook")
+                .header("X-Moon-Event", "target.ran")
+                .json_body_partial(r#"{"type":"target.ran"}"#);
+            then.status(200);
+        });
+
+        let sandbox = system_sandbox(PartialNotifierConfig {
+            webhook_events: Some(string_vec!["pipeline.finished", "target.ran"]),
+            webhook_url: Some(server.url("/webhook")),
+            ..PartialNotifierConfig::default()
+        });
+
+        let assert = sandbox.run_moon(|cmd| {
+            cmd.arg("run").arg("unix:echo");
+        });
+
+        assert.success();
+        finished.assert_hits(1);
+        ran.assert_hits(1);
+    }
+
+    #[tokio::test]
+    async fn signs_payloads_with_secret() {
+        let server = MockServer::start();
+
+        let signed = server.mock(|when, then| {
+            when.method(POST).path("/webhook").matches(|req| {
+                let body = String::from_utf8(req.body.clone().unwrap()).unwrap();
+                let signature = req.headers.as_ref().and_then(|headers| {
+                    headers
+                        .iter()
+                        .find(|(name, _)| name.eq_ignore_ascii_case("X-Moon-Signature-256"))
+                        .map(|(_, value)| value.to_owned())
+                });
+
+                signature == Some(moon_notifier::sign_payload("shh", &body))
+            });
+            then.status(200);
+        });
+
+        let sandbox = system_sandbox(PartialNotifierConfig {
+            webhook_events: Some(string_vec!["pipeline.finished"]),
+            webhook_secret: Some("shh".into()),
+            webhook_url: Some(server.url("/webhook")),
+            ..PartialNotifierConfig::default()
+        });
+
+        sandbox.run_moon(|cmd| {
+            cmd.arg("run").arg("unix:echo");
+        });
+
+        signed.assert_hits(1);
+    }
+
+    #[tokio::test]
+    async fn retries_failed_requests() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/webhook");
+            then.status(503);
+        });
+
+        let sandbox = system_sandbox(PartialNotifierConfig {
+            webhook_events: Some(string_vec!["pipeline.finished"]),
+            webhook_url: Some(server.url("/webhook")),
+            ..PartialNotifierConfig::default()
+        });
+
+        let assert = sandbox.run_moon(|cmd| {
+            cmd.arg("run").arg("unix:echo");
+        });
+
+        // Failed webhooks should not fail the run
+        assert.success();
+        mock.assert_hits(4);
+    }
+
+    #[tokio::test]
+    async fn doesnt_retry_client_errors() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/webhook");
+            then.status(400);
+        });
+
+        let sandbox = system_sandbox(PartialNotifierConfig {
+            webhook_events: Some(string_vec!["pipeline.finished"]),
+            webhook_url: Some(server.url("/webhook")),
+            ..PartialNotifierConfig::default()
+        });
+
+        sandbox.run_moon(|cmd| {
+            cmd.arg("run").arg("unix:echo");
+        });
+
+        mock.assert_hits(1);
+    }
+
+    #[tokio::test]
+    async fn flushes_all_queued_requests() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/webhook");
+            then.status(200).delay(std::time::Duration::from_millis(50));
+        });
+
+        let sandbox = system_sandbox(PartialNotifierConfig {
+            webhook_events: Some(string_vec![
+                "target.running",
+                "target.ran",
+                "pipeline.finished"
+            ]),
+            webhook_url: Some(server.url("/webhook")),
+            ..PartialNotifierConfig::default()
+        });
+
+        sandbox.run_moon(|cmd| {
+            cmd.arg("run").arg("unix:echo").arg("unix:ls");
+        });
+
+        mock.assert_hits(5);
+    }
+}
@@ -10,6 +10,7 @@ use moon_target::Target;
 use moon_workspace::Workspace;
 use starbase_styles::color;
 use std::env;
+use std::mem;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -67,7 +68,7 @@ pub async fn run_target(
     let attempts = if is_cache_enabled {
         let context = context.read().await;
 
-        runner.create_and_run_command(&context, runtime).await?
+        runner.create_and_run_command(&context, runtime).await
     } else {
         // Concurrent long-running tasks will cause a deadlock, as some threads will
         // attempt to write to context while others are reading from it, and long-running
@@ -75,7 +76,17 @@ pub async fn run_target(
         // around it, so revisit in the future.
         let context = (context.read().await).clone();
 
-        runner.create_and_run_command(&context, runtime).await?
+        runner.create_and_run_command(&context, runtime).await
+    };
+
+    let attempts = match attempts {
+        Ok(attempts) => attempts,
+        Err(error) => {
+            // Track the attempts of a process that failed, so that reporters know it ran
+            action.set_attempts(mem::take(&mut runner.failed_attempts));
+
+            return Err(error.into());
+        }
     };
 
     let status = if action.set_attempts(attempts) {
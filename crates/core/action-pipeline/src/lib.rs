@@ -3,7 +3,9 @@ mod errors;
 pub mod estimator;
 mod pipeline;
 mod processor;
+pub mod reporter;
 mod run_report;
+pub mod sharder;
 mod subscribers;
 
 pub use errors::*;
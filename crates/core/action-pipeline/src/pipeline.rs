@@ -1,24 +1,38 @@
 use crate::errors::PipelineError;
 use crate::estimator::Estimator;
 use crate::processor::process_action;
+use crate::reporter::Reporter;
 use crate::run_report::RunReport;
+use crate::subscribers::bazel_remote_cache::BazelRemoteCacheSubscriber;
+use crate::subscribers::github_summary::GithubSummarySubscriber;
+use crate::subscribers::junit::JunitSubscriber;
 use crate::subscribers::local_cache::LocalCacheSubscriber;
 use crate::subscribers::moonbase::MoonbaseSubscriber;
+use crate::subscribers::remote_cache::RemoteCacheSubscriber;
+use crate::subscribers::trace::TraceSubscriber;
 use console::Term;
-use moon_action::{Action, ActionStatus};
+use moon_action::{Action, ActionNode, ActionStatus};
 use moon_action_context::ActionContext;
-use moon_dep_graph::DepGraph;
-use moon_emitter::{Emitter, Event};
-use moon_logger::{debug, error, trace};
-use moon_notifier::WebhooksSubscriber;
+use moon_cache::{BazelRemoteCache, RemoteCache};
+use moon_config::RemoteCacheProtocol;
+use moon_dep_graph::{CriticalPaths, DepGraph};
+use moon_emitter::{Emitter, Event, EventFlow, Subscriber};
+use moon_logger::{debug, error, trace, warn};
+use moon_notifier::{ExternalSubscriber, WebhooksSubscriber};
 use moon_project_graph::ProjectGraph;
 use moon_terminal::{label_to_the_moon, ExtendedTerm};
 use moon_utils::{is_ci, is_test_env, time};
 use moon_workspace::Workspace;
+use petgraph::graph::NodeIndex;
+use rustc_hash::FxHashMap;
 use starbase_styles::color;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::{RwLock, Semaphore};
+use tokio::task::JoinSet;
 
 const LOG_TARGET: &str = "moon:action-pipeline";
 
@@ -35,6 +49,10 @@ pub struct Pipeline {
 
     report_name: Option<String>,
 
+    reporters: Vec<Reporter>,
+
+    trace_path: Option<PathBuf>,
+
     workspace: Arc<RwLock<Workspace>>,
 }
 
@@ -46,10 +64,17 @@ impl Pipeline {
             duration: None,
             project_graph: Arc::new(RwLock::new(project_graph)),
             report_name: None,
+            reporters: vec![],
+            trace_path: None,
             workspace: Arc::new(RwLock::new(workspace)),
         }
     }
 
+    pub fn add_reporter(&mut self, reporter: Reporter) -> &mut Self {
+        self.reporters.push(reporter);
+        self
+    }
+
     pub fn bail_on_error(&mut self) -> &mut Self {
         self.bail = true;
         self
@@ -65,6 +90,11 @@ impl Pipeline {
         self
     }
 
+    pub fn generate_trace(&mut self, path: PathBuf) -> &mut Self {
+        self.trace_path = Some(path);
+        self
+    }
+
     pub async fn run(
         &mut self,
         dep_graph: DepGraph,
@@ -73,7 +103,12 @@ impl Pipeline {
         let start = Instant::now();
         let context = Arc::new(RwLock::new(context.unwrap_or_default()));
         let emitter = Arc::new(RwLock::new(
-            create_emitter(Arc::clone(&self.workspace)).await,
+            create_emitter(
+                Arc::clone(&self.workspace),
+                self.trace_path.clone(),
+                &self.reporters,
+            )
+            .await,
         ));
         let workspace = Arc::clone(&self.workspace);
         let project_graph = Arc::clone(&self.project_graph);
@@ -82,117 +117,190 @@ impl Pipeline {
         let mut cached_count = 0;
         let mut failed_count = 0;
 
-        // Queue actions in topological order that need to be processed,
-        // grouped into batches based on dependency requirements
+        // Prioritize actions by the length of their critical path, so that long
+        // chains of dependent actions are started as early as possible
         let total_actions_count = dep_graph.get_node_count();
-        let batches = dep_graph.sort_batched_topological()?;
-        let batches_count = batches.len();
+        let critical_paths = {
+            let weigh = create_action_weigher(&*workspace.read().await, &dep_graph);
+
+            dep_graph.calculate_critical_paths(weigh)?
+        };
         let local_emitter = emitter.read().await;
 
         debug!(
             target: LOG_TARGET,
-            "Running {} actions across {} batches", total_actions_count, batches_count
+            "Running {} actions, prioritized by critical path", total_actions_count
         );
 
-        local_emitter
+        let flow = local_emitter
             .emit(Event::PipelineStarted {
                 actions_count: total_actions_count,
                 context: &*context.read().await,
             })
             .await?;
 
+        // A subscriber has vetoed the run, for example from a policy check
+        if matches!(flow, EventFlow::Break) {
+            let abort_error = "Pipeline run was vetoed by a subscriber.".to_owned();
+
+            error!(target: LOG_TARGET, "{}", abort_error);
+
+            local_emitter
+                .emit(Event::PipelineAborted {
+                    error: abort_error.clone(),
+                })
+                .await?;
+
+            return Err(PipelineError::Aborted(abort_error).into());
+        }
+
         // This limits how many tasks can run in parallel
         let semaphore = Arc::new(Semaphore::new(
             self.concurrency.unwrap_or_else(num_cpus::get),
         ));
 
-        for (b, batch) in batches.into_iter().enumerate() {
-            let batch_index = b + 1;
-            let batch_target_name = format!("{LOG_TARGET}:batch:{batch_index}");
-            let actions_count = batch.len();
-            let mut action_handles = vec![];
-
-            trace!(
-                target: &batch_target_name,
-                "Running {} actions in batch {}",
-                actions_count,
-                batch_index
-            );
-
-            for (i, node_index) in batch.into_iter().enumerate() {
-                let action_index = i + 1;
-
-                if let Some(node) = dep_graph.get_node_from_index(&node_index) {
-                    let context_clone = Arc::clone(&context);
-                    let emitter_clone = Arc::clone(&emitter);
-                    let workspace_clone = Arc::clone(&workspace);
-                    let project_graph_clone = Arc::clone(&project_graph);
-
-                    let mut action = Action::new(node.to_owned());
-                    action.log_target = format!("{batch_target_name}:{action_index}");
-
-                    let Ok(permit) = semaphore.clone().acquire_owned().await else {
-                        break; // Should error?
-                    };
-
-                    action_handles.push(tokio::spawn(async move {
-                        let result = process_action(
-                            action,
-                            context_clone,
-                            emitter_clone,
-                            workspace_clone,
-                            project_graph_clone,
-                        )
-                        .await;
-
-                        drop(permit);
-
-                        result
-                    }));
-                } else {
+        // Track how many dependencies each action is waiting on,
+        // and queue the actions that have none
+        let mut pending_deps = FxHashMap::default();
+        let mut ready_queue = BinaryHeap::new();
+        let mut remaining_count = 0;
+
+        for (node_index, path) in &critical_paths {
+            let deps_count = dep_graph.get_dependencies_of(node_index).len();
+
+            if deps_count == 0 {
+                ready_queue.push((*path, Reverse(*node_index)));
+            }
+
+            pending_deps.insert(*node_index, deps_count);
+        }
+
+        // Persistent targets never complete, so they are held until
+        // all other actions have completed, and then ran together
+        let mut persistent_queue = vec![];
+        let mut run_persistent = false;
+
+        // When the pipeline itself is cancelled (dropped before completion), the actions
+        // are aborted instead of detached, so that their child processes are killed and
+        // not left running in the background.
+        let mut action_handles = JoinSet::new();
+        let mut action_index = 0;
+
+        loop {
+            while let Some((_, Reverse(node_index))) = ready_queue.peek().copied() {
+                let Some(node) = dep_graph.get_node_from_index(&node_index) else {
                     return Err(PipelineError::UnknownActionNode.into());
+                };
+
+                if !run_persistent && matches!(node, ActionNode::RunPersistentTarget(_, _)) {
+                    ready_queue.pop();
+                    persistent_queue.push(node_index);
+
+                    // Nothing can wait on a persistent target to complete
+                    for dep_index in dep_graph.get_dependents_of(&node_index) {
+                        release_action(
+                            dep_index,
+                            &mut pending_deps,
+                            &mut ready_queue,
+                            &critical_paths,
+                        );
+                    }
+
+                    continue;
                 }
+
+                let Ok(permit) = semaphore.clone().try_acquire_owned() else {
+                    break;
+                };
+
+                ready_queue.pop();
+                remaining_count += 1;
+                action_index += 1;
+
+                let context_clone = Arc::clone(&context);
+                let emitter_clone = Arc::clone(&emitter);
+                let workspace_clone = Arc::clone(&workspace);
+                let project_graph_clone = Arc::clone(&project_graph);
+
+                let mut action = Action::new(node.to_owned());
+                action.log_target = format!("{LOG_TARGET}:action:{action_index}");
+
+                trace!(
+                    target: &action.log_target,
+                    "Starting action {} with a critical path of {:?}",
+                    action.label,
+                    critical_paths[&node_index]
+                );
+
+                action_handles.spawn(async move {
+                    let result = process_action(
+                        action,
+                        context_clone,
+                        emitter_clone,
+                        workspace_clone,
+                        project_graph_clone,
+                    )
+                    .await;
+
+                    drop(permit);
+
+                    (node_index, result)
+                });
+            }
+
+            if remaining_count == 0 && ready_queue.is_empty() && !persistent_queue.is_empty() {
+                run_persistent = true;
+
+                for node_index in persistent_queue.drain(..) {
+                    ready_queue.push((critical_paths[&node_index], Reverse(node_index)));
+                }
+
+                continue;
             }
 
-            // Wait for all actions in this batch to complete
-            let mut abort_error = None;
+            // Wait for the next action to complete
+            let Some(handle) = action_handles.join_next().await else {
+                break;
+            };
+
+            remaining_count -= 1;
 
-            for handle in action_handles {
-                if abort_error.is_some() {
-                    if !handle.is_finished() {
-                        handle.abort();
+            let abort_error = match handle {
+                Ok((node_index, Ok(result))) => {
+                    if result.has_failed() {
+                        failed_count += 1;
+                    } else if result.was_cached() {
+                        cached_count += 1;
+                    } else {
+                        passed_count += 1;
                     }
-                } else {
-                    match handle.await {
-                        Ok(Ok(result)) => {
-                            if result.has_failed() {
-                                failed_count += 1;
-                            } else if result.was_cached() {
-                                cached_count += 1;
-                            } else {
-                                passed_count += 1;
-                            }
-
-                            if self.bail && result.has_failed() || result.should_abort() {
-                                abort_error =
-                                    Some(result.error.unwrap_or_else(|| "Unknown error!".into()));
-                            } else {
-                                results.push(result);
-                            }
-                        }
-                        Ok(Err(error)) => {
-                            abort_error = Some(error.to_string());
-                        }
-                        _ => {
-                            abort_error = Some("Unknown error!".into());
+
+                    if self.bail && result.has_failed() || result.should_abort() {
+                        Some(result.error.unwrap_or_else(|| "Unknown error!".into()))
+                    } else {
+                        results.push(result);
+
+                        for dep_index in dep_graph.get_dependents_of(&node_index) {
+                            release_action(
+                                dep_index,
+                                &mut pending_deps,
+                                &mut ready_queue,
+                                &critical_paths,
+                            );
                         }
-                    };
+
+                        None
+                    }
                 }
-            }
+                Ok((_, Err(error))) => Some(error.to_string()),
+                Err(_) => Some("Unknown error!".into()),
+            };
 
             if let Some(abort_error) = abort_error {
+                action_handles.abort_all();
+
                 error!(
-                    target: &batch_target_name,
+                    target: LOG_TARGET,
                     "Encountered a critical error, aborting the action pipeline"
                 );
 
@@ -245,7 +353,7 @@ impl Pipeline {
                 | ActionStatus::Cached
                 | ActionStatus::CachedFromRemote
                 | ActionStatus::Skipped => color::success("pass"),
-                ActionStatus::Failed | ActionStatus::FailedAndAbort => {
+                ActionStatus::Failed | ActionStatus::FailedAndAbort | ActionStatus::TimedOut => {
                     failed = true;
                     color::failure("fail")
                 }
@@ -307,7 +415,7 @@ impl Pipeline {
                 ActionStatus::Passed | ActionStatus::Skipped => {
                     pass_count += 1;
                 }
-                ActionStatus::Failed | ActionStatus::FailedAndAbort => {
+                ActionStatus::Failed | ActionStatus::FailedAndAbort | ActionStatus::TimedOut => {
                     fail_count += 1;
                 }
                 ActionStatus::Invalid => {
@@ -380,19 +488,113 @@ impl Pipeline {
     }
 }
 
-async fn create_emitter(workspace: Arc<RwLock<Workspace>>) -> Emitter {
+/// Decrement the pending dependencies of an action, and queue it once there are none.
+fn release_action(
+    node_index: NodeIndex,
+    pending_deps: &mut FxHashMap<NodeIndex, usize>,
+    ready_queue: &mut BinaryHeap<(Duration, Reverse<NodeIndex>)>,
+    critical_paths: &CriticalPaths,
+) {
+    if let Some(count) = pending_deps.get_mut(&node_index) {
+        *count -= 1;
+
+        if *count == 0 {
+            ready_queue.push((
+                critical_paths.get(&node_index).copied().unwrap_or_default(),
+                Reverse(node_index),
+            ));
+        }
+    }
+}
+
+/// Weigh actions based on how long their target took to run previously. Actions
+/// without any history are assumed to take the average duration.
+fn create_action_weigher(
+    workspace: &Workspace,
+    dep_graph: &DepGraph,
+) -> impl Fn(&ActionNode) -> Duration {
+    let mut durations = FxHashMap::default();
+
+    for node_index in dep_graph.sort_topological().unwrap_or_default() {
+        let Some(
+            ActionNode::RunTarget(_, target) | ActionNode::RunPersistentTarget(_, target),
+        ) = dep_graph.get_node_from_index(&node_index)
+        else {
+            continue;
+        };
+
+        if let Ok(state) = workspace.cache.cache_run_target_state(&target.id) {
+            if state.last_run_duration > 0 {
+                durations.insert(
+                    target.to_owned(),
+                    Duration::from_millis(state.last_run_duration as u64),
+                );
+            }
+        }
+    }
+
+    let fallback = if durations.is_empty() {
+        Duration::from_secs(1)
+    } else {
+        durations.values().sum::<Duration>() / durations.len() as u32
+    };
+
+    move |node| match node {
+        ActionNode::RunTarget(_, target) | ActionNode::RunPersistentTarget(_, target) => {
+            durations.get(target).copied().unwrap_or(fallback)
+        }
+        // Setup, install, and sync actions are typically quick (or a no-op when cached),
+        // so they shouldn't be weighed like a task, which would inflate critical paths
+        _ => Duration::from_millis(10),
+    }
+}
+
+async fn create_emitter(
+    workspace: Arc<RwLock<Workspace>>,
+    trace_path: Option<PathBuf>,
+    reporters: &[Reporter],
+) -> Emitter {
     let mut emitter = Emitter::new(Arc::clone(&workspace));
 
+    // Tracing and reporters must be first, as other subscribers may stop propagation
+    if let Some(trace_path) = trace_path {
+        emitter
+            .subscribers
+            .push(Arc::new(RwLock::new(TraceSubscriber::new(trace_path))));
+    }
+
+    for reporter in reporters {
+        emitter.subscribers.push(match reporter {
+            Reporter::GithubSummary(path) => {
+                Arc::new(RwLock::new(GithubSummarySubscriber::new(path.to_owned())))
+                    as Arc<RwLock<dyn Subscriber>>
+            }
+            Reporter::Junit(path) => Arc::new(RwLock::new(JunitSubscriber::new(path.to_owned()))),
+        });
+    }
+
     {
         let local_workspace = workspace.read().await;
 
+        for subscriber_config in &local_workspace.config.notifier.subscribers {
+            emitter
+                .subscribers
+                .push(Arc::new(RwLock::new(ExternalSubscriber::new(
+                    subscriber_config,
+                ))));
+        }
+
         // For security and privacy purposes, only send webhooks from a CI environment
         if is_ci() || is_test_env() {
-            if let Some(webhook_url) = &local_workspace.config.notifier.webhook_url {
+            let notifier_config = &local_workspace.config.notifier;
+
+            if let Some(webhook_url) = &notifier_config.webhook_url {
                 emitter
                     .subscribers
                     .push(Arc::new(RwLock::new(WebhooksSubscriber::new(
                         webhook_url.to_owned(),
+                        notifier_config.webhook_events.clone(),
+                        notifier_config.webhook_secret.clone(),
                     ))));
             }
         }
@@ -402,6 +604,40 @@ async fn create_emitter(workspace: Arc<RwLock<Workspace>>) -> Emitter {
                 .subscribers
                 .push(Arc::new(RwLock::new(MoonbaseSubscriber::new())));
         }
+
+        let remote_cache_config = &local_workspace.config.runner.remote_cache;
+        let remote_cache = match remote_cache_config.protocol {
+            RemoteCacheProtocol::Grpc => {
+                BazelRemoteCache::from_config(remote_cache_config).map(|remote| {
+                    remote.map(|r| {
+                        Arc::new(RwLock::new(BazelRemoteCacheSubscriber::new(r)))
+                            as Arc<RwLock<dyn Subscriber>>
+                    })
+                })
+            }
+            RemoteCacheProtocol::Http => {
+                RemoteCache::from_config(remote_cache_config).map(|remote| {
+                    remote.map(|r| {
+                        Arc::new(RwLock::new(RemoteCacheSubscriber::new(r)))
+                            as Arc<RwLock<dyn Subscriber>>
+                    })
+                })
+            }
+        };
+
+        match remote_cache {
+            Ok(Some(subscriber)) => {
+                emitter.subscribers.push(subscriber);
+            }
+            Ok(None) => {}
+            Err(error) => {
+                warn!(
+                    target: LOG_TARGET,
+                    "Unable to configure remote cache, falling back to local cache. Failure: {}",
+                    color::muted_light(error.to_string())
+                );
+            }
+        };
     }
 
     // Must be last as its the final line of defense
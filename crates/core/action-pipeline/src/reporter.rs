@@ -0,0 +1,129 @@
+use moon_action::{Action, ActionNode, ActionStatus};
+use moon_target::Target;
+use moon_workspace::Workspace;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+pub use crate::subscribers::github_summary::render_github_summary;
+pub use crate::subscribers::junit::render_junit;
+
+/// A reporter that writes the results of all ran targets to a file,
+/// in a format that is natively supported by CI systems.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Reporter {
+    /// GitHub Actions step summary (markdown). When a path is not provided,
+    /// the summary will be appended to `GITHUB_STEP_SUMMARY`.
+    GithubSummary(Option<PathBuf>),
+
+    /// JUnit XML, with a test case per target.
+    Junit(PathBuf),
+}
+
+impl Reporter {
+    /// Resolve relative file paths against the provided directory.
+    pub fn resolve(self, dir: &Path) -> Self {
+        match self {
+            Reporter::GithubSummary(path) => {
+                Reporter::GithubSummary(path.map(|path| dir.join(path)))
+            }
+            Reporter::Junit(path) => Reporter::Junit(dir.join(path)),
+        }
+    }
+}
+
+impl FromStr for Reporter {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (name, path) = match value.split_once('=') {
+            Some((name, path)) => (name, Some(PathBuf::from(path))),
+            None => (value, None),
+        };
+
+        match (name, path) {
+            ("github", path) => Ok(Reporter::GithubSummary(path)),
+            ("junit", Some(path)) => Ok(Reporter::Junit(path)),
+            ("junit", None) => {
+                Err("the junit reporter requires a file path, for example junit=report.xml".into())
+            }
+            (name, _) => Err(format!(
+                "unknown reporter \"{name}\", expected github or junit"
+            )),
+        }
+    }
+}
+
+/// The result of a ran target, as collected by reporters.
+#[derive(Debug)]
+pub struct TargetResult {
+    pub duration: Duration,
+
+    pub error: Option<String>,
+
+    pub status: ActionStatus,
+
+    pub stderr: String,
+
+    pub target: Target,
+}
+
+impl TargetResult {
+    /// Create a result from a finished action, if the action ran a target.
+    pub fn from_action(action: &Action, node: &ActionNode, workspace: &Workspace) -> Option<Self> {
+        let (ActionNode::RunTarget(_, target) | ActionNode::RunPersistentTarget(_, target)) = node
+        else {
+            return None;
+        };
+
+        let has_ran = action
+            .attempts
+            .as_ref()
+            .map(|attempts| !attempts.is_empty())
+            .unwrap_or_default();
+
+        // Logs on disk may be from a previous run when the process didn't run
+        // in this pipeline (skipped, hydrated, or failed to start), so avoid them
+        let stderr = if has_ran {
+            workspace
+                .cache
+                .cache_run_target_state(&target.id)
+                .and_then(|state| state.load_output_logs())
+                .map(|(_, stderr)| stderr)
+                .unwrap_or_default()
+        } else {
+            String::new()
+        };
+
+        Some(TargetResult {
+            duration: action.duration.unwrap_or_default(),
+            error: action.error.clone(),
+            status: action.status,
+            stderr,
+            target: target.to_owned(),
+        })
+    }
+
+    pub fn has_failed(&self) -> bool {
+        matches!(
+            self.status,
+            ActionStatus::Failed | ActionStatus::FailedAndAbort | ActionStatus::TimedOut
+        )
+    }
+
+    pub fn was_skipped(&self) -> bool {
+        matches!(self.status, ActionStatus::Skipped | ActionStatus::Invalid)
+    }
+
+    pub fn get_status_label(&self) -> &'static str {
+        match self.status {
+            ActionStatus::Cached | ActionStatus::CachedFromRemote => "cached",
+            ActionStatus::Failed | ActionStatus::FailedAndAbort => "failed",
+            ActionStatus::Invalid => "invalid",
+            ActionStatus::Passed => "passed",
+            ActionStatus::Running => "running",
+            ActionStatus::Skipped => "skipped",
+            ActionStatus::TimedOut => "timed out",
+        }
+    }
+}
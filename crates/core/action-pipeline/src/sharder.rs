@@ -0,0 +1,238 @@
+use moon_logger::{debug, warn};
+use moon_target::Target;
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::Deserialize;
+use starbase_styles::color;
+use starbase_utils::json;
+use std::path::Path;
+use std::time::Duration;
+
+const LOG_TARGET: &str = "moon:action-pipeline:sharder";
+
+#[derive(Deserialize)]
+struct ReportAction {
+    duration: Option<Duration>,
+    label: String,
+}
+
+#[derive(Deserialize)]
+struct Report {
+    actions: Vec<ReportAction>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Shard {
+    /// Estimated duration of all targets in the shard, based on historical data.
+    pub duration: Duration,
+
+    pub targets: Vec<Target>,
+}
+
+/// Distributes targets across a fixed number of shards (CI jobs), using historical
+/// durations so that each shard takes roughly the same amount of time to complete.
+/// The distribution is deterministic, so that every job computes the same shards.
+#[derive(Default)]
+pub struct Sharder {
+    durations: FxHashMap<Target, Duration>,
+    links: FxHashMap<Target, Vec<Target>>,
+}
+
+impl Sharder {
+    pub fn new() -> Self {
+        Sharder::default()
+    }
+
+    /// Load target durations from a previously generated run report.
+    /// Invalid or missing reports are ignored, as they are purely informational.
+    pub fn load_report(&mut self, path: &Path) {
+        if !path.exists() {
+            return;
+        }
+
+        let report: Report = match json::read_file(path) {
+            Ok(report) => report,
+            Err(error) => {
+                warn!(
+                    target: LOG_TARGET,
+                    "Failed to load run report {}, ignoring: {}",
+                    color::path(path),
+                    color::muted_light(error.to_string()),
+                );
+
+                return;
+            }
+        };
+
+        debug!(
+            target: LOG_TARGET,
+            "Loading historical durations from run report {}",
+            color::path(path),
+        );
+
+        for action in report.actions {
+            let Some(duration) = action.duration else {
+                continue;
+            };
+
+            let Some(target_id) = action
+                .label
+                .strip_prefix("RunTarget(")
+                .and_then(|label| label.strip_suffix(')'))
+            else {
+                continue;
+            };
+
+            if let Ok(target) = Target::parse(target_id) {
+                self.durations.insert(target, duration);
+            }
+        }
+    }
+
+    /// Return true if a duration has been recorded for the target.
+    pub fn has_duration(&self, target: &Target) -> bool {
+        self.durations.contains_key(target)
+    }
+
+    /// Set the historical duration for a target, overwriting any existing value.
+    pub fn set_duration(&mut self, target: &Target, duration: Duration) {
+        self.durations.insert(target.to_owned(), duration);
+    }
+
+    /// Link a target to one of its dependencies. Linked targets are
+    /// kept on the same shard when it does not unbalance the shards.
+    pub fn link(&mut self, target: &Target, dep: &Target) {
+        self.links
+            .entry(target.to_owned())
+            .or_default()
+            .push(dep.to_owned());
+    }
+
+    /// Distribute the provided targets across the total number of shards.
+    pub fn distribute(&self, targets: &[Target], total: usize) -> Vec<Shard> {
+        let mut shards = (0..total.max(1))
+            .map(|_| Shard::default())
+            .collect::<Vec<_>>();
+
+        // Targets without a known duration are assumed to be average
+        let fallback = self.get_fallback_duration(targets);
+        let get_duration = |target: &Target| -> Duration {
+            self.durations.get(target).copied().unwrap_or(fallback)
+        };
+
+        let mut units = vec![];
+        let groups = self.group_targets(targets);
+        let ideal = targets.iter().map(get_duration).sum::<Duration>() / shards.len() as u32;
+
+        for group in groups {
+            let duration = group.iter().map(get_duration).sum::<Duration>();
+
+            // Keeping a group together would exceed the ideal shard duration,
+            // so break it apart and distribute each target individually
+            if group.len() > 1 && duration > ideal {
+                for target in group {
+                    units.push((get_duration(&target), vec![target]));
+                }
+            } else {
+                units.push((duration, group));
+            }
+        }
+
+        // Longest processing time first, with IDs to break ties
+        units.sort_by(|a, d| d.0.cmp(&a.0).then_with(|| a.1[0].cmp(&d.1[0])));
+
+        for (duration, group) in units {
+            let shard = shards
+                .iter_mut()
+                .min_by_key(|shard| shard.duration)
+                .unwrap();
+
+            shard.duration += duration;
+            shard.targets.extend(group);
+        }
+
+        for shard in &mut shards {
+            shard.targets.sort();
+        }
+
+        shards
+    }
+
+    fn get_fallback_duration(&self, targets: &[Target]) -> Duration {
+        let known = targets
+            .iter()
+            .filter_map(|target| self.durations.get(target))
+            .collect::<Vec<_>>();
+
+        if known.is_empty() {
+            return Duration::from_secs(1);
+        }
+
+        known.iter().copied().sum::<Duration>() / known.len() as u32
+    }
+
+    /// Group targets that are connected through their dependencies,
+    /// including transitive dependencies that are not being distributed.
+    fn group_targets(&self, targets: &[Target]) -> Vec<Vec<Target>> {
+        let mut sorted_targets = targets.to_vec();
+        sorted_targets.sort();
+        sorted_targets.dedup();
+
+        let indexes = sorted_targets
+            .iter()
+            .enumerate()
+            .map(|(index, target)| (target, index))
+            .collect::<FxHashMap<_, _>>();
+        let mut parents = (0..sorted_targets.len()).collect::<Vec<_>>();
+
+        fn find(parents: &mut [usize], index: usize) -> usize {
+            let mut root = index;
+
+            while parents[root] != root {
+                root = parents[root];
+            }
+
+            parents[index] = root;
+            root
+        }
+
+        for (index, target) in sorted_targets.iter().enumerate() {
+            let mut visited = FxHashSet::default();
+            let mut queue = vec![target];
+
+            while let Some(current) = queue.pop() {
+                let Some(deps) = self.links.get(current) else {
+                    continue;
+                };
+
+                for dep in deps {
+                    if !visited.insert(dep) {
+                        continue;
+                    }
+
+                    if let Some(dep_index) = indexes.get(dep) {
+                        let a = find(&mut parents, index);
+                        let b = find(&mut parents, *dep_index);
+
+                        // Always use the lowest index as the root for determinism
+                        parents[a.max(b)] = a.min(b);
+                    }
+
+                    queue.push(dep);
+                }
+            }
+        }
+
+        let mut groups: Vec<Vec<Target>> = vec![vec![]; sorted_targets.len()];
+
+        for (index, target) in sorted_targets.iter().enumerate() {
+            let root = find(&mut parents, index);
+
+            groups[root].push(target.to_owned());
+        }
+
+        groups
+            .into_iter()
+            .filter(|group| !group.is_empty())
+            .collect()
+    }
+}
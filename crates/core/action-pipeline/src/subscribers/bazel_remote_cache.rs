@@ -0,0 +1,175 @@
+use moon_cache::{
+    collect_output_files, get_cache_mode, reapi_proto::ActionResult, BazelRemoteCache,
+    RemoteCacheError,
+};
+use moon_emitter::{Event, EventFlow, Subscriber};
+use moon_error::MoonError;
+use moon_logger::warn;
+use moon_utils::{async_trait, path};
+use moon_workspace::Workspace;
+use rustc_hash::FxHashMap;
+use tokio::task::JoinHandle;
+
+const LOG_TARGET: &str = "moon:remote-cache";
+
+// We don't want errors to bubble up and crash the program,
+// so instead, we log the error (as a warning) to the console!
+fn log_failure(error: RemoteCacheError) {
+    warn!(
+        target: LOG_TARGET,
+        "Remote caching failure: {}",
+        error.to_string()
+    );
+}
+
+/// The Bazel remote cache subscriber reads and writes task outputs to a server
+/// that implements the Bazel remote execution API, configured with
+/// `runner.remoteCache` and a `protocol` of `grpc`. Unlike the HTTP remote cache,
+/// outputs are not archived, but are stored as individual blobs, so that
+/// unchanged files are never uploaded twice.
+pub struct BazelRemoteCacheSubscriber {
+    remote: BazelRemoteCache,
+
+    // Action results found during cache checks, keyed by hash
+    results: FxHashMap<String, ActionResult>,
+
+    // In-flight requests
+    requests: Vec<JoinHandle<()>>,
+}
+
+impl BazelRemoteCacheSubscriber {
+    pub fn new(remote: BazelRemoteCache) -> Self {
+        BazelRemoteCacheSubscriber {
+            remote,
+            results: FxHashMap::default(),
+            requests: vec![],
+        }
+    }
+}
+
+#[async_trait]
+impl Subscriber for BazelRemoteCacheSubscriber {
+    async fn on_emit<'e>(
+        &mut self,
+        event: &Event<'e>,
+        workspace: &Workspace,
+    ) -> Result<EventFlow, MoonError> {
+        match event {
+            // Check if an action result exists in the remote. If an archive already
+            // exists locally, avoid the request and let the local cache subscriber handle it.
+            Event::TargetOutputCacheCheck { hash, .. } => {
+                if get_cache_mode().is_readable()
+                    && !workspace.cache.get_hash_archive_path(hash).exists()
+                {
+                    match self.remote.get_action_result(hash).await {
+                        Ok(Some(result)) => {
+                            self.results.insert((*hash).to_owned(), result);
+
+                            return Ok(EventFlow::Return("remote-cache".into()));
+                        }
+                        Ok(None) => {
+                            // Not remote cached
+                        }
+                        Err(error) => {
+                            log_failure(error);
+
+                            // Fallthrough and check local cache
+                        }
+                    }
+                }
+            }
+
+            // Upload the task's outputs as blobs. This runs *before* the local cache
+            // subscriber, but does not stop the flow, so that an archive is still created.
+            Event::TargetOutputArchiving {
+                cache,
+                hash,
+                project,
+                task,
+                ..
+            } => {
+                if get_cache_mode().is_writable() && self.remote.writable {
+                    let files =
+                        match collect_output_files(&workspace.root, &project.source, &task.outputs)
+                        {
+                            Ok(files) => files,
+                            Err(error) => {
+                                log_failure(error);
+
+                                return Ok(EventFlow::Continue);
+                            }
+                        };
+
+                    let remote = self.remote.clone();
+                    let hash = (*hash).to_owned();
+                    let workspace_root = workspace.root.clone();
+                    let (stdout_log, stderr_log) = cache.get_output_logs();
+                    let exit_code = cache.exit_code;
+
+                    // Run this in the background so we don't slow down the pipeline
+                    // while waiting for very large outputs to upload
+                    self.requests.push(tokio::spawn(async move {
+                        if let Err(error) = remote
+                            .upload_outputs(
+                                &hash,
+                                &workspace_root,
+                                &files,
+                                (&stdout_log, &stderr_log),
+                                exit_code,
+                            )
+                            .await
+                        {
+                            log_failure(error);
+                        }
+                    }));
+                }
+            }
+
+            // Download the blobs from the remote and write them to the task's outputs.
+            // If successful, this will stop the flow and skip local hydration.
+            Event::TargetOutputHydrating {
+                cache,
+                hash,
+                project,
+                task,
+                ..
+            } => {
+                if get_cache_mode().is_readable() {
+                    if let Some(result) = self.results.remove(*hash) {
+                        let (stdout_log, stderr_log) = cache.get_output_logs();
+
+                        match self
+                            .remote
+                            .download_outputs(
+                                &result,
+                                &workspace.root,
+                                &project.source,
+                                &task.outputs,
+                                (&stdout_log, &stderr_log),
+                            )
+                            .await
+                        {
+                            Ok(_) => {
+                                return Ok(EventFlow::Return(path::to_string(cache.get_dir())?));
+                            }
+                            Err(error) => {
+                                log_failure(error);
+                            }
+                        }
+                    }
+                }
+            }
+
+            _ => {}
+        }
+
+        // For the last event, we want to ensure that all uploads have been completed!
+        if event.is_end() {
+            for future in self.requests.drain(0..) {
+                let _ = future.await;
+            }
+        }
+
+        Ok(EventFlow::Continue)
+    }
+}
@@ -0,0 +1,175 @@
+use crate::reporter::TargetResult;
+use moon_emitter::{Event, EventFlow, Subscriber};
+use moon_error::MoonError;
+use moon_logger::{debug, warn};
+use moon_utils::{async_trait, time};
+use moon_workspace::Workspace;
+use starbase_styles::color;
+use std::env;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const LOG_TARGET: &str = "moon:action-pipeline:github-summary";
+
+// GitHub limits step summaries to 1MiB, so only include the tail of logs
+const MAX_LOG_LINES: usize = 100;
+
+fn get_status_icon(result: &TargetResult) -> &'static str {
+    if result.has_failed() {
+        "❌"
+    } else if result.was_skipped() {
+        "⏭️"
+    } else {
+        "✅"
+    }
+}
+
+fn tail_lines(value: &str) -> String {
+    let lines = value.trim_end().lines().collect::<Vec<_>>();
+
+    lines[lines.len().saturating_sub(MAX_LOG_LINES)..].join("\n")
+}
+
+/// Render results as a GitHub Actions step summary (markdown), with a table
+/// of all targets, and the captured stderr of each failed target.
+pub fn render_github_summary(results: &[TargetResult], duration: &Duration) -> String {
+    let mut sorted_results = results.iter().collect::<Vec<_>>();
+    sorted_results.sort_by(|a, d| a.target.cmp(&d.target));
+
+    let mut md = vec![
+        "### Run summary".to_owned(),
+        String::new(),
+        "| Target | Status | Duration |".to_owned(),
+        "| :-- | :-- | --: |".to_owned(),
+    ];
+
+    for result in &sorted_results {
+        md.push(format!(
+            "| `{}` | {} {} | {} |",
+            result.target.id,
+            get_status_icon(result),
+            result.get_status_label(),
+            time::elapsed(result.duration),
+        ));
+    }
+
+    let failed_count = results.iter().filter(|result| result.has_failed()).count();
+    let skipped_count = results.iter().filter(|result| result.was_skipped()).count();
+
+    md.push(String::new());
+    md.push(format!(
+        "**Targets:** {} passed, {} failed, {} skipped · **Time:** {}",
+        results.len() - failed_count - skipped_count,
+        failed_count,
+        skipped_count,
+        time::elapsed(*duration),
+    ));
+
+    for result in sorted_results {
+        if !result.has_failed() {
+            continue;
+        }
+
+        md.push(String::new());
+        md.push(format!(
+            "<details><summary><code>{}</code> failed</summary>",
+            result.target.id
+        ));
+        md.push(String::new());
+
+        if let Some(error) = &result.error {
+            md.push(format!("> {}", error.lines().next().unwrap_or_default()));
+            md.push(String::new());
+        }
+
+        if !result.stderr.trim().is_empty() {
+            md.push("```".into());
+            md.push(tail_lines(&result.stderr));
+            md.push("```".into());
+            md.push(String::new());
+        }
+
+        md.push("</details>".into());
+    }
+
+    md.push(String::new());
+    md.join("\n")
+}
+
+/// The GitHub summary subscriber collects the results of all ran targets, and appends
+/// them to the step summary once the pipeline has finished (or aborted).
+pub struct GithubSummarySubscriber {
+    path: Option<PathBuf>,
+
+    results: Vec<TargetResult>,
+}
+
+impl GithubSummarySubscriber {
+    pub fn new(path: Option<PathBuf>) -> Self {
+        GithubSummarySubscriber {
+            path,
+            results: vec![],
+        }
+    }
+
+    fn write_summary(&self, duration: &Duration) -> Result<(), MoonError> {
+        let Some(path) = self
+            .path
+            .clone()
+            .or_else(|| env::var_os("GITHUB_STEP_SUMMARY").map(PathBuf::from))
+        else {
+            warn!(
+                target: LOG_TARGET,
+                "Unable to write GitHub summary, as a file path was not provided and GITHUB_STEP_SUMMARY is not set",
+            );
+
+            return Ok(());
+        };
+
+        debug!(
+            target: LOG_TARGET,
+            "Writing GitHub summary to {}",
+            color::path(&path)
+        );
+
+        // Summaries are appended, as other steps may have written to it
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|error| MoonError::FileSystem(path.clone(), error))?;
+
+        writeln!(file, "{}", render_github_summary(&self.results, duration))
+            .map_err(|error| MoonError::FileSystem(path, error))?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Subscriber for GithubSummarySubscriber {
+    async fn on_emit<'e>(
+        &mut self,
+        event: &Event<'e>,
+        workspace: &Workspace,
+    ) -> Result<EventFlow, MoonError> {
+        match event {
+            Event::ActionFinished { action, node, .. } => {
+                if let Some(result) = TargetResult::from_action(action, node, workspace) {
+                    self.results.push(result);
+                }
+            }
+            Event::PipelineFinished { duration, .. } => {
+                self.write_summary(duration)?;
+            }
+            Event::PipelineAborted { .. } => {
+                self.write_summary(&self.results.iter().map(|result| result.duration).sum())?;
+            }
+            _ => {}
+        }
+
+        Ok(EventFlow::Continue)
+    }
+}
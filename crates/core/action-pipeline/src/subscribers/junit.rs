@@ -0,0 +1,180 @@
+use crate::reporter::TargetResult;
+use moon_emitter::{Event, EventFlow, Subscriber};
+use moon_error::MoonError;
+use moon_logger::debug;
+use moon_utils::async_trait;
+use moon_workspace::Workspace;
+use starbase_styles::color;
+use starbase_utils::fs;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const LOG_TARGET: &str = "moon:action-pipeline:junit";
+
+fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // Control characters are not allowed in XML 1.0
+            ch if ch.is_control() && ch != '\n' && ch != '\r' && ch != '\t' => {}
+            ch => escaped.push(ch),
+        }
+    }
+
+    escaped
+}
+
+fn format_seconds(duration: &Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64())
+}
+
+/// Render results as JUnit XML, with a test suite per project,
+/// and a test case per target.
+pub fn render_junit(results: &[TargetResult], duration: &Duration) -> String {
+    let mut suites: BTreeMap<&str, Vec<&TargetResult>> = BTreeMap::new();
+
+    for result in results {
+        suites
+            .entry(
+                result
+                    .target
+                    .scope_id
+                    .as_deref()
+                    .map_or("", |id| id.as_str()),
+            )
+            .or_default()
+            .push(result);
+    }
+
+    let count = |list: &[&TargetResult], op: fn(&TargetResult) -> bool| {
+        list.iter().filter(|result| op(result)).count()
+    };
+    let all = results.iter().collect::<Vec<_>>();
+    let mut xml = vec![
+        r#"<?xml version="1.0" encoding="UTF-8"?>"#.to_owned(),
+        format!(
+            r#"<testsuites name="moon" tests="{}" failures="{}" skipped="{}" time="{}">"#,
+            all.len(),
+            count(&all, TargetResult::has_failed),
+            count(&all, TargetResult::was_skipped),
+            format_seconds(duration),
+        ),
+    ];
+
+    for (project_id, mut cases) in suites {
+        cases.sort_by(|a, d| a.target.cmp(&d.target));
+
+        xml.push(format!(
+            r#"  <testsuite name="{}" tests="{}" failures="{}" skipped="{}" time="{}">"#,
+            escape_xml(project_id),
+            cases.len(),
+            count(&cases, TargetResult::has_failed),
+            count(&cases, TargetResult::was_skipped),
+            format_seconds(&cases.iter().map(|result| result.duration).sum()),
+        ));
+
+        for case in cases {
+            let open = format!(
+                r#"    <testcase name="{}" classname="{}" time="{}">"#,
+                escape_xml(&case.target.id),
+                escape_xml(project_id),
+                format_seconds(&case.duration),
+            );
+            let mut body = vec![];
+
+            if case.has_failed() {
+                body.push(format!(
+                    r#"      <failure message="{}">{}</failure>"#,
+                    escape_xml(case.error.as_deref().unwrap_or("Target failed")),
+                    escape_xml(&case.stderr),
+                ));
+            } else if case.was_skipped() {
+                body.push(format!(
+                    r#"      <skipped message="{}" />"#,
+                    case.get_status_label()
+                ));
+            } else if !case.stderr.is_empty() {
+                body.push(format!(
+                    "      <system-err>{}</system-err>",
+                    escape_xml(&case.stderr)
+                ));
+            }
+
+            if body.is_empty() {
+                xml.push(format!("{}</testcase>", open));
+            } else {
+                xml.push(open);
+                xml.extend(body);
+                xml.push("    </testcase>".into());
+            }
+        }
+
+        xml.push("  </testsuite>".into());
+    }
+
+    xml.push("</testsuites>".into());
+    xml.push(String::new());
+    xml.join("\n")
+}
+
+/// The JUnit subscriber collects the results of all ran targets, and writes
+/// them to a JUnit XML file once the pipeline has finished (or aborted).
+pub struct JunitSubscriber {
+    path: PathBuf,
+
+    results: Vec<TargetResult>,
+}
+
+impl JunitSubscriber {
+    pub fn new(path: PathBuf) -> Self {
+        JunitSubscriber {
+            path,
+            results: vec![],
+        }
+    }
+
+    fn write_report(&self, duration: &Duration) -> Result<(), MoonError> {
+        debug!(
+            target: LOG_TARGET,
+            "Writing JUnit report to {}",
+            color::path(&self.path)
+        );
+
+        fs::write_file(&self.path, render_junit(&self.results, duration))?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Subscriber for JunitSubscriber {
+    async fn on_emit<'e>(
+        &mut self,
+        event: &Event<'e>,
+        workspace: &Workspace,
+    ) -> Result<EventFlow, MoonError> {
+        match event {
+            Event::ActionFinished { action, node, .. } => {
+                if let Some(result) = TargetResult::from_action(action, node, workspace) {
+                    self.results.push(result);
+                }
+            }
+            Event::PipelineFinished { duration, .. } => {
+                self.write_report(duration)?;
+            }
+            Event::PipelineAborted { .. } => {
+                self.write_report(&self.results.iter().map(|result| result.duration).sum())?;
+            }
+            _ => {}
+        }
+
+        Ok(EventFlow::Continue)
+    }
+}
@@ -1,4 +1,5 @@
 use moon_cache::get_cache_mode;
+use moon_config::{OutputStoreLayout, RemoteCacheProtocol};
 use moon_emitter::{Event, EventFlow, Subscriber};
 use moon_error::MoonError;
 use moon_utils::{async_trait, path};
@@ -6,13 +7,33 @@ use moon_workspace::Workspace;
 
 /// The local cache subscriber is in charge of managing archives
 /// (task output's archived as tarballs), by reading and writing them
-/// to the `.moon/cache/{outputs,hashes}` directories.
+/// to the `.moon/cache/{outputs,hashes}` directories. When the `cas` output
+/// store is enabled, outputs are stored as content-addressed blobs instead.
 ///
 /// This is the last subscriber amongst all subscribers, as local
 /// cache is the last line of defense. However, other subscribers
 /// will piggyback off of it, like remote cache.
 pub struct LocalCacheSubscriber {}
 
+fn is_cas_enabled(workspace: &Workspace) -> bool {
+    matches!(
+        workspace.config.runner.output_store.layout,
+        OutputStoreLayout::Cas
+    )
+}
+
+// HTTP remote caches only transfer archives, so continue to create them
+// alongside blobs when one has been configured. The gRPC protocol
+// uploads the output files directly.
+fn requires_archive(workspace: &Workspace) -> bool {
+    let remote_cache = &workspace.config.runner.remote_cache;
+
+    !is_cas_enabled(workspace)
+        || workspace.session.is_some()
+        || (remote_cache.url.is_some()
+            && matches!(remote_cache.protocol, RemoteCacheProtocol::Http))
+}
+
 impl LocalCacheSubscriber {
     pub fn new() -> Self {
         LocalCacheSubscriber {}
@@ -31,7 +52,9 @@ impl Subscriber for LocalCacheSubscriber {
             // We only check for the archive, as the manifest is purely for local debugging!
             Event::TargetOutputCacheCheck { hash, .. } => {
                 if get_cache_mode().is_readable()
-                    && workspace.cache.get_hash_archive_path(hash).exists()
+                    && (workspace.cache.get_hash_archive_path(hash).exists()
+                        || is_cas_enabled(workspace)
+                            && workspace.cache.get_hash_blobs_manifest_path(hash).exists())
                 {
                     return Ok(EventFlow::Return("local-cache".into()));
                 }
@@ -45,6 +68,22 @@ impl Subscriber for LocalCacheSubscriber {
                 task,
                 ..
             } => {
+                if is_cas_enabled(workspace) {
+                    let manifest_path = workspace.cache.get_hash_blobs_manifest_path(hash);
+
+                    cache.archive_outputs_to_store(
+                        &manifest_path,
+                        &workspace.cache.get_output_store(),
+                        &workspace.root,
+                        &project.source,
+                        &task.outputs,
+                    )?;
+
+                    if !requires_archive(workspace) {
+                        return Ok(EventFlow::Return(path::to_string(manifest_path)?));
+                    }
+                }
+
                 let archive_path = workspace.cache.get_hash_archive_path(hash);
 
                 if cache.archive_outputs(
@@ -65,6 +104,26 @@ impl Subscriber for LocalCacheSubscriber {
                 task,
                 ..
             } => {
+                if is_cas_enabled(workspace) {
+                    let manifest_path = workspace.cache.get_hash_blobs_manifest_path(hash);
+                    let mut store = workspace.cache.get_output_store();
+
+                    store.set_hardlink(workspace.config.runner.output_store.hardlink);
+
+                    if cache.hydrate_outputs_from_store(
+                        &manifest_path,
+                        &store,
+                        &workspace.root,
+                        &project.source,
+                        &task.outputs,
+                    )? {
+                        return Ok(EventFlow::Return(path::to_string(manifest_path)?));
+                    }
+
+                    // Otherwise fallthrough, as the archive may have been
+                    // downloaded from a remote cache
+                }
+
                 let archive_path = workspace.cache.get_hash_archive_path(hash);
 
                 if cache.hydrate_outputs(
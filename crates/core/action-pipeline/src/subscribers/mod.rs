@@ -1,2 +1,7 @@
+pub mod bazel_remote_cache;
+pub mod github_summary;
+pub mod junit;
 pub mod local_cache;
 pub mod moonbase;
+pub mod remote_cache;
+pub mod trace;
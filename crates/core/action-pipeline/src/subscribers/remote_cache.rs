@@ -0,0 +1,116 @@
+use moon_cache::{get_cache_mode, RemoteCache, RemoteCacheError};
+use moon_emitter::{Event, EventFlow, Subscriber};
+use moon_error::MoonError;
+use moon_logger::warn;
+use moon_utils::async_trait;
+use moon_workspace::Workspace;
+use tokio::task::JoinHandle;
+
+const LOG_TARGET: &str = "moon:remote-cache";
+
+// We don't want errors to bubble up and crash the program,
+// so instead, we log the error (as a warning) to the console!
+fn log_failure(error: RemoteCacheError) {
+    warn!(
+        target: LOG_TARGET,
+        "Remote caching failure: {}",
+        error.to_string()
+    );
+}
+
+/// The remote cache subscriber reads and writes archives to a self-hosted
+/// HTTP server, configured with `runner.remoteCache`. It piggybacks off
+/// of the local cache subscriber, by downloading archives into
+/// `.moon/cache/outputs` before hydration, and uploading them after
+/// they have been archived.
+pub struct RemoteCacheSubscriber {
+    remote: RemoteCache,
+
+    // In-flight requests
+    requests: Vec<JoinHandle<()>>,
+}
+
+impl RemoteCacheSubscriber {
+    pub fn new(remote: RemoteCache) -> Self {
+        RemoteCacheSubscriber {
+            remote,
+            requests: vec![],
+        }
+    }
+}
+
+#[async_trait]
+impl Subscriber for RemoteCacheSubscriber {
+    async fn on_emit<'e>(
+        &mut self,
+        event: &Event<'e>,
+        workspace: &Workspace,
+    ) -> Result<EventFlow, MoonError> {
+        match event {
+            // Check if the archive exists in the remote. If it already exists locally,
+            // avoid the request and let the local cache subscriber handle it.
+            Event::TargetOutputCacheCheck { hash, .. } => {
+                if get_cache_mode().is_readable()
+                    && !workspace.cache.get_hash_archive_path(hash).exists()
+                {
+                    match self.remote.has_artifact(hash).await {
+                        Ok(true) => {
+                            return Ok(EventFlow::Return("remote-cache".into()));
+                        }
+                        Ok(false) => {
+                            // Not remote cached
+                        }
+                        Err(error) => {
+                            log_failure(error);
+
+                            // Fallthrough and check local cache
+                        }
+                    }
+                }
+            }
+
+            // The local cache subscriber uses the `TargetOutputArchiving` event to create
+            // the tarball. This runs *after* it's been created so that we can upload it.
+            Event::TargetOutputArchived {
+                archive_path, hash, ..
+            } => {
+                if get_cache_mode().is_writable() && self.remote.writable && archive_path.exists() {
+                    let remote = self.remote.clone();
+                    let hash = (*hash).to_owned();
+                    let archive_path = archive_path.to_owned();
+
+                    // Run this in the background so we don't slow down the pipeline
+                    // while waiting for very large archives to upload
+                    self.requests.push(tokio::spawn(async move {
+                        if let Err(error) = remote.upload_artifact(&hash, &archive_path).await {
+                            log_failure(error);
+                        }
+                    }));
+                }
+            }
+
+            // Attempt to download the artifact from the remote cache to `.moon/outputs/<hash>`.
+            // This runs *before* the local cache, which will handle the actual hydration.
+            Event::TargetOutputHydrating { hash, .. } => {
+                let archive_file = workspace.cache.get_hash_archive_path(hash);
+
+                if get_cache_mode().is_readable() && !archive_file.exists() {
+                    if let Err(error) = self.remote.download_artifact(hash, &archive_file).await {
+                        log_failure(error);
+                    }
+                }
+            }
+
+            _ => {}
+        }
+
+        // For the last event, we want to ensure that all uploads have been completed!
+        if event.is_end() {
+            for future in self.requests.drain(0..) {
+                let _ = future.await;
+            }
+        }
+
+        Ok(EventFlow::Continue)
+    }
+}
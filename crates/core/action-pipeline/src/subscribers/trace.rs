@@ -0,0 +1,337 @@
+use moon_action::{Action, ActionNode};
+use moon_emitter::{Event, EventFlow, Subscriber};
+use moon_error::MoonError;
+use moon_logger::debug;
+use moon_target::Target;
+use moon_utils::async_trait;
+use moon_workspace::Workspace;
+use rustc_hash::FxHashMap;
+use serde::Serialize;
+use starbase_styles::color;
+use starbase_utils::json;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Instant;
+
+const LOG_TARGET: &str = "moon:action-pipeline:trace";
+
+/// An event in the Chrome Trace Event format, which can be loaded
+/// into `chrome://tracing` or https://ui.perfetto.dev.
+#[derive(Debug, Serialize)]
+pub struct TraceEvent {
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub args: BTreeMap<String, String>,
+
+    pub cat: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dur: Option<u64>,
+
+    pub name: String,
+
+    pub ph: String,
+
+    pub pid: u32,
+
+    pub tid: usize,
+
+    pub ts: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TraceFile<'a> {
+    display_time_unit: &'a str,
+    trace_events: &'a [TraceEvent],
+}
+
+struct RunningAction {
+    category: String,
+    lane: usize,
+    start: Instant,
+}
+
+struct RunningPhase {
+    lane: usize,
+    name: String,
+    start: Instant,
+}
+
+/// The trace subscriber records a span for every action, attempt, and output
+/// hydration/archiving phase, laid out on a lane for the worker that ran it.
+/// Once the pipeline has finished (or aborted), the spans are written to a
+/// Chrome Trace Event file.
+pub struct TraceSubscriber {
+    events: Vec<TraceEvent>,
+
+    /// Whether each worker lane is currently running an action.
+    lanes: Vec<bool>,
+
+    origin: Instant,
+
+    path: PathBuf,
+
+    phases: FxHashMap<Target, RunningPhase>,
+
+    running: FxHashMap<String, RunningAction>,
+
+    target_lanes: FxHashMap<Target, usize>,
+}
+
+fn get_category(node: &ActionNode) -> &'static str {
+    match node {
+        ActionNode::InstallDeps(_) | ActionNode::InstallProjectDeps(_, _) => "install-deps",
+        ActionNode::RunTarget(_, _) => "run-target",
+        ActionNode::RunPersistentTarget(_, _) => "run-persistent-target",
+        ActionNode::SetupTool(_) => "setup-tool",
+        ActionNode::SyncProject(_, _) => "sync-project",
+    }
+}
+
+impl TraceSubscriber {
+    pub fn new(path: PathBuf) -> Self {
+        TraceSubscriber {
+            events: vec![],
+            lanes: vec![],
+            origin: Instant::now(),
+            path,
+            phases: FxHashMap::default(),
+            running: FxHashMap::default(),
+            target_lanes: FxHashMap::default(),
+        }
+    }
+
+    fn acquire_lane(&mut self) -> usize {
+        let index = match self.lanes.iter().position(|busy| !busy) {
+            Some(index) => index,
+            None => {
+                self.lanes.push(false);
+                self.lanes.len() - 1
+            }
+        };
+
+        self.lanes[index] = true;
+
+        // Lanes are 1-based for readability
+        index + 1
+    }
+
+    fn release_lane(&mut self, lane: usize) {
+        if let Some(busy) = self.lanes.get_mut(lane - 1) {
+            *busy = false;
+        }
+    }
+
+    fn create_span(
+        &self,
+        name: String,
+        category: &str,
+        lane: usize,
+        start: Instant,
+        end: Instant,
+        args: BTreeMap<String, String>,
+    ) -> TraceEvent {
+        TraceEvent {
+            args,
+            cat: category.to_owned(),
+            dur: Some(end.saturating_duration_since(start).as_micros() as u64),
+            name,
+            ph: "X".into(),
+            pid: 1,
+            tid: lane,
+            ts: start.saturating_duration_since(self.origin).as_micros() as u64,
+        }
+    }
+
+    fn start_action(&mut self, action: &Action, node: &ActionNode) {
+        let lane = self.acquire_lane();
+
+        if let ActionNode::RunTarget(_, target) | ActionNode::RunPersistentTarget(_, target) = node
+        {
+            self.target_lanes.insert(target.to_owned(), lane);
+        }
+
+        self.running.insert(
+            action.label.clone(),
+            RunningAction {
+                category: get_category(node).to_owned(),
+                lane,
+                start: Instant::now(),
+            },
+        );
+    }
+
+    fn finish_action(&mut self, action: &Action, node: &ActionNode, error: Option<&String>) {
+        let Some(running) = self.running.remove(&action.label) else {
+            return;
+        };
+
+        let end = Instant::now();
+        let mut args = BTreeMap::from_iter([("status".into(), format!("{:?}", action.status))]);
+
+        if let Some(error) = error {
+            args.insert("error".into(), error.to_owned());
+        }
+
+        if let Some(attempts) = &action.attempts {
+            for attempt in attempts {
+                if let (Some(start), Some(duration)) = (attempt.start_time, attempt.duration) {
+                    let event = self.create_span(
+                        format!("Attempt {}", attempt.index),
+                        "attempt",
+                        running.lane,
+                        start,
+                        start + duration,
+                        BTreeMap::from_iter([("status".into(), format!("{:?}", attempt.status))]),
+                    );
+
+                    self.events.push(event);
+                }
+            }
+        }
+
+        if let ActionNode::RunTarget(_, target) | ActionNode::RunPersistentTarget(_, target) = node
+        {
+            self.target_lanes.remove(target);
+
+            // Phases that never completed (nothing was archived or hydrated)
+            if let Some(phase) = self.phases.remove(target) {
+                self.finish_phase(phase, end);
+            }
+        }
+
+        let event = self.create_span(
+            action.label.clone(),
+            &running.category,
+            running.lane,
+            running.start,
+            end,
+            args,
+        );
+
+        self.events.push(event);
+        self.release_lane(running.lane);
+    }
+
+    fn start_phase(&mut self, target: &Target, name: &str) {
+        if let Some(lane) = self.target_lanes.get(target) {
+            self.phases.insert(
+                target.to_owned(),
+                RunningPhase {
+                    lane: *lane,
+                    name: name.to_owned(),
+                    start: Instant::now(),
+                },
+            );
+        }
+    }
+
+    fn finish_phase(&mut self, phase: RunningPhase, end: Instant) {
+        let event = self.create_span(
+            phase.name,
+            "output",
+            phase.lane,
+            phase.start,
+            end,
+            BTreeMap::new(),
+        );
+
+        self.events.push(event);
+    }
+
+    fn write_trace(&mut self) -> Result<(), MoonError> {
+        let end = Instant::now();
+
+        // Actions that were still running when the pipeline was aborted
+        let running = self.running.drain().collect::<Vec<_>>();
+
+        for (label, action) in running {
+            let event = self.create_span(
+                label,
+                &action.category,
+                action.lane,
+                action.start,
+                end,
+                BTreeMap::from_iter([("status".into(), "Aborted".into())]),
+            );
+
+            self.events.push(event);
+        }
+
+        self.events
+            .sort_by(|a, d| a.ts.cmp(&d.ts).then_with(|| a.tid.cmp(&d.tid)));
+
+        for lane in 1..=self.lanes.len() {
+            self.events.push(TraceEvent {
+                args: BTreeMap::from_iter([("name".into(), format!("Worker {lane}"))]),
+                cat: "__metadata".into(),
+                dur: None,
+                name: "thread_name".into(),
+                ph: "M".into(),
+                pid: 1,
+                tid: lane,
+                ts: 0,
+            });
+        }
+
+        debug!(
+            target: LOG_TARGET,
+            "Writing pipeline trace to {}",
+            color::path(&self.path)
+        );
+
+        json::write_file(
+            &self.path,
+            &TraceFile {
+                display_time_unit: "ms",
+                trace_events: &self.events,
+            },
+            false,
+        )?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Subscriber for TraceSubscriber {
+    async fn on_emit<'e>(
+        &mut self,
+        event: &Event<'e>,
+        _workspace: &Workspace,
+    ) -> Result<EventFlow, MoonError> {
+        match event {
+            Event::PipelineStarted { .. } => {
+                self.origin = Instant::now();
+            }
+            Event::ActionStarted { action, node } => {
+                self.start_action(action, node);
+            }
+            Event::ActionFinished {
+                action,
+                error,
+                node,
+            } => {
+                self.finish_action(action, node, error.as_ref());
+            }
+            Event::TargetOutputArchiving { target, .. } => {
+                self.start_phase(target, "Archiving outputs");
+            }
+            Event::TargetOutputHydrating { target, .. } => {
+                self.start_phase(target, "Hydrating outputs");
+            }
+            Event::TargetOutputArchived { target, .. }
+            | Event::TargetOutputHydrated { target, .. } => {
+                if let Some(phase) = self.phases.remove(*target) {
+                    self.finish_phase(phase, Instant::now());
+                }
+            }
+            Event::PipelineFinished { .. } | Event::PipelineAborted { .. } => {
+                self.write_trace()?;
+            }
+            _ => {}
+        }
+
+        Ok(EventFlow::Continue)
+    }
+}
@@ -0,0 +1,108 @@
+use moon::{build_dep_graph, generate_project_graph, load_workspace_from};
+use moon_action_pipeline::Pipeline;
+use moon_config::{PartialWorkspaceConfig, WorkspaceProjects};
+use moon_dep_graph::DepGraph;
+use moon_target::Target;
+use moon_test_utils::{create_sandbox_with_config, Sandbox};
+use rustc_hash::FxHashMap;
+use std::fs;
+
+fn create_pipeline_sandbox() -> Sandbox {
+    let workspace_config = PartialWorkspaceConfig {
+        projects: Some(WorkspaceProjects::Sources(FxHashMap::from_iter([(
+            "app".into(),
+            "app".to_owned(),
+        )]))),
+        ..PartialWorkspaceConfig::default()
+    };
+
+    create_sandbox_with_config("pipeline", Some(workspace_config), None, None)
+}
+
+async fn create_pipeline(sandbox: &Sandbox, targets: &[&str]) -> (Pipeline, DepGraph) {
+    let mut workspace = load_workspace_from(sandbox.path()).await.unwrap();
+    let project_graph = generate_project_graph(&mut workspace).await.unwrap();
+
+    let mut dep_builder = build_dep_graph(&workspace, &project_graph);
+
+    for target in targets {
+        dep_builder
+            .run_target(Target::parse(target).unwrap(), None)
+            .unwrap();
+    }
+
+    let dep_graph = dep_builder.build();
+
+    (Pipeline::new(workspace, project_graph), dep_graph)
+}
+
+fn read_log(sandbox: &Sandbox) -> Vec<String> {
+    fs::read_to_string(sandbox.path().join("pipeline.log"))
+        .unwrap_or_default()
+        .lines()
+        .map(|line| line.to_owned())
+        .collect()
+}
+
+fn position_of(log: &[String], line: &str) -> usize {
+    log.iter()
+        .position(|item| item == line)
+        .unwrap_or_else(|| panic!("Missing {line} in {log:?}"))
+}
+
+#[tokio::test]
+async fn starts_actions_without_waiting_for_unrelated_actions() {
+    let sandbox = create_pipeline_sandbox();
+    let (mut pipeline, dep_graph) =
+        create_pipeline(&sandbox, &["app:slow", "app:afterQuick"]).await;
+
+    pipeline.concurrency(4);
+    pipeline.run(dep_graph, None).await.unwrap();
+
+    let log = read_log(&sandbox);
+
+    // The dependent of `quick` should not wait for `slow` to complete
+    assert!(position_of(&log, "afterQuick:start") < position_of(&log, "slow:end"));
+}
+
+#[tokio::test]
+async fn limits_running_actions_to_concurrency() {
+    let sandbox = create_pipeline_sandbox();
+    let (mut pipeline, dep_graph) =
+        create_pipeline(&sandbox, &["app:one", "app:two", "app:three", "app:four"]).await;
+
+    pipeline.concurrency(2);
+    pipeline.run(dep_graph, None).await.unwrap();
+
+    let mut running = 0;
+    let mut max_running = 0;
+
+    for line in read_log(&sandbox) {
+        if line.ends_with(":start") {
+            running += 1;
+            max_running = max_running.max(running);
+        } else {
+            running -= 1;
+        }
+    }
+
+    assert_eq!(max_running, 2);
+}
+
+#[tokio::test]
+async fn bail_stops_pending_actions() {
+    let sandbox = create_pipeline_sandbox();
+    let (mut pipeline, dep_graph) = create_pipeline(&sandbox, &["app:fail", "app:afterWait"]).await;
+
+    pipeline.concurrency(4);
+
+    let result = pipeline.bail_on_error().run(dep_graph, None).await;
+
+    assert!(result.is_err());
+
+    let log = read_log(&sandbox);
+
+    assert!(log.contains(&"fail:end".to_owned()));
+    assert!(!log.contains(&"wait:end".to_owned()));
+    assert!(!log.contains(&"afterWait:start".to_owned()));
+}
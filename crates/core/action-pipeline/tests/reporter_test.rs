@@ -0,0 +1,116 @@
+use moon_action::ActionStatus;
+use moon_action_pipeline::reporter::{render_github_summary, render_junit, Reporter, TargetResult};
+use moon_target::Target;
+use moon_test_utils::assert_snapshot;
+use std::path::PathBuf;
+use std::time::Duration;
+
+fn create_results() -> Vec<TargetResult> {
+    vec![
+        TargetResult {
+            duration: Duration::from_millis(1500),
+            error: None,
+            status: ActionStatus::Passed,
+            stderr: "warning: unused variable".into(),
+            target: Target::parse("app:build").unwrap(),
+        },
+        TargetResult {
+            duration: Duration::from_millis(2250),
+            error: Some("Process failed with exit code 1".into()),
+            status: ActionStatus::Failed,
+            stderr: "expected <true> & got \"false\"".into(),
+            target: Target::parse("app:test").unwrap(),
+        },
+        TargetResult {
+            duration: Duration::from_millis(10),
+            error: None,
+            status: ActionStatus::Cached,
+            stderr: String::new(),
+            target: Target::parse("lib:build").unwrap(),
+        },
+        TargetResult {
+            duration: Duration::from_millis(0),
+            error: None,
+            status: ActionStatus::Skipped,
+            stderr: String::new(),
+            target: Target::parse("lib:lint").unwrap(),
+        },
+    ]
+}
+
+mod reporter {
+    use super::*;
+
+    #[test]
+    fn parses_github() {
+        assert_eq!(
+            "github".parse::<Reporter>().unwrap(),
+            Reporter::GithubSummary(None)
+        );
+        assert_eq!(
+            "github=summary.md".parse::<Reporter>().unwrap(),
+            Reporter::GithubSummary(Some(PathBuf::from("summary.md")))
+        );
+    }
+
+    #[test]
+    fn parses_junit() {
+        assert_eq!(
+            "junit=out/report.xml".parse::<Reporter>().unwrap(),
+            Reporter::Junit(PathBuf::from("out/report.xml"))
+        );
+    }
+
+    #[test]
+    fn errors_for_junit_without_path() {
+        assert!("junit".parse::<Reporter>().is_err());
+    }
+
+    #[test]
+    fn errors_for_unknown() {
+        assert_eq!(
+            "tap=out.tap".parse::<Reporter>().unwrap_err(),
+            "unknown reporter \"tap\", expected github or junit"
+        );
+    }
+
+    #[test]
+    fn resolves_paths() {
+        let dir = PathBuf::from("/root");
+
+        assert_eq!(
+            Reporter::Junit(PathBuf::from("report.xml")).resolve(&dir),
+            Reporter::Junit(PathBuf::from("/root/report.xml"))
+        );
+        assert_eq!(
+            Reporter::GithubSummary(None).resolve(&dir),
+            Reporter::GithubSummary(None)
+        );
+    }
+}
+
+mod junit {
+    use super::*;
+
+    #[test]
+    fn renders_results() {
+        assert_snapshot!(render_junit(&create_results(), &Duration::from_secs(4)));
+    }
+
+    #[test]
+    fn renders_empty() {
+        assert_snapshot!(render_junit(&[], &Duration::from_secs(0)));
+    }
+}
+
+mod github_summary {
+    use super::*;
+
+    #[test]
+    fn renders_results() {
+        assert_snapshot!(render_github_summary(
+            &create_results(),
+            &Duration::from_secs(4)
+        ));
+    }
+}
@@ -0,0 +1,170 @@
+use moon_action_pipeline::sharder::{Shard, Sharder};
+use moon_target::Target;
+use moon_test_utils::create_sandbox;
+use std::time::Duration;
+
+fn target(id: &str) -> Target {
+    Target::parse(id).unwrap()
+}
+
+fn targets(ids: &[&str]) -> Vec<Target> {
+    ids.iter().map(|id| target(id)).collect()
+}
+
+fn secs(value: u64) -> Duration {
+    Duration::from_secs(value)
+}
+
+mod sharder {
+    use super::*;
+
+    #[test]
+    fn returns_a_shard_per_job() {
+        let shards = Sharder::new().distribute(&[], 3);
+
+        assert_eq!(
+            shards,
+            vec![Shard::default(), Shard::default(), Shard::default()]
+        );
+    }
+
+    #[test]
+    fn evenly_distributes_without_durations() {
+        let shards = Sharder::new().distribute(&targets(&["a:build", "b:build", "c:build"]), 2);
+
+        assert_eq!(
+            shards,
+            vec![
+                Shard {
+                    duration: secs(2),
+                    targets: targets(&["a:build", "c:build"]),
+                },
+                Shard {
+                    duration: secs(1),
+                    targets: targets(&["b:build"]),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn balances_by_duration() {
+        let mut sharder = Sharder::new();
+        sharder.set_duration(&target("a:build"), secs(40));
+        sharder.set_duration(&target("b:build"), secs(10));
+        sharder.set_duration(&target("c:build"), secs(10));
+        sharder.set_duration(&target("d:build"), secs(10));
+        sharder.set_duration(&target("e:build"), secs(10));
+
+        let shards = sharder.distribute(
+            &targets(&["a:build", "b:build", "c:build", "d:build", "e:build"]),
+            2,
+        );
+
+        assert_eq!(
+            shards,
+            vec![
+                Shard {
+                    duration: secs(40),
+                    targets: targets(&["a:build"]),
+                },
+                Shard {
+                    duration: secs(40),
+                    targets: targets(&["b:build", "c:build", "d:build", "e:build"]),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn uses_average_for_unknown_targets() {
+        let mut sharder = Sharder::new();
+        sharder.set_duration(&target("a:build"), secs(10));
+        sharder.set_duration(&target("b:build"), secs(30));
+
+        let shards = sharder.distribute(&targets(&["a:build", "b:build", "c:build"]), 2);
+
+        assert_eq!(
+            shards,
+            vec![
+                Shard {
+                    duration: secs(30),
+                    targets: targets(&["b:build"]),
+                },
+                Shard {
+                    duration: secs(30),
+                    targets: targets(&["a:build", "c:build"]),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_dependencies_together() {
+        let mut sharder = Sharder::new();
+        sharder.link(&target("b:build"), &target("a:build"));
+
+        let shards = sharder.distribute(&targets(&["a:build", "b:build", "c:build", "d:build"]), 2);
+
+        assert_eq!(shards[0].targets, targets(&["a:build", "b:build"]));
+        assert_eq!(shards[1].targets, targets(&["c:build", "d:build"]));
+    }
+
+    #[test]
+    fn keeps_transitive_dependencies_together() {
+        let mut sharder = Sharder::new();
+        sharder.link(&target("c:build"), &target("x:build"));
+        sharder.link(&target("x:build"), &target("a:build"));
+
+        let shards = sharder.distribute(&targets(&["a:build", "b:build", "c:build", "d:build"]), 2);
+
+        assert_eq!(shards[0].targets, targets(&["a:build", "c:build"]));
+        assert_eq!(shards[1].targets, targets(&["b:build", "d:build"]));
+    }
+
+    #[test]
+    fn splits_groups_that_exceed_ideal_duration() {
+        let mut sharder = Sharder::new();
+        sharder.link(&target("b:build"), &target("a:build"));
+        sharder.link(&target("c:build"), &target("b:build"));
+
+        let shards = sharder.distribute(&targets(&["a:build", "b:build", "c:build"]), 3);
+
+        assert_eq!(shards[0].targets, targets(&["a:build"]));
+        assert_eq!(shards[1].targets, targets(&["b:build"]));
+        assert_eq!(shards[2].targets, targets(&["c:build"]));
+    }
+
+    #[test]
+    fn loads_durations_from_report() {
+        let sandbox = create_sandbox("base");
+        sandbox.create_file(
+            "ciReport.json",
+            r#"{
+                "actions": [
+                    { "label": "SetupNodeTool", "duration": { "secs": 100, "nanos": 0 } },
+                    { "label": "RunTarget(a:build)", "duration": { "secs": 30, "nanos": 0 } },
+                    { "label": "RunTarget(b:build)", "duration": null }
+                ]
+            }"#,
+        );
+
+        let mut sharder = Sharder::new();
+        sharder.load_report(&sandbox.path().join("ciReport.json"));
+
+        assert!(sharder.has_duration(&target("a:build")));
+        assert!(!sharder.has_duration(&target("b:build")));
+    }
+
+    #[test]
+    fn ignores_invalid_reports() {
+        let sandbox = create_sandbox("base");
+        sandbox.create_file("ciReport.json", "{");
+
+        let mut sharder = Sharder::new();
+        sharder.load_report(&sandbox.path().join("ciReport.json"));
+        sharder.load_report(&sandbox.path().join("missing.json"));
+
+        assert!(!sharder.has_duration(&target("a:build")));
+    }
+}
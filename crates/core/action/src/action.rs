@@ -4,7 +4,9 @@ use serde::{Deserialize, Serialize};
 use std::time::{Duration, Instant};
 
 fn has_failed(status: &ActionStatus) -> bool {
-    matches!(status, ActionStatus::Failed) || matches!(status, ActionStatus::FailedAndAbort)
+    matches!(status, ActionStatus::Failed)
+        || matches!(status, ActionStatus::FailedAndAbort)
+        || matches!(status, ActionStatus::TimedOut)
 }
 
 #[derive(Copy, Clone, Debug, Default, Deserialize, Serialize)]
@@ -19,6 +21,7 @@ pub enum ActionStatus {
     #[default]
     Running,
     Skipped, // When nothing happened
+    TimedOut,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
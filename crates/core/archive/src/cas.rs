@@ -0,0 +1,379 @@
+use crate::errors::ArchiveError;
+use crate::helpers::prepend_name;
+use crate::tree_differ::TreeDiffer;
+use moon_logger::{debug, trace};
+use moon_utils::path;
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use starbase_styles::color;
+use starbase_utils::{fs, glob, json};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const LOG_TARGET: &str = "moon:archive:cas";
+
+static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// A single file within a manifest, that points to a blob in the store.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CasManifestEntry {
+    pub executable: bool,
+
+    /// SHA-256 of the file contents, and the blob's name.
+    pub hash: String,
+
+    /// Path relative to the input root, using forward slashes.
+    pub path: String,
+
+    pub size: u64,
+}
+
+/// A list of files that were packed into the store, for a single run.
+#[derive(Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CasManifest {
+    pub files: Vec<CasManifestEntry>,
+}
+
+impl CasManifest {
+    pub fn load(path: &Path) -> Result<Self, ArchiveError> {
+        Ok(json::read_file(path)?)
+    }
+}
+
+/// Hash the contents of a file with SHA-256.
+pub fn hash_file(path: &Path) -> Result<String, ArchiveError> {
+    let mut file = fs::open_file(path)?;
+    let mut sha = Sha256::new();
+
+    io::copy(&mut file, &mut sha)?;
+
+    Ok(format!("{:x}", sha.finalize()))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> Result<bool, ArchiveError> {
+    use std::os::unix::fs::PermissionsExt;
+
+    Ok(fs::metadata(path)?.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(_path: &Path) -> Result<bool, ArchiveError> {
+    Ok(false)
+}
+
+#[cfg(unix)]
+fn set_writable(path: &Path, executable: bool) -> Result<(), ArchiveError> {
+    fs::update_perms(path, Some(if executable { 0o755 } else { 0o644 }))?;
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+#[allow(clippy::permissions_set_readonly_false)]
+fn set_writable(path: &Path, _executable: bool) -> Result<(), ArchiveError> {
+    let mut perms = fs::metadata(path)?.permissions();
+    perms.set_readonly(false);
+
+    std::fs::set_permissions(path, perms)?;
+
+    Ok(())
+}
+
+fn set_readonly(path: &Path) -> Result<(), ArchiveError> {
+    let mut perms = fs::metadata(path)?.permissions();
+    perms.set_readonly(true);
+
+    std::fs::set_permissions(path, perms)?;
+
+    Ok(())
+}
+
+/// A content-addressable store of file blobs, keyed by the SHA-256 of their
+/// contents. Identical files across runs (and across targets) are only stored once.
+pub struct CasStore {
+    pub blobs_dir: PathBuf,
+
+    /// Hardlink blobs into their destination instead of copying them.
+    /// Blobs are read-only, so hardlinked files will also be read-only.
+    pub hardlink: bool,
+}
+
+impl CasStore {
+    pub fn new(blobs_dir: &Path) -> Self {
+        CasStore {
+            blobs_dir: blobs_dir.to_path_buf(),
+            hardlink: false,
+        }
+    }
+
+    pub fn set_hardlink(&mut self, hardlink: bool) -> &mut Self {
+        self.hardlink = hardlink;
+        self
+    }
+
+    /// Blobs are sharded by the first 2 characters of their hash,
+    /// to avoid a single directory with an excessive amount of files.
+    pub fn get_blob_path(&self, hash: &str) -> PathBuf {
+        self.blobs_dir.join(&hash[0..2]).join(hash)
+    }
+
+    /// Add the source file to the store, if a blob with the same contents
+    /// does not already exist, and return an entry that points to it.
+    pub fn add_blob(&self, source: &Path, name: &str) -> Result<CasManifestEntry, ArchiveError> {
+        let hash = hash_file(source)?;
+        let blob_path = self.get_blob_path(&hash);
+
+        if blob_path.exists() {
+            trace!(
+                target: LOG_TARGET,
+                "Blob for file {} already exists, skipping",
+                color::path(source)
+            );
+        } else {
+            trace!(target: LOG_TARGET, "Storing file {}", color::path(source));
+
+            // Copy to a temporary file first, so that an interrupted
+            // copy never results in a partial blob. The name is unique per writer,
+            // as multiple processes or threads may store the same blob concurrently.
+            let temp_path = blob_path.with_extension(format!(
+                "{}-{}.tmp",
+                process::id(),
+                TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+            ));
+
+            fs::copy_file(source, &temp_path)?;
+            set_readonly(&temp_path)?;
+
+            if let Err(error) = fs::rename(&temp_path, &blob_path) {
+                // Another writer may have stored the same blob first
+                if !blob_path.exists() {
+                    return Err(error.into());
+                }
+
+                fs::remove_file(&temp_path)?;
+            }
+        }
+
+        Ok(CasManifestEntry {
+            executable: is_executable(source)?,
+            hash,
+            path: name.to_owned(),
+            size: fs::metadata(source)?.len(),
+        })
+    }
+
+    /// Copy the blob of the provided entry to the destination,
+    /// and ensure the destination is writable.
+    pub fn copy_blob(&self, entry: &CasManifestEntry, dest: &Path) -> Result<(), ArchiveError> {
+        let blob_path = self.get_blob_path(&entry.hash);
+
+        if !blob_path.exists() {
+            return Err(ArchiveError::MissingBlob(
+                entry.hash.clone(),
+                entry.path.clone(),
+            ));
+        }
+
+        if dest.exists() {
+            fs::remove_file(dest)?;
+        }
+
+        fs::copy_file(&blob_path, dest)?;
+        set_writable(dest, entry.executable)?;
+
+        Ok(())
+    }
+
+    /// Restore the blob of the provided entry to the destination, either
+    /// by hardlinking (when enabled and possible) or by copying.
+    pub fn restore_blob(&self, entry: &CasManifestEntry, dest: &Path) -> Result<(), ArchiveError> {
+        let blob_path = self.get_blob_path(&entry.hash);
+
+        // Hardlinks share permissions with the blob, so only link
+        // when the executable bit of both files match
+        if self.hardlink && blob_path.exists() && is_executable(&blob_path)? == entry.executable {
+            if dest.exists() {
+                fs::remove_file(dest)?;
+            }
+
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent)?;
+            }
+
+            if std::fs::hard_link(&blob_path, dest).is_ok() {
+                return Ok(());
+            }
+
+            // Linking across devices is not possible, so fallback to copying
+        }
+
+        self.copy_blob(entry, dest)
+    }
+
+    /// Remove all blobs that are not referenced in the provided set of hashes,
+    /// and return the amount of files deleted and bytes saved.
+    pub fn remove_unreferenced_blobs(
+        &self,
+        referenced: &FxHashSet<String>,
+    ) -> Result<(usize, u64), ArchiveError> {
+        let mut files_deleted = 0;
+        let mut bytes_saved = 0;
+
+        if !self.blobs_dir.exists() {
+            return Ok((files_deleted, bytes_saved));
+        }
+
+        for entry in fs::read_dir_all(&self.blobs_dir)? {
+            let blob_path = entry.path();
+
+            if referenced.contains(&fs::file_name(&blob_path)) {
+                continue;
+            }
+
+            let bytes = entry.metadata().map(|meta| meta.len()).unwrap_or_default();
+
+            // Windows does not allow read-only files to be removed
+            set_writable(&blob_path, false)?;
+
+            if fs::remove_file(&blob_path).is_ok() {
+                files_deleted += 1;
+                bytes_saved += bytes;
+            }
+        }
+
+        Ok((files_deleted, bytes_saved))
+    }
+}
+
+pub struct CasArchiver<'l> {
+    input_root: &'l Path,
+
+    output_file: &'l Path,
+
+    store: &'l CasStore,
+
+    // relative file in manifest -> absolute file path to source
+    sources: FxHashMap<String, PathBuf>,
+
+    source_globs: Vec<String>,
+}
+
+impl<'l> CasArchiver<'l> {
+    pub fn new(input_root: &'l Path, output_file: &'l Path, store: &'l CasStore) -> Self {
+        CasArchiver {
+            input_root,
+            output_file,
+            store,
+            sources: FxHashMap::default(),
+            source_globs: vec![],
+        }
+    }
+
+    pub fn add_source<P: AsRef<Path>>(&mut self, source: P, name: Option<&str>) -> &mut Self {
+        let source = source.as_ref();
+        let name = match name {
+            Some(n) => n.to_owned(),
+            None => fs::file_name(source),
+        };
+
+        self.sources.insert(name, source.to_path_buf());
+        self
+    }
+
+    pub fn add_source_glob(&mut self, glob: &str) -> &mut Self {
+        self.source_globs.push(glob.to_owned());
+        self
+    }
+
+    /// Store all sources as blobs, and write a manifest of them to the output file.
+    pub fn pack(&self) -> Result<CasManifest, ArchiveError> {
+        debug!(
+            target: LOG_TARGET,
+            "Packing blobs from {} with manifest {}",
+            color::path(self.input_root),
+            color::path(self.output_file),
+        );
+
+        let mut files = FxHashMap::default();
+
+        for (name, source) in &self.sources {
+            if source.is_file() {
+                files.insert(name.to_owned(), self.store.add_blob(source, name)?);
+            } else if source.is_dir() {
+                for entry in fs::read_dir_all(source)? {
+                    let file = entry.path();
+                    let file_name =
+                        prepend_name(&path::to_string(file.strip_prefix(source).unwrap())?, name);
+                    let file_name = path::standardize_separators(file_name);
+
+                    files.insert(file_name.clone(), self.store.add_blob(&file, &file_name)?);
+                }
+            } else {
+                trace!(
+                    target: LOG_TARGET,
+                    "Source file {} does not exist, skipping",
+                    color::path(source)
+                );
+            }
+        }
+
+        for glob in &self.source_globs {
+            for file in glob::walk_files(self.input_root, [glob])? {
+                let file_name = path::standardize_separators(path::to_string(
+                    file.strip_prefix(self.input_root).unwrap(),
+                )?);
+
+                files.insert(file_name.clone(), self.store.add_blob(&file, &file_name)?);
+            }
+        }
+
+        let mut manifest = CasManifest {
+            files: files.into_values().collect(),
+        };
+
+        manifest.files.sort_by(|a, d| a.path.cmp(&d.path));
+
+        json::write_file(self.output_file, &manifest, true)?;
+
+        Ok(manifest)
+    }
+}
+
+/// Restore the provided manifest entries into the output directory, but only
+/// write files that have changed, and remove files that are no longer tracked.
+#[track_caller]
+pub fn unpack_cas_with_diff<O: AsRef<Path>>(
+    differ: &mut TreeDiffer,
+    store: &CasStore,
+    entries: &[CasManifestEntry],
+    output_dir: O,
+) -> Result<(), ArchiveError> {
+    let output_dir = output_dir.as_ref();
+
+    debug!(
+        target: LOG_TARGET,
+        "Unpacking {} blobs to {}",
+        entries.len(),
+        color::path(output_dir),
+    );
+
+    for entry in entries {
+        let output_path = output_dir.join(&entry.path);
+
+        if differ.should_write_blob(entry.size, &entry.hash, &output_path)? {
+            store.restore_blob(entry, &output_path)?;
+        }
+
+        differ.untrack_file(&output_path);
+    }
+
+    differ.remove_stale_tracked_files();
+
+    Ok(())
+}
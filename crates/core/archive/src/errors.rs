@@ -1,6 +1,7 @@
 use moon_error::MoonError;
 use starbase_utils::fs::FsError;
 use starbase_utils::glob::GlobError;
+use starbase_utils::json::JsonError;
 use thiserror::Error;
 use zip::result::ZipError;
 
@@ -15,6 +16,12 @@ pub enum ArchiveError {
     #[error(transparent)]
     Io(#[from] std::io::Error),
 
+    #[error(transparent)]
+    Json(#[from] JsonError),
+
+    #[error("Missing blob {0} for file {1}.")]
+    MissingBlob(String, String),
+
     #[error(transparent)]
     Moon(#[from] MoonError),
 
@@ -1,9 +1,11 @@
+mod cas;
 mod errors;
 mod helpers;
 mod tar;
 mod tree_differ;
 mod zip;
 
+pub use crate::cas::*;
 pub use crate::tar::*;
 pub use crate::zip::*;
 pub use errors::ArchiveError;
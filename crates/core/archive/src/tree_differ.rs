@@ -1,3 +1,4 @@
+use crate::cas::hash_file;
 use crate::errors::ArchiveError;
 use rustc_hash::FxHashMap;
 use starbase_utils::{fs, glob};
@@ -122,6 +123,27 @@ impl TreeDiffer {
         Ok(true)
     }
 
+    /// Determine whether a blob should be written to the destination. Unlike sources,
+    /// blobs have a known content hash, so we can skip files that are equal.
+    pub fn should_write_blob(
+        &self,
+        blob_size: u64,
+        blob_hash: &str,
+        dest_path: &Path,
+    ) -> Result<bool, ArchiveError> {
+        // If the destination doesn't exist, always use the blob
+        let Some(dest_size) = self.files.get(dest_path) else {
+            return Ok(true);
+        };
+
+        if !dest_path.exists() || blob_size != *dest_size {
+            return Ok(true);
+        }
+
+        // If the file sizes are the same, compare content hashes to determine a difference
+        Ok(hash_file(dest_path)? != blob_hash)
+    }
+
     /// Untrack a destination file from the internal registry.
     pub fn untrack_file(&mut self, dest: &Path) {
         self.files.remove(dest);
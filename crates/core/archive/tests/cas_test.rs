@@ -0,0 +1,257 @@
+use moon_archive::{
+    hash_file, unpack_cas_with_diff, CasArchiver, CasManifest, CasStore, TreeDiffer,
+};
+use moon_test_utils::create_temp_dir;
+use moon_utils::string_vec;
+use rustc_hash::FxHashSet;
+use std::fs;
+use std::path::Path;
+
+fn create_files(root: &Path) {
+    fs::create_dir_all(root.join("folder/nested")).unwrap();
+    fs::write(root.join("file.txt"), "file").unwrap();
+    fs::write(root.join("folder/a.txt"), "same").unwrap();
+    fs::write(root.join("folder/nested/b.txt"), "same").unwrap();
+    fs::write(root.join("folder/c.log"), "log").unwrap();
+}
+
+fn pack(root: &Path, store: &CasStore, manifest_file: &Path) -> CasManifest {
+    let mut cas = CasArchiver::new(root, manifest_file, store);
+    cas.add_source(root.join("file.txt"), Some("file.txt"));
+    cas.add_source(root.join("folder"), Some("folder"));
+    cas.add_source(root.join("missing.txt"), Some("missing.txt"));
+    cas.pack().unwrap()
+}
+
+fn count_blobs(store: &CasStore) -> usize {
+    let mut count = 0;
+
+    for shard in fs::read_dir(&store.blobs_dir).unwrap() {
+        count += fs::read_dir(shard.unwrap().path()).unwrap().count();
+    }
+
+    count
+}
+
+#[test]
+fn packs_files_and_dirs_into_manifest() {
+    let dir = create_temp_dir();
+    let store = CasStore::new(&dir.path().join("blobs"));
+    let manifest_file = dir.path().join("manifest.json");
+
+    create_files(&dir.path().join("in"));
+
+    let manifest = pack(&dir.path().join("in"), &store, &manifest_file);
+
+    assert!(manifest_file.exists());
+    assert_eq!(CasManifest::load(&manifest_file).unwrap(), manifest);
+    assert_eq!(
+        manifest
+            .files
+            .iter()
+            .map(|f| f.path.as_str())
+            .collect::<Vec<_>>(),
+        vec![
+            "file.txt",
+            "folder/a.txt",
+            "folder/c.log",
+            "folder/nested/b.txt"
+        ]
+    );
+    assert_eq!(manifest.files[0].size, 4);
+    assert_eq!(
+        manifest.files[0].hash,
+        hash_file(&dir.path().join("in/file.txt")).unwrap()
+    );
+}
+
+#[test]
+fn packs_globs() {
+    let dir = create_temp_dir();
+    let store = CasStore::new(&dir.path().join("blobs"));
+    let manifest_file = dir.path().join("manifest.json");
+
+    create_files(&dir.path().join("in"));
+
+    let input = dir.path().join("in");
+    let mut cas = CasArchiver::new(&input, &manifest_file, &store);
+    cas.add_source_glob("folder/**/*.txt");
+
+    let manifest = cas.pack().unwrap();
+
+    assert_eq!(
+        manifest
+            .files
+            .iter()
+            .map(|f| f.path.as_str())
+            .collect::<Vec<_>>(),
+        vec!["folder/a.txt", "folder/nested/b.txt"]
+    );
+}
+
+#[test]
+fn dedupes_identical_files() {
+    let dir = create_temp_dir();
+    let store = CasStore::new(&dir.path().join("blobs"));
+
+    create_files(&dir.path().join("in"));
+
+    pack(&dir.path().join("in"), &store, &dir.path().join("1.json"));
+
+    // file, same, log
+    assert_eq!(count_blobs(&store), 3);
+
+    // Only the changed file should create a new blob
+    fs::write(dir.path().join("in/file.txt"), "changed").unwrap();
+
+    pack(&dir.path().join("in"), &store, &dir.path().join("2.json"));
+
+    assert_eq!(count_blobs(&store), 4);
+}
+
+#[test]
+fn stores_same_blob_concurrently() {
+    let dir = create_temp_dir();
+    let store = CasStore::new(&dir.path().join("blobs"));
+    let source = dir.path().join("file.txt");
+
+    fs::write(&source, "concurrent".repeat(10_000)).unwrap();
+
+    std::thread::scope(|scope| {
+        for _ in 0..8 {
+            scope.spawn(|| store.add_blob(&source, "file.txt").unwrap());
+        }
+    });
+
+    // No temporary files should be left behind
+    assert_eq!(count_blobs(&store), 1);
+}
+
+#[test]
+fn unpacks_manifest_and_removes_stale_files() {
+    let dir = create_temp_dir();
+    let store = CasStore::new(&dir.path().join("blobs"));
+    let input = dir.path().join("in");
+    let output = dir.path().join("out");
+
+    create_files(&input);
+
+    let manifest = pack(&input, &store, &dir.path().join("manifest.json"));
+
+    fs::create_dir_all(output.join("folder")).unwrap();
+    fs::write(output.join("folder/a.txt"), "diff").unwrap();
+    fs::write(output.join("folder/stale.txt"), "stale").unwrap();
+
+    let mut differ = TreeDiffer::load(&output, &string_vec!["file.txt", "folder"]).unwrap();
+
+    unpack_cas_with_diff(&mut differ, &store, &manifest.files, &output).unwrap();
+
+    assert_eq!(fs::read_to_string(output.join("file.txt")).unwrap(), "file");
+    assert_eq!(
+        fs::read_to_string(output.join("folder/a.txt")).unwrap(),
+        "same"
+    );
+    assert_eq!(
+        fs::read_to_string(output.join("folder/nested/b.txt")).unwrap(),
+        "same"
+    );
+    assert!(!output.join("folder/stale.txt").exists());
+
+    // Restored files should be writable
+    fs::write(output.join("file.txt"), "writable").unwrap();
+}
+
+#[test]
+fn skips_unchanged_files() {
+    let dir = create_temp_dir();
+    let store = CasStore::new(&dir.path().join("blobs"));
+    let input = dir.path().join("in");
+
+    create_files(&input);
+
+    let manifest = pack(&input, &store, &dir.path().join("manifest.json"));
+    let differ = TreeDiffer::load(&input, &string_vec!["file.txt", "folder"]).unwrap();
+    let entry = &manifest.files[0];
+
+    assert!(!differ
+        .should_write_blob(entry.size, &entry.hash, &input.join("file.txt"))
+        .unwrap());
+
+    fs::write(input.join("file.txt"), "diff").unwrap();
+
+    assert!(differ
+        .should_write_blob(entry.size, &entry.hash, &input.join("file.txt"))
+        .unwrap());
+}
+
+#[test]
+fn errors_for_missing_blob() {
+    let dir = create_temp_dir();
+    let store = CasStore::new(&dir.path().join("blobs"));
+    let input = dir.path().join("in");
+
+    create_files(&input);
+
+    let manifest = pack(&input, &store, &dir.path().join("manifest.json"));
+
+    store
+        .remove_unreferenced_blobs(&FxHashSet::default())
+        .unwrap();
+
+    let mut differ = TreeDiffer::load(&dir.path().join("out"), &[]).unwrap();
+
+    assert!(
+        unpack_cas_with_diff(&mut differ, &store, &manifest.files, dir.path().join("out")).is_err()
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn hardlinks_blobs() {
+    use std::os::unix::fs::MetadataExt;
+
+    let dir = create_temp_dir();
+    let mut store = CasStore::new(&dir.path().join("blobs"));
+    let input = dir.path().join("in");
+    let output = dir.path().join("out");
+
+    create_files(&input);
+
+    let manifest = pack(&input, &store, &dir.path().join("manifest.json"));
+
+    store.set_hardlink(true);
+
+    let mut differ = TreeDiffer::load(&output, &[]).unwrap();
+
+    unpack_cas_with_diff(&mut differ, &store, &manifest.files, &output).unwrap();
+
+    let entry = &manifest.files[0];
+
+    assert_eq!(
+        fs::metadata(output.join("file.txt")).unwrap().ino(),
+        fs::metadata(store.get_blob_path(&entry.hash))
+            .unwrap()
+            .ino()
+    );
+}
+
+#[test]
+fn removes_unreferenced_blobs() {
+    let dir = create_temp_dir();
+    let store = CasStore::new(&dir.path().join("blobs"));
+    let input = dir.path().join("in");
+
+    create_files(&input);
+
+    let manifest = pack(&input, &store, &dir.path().join("manifest.json"));
+    let file_hash = manifest.files[0].hash.clone();
+
+    let (deleted, bytes) = store
+        .remove_unreferenced_blobs(&FxHashSet::from_iter([file_hash.clone()]))
+        .unwrap();
+
+    assert_eq!(deleted, 2);
+    assert_eq!(bytes, 7);
+    assert_eq!(count_blobs(&store), 1);
+    assert!(store.get_blob_path(&file_hash).exists());
+}
@@ -2,11 +2,13 @@ use crate::helpers::LOG_TARGET;
 use crate::items::{DependenciesState, ProjectsState, RunTargetState, ToolState};
 use crate::runfiles::Runfile;
 use crate::{get_cache_mode, CacheMode};
+use moon_archive::{CasManifest, CasStore};
 use moon_common::consts::CONFIG_DIRNAME;
 use moon_error::MoonError;
-use moon_logger::{debug, trace};
+use moon_logger::{debug, trace, warn};
 use moon_platform_runtime::Runtime;
 use moon_utils::time;
+use rustc_hash::FxHashSet;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use starbase_styles::color;
@@ -21,7 +23,8 @@ pub struct CacheEngine {
     /// The `.moon/cache/hashes` directory. Stores hash manifests.
     pub hashes_dir: PathBuf,
 
-    /// The `.moon/cache/outputs` directory. Stores task outputs as hashed archives.
+    /// The `.moon/cache/outputs` directory. Stores task outputs as hashed archives,
+    /// or as content-addressed blobs with a manifest per hash.
     pub outputs_dir: PathBuf,
 
     /// The `.moon/cache/states` directory. Stores state information about anything...
@@ -115,8 +118,12 @@ impl CacheEngine {
         let hashes_dir = fs::remove_dir_stale_contents(&self.hashes_dir, duration)?;
         let outputs_dir = fs::remove_dir_stale_contents(&self.outputs_dir, duration)?;
 
-        let deleted = hashes_dir.files_deleted + outputs_dir.files_deleted;
-        let bytes = hashes_dir.bytes_saved + outputs_dir.bytes_saved;
+        // Blobs may be shared between manifests, so only delete
+        // the blobs that are no longer referenced by any manifest
+        let (blobs_deleted, blobs_bytes) = self.clean_unreferenced_blobs()?;
+
+        let deleted = hashes_dir.files_deleted + outputs_dir.files_deleted + blobs_deleted;
+        let bytes = hashes_dir.bytes_saved + outputs_dir.bytes_saved + blobs_bytes;
 
         trace!(
             target: LOG_TARGET,
@@ -128,6 +135,52 @@ impl CacheEngine {
         Ok((deleted, bytes))
     }
 
+    pub fn clean_unreferenced_blobs(&self) -> Result<(usize, u64), MoonError> {
+        let store = self.get_output_store();
+
+        if !store.blobs_dir.exists() {
+            return Ok((0, 0));
+        }
+
+        let mut referenced = FxHashSet::default();
+
+        for entry in fs::read_dir(&self.outputs_dir)? {
+            let path = entry.path();
+
+            if path.is_file()
+                && path
+                    .extension()
+                    .map(|ext| ext == "json")
+                    .unwrap_or_default()
+            {
+                // Skip manifests that can't be read, instead of aborting the clean,
+                // as their blobs will be removed and the outputs rebuilt on next run
+                match CasManifest::load(&path) {
+                    Ok(manifest) => {
+                        referenced.extend(manifest.files.into_iter().map(|file| file.hash));
+                    }
+                    Err(error) => {
+                        warn!(
+                            target: LOG_TARGET,
+                            "Failed to read outputs manifest {}, skipping: {}",
+                            color::path(&path),
+                            error
+                        );
+                    }
+                }
+            }
+        }
+
+        trace!(
+            target: LOG_TARGET,
+            "Deleting blobs that are not referenced by an outputs manifest"
+        );
+
+        store
+            .remove_unreferenced_blobs(&referenced)
+            .map_err(|e| MoonError::Generic(e.to_string()))
+    }
+
     pub fn create_hash_manifest<T>(&self, hash: &str, contents: &T) -> Result<(), MoonError>
     where
         T: ?Sized + Serialize,
@@ -167,6 +220,10 @@ impl CacheEngine {
         self.outputs_dir.join(format!("{hash}.tar.gz"))
     }
 
+    pub fn get_hash_blobs_manifest_path(&self, hash: &str) -> PathBuf {
+        self.outputs_dir.join(format!("{hash}.json"))
+    }
+
     pub fn get_hash_manifest_path(&self, hash: &str) -> PathBuf {
         self.hashes_dir.join(format!("{hash}.json"))
     }
@@ -175,6 +232,10 @@ impl CacheEngine {
         get_cache_mode()
     }
 
+    pub fn get_output_store(&self) -> CasStore {
+        CasStore::new(&self.outputs_dir.join("blobs"))
+    }
+
     pub fn get_state_path<T: AsRef<str>>(&self, file: T) -> PathBuf {
         self.states_dir.join(file.as_ref())
     }
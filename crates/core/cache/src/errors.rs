@@ -0,0 +1,45 @@
+use miette::Diagnostic;
+use moon_error::MoonError;
+use thiserror::Error;
+
+#[derive(Error, Debug, Diagnostic)]
+pub enum RemoteCacheError {
+    #[error("Failed to check for remote artifact {0}: {1}")]
+    ArtifactCheckFailure(String, String),
+
+    #[error("Failed to download remote artifact {0}: {1}")]
+    ArtifactDownloadFailure(String, String),
+
+    #[error("Failed to upload remote artifact {0}: {1}")]
+    ArtifactUploadFailure(String, String),
+
+    #[error("Remote cache is missing the blob for output {0}.")]
+    BlobMissing(String),
+
+    #[error("Remote cache request failed ({0}): {1}")]
+    GrpcFailure(String, String),
+
+    #[error("Invalid remote cache header {0}: {1}")]
+    InvalidHeader(String, String),
+
+    #[error("Remote cache returned an invalid output path {0}. Paths must be relative to the workspace root.")]
+    InvalidOutputPath(String),
+
+    #[error("Invalid remote cache URL {0}: {1}")]
+    InvalidUrl(String, String),
+
+    #[error("Failed to send request to remote cache: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[diagnostic(transparent)]
+    #[error(transparent)]
+    Moon(#[from] MoonError),
+
+    #[diagnostic(transparent)]
+    #[error(transparent)]
+    StarFs(#[from] starbase_utils::fs::FsError),
+
+    #[diagnostic(transparent)]
+    #[error(transparent)]
+    StarGlob(#[from] starbase_utils::glob::GlobError),
+}
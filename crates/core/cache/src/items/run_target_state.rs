@@ -1,6 +1,9 @@
 use crate::cache_item;
 use crate::helpers::get_cache_mode;
-use moon_archive::{untar_with_diff, TarArchiver, TreeDiffer};
+use moon_archive::{
+    unpack_cas_with_diff, untar_with_diff, CasArchiver, CasManifest, CasStore, TarArchiver,
+    TreeDiffer,
+};
 use moon_error::MoonError;
 use moon_logger::{map_list, trace, warn};
 use serde::{Deserialize, Serialize};
@@ -16,6 +19,9 @@ pub struct RunTargetState {
 
     pub hash: String,
 
+    /// How long the last run took to execute, in milliseconds.
+    pub last_run_duration: u128,
+
     pub last_run_time: u128,
 
     pub target: String,
@@ -26,7 +32,7 @@ pub struct RunTargetState {
 
 cache_item!(RunTargetState);
 
-fn prepare_outputs_list(outputs: &[String], source: &str) -> Vec<String> {
+pub(crate) fn prepare_outputs_list(outputs: &[String], source: &str) -> Vec<String> {
     let mut list = vec![];
 
     for output in outputs {
@@ -85,6 +91,49 @@ impl RunTargetState {
         Ok(false)
     }
 
+    pub fn archive_outputs_to_store(
+        &self,
+        manifest_file: &Path,
+        store: &CasStore,
+        workspace_root: &Path,
+        project_source: &str,
+        outputs: &[String],
+    ) -> Result<bool, MoonError> {
+        if get_cache_mode().is_writable() && !manifest_file.exists() {
+            let mut cas = CasArchiver::new(workspace_root, manifest_file, store);
+
+            // Outputs are relative from project root (the input)
+            if !outputs.is_empty() {
+                let outputs = prepare_outputs_list(outputs, project_source);
+
+                for output in &outputs {
+                    if glob::is_glob(output) {
+                        cas.add_source_glob(output);
+                    } else {
+                        cas.add_source(workspace_root.join(output), Some(output));
+                    }
+                }
+            }
+
+            // Also include stdout/stderr logs at the root of the manifest
+            let (stdout_path, stderr_path) = self.get_output_logs();
+
+            if stdout_path.exists() {
+                cas.add_source(stdout_path, Some("stdout.log"));
+            }
+
+            if stderr_path.exists() {
+                cas.add_source(stderr_path, Some("stderr.log"));
+            }
+
+            cas.pack().map_err(|e| MoonError::Generic(e.to_string()))?;
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
     pub fn hydrate_outputs(
         &self,
         archive_file: &Path,
@@ -154,6 +203,66 @@ impl RunTargetState {
         Ok(false)
     }
 
+    pub fn hydrate_outputs_from_store(
+        &self,
+        manifest_file: &Path,
+        store: &CasStore,
+        workspace_root: &Path,
+        project_source: &str,
+        outputs: &[String],
+    ) -> Result<bool, MoonError> {
+        if get_cache_mode().is_readable() && manifest_file.exists() {
+            let manifest =
+                CasManifest::load(manifest_file).map_err(|e| MoonError::Generic(e.to_string()))?;
+            let outputs = prepare_outputs_list(outputs, project_source);
+            let (stdout_log, stderr_log) = self.get_output_logs();
+
+            // Logs are always copied, as they are written to after hydration
+            let (logs, files): (Vec<_>, Vec<_>) = manifest
+                .files
+                .into_iter()
+                .partition(|file| file.path == "stdout.log" || file.path == "stderr.log");
+
+            let hydrate = || {
+                let mut differ = TreeDiffer::load(workspace_root, &outputs)?;
+
+                unpack_cas_with_diff(&mut differ, store, &files, workspace_root)?;
+
+                for log in &logs {
+                    store.copy_blob(
+                        log,
+                        if log.path == "stdout.log" {
+                            &stdout_log
+                        } else {
+                            &stderr_log
+                        },
+                    )?;
+                }
+
+                Ok::<(), moon_archive::ArchiveError>(())
+            };
+
+            if let Err(e) = hydrate() {
+                warn!(
+                    "Failed to hydrate outputs ({}) from cache: {}",
+                    map_list(&outputs, |f| color::file(f)),
+                    color::muted_light(e.to_string())
+                );
+
+                // Delete target outputs to ensure a clean slate
+                for output in outputs {
+                    fs::remove(workspace_root.join(output))?;
+                }
+
+                return Ok(false);
+            }
+
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
     pub fn get_output_logs(&self) -> (PathBuf, PathBuf) {
         (
             self.get_dir().join("stdout.log"),
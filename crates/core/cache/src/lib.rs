@@ -1,10 +1,16 @@
 mod engine;
+mod errors;
 mod helpers;
 mod item;
 mod items;
+mod reapi;
+mod remote_cache;
 mod runfiles;
 
 pub use engine::CacheEngine;
+pub use errors::RemoteCacheError;
 pub use helpers::*;
 pub use item::*;
 pub use items::*;
+pub use reapi::{collect_output_files, create_digest, proto as reapi_proto, BazelRemoteCache};
+pub use remote_cache::RemoteCache;
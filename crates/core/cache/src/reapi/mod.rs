@@ -0,0 +1,744 @@
+pub mod proto;
+
+use crate::errors::RemoteCacheError;
+use crate::helpers::LOG_TARGET;
+use crate::items::prepare_outputs_list;
+use moon_archive::TreeDiffer;
+use moon_config::RemoteCacheConfig;
+use moon_error::MoonError;
+use moon_logger::trace;
+use proto::*;
+use rustc_hash::FxHashMap;
+use sha2::{Digest as _, Sha256};
+use starbase_styles::color;
+use starbase_utils::{
+    fs::{self, FsError},
+    glob,
+};
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tonic::codec::ProstCodec;
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::metadata::{AsciiMetadataKey, AsciiMetadataValue, MetadataMap};
+use tonic::transport::{Channel, Endpoint};
+use tonic::{Code, Request};
+
+// Most servers limit gRPC messages to 4MiB, so leave some headroom
+// for the message envelope. Blobs larger than this are streamed.
+const MAX_BATCH_SIZE: i64 = 3 * 1024 * 1024;
+const STREAM_CHUNK_SIZE: usize = 1024 * 1024;
+
+const GET_ACTION_RESULT: &str = "/build.bazel.remote.execution.v2.ActionCache/GetActionResult";
+const UPDATE_ACTION_RESULT: &str =
+    "/build.bazel.remote.execution.v2.ActionCache/UpdateActionResult";
+const FIND_MISSING_BLOBS: &str =
+    "/build.bazel.remote.execution.v2.ContentAddressableStorage/FindMissingBlobs";
+const BATCH_UPDATE_BLOBS: &str =
+    "/build.bazel.remote.execution.v2.ContentAddressableStorage/BatchUpdateBlobs";
+const BATCH_READ_BLOBS: &str =
+    "/build.bazel.remote.execution.v2.ContentAddressableStorage/BatchReadBlobs";
+const BYTESTREAM_READ: &str = "/google.bytestream.ByteStream/Read";
+const BYTESTREAM_WRITE: &str = "/google.bytestream.ByteStream/Write";
+
+/// Create a content digest (SHA-256 and size) for the provided bytes.
+pub fn create_digest(data: &[u8]) -> Digest {
+    let mut sha = Sha256::new();
+    sha.update(data);
+
+    Digest {
+        hash: format!("{:x}", sha.finalize()),
+        size_bytes: data.len() as i64,
+    }
+}
+
+/// Create a content digest for the file at the provided path, without reading
+/// the entire file into memory.
+fn create_file_digest(path: &Path) -> Result<Digest, RemoteCacheError> {
+    let map_error = |error| FsError::Read {
+        path: path.to_path_buf(),
+        error,
+    };
+
+    let mut file = std::fs::File::open(path).map_err(map_error)?;
+    let mut sha = Sha256::new();
+    let size = std::io::copy(&mut file, &mut sha).map_err(map_error)?;
+
+    Ok(Digest {
+        hash: format!("{:x}", sha.finalize()),
+        size_bytes: size as i64,
+    })
+}
+
+/// Expand the task outputs (files, directories, and globs) into a list of
+/// individual files, relative to the workspace root.
+pub fn collect_output_files(
+    workspace_root: &Path,
+    project_source: &str,
+    outputs: &[String],
+) -> Result<Vec<PathBuf>, RemoteCacheError> {
+    let mut files = vec![];
+
+    for output in prepare_outputs_list(outputs, project_source) {
+        let abs_output = workspace_root.join(&output);
+
+        if glob::is_glob(&output) {
+            files.extend(glob::walk_files(workspace_root, [&output])?);
+        } else if abs_output.is_dir() {
+            files.extend(glob::walk_files(&abs_output, ["**/*"])?);
+        } else if abs_output.is_file() {
+            files.push(abs_output);
+        }
+    }
+
+    let mut files = files
+        .into_iter()
+        .filter_map(|file| file.strip_prefix(workspace_root).ok().map(PathBuf::from))
+        .collect::<Vec<_>>();
+
+    files.sort();
+    files.dedup();
+
+    Ok(files)
+}
+
+fn map_status(status: tonic::Status) -> RemoteCacheError {
+    RemoteCacheError::GrpcFailure(format!("{:?}", status.code()), status.message().to_owned())
+}
+
+/// A remote cache that speaks the Bazel remote execution API over gRPC, and is
+/// compatible with servers like bazel-remote and BuildBuddy. Each task output file
+/// is stored as an individual blob in the content-addressable storage (CAS), while
+/// the action cache (AC) maps the target hash to the list of output files.
+#[derive(Clone, Debug)]
+pub struct BazelRemoteCache {
+    channel: Channel,
+
+    metadata: MetadataMap,
+
+    pub instance_name: String,
+
+    /// Whether outputs should be uploaded to the remote.
+    pub writable: bool,
+}
+
+impl BazelRemoteCache {
+    /// Create a remote cache from the `runner.remoteCache` setting. Will return
+    /// `None` if a URL has not been configured. The connection is established lazily.
+    pub fn from_config(config: &RemoteCacheConfig) -> Result<Option<Self>, RemoteCacheError> {
+        let Some(url) = &config.url else {
+            return Ok(None);
+        };
+
+        // tonic only supports HTTP schemes
+        let url = if let Some(rest) = url.strip_prefix("grpcs://") {
+            format!("https://{rest}")
+        } else if let Some(rest) = url.strip_prefix("grpc://") {
+            format!("http://{rest}")
+        } else {
+            url.to_owned()
+        };
+
+        let endpoint = Endpoint::from_shared(url.clone())
+            .map_err(|e| RemoteCacheError::InvalidUrl(url.clone(), e.to_string()))?;
+
+        let mut metadata = MetadataMap::new();
+        let mut headers = config
+            .headers
+            .iter()
+            .map(|(k, v)| (k.to_owned(), v.to_owned()))
+            .collect::<Vec<_>>();
+
+        if let Some(token) = &config.token {
+            headers.push(("Authorization".into(), format!("Bearer {token}")));
+        }
+
+        for (key, value) in headers {
+            metadata.insert(
+                AsciiMetadataKey::from_bytes(key.to_lowercase().as_bytes())
+                    .map_err(|e| RemoteCacheError::InvalidHeader(key.clone(), e.to_string()))?,
+                AsciiMetadataValue::try_from(value.as_str())
+                    .map_err(|e| RemoteCacheError::InvalidHeader(key.clone(), e.to_string()))?,
+            );
+        }
+
+        Ok(Some(BazelRemoteCache {
+            channel: endpoint.connect_lazy(),
+            metadata,
+            instance_name: config.instance_name.clone().unwrap_or_default(),
+            writable: config.writable,
+        }))
+    }
+
+    /// Load the action result for the provided target hash from the action cache.
+    /// Will return `None` if the hash has not been cached.
+    pub async fn get_action_result(
+        &self,
+        hash: &str,
+    ) -> Result<Option<ActionResult>, RemoteCacheError> {
+        let response = self
+            .unary::<_, ActionResult>(
+                GET_ACTION_RESULT,
+                GetActionResultRequest {
+                    instance_name: self.instance_name.clone(),
+                    action_digest: Some(create_action_digest(hash)),
+                    inline_stdout: true,
+                    inline_stderr: true,
+                },
+            )
+            .await;
+
+        match response {
+            Ok(result) => Ok(Some(result)),
+            Err(status) if status.code() == Code::NotFound => Ok(None),
+            Err(status) => Err(map_status(status)),
+        }
+    }
+
+    /// Upload the provided output files (relative to the workspace root) and logs as
+    /// individual blobs, and then map the target hash to them in the action cache.
+    /// Blobs that already exist in the remote are not uploaded again.
+    pub async fn upload_outputs(
+        &self,
+        hash: &str,
+        workspace_root: &Path,
+        files: &[PathBuf],
+        logs: (&Path, &Path),
+        exit_code: i32,
+    ) -> Result<(), RemoteCacheError> {
+        // Blobs are only read into memory when uploaded, and large
+        // blobs are streamed from the file system in chunks
+        let mut blobs = FxHashMap::default();
+        let mut result = ActionResult {
+            exit_code,
+            ..ActionResult::default()
+        };
+
+        for file in files {
+            let abs_file = workspace_root.join(file);
+            let digest = create_file_digest(&abs_file)?;
+
+            result.output_files.push(OutputFile {
+                path: to_unix_path(file),
+                digest: Some(digest.clone()),
+                is_executable: is_executable(&abs_file),
+                ..OutputFile::default()
+            });
+
+            blobs.insert(digest, abs_file);
+        }
+
+        if logs.0.exists() {
+            let digest = create_file_digest(logs.0)?;
+
+            result.stdout_digest = Some(digest.clone());
+            blobs.insert(digest, logs.0.to_path_buf());
+        }
+
+        if logs.1.exists() {
+            let digest = create_file_digest(logs.1)?;
+
+            result.stderr_digest = Some(digest.clone());
+            blobs.insert(digest, logs.1.to_path_buf());
+        }
+
+        // Only upload blobs that the remote doesn't already have
+        let missing = self
+            .unary::<_, FindMissingBlobsResponse>(
+                FIND_MISSING_BLOBS,
+                FindMissingBlobsRequest {
+                    instance_name: self.instance_name.clone(),
+                    blob_digests: blobs.keys().cloned().collect(),
+                },
+            )
+            .await
+            .map_err(map_status)?
+            .missing_blob_digests;
+
+        trace!(
+            target: LOG_TARGET,
+            "Uploading {} of {} blobs for hash {} to remote cache",
+            missing.len(),
+            blobs.len(),
+            color::hash(hash),
+        );
+
+        let mut batch = vec![];
+        let mut batch_size = 0;
+
+        for digest in missing {
+            let Some(path) = blobs.remove(&digest) else {
+                continue;
+            };
+
+            if digest.size_bytes > MAX_BATCH_SIZE {
+                self.write_blob(&digest, &path).await?;
+                continue;
+            }
+
+            if batch_size + digest.size_bytes > MAX_BATCH_SIZE {
+                self.batch_update_blobs(std::mem::take(&mut batch)).await?;
+                batch_size = 0;
+            }
+
+            batch_size += digest.size_bytes;
+            batch.push(batch_update_blobs_request::Request {
+                data: fs::read_file_bytes(&path)?,
+                digest: Some(digest),
+            });
+        }
+
+        if !batch.is_empty() {
+            self.batch_update_blobs(batch).await?;
+        }
+
+        self.unary::<_, ActionResult>(
+            UPDATE_ACTION_RESULT,
+            UpdateActionResultRequest {
+                instance_name: self.instance_name.clone(),
+                action_digest: Some(create_action_digest(hash)),
+                action_result: Some(result),
+            },
+        )
+        .await
+        .map_err(map_status)?;
+
+        Ok(())
+    }
+
+    /// Download all output files and logs of the action result, and write them
+    /// to their original locations. Returns the list of written files.
+    pub async fn download_outputs(
+        &self,
+        result: &ActionResult,
+        workspace_root: &Path,
+        project_source: &str,
+        outputs: &[String],
+        logs: (&Path, &Path),
+    ) -> Result<Vec<PathBuf>, RemoteCacheError> {
+        // Paths come from the server, so never trust them to stay within the workspace
+        for file in &result.output_files {
+            if !is_normalized_relative_path(&file.path) {
+                return Err(RemoteCacheError::InvalidOutputPath(file.path.clone()));
+            }
+        }
+
+        // Track the current outputs, so that stale files can be removed after hydrating
+        let mut differ = TreeDiffer::load(
+            workspace_root,
+            &prepare_outputs_list(outputs, project_source),
+        )
+        .map_err(|error| MoonError::Generic(error.to_string()))?;
+
+        // Map each blob to the files it should be written to, so that a blob is only
+        // downloaded once, and is written to the file system as soon as it's received
+        let mut targets: FxHashMap<Digest, Vec<PathBuf>> = FxHashMap::default();
+        let mut add_target = |inline: &[u8],
+                              digest: &Option<Digest>,
+                              path: PathBuf|
+         -> Result<bool, RemoteCacheError> {
+            if !inline.is_empty() {
+                fs::write_file(&path, inline)?;
+
+                return Ok(true);
+            }
+
+            match digest {
+                Some(digest) if digest.size_bytes == 0 => {
+                    fs::write_file(&path, [])?;
+                }
+                Some(digest) => {
+                    targets.entry(digest.clone()).or_default().push(path);
+                }
+                None => {
+                    return Ok(false);
+                }
+            };
+
+            Ok(true)
+        };
+
+        let mut written = vec![];
+
+        for file in &result.output_files {
+            let abs_file = workspace_root.join(&file.path);
+
+            if !add_target(&file.contents, &file.digest, abs_file.clone())? {
+                return Err(RemoteCacheError::BlobMissing(file.path.clone()));
+            }
+
+            written.push(abs_file);
+        }
+
+        add_target(
+            &result.stdout_raw,
+            &result.stdout_digest,
+            logs.0.to_path_buf(),
+        )?;
+
+        add_target(
+            &result.stderr_raw,
+            &result.stderr_digest,
+            logs.1.to_path_buf(),
+        )?;
+
+        self.read_blobs(&targets).await?;
+
+        for file in &result.output_files {
+            if file.is_executable {
+                fs::update_perms(workspace_root.join(&file.path), Some(0o755))?;
+            }
+        }
+
+        for file in &written {
+            differ.untrack_file(file);
+        }
+
+        differ.remove_stale_tracked_files();
+
+        Ok(written)
+    }
+
+    async fn batch_update_blobs(
+        &self,
+        requests: Vec<batch_update_blobs_request::Request>,
+    ) -> Result<(), RemoteCacheError> {
+        let response = self
+            .unary::<_, BatchUpdateBlobsResponse>(
+                BATCH_UPDATE_BLOBS,
+                BatchUpdateBlobsRequest {
+                    instance_name: self.instance_name.clone(),
+                    requests,
+                },
+            )
+            .await
+            .map_err(map_status)?;
+
+        for res in response.responses {
+            if let Some(status) = res.status {
+                if status.code != 0 {
+                    return Err(RemoteCacheError::ArtifactUploadFailure(
+                        res.digest.map(|d| d.hash).unwrap_or_default(),
+                        status.message,
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn read_blobs(
+        &self,
+        targets: &FxHashMap<Digest, Vec<PathBuf>>,
+    ) -> Result<(), RemoteCacheError> {
+        let mut batch = vec![];
+        let mut batch_size = 0;
+
+        for digest in targets.keys() {
+            if digest.size_bytes > MAX_BATCH_SIZE {
+                self.read_blob(digest, &targets[digest]).await?;
+                continue;
+            }
+
+            if batch_size + digest.size_bytes > MAX_BATCH_SIZE {
+                self.batch_read_blobs(std::mem::take(&mut batch), targets)
+                    .await?;
+                batch_size = 0;
+            }
+
+            batch_size += digest.size_bytes;
+            batch.push(digest.clone());
+        }
+
+        if !batch.is_empty() {
+            self.batch_read_blobs(batch, targets).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn batch_read_blobs(
+        &self,
+        digests: Vec<Digest>,
+        targets: &FxHashMap<Digest, Vec<PathBuf>>,
+    ) -> Result<(), RemoteCacheError> {
+        let response = self
+            .unary::<_, BatchReadBlobsResponse>(
+                BATCH_READ_BLOBS,
+                BatchReadBlobsRequest {
+                    instance_name: self.instance_name.clone(),
+                    digests: digests.clone(),
+                },
+            )
+            .await
+            .map_err(map_status)?;
+
+        let mut received = 0;
+
+        for res in response.responses {
+            let Some(digest) = res.digest else {
+                continue;
+            };
+
+            if let Some(status) = res.status {
+                if status.code != 0 {
+                    return Err(RemoteCacheError::ArtifactDownloadFailure(
+                        digest.hash,
+                        status.message,
+                    ));
+                }
+            }
+
+            if let Some(paths) = targets.get(&digest) {
+                for path in paths {
+                    fs::write_file(path, &res.data)?;
+                }
+
+                received += 1;
+            }
+        }
+
+        if received < digests.len() {
+            return Err(RemoteCacheError::ArtifactDownloadFailure(
+                digests
+                    .iter()
+                    .map(|d| d.hash.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                "Remote cache did not return all requested blobs.".into(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Stream the blob into the first file, and then copy it to the others.
+    async fn read_blob(&self, digest: &Digest, paths: &[PathBuf]) -> Result<(), RemoteCacheError> {
+        let Some((path, others)) = paths.split_first() else {
+            return Ok(());
+        };
+
+        let mut client = self.create_client().await?;
+        let mut stream = client
+            .server_streaming(
+                self.create_request(ReadRequest {
+                    resource_name: self.get_resource_name(digest, None),
+                    ..ReadRequest::default()
+                }),
+                PathAndQuery::from_static(BYTESTREAM_READ),
+                ProstCodec::<ReadRequest, ReadResponse>::default(),
+            )
+            .await
+            .map_err(map_status)?
+            .into_inner();
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let map_write_error = |error| FsError::Write {
+            path: path.to_path_buf(),
+            error,
+        };
+
+        let mut file = tokio::fs::File::create(path)
+            .await
+            .map_err(|error| FsError::Create {
+                path: path.to_path_buf(),
+                error,
+            })?;
+
+        while let Some(chunk) = stream.message().await.map_err(map_status)? {
+            file.write_all(&chunk.data).await.map_err(map_write_error)?;
+        }
+
+        file.flush().await.map_err(map_write_error)?;
+
+        for other in others {
+            fs::copy_file(path, other)?;
+        }
+
+        Ok(())
+    }
+
+    /// Stream the file to the remote in chunks, without reading it entirely into memory.
+    async fn write_blob(&self, digest: &Digest, path: &Path) -> Result<(), RemoteCacheError> {
+        let resource_name = self.get_resource_name(digest, Some(uuid::Uuid::new_v4().to_string()));
+        let size = digest.size_bytes;
+        let read_error = Arc::new(Mutex::new(None));
+        let file = tokio::fs::File::open(path)
+            .await
+            .map_err(|error| FsError::Read {
+                path: path.to_path_buf(),
+                error,
+            })?;
+
+        let stream_read_error = Arc::clone(&read_error);
+        let requests =
+            futures::stream::unfold((file, 0, false), move |(mut file, offset, finished)| {
+                let resource_name = resource_name.clone();
+                let read_error = Arc::clone(&stream_read_error);
+
+                async move {
+                    if finished {
+                        return None;
+                    }
+
+                    let mut data = Vec::with_capacity(STREAM_CHUNK_SIZE);
+
+                    if let Err(error) = (&mut file)
+                        .take(STREAM_CHUNK_SIZE as u64)
+                        .read_to_end(&mut data)
+                        .await
+                    {
+                        *read_error.lock().unwrap() = Some(error);
+
+                        return None;
+                    }
+
+                    let next_offset = offset + data.len() as i64;
+                    let finish_write = data.is_empty() || next_offset >= size;
+
+                    Some((
+                        WriteRequest {
+                            resource_name: if offset == 0 {
+                                resource_name
+                            } else {
+                                String::new()
+                            },
+                            write_offset: offset,
+                            finish_write,
+                            data,
+                        },
+                        (file, next_offset, finish_write),
+                    ))
+                }
+            });
+
+        let mut client = self.create_client().await?;
+        let response = client
+            .client_streaming(
+                self.create_request(requests),
+                PathAndQuery::from_static(BYTESTREAM_WRITE),
+                ProstCodec::<WriteRequest, WriteResponse>::default(),
+            )
+            .await;
+
+        if let Some(error) = read_error.lock().unwrap().take() {
+            return Err(FsError::Read {
+                path: path.to_path_buf(),
+                error,
+            }
+            .into());
+        }
+
+        response.map_err(map_status)?;
+
+        Ok(())
+    }
+
+    fn get_resource_name(&self, digest: &Digest, upload_id: Option<String>) -> String {
+        let mut parts = vec![];
+
+        if !self.instance_name.is_empty() {
+            parts.push(self.instance_name.clone());
+        }
+
+        if let Some(id) = upload_id {
+            parts.push("uploads".into());
+            parts.push(id);
+        }
+
+        parts.push("blobs".into());
+        parts.push(digest.hash.clone());
+        parts.push(digest.size_bytes.to_string());
+        parts.join("/")
+    }
+
+    async fn create_client(&self) -> Result<tonic::client::Grpc<Channel>, RemoteCacheError> {
+        let mut client = tonic::client::Grpc::new(self.channel.clone());
+
+        client
+            .ready()
+            .await
+            .map_err(|e| RemoteCacheError::GrpcFailure("Unavailable".into(), e.to_string()))?;
+
+        Ok(client)
+    }
+
+    fn create_request<T>(&self, message: T) -> Request<T> {
+        let mut request = Request::new(message);
+
+        for entry in self.metadata.iter() {
+            if let tonic::metadata::KeyAndValueRef::Ascii(key, value) = entry {
+                request.metadata_mut().insert(key.clone(), value.clone());
+            }
+        }
+
+        request
+    }
+
+    async fn unary<I, O>(&self, path: &'static str, message: I) -> Result<O, tonic::Status>
+    where
+        I: prost::Message + Send + Sync + 'static,
+        O: prost::Message + Default + Send + Sync + 'static,
+    {
+        let mut client = tonic::client::Grpc::new(self.channel.clone());
+
+        client
+            .ready()
+            .await
+            .map_err(|e| tonic::Status::unavailable(e.to_string()))?;
+
+        let response = client
+            .unary(
+                self.create_request(message),
+                PathAndQuery::from_static(path),
+                ProstCodec::<I, O>::default(),
+            )
+            .await?;
+
+        Ok(response.into_inner())
+    }
+}
+
+/// The action digest is derived from the moon generated target hash,
+/// as we do not have an actual action definition to hash.
+fn create_action_digest(hash: &str) -> Digest {
+    create_digest(hash.as_bytes())
+}
+
+fn to_unix_path(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Output paths must be relative, use forward slashes, and not contain
+/// empty, `.`, or `..` segments, so that they can't escape the workspace.
+fn is_normalized_relative_path(path: &str) -> bool {
+    !path.is_empty()
+        && !path.contains('\\')
+        && path
+            .split('/')
+            .all(|segment| !segment.is_empty() && segment != "." && segment != "..")
+        && Path::new(path)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::metadata(path)
+        .map(|meta| meta.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(_path: &Path) -> bool {
+    false
+}
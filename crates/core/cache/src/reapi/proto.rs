@@ -0,0 +1,215 @@
+// A subset of the Bazel remote execution API (v2) messages that are required
+// for remote caching. These are hand-written instead of generated by `prost-build`,
+// so that we don't require `protoc` at build time.
+// https://github.com/bazelbuild/remote-apis/blob/main/build/bazel/remote/execution/v2/remote_execution.proto
+
+#[derive(Clone, Eq, Hash, PartialEq, ::prost::Message)]
+pub struct Digest {
+    #[prost(string, tag = "1")]
+    pub hash: String,
+
+    #[prost(int64, tag = "2")]
+    pub size_bytes: i64,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OutputFile {
+    #[prost(string, tag = "1")]
+    pub path: String,
+
+    #[prost(message, optional, tag = "2")]
+    pub digest: Option<Digest>,
+
+    #[prost(bool, tag = "4")]
+    pub is_executable: bool,
+
+    #[prost(bytes = "vec", tag = "5")]
+    pub contents: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ActionResult {
+    #[prost(message, repeated, tag = "2")]
+    pub output_files: Vec<OutputFile>,
+
+    #[prost(int32, tag = "4")]
+    pub exit_code: i32,
+
+    #[prost(bytes = "vec", tag = "5")]
+    pub stdout_raw: Vec<u8>,
+
+    #[prost(message, optional, tag = "6")]
+    pub stdout_digest: Option<Digest>,
+
+    #[prost(bytes = "vec", tag = "7")]
+    pub stderr_raw: Vec<u8>,
+
+    #[prost(message, optional, tag = "8")]
+    pub stderr_digest: Option<Digest>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetActionResultRequest {
+    #[prost(string, tag = "1")]
+    pub instance_name: String,
+
+    #[prost(message, optional, tag = "2")]
+    pub action_digest: Option<Digest>,
+
+    #[prost(bool, tag = "3")]
+    pub inline_stdout: bool,
+
+    #[prost(bool, tag = "4")]
+    pub inline_stderr: bool,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateActionResultRequest {
+    #[prost(string, tag = "1")]
+    pub instance_name: String,
+
+    #[prost(message, optional, tag = "2")]
+    pub action_digest: Option<Digest>,
+
+    #[prost(message, optional, tag = "3")]
+    pub action_result: Option<ActionResult>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FindMissingBlobsRequest {
+    #[prost(string, tag = "1")]
+    pub instance_name: String,
+
+    #[prost(message, repeated, tag = "2")]
+    pub blob_digests: Vec<Digest>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FindMissingBlobsResponse {
+    #[prost(message, repeated, tag = "2")]
+    pub missing_blob_digests: Vec<Digest>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BatchUpdateBlobsRequest {
+    #[prost(string, tag = "1")]
+    pub instance_name: String,
+
+    #[prost(message, repeated, tag = "2")]
+    pub requests: Vec<batch_update_blobs_request::Request>,
+}
+
+pub mod batch_update_blobs_request {
+    use super::Digest;
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Request {
+        #[prost(message, optional, tag = "1")]
+        pub digest: Option<Digest>,
+
+        #[prost(bytes = "vec", tag = "2")]
+        pub data: Vec<u8>,
+    }
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BatchUpdateBlobsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub responses: Vec<batch_update_blobs_response::Response>,
+}
+
+pub mod batch_update_blobs_response {
+    use super::{Digest, Status};
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Response {
+        #[prost(message, optional, tag = "1")]
+        pub digest: Option<Digest>,
+
+        #[prost(message, optional, tag = "2")]
+        pub status: Option<Status>,
+    }
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BatchReadBlobsRequest {
+    #[prost(string, tag = "1")]
+    pub instance_name: String,
+
+    #[prost(message, repeated, tag = "2")]
+    pub digests: Vec<Digest>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BatchReadBlobsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub responses: Vec<batch_read_blobs_response::Response>,
+}
+
+pub mod batch_read_blobs_response {
+    use super::{Digest, Status};
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Response {
+        #[prost(message, optional, tag = "1")]
+        pub digest: Option<Digest>,
+
+        #[prost(bytes = "vec", tag = "2")]
+        pub data: Vec<u8>,
+
+        #[prost(message, optional, tag = "3")]
+        pub status: Option<Status>,
+    }
+}
+
+// google.bytestream
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReadRequest {
+    #[prost(string, tag = "1")]
+    pub resource_name: String,
+
+    #[prost(int64, tag = "2")]
+    pub read_offset: i64,
+
+    #[prost(int64, tag = "3")]
+    pub read_limit: i64,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReadResponse {
+    #[prost(bytes = "vec", tag = "10")]
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WriteRequest {
+    #[prost(string, tag = "1")]
+    pub resource_name: String,
+
+    #[prost(int64, tag = "2")]
+    pub write_offset: i64,
+
+    #[prost(bool, tag = "3")]
+    pub finish_write: bool,
+
+    #[prost(bytes = "vec", tag = "10")]
+    pub data: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WriteResponse {
+    #[prost(int64, tag = "1")]
+    pub committed_size: i64,
+}
+
+// google.rpc
+
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Status {
+    #[prost(int32, tag = "1")]
+    pub code: i32,
+
+    #[prost(string, tag = "2")]
+    pub message: String,
+}
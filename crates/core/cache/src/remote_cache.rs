@@ -0,0 +1,186 @@
+use crate::errors::RemoteCacheError;
+use crate::helpers::LOG_TARGET;
+use moon_config::RemoteCacheConfig;
+use moon_error::map_io_to_fs_error;
+use moon_logger::trace;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, CONTENT_LENGTH};
+use reqwest::{Body, Client, StatusCode};
+use starbase_styles::color;
+use std::path::Path;
+use tokio::fs;
+use tokio_util::codec::{BytesCodec, FramedRead};
+
+/// A generic remote cache that stores task output archives on an HTTP server,
+/// keyed by the target hash. The server is expected to support the following:
+///
+/// - `HEAD <url>/<hash>` - Respond with a 200 if the artifact exists, otherwise a 404.
+/// - `GET <url>/<hash>` - Respond with the artifact contents, otherwise a 404.
+/// - `PUT <url>/<hash>` - Store the request body as the artifact.
+#[derive(Clone, Debug)]
+pub struct RemoteCache {
+    client: Client,
+
+    headers: HeaderMap,
+
+    /// Base URL that artifacts are read from and written to.
+    pub url: String,
+
+    /// Whether artifacts should be uploaded to the remote.
+    pub writable: bool,
+}
+
+impl RemoteCache {
+    /// Create a remote cache from the `runner.remoteCache` setting. Will return
+    /// `None` if a URL has not been configured.
+    pub fn from_config(config: &RemoteCacheConfig) -> Result<Option<Self>, RemoteCacheError> {
+        let Some(url) = &config.url else {
+            return Ok(None);
+        };
+
+        let mut headers = HeaderMap::new();
+
+        for (key, value) in &config.headers {
+            headers.insert(
+                HeaderName::from_bytes(key.as_bytes())
+                    .map_err(|e| RemoteCacheError::InvalidHeader(key.to_owned(), e.to_string()))?,
+                HeaderValue::from_str(value)
+                    .map_err(|e| RemoteCacheError::InvalidHeader(key.to_owned(), e.to_string()))?,
+            );
+        }
+
+        if let Some(token) = &config.token {
+            headers.insert(
+                AUTHORIZATION,
+                HeaderValue::from_str(&format!("Bearer {token}")).map_err(|e| {
+                    RemoteCacheError::InvalidHeader("Authorization".into(), e.to_string())
+                })?,
+            );
+        }
+
+        Ok(Some(RemoteCache {
+            client: Client::new(),
+            headers,
+            url: url.trim_end_matches('/').to_owned(),
+            writable: config.writable,
+        }))
+    }
+
+    pub fn get_artifact_url(&self, hash: &str) -> String {
+        format!("{}/{hash}", self.url)
+    }
+
+    /// Check whether an artifact with the provided hash exists in the remote.
+    pub async fn has_artifact(&self, hash: &str) -> Result<bool, RemoteCacheError> {
+        let response = self
+            .client
+            .head(self.get_artifact_url(hash))
+            .headers(self.headers.clone())
+            .send()
+            .await?;
+        let status = response.status();
+
+        if status.is_success() {
+            return Ok(true);
+        }
+
+        if status == StatusCode::NOT_FOUND {
+            return Ok(false);
+        }
+
+        Err(RemoteCacheError::ArtifactCheckFailure(
+            hash.to_owned(),
+            get_status_reason(status),
+        ))
+    }
+
+    /// Download the artifact with the provided hash to the destination file.
+    /// Will return false if the artifact does not exist in the remote.
+    pub async fn download_artifact(
+        &self,
+        hash: &str,
+        dest_path: &Path,
+    ) -> Result<bool, RemoteCacheError> {
+        trace!(
+            target: LOG_TARGET,
+            "Downloading artifact {} from {}",
+            color::file(hash),
+            color::url(&self.url)
+        );
+
+        let response = self
+            .client
+            .get(self.get_artifact_url(hash))
+            .headers(self.headers.clone())
+            .send()
+            .await?;
+        let status = response.status();
+
+        if status == StatusCode::NOT_FOUND {
+            return Ok(false);
+        }
+
+        if !status.is_success() {
+            return Err(RemoteCacheError::ArtifactDownloadFailure(
+                hash.to_owned(),
+                get_status_reason(status),
+            ));
+        }
+
+        let contents = response.bytes().await?;
+
+        fs::write(dest_path, contents)
+            .await
+            .map_err(|e| map_io_to_fs_error(e, dest_path.to_path_buf()))?;
+
+        Ok(true)
+    }
+
+    /// Upload the archive at the provided path to the remote, keyed by hash.
+    pub async fn upload_artifact(
+        &self,
+        hash: &str,
+        archive_path: &Path,
+    ) -> Result<(), RemoteCacheError> {
+        let file = fs::File::open(archive_path)
+            .await
+            .map_err(|e| map_io_to_fs_error(e, archive_path.to_path_buf()))?;
+        let file_length = match file.metadata().await {
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        };
+
+        trace!(
+            target: LOG_TARGET,
+            "Uploading artifact {} ({} bytes) to {}",
+            color::file(hash),
+            file_length,
+            color::url(&self.url)
+        );
+
+        let response = self
+            .client
+            .put(self.get_artifact_url(hash))
+            .headers(self.headers.clone())
+            .header(CONTENT_LENGTH, file_length)
+            .body(Body::wrap_stream(FramedRead::new(file, BytesCodec::new())))
+            .send()
+            .await?;
+        let status = response.status();
+
+        if status.is_success() {
+            return Ok(());
+        }
+
+        Err(RemoteCacheError::ArtifactUploadFailure(
+            hash.to_owned(),
+            get_status_reason(status),
+        ))
+    }
+}
+
+fn get_status_reason(status: StatusCode) -> String {
+    status
+        .canonical_reason()
+        .unwrap_or("Internal server error")
+        .to_owned()
+}
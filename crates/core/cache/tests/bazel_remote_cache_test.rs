@@ -0,0 +1,558 @@
+use moon_cache::reapi_proto::{ActionResult, OutputFile};
+use moon_cache::{collect_output_files, create_digest, BazelRemoteCache, RemoteCacheError};
+use moon_config::RemoteCacheConfig;
+use moon_test_utils::create_temp_dir;
+use rustc_hash::FxHashMap;
+use std::fs;
+use std::path::PathBuf;
+
+mod from_config {
+    use super::*;
+
+    #[tokio::test]
+    async fn returns_none_without_url() {
+        assert!(BazelRemoteCache::from_config(&RemoteCacheConfig::default())
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn supports_grpc_schemes() {
+        for url in [
+            "grpc://localhost:9092",
+            "grpcs://localhost:9092",
+            "http://localhost:9092",
+        ] {
+            assert!(BazelRemoteCache::from_config(&RemoteCacheConfig {
+                url: Some(url.into()),
+                ..RemoteCacheConfig::default()
+            })
+            .unwrap()
+            .is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn sets_instance_name() {
+        let remote = BazelRemoteCache::from_config(&RemoteCacheConfig {
+            instance_name: Some("main".into()),
+            url: Some("grpc://localhost:9092".into()),
+            writable: false,
+            ..RemoteCacheConfig::default()
+        })
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(remote.instance_name, "main");
+        assert!(!remote.writable);
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "InvalidHeader")]
+    async fn errors_for_invalid_header() {
+        BazelRemoteCache::from_config(&RemoteCacheConfig {
+            headers: FxHashMap::from_iter([("bad header".into(), "value".into())]),
+            url: Some("grpc://localhost:9092".into()),
+            ..RemoteCacheConfig::default()
+        })
+        .unwrap();
+    }
+}
+
+mod create_digest {
+    use super::*;
+
+    #[test]
+    fn hashes_content() {
+        let digest = create_digest(b"hello");
+
+        assert_eq!(
+            digest.hash,
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+        assert_eq!(digest.size_bytes, 5);
+    }
+
+    #[test]
+    fn supports_empty_content() {
+        let digest = create_digest(b"");
+
+        assert_eq!(
+            digest.hash,
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(digest.size_bytes, 0);
+    }
+}
+
+mod collect_output_files {
+    use super::*;
+
+    #[test]
+    fn expands_files_dirs_and_globs() {
+        let dir = create_temp_dir();
+
+        fs::create_dir_all(dir.path().join("project/build/nested")).unwrap();
+        fs::write(dir.path().join("project/file.txt"), "file").unwrap();
+        fs::write(dir.path().join("project/build/a.js"), "a").unwrap();
+        fs::write(dir.path().join("project/build/nested/b.js"), "b").unwrap();
+        fs::write(dir.path().join("project/one.log"), "1").unwrap();
+        fs::write(dir.path().join("project/two.log"), "2").unwrap();
+        fs::write(dir.path().join("root.txt"), "root").unwrap();
+
+        let files = collect_output_files(
+            dir.path(),
+            "project",
+            &[
+                "file.txt".into(),
+                "build".into(),
+                "*.log".into(),
+                "/root.txt".into(),
+                "missing.txt".into(),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            files,
+            vec![
+                PathBuf::from("project/build/a.js"),
+                PathBuf::from("project/build/nested/b.js"),
+                PathBuf::from("project/file.txt"),
+                PathBuf::from("project/one.log"),
+                PathBuf::from("project/two.log"),
+                PathBuf::from("root.txt"),
+            ]
+        );
+    }
+}
+
+mod round_trip {
+    use super::*;
+    use moon_cache::reapi_proto::*;
+    use std::convert::Infallible;
+    use std::sync::{Arc, Mutex};
+    use std::task::{Context, Poll};
+    use tonic::codec::{ProstCodec, Streaming};
+    use tonic::codegen::{http, Body, BoxFuture, Service, StdError};
+    use tonic::server::{
+        ClientStreamingService, Grpc, NamedService, ServerStreamingService, UnaryService,
+    };
+    use tonic::{Code, Status};
+
+    const LARGE_BLOB_SIZE: usize = 4 * 1024 * 1024;
+
+    #[derive(Default)]
+    struct MockState {
+        action_results: FxHashMap<String, ActionResult>,
+        blobs: FxHashMap<String, Vec<u8>>,
+        stream_reads: usize,
+        stream_writes: usize,
+    }
+
+    type State = Arc<Mutex<MockState>>;
+
+    struct UnaryHandler<F>(State, F);
+
+    impl<I, O, F> UnaryService<I> for UnaryHandler<F>
+    where
+        F: Fn(&mut MockState, I) -> Result<O, Status>,
+        O: Send + 'static,
+    {
+        type Response = O;
+        type Future = BoxFuture<tonic::Response<O>, Status>;
+
+        fn call(&mut self, request: tonic::Request<I>) -> Self::Future {
+            let result = (self.1)(&mut self.0.lock().unwrap(), request.into_inner())
+                .map(tonic::Response::new);
+
+            Box::pin(async move { result })
+        }
+    }
+
+    struct ReadHandler(State);
+
+    impl ServerStreamingService<ReadRequest> for ReadHandler {
+        type Response = ReadResponse;
+        type ResponseStream =
+            futures::stream::Iter<std::vec::IntoIter<Result<ReadResponse, Status>>>;
+        type Future = BoxFuture<tonic::Response<Self::ResponseStream>, Status>;
+
+        fn call(&mut self, request: tonic::Request<ReadRequest>) -> Self::Future {
+            let mut state = self.0.lock().unwrap();
+            let resource_name = request.into_inner().resource_name;
+            let hash = resource_name.split('/').rev().nth(1).unwrap().to_owned();
+
+            state.stream_reads += 1;
+
+            let result = match state.blobs.get(&hash) {
+                Some(data) => Ok(tonic::Response::new(futures::stream::iter(
+                    data.chunks(1024 * 1024)
+                        .map(|chunk| {
+                            Ok(ReadResponse {
+                                data: chunk.to_vec(),
+                            })
+                        })
+                        .collect::<Vec<_>>(),
+                ))),
+                None => Err(Status::new(Code::NotFound, "missing")),
+            };
+
+            Box::pin(async move { result })
+        }
+    }
+
+    struct WriteHandler(State);
+
+    impl ClientStreamingService<WriteRequest> for WriteHandler {
+        type Response = WriteResponse;
+        type Future = BoxFuture<tonic::Response<WriteResponse>, Status>;
+
+        fn call(&mut self, request: tonic::Request<Streaming<WriteRequest>>) -> Self::Future {
+            let state = self.0.clone();
+
+            Box::pin(async move {
+                let mut stream = request.into_inner();
+                let mut hash = String::new();
+                let mut data = vec![];
+
+                while let Some(chunk) = stream.message().await? {
+                    if chunk.write_offset != data.len() as i64 {
+                        return Err(Status::invalid_argument("invalid offset"));
+                    }
+
+                    if !chunk.resource_name.is_empty() {
+                        hash = chunk
+                            .resource_name
+                            .split('/')
+                            .rev()
+                            .nth(1)
+                            .unwrap()
+                            .to_owned();
+                    }
+
+                    data.extend(chunk.data);
+                }
+
+                let committed_size = data.len() as i64;
+                let mut state = state.lock().unwrap();
+
+                state.stream_writes += 1;
+                state.blobs.insert(hash, data);
+
+                Ok(tonic::Response::new(WriteResponse { committed_size }))
+            })
+        }
+    }
+
+    macro_rules! mock_service {
+        ($name:ident, $service:literal) => {
+            #[derive(Clone)]
+            struct $name(State);
+
+            impl NamedService for $name {
+                const NAME: &'static str = $service;
+            }
+
+            impl<B> Service<http::Request<B>> for $name
+            where
+                B: Body + Send + 'static,
+                B::Error: Into<StdError> + Send + 'static,
+            {
+                type Response = http::Response<tonic::body::BoxBody>;
+                type Error = Infallible;
+                type Future = BoxFuture<Self::Response, Self::Error>;
+
+                fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+                    Poll::Ready(Ok(()))
+                }
+
+                fn call(&mut self, req: http::Request<B>) -> Self::Future {
+                    let state = self.0.clone();
+                    let path = req.uri().path().to_owned();
+
+                    Box::pin(async move {
+                        Ok(match path.as_str() {
+                            "/build.bazel.remote.execution.v2.ActionCache/GetActionResult" => {
+                                let handler = |state: &mut MockState, req: GetActionResultRequest| {
+                                    state
+                                        .action_results
+                                        .get(&req.action_digest.unwrap().hash)
+                                        .cloned()
+                                        .ok_or_else(|| Status::new(Code::NotFound, "missing"))
+                                };
+
+                                Grpc::new(ProstCodec::default())
+                                    .unary(UnaryHandler(state, handler), req)
+                                    .await
+                            }
+                            "/build.bazel.remote.execution.v2.ActionCache/UpdateActionResult" => {
+                                let handler =
+                                    |state: &mut MockState, req: UpdateActionResultRequest| {
+                                        let result = req.action_result.unwrap();
+
+                                        state
+                                            .action_results
+                                            .insert(req.action_digest.unwrap().hash, result.clone());
+
+                                        Ok(result)
+                                    };
+
+                                Grpc::new(ProstCodec::default())
+                                    .unary(UnaryHandler(state, handler), req)
+                                    .await
+                            }
+                            "/build.bazel.remote.execution.v2.ContentAddressableStorage/FindMissingBlobs" => {
+                                let handler = |state: &mut MockState, req: FindMissingBlobsRequest| {
+                                    Ok(FindMissingBlobsResponse {
+                                        missing_blob_digests: req
+                                            .blob_digests
+                                            .into_iter()
+                                            .filter(|d| !state.blobs.contains_key(&d.hash))
+                                            .collect(),
+                                    })
+                                };
+
+                                Grpc::new(ProstCodec::default())
+                                    .unary(UnaryHandler(state, handler), req)
+                                    .await
+                            }
+                            "/build.bazel.remote.execution.v2.ContentAddressableStorage/BatchUpdateBlobs" => {
+                                let handler = |state: &mut MockState, req: BatchUpdateBlobsRequest| {
+                                    for blob in req.requests {
+                                        state.blobs.insert(blob.digest.unwrap().hash, blob.data);
+                                    }
+
+                                    Ok(BatchUpdateBlobsResponse::default())
+                                };
+
+                                Grpc::new(ProstCodec::default())
+                                    .unary(UnaryHandler(state, handler), req)
+                                    .await
+                            }
+                            "/build.bazel.remote.execution.v2.ContentAddressableStorage/BatchReadBlobs" => {
+                                let handler = |state: &mut MockState, req: BatchReadBlobsRequest| {
+                                    Ok(BatchReadBlobsResponse {
+                                        responses: req
+                                            .digests
+                                            .into_iter()
+                                            .map(|digest| batch_read_blobs_response::Response {
+                                                data: state
+                                                    .blobs
+                                                    .get(&digest.hash)
+                                                    .cloned()
+                                                    .unwrap_or_default(),
+                                                digest: Some(digest),
+                                                status: None,
+                                            })
+                                            .collect(),
+                                    })
+                                };
+
+                                Grpc::new(ProstCodec::default())
+                                    .unary(UnaryHandler(state, handler), req)
+                                    .await
+                            }
+                            "/google.bytestream.ByteStream/Read" => {
+                                Grpc::new(ProstCodec::default())
+                                    .server_streaming(ReadHandler(state), req)
+                                    .await
+                            }
+                            "/google.bytestream.ByteStream/Write" => {
+                                Grpc::new(ProstCodec::default())
+                                    .client_streaming(WriteHandler(state), req)
+                                    .await
+                            }
+                            _ => Status::unimplemented(path).to_http(),
+                        })
+                    })
+                }
+            }
+        };
+    }
+
+    mock_service!(ActionCache, "build.bazel.remote.execution.v2.ActionCache");
+    mock_service!(
+        Cas,
+        "build.bazel.remote.execution.v2.ContentAddressableStorage"
+    );
+    mock_service!(ByteStream, "google.bytestream.ByteStream");
+
+    async fn start_server() -> (BazelRemoteCache, State) {
+        let state = State::default();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("grpc://{}", listener.local_addr().unwrap());
+
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(ActionCache(state.clone()))
+                .add_service(Cas(state.clone()))
+                .add_service(ByteStream(state.clone()))
+                .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener)),
+        );
+
+        let remote = BazelRemoteCache::from_config(&RemoteCacheConfig {
+            url: Some(url),
+            ..RemoteCacheConfig::default()
+        })
+        .unwrap()
+        .unwrap();
+
+        (remote, state)
+    }
+
+    #[tokio::test]
+    async fn uploads_and_downloads_outputs() {
+        let (remote, state) = start_server().await;
+        let source = create_temp_dir();
+        let large = (0..LARGE_BLOB_SIZE)
+            .map(|i| (i % 251) as u8)
+            .collect::<Vec<_>>();
+
+        fs::create_dir_all(source.path().join("project/build")).unwrap();
+        fs::write(source.path().join("project/build/small.js"), "small").unwrap();
+        fs::write(source.path().join("project/build/copy.js"), "small").unwrap();
+        fs::write(source.path().join("project/build/empty.js"), "").unwrap();
+        fs::write(source.path().join("project/build/large.bin"), &large).unwrap();
+        fs::write(source.path().join("stdout.log"), "out").unwrap();
+
+        let files = collect_output_files(source.path(), "project", &["build".into()]).unwrap();
+
+        remote
+            .upload_outputs(
+                "hash123",
+                source.path(),
+                &files,
+                (
+                    &source.path().join("stdout.log"),
+                    &source.path().join("stderr.log"),
+                ),
+                0,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(state.lock().unwrap().stream_writes, 1);
+        assert!(remote.get_action_result("unknown").await.unwrap().is_none());
+
+        let result = remote.get_action_result("hash123").await.unwrap().unwrap();
+        let dest = create_temp_dir();
+
+        fs::create_dir_all(dest.path().join("project/build")).unwrap();
+        fs::write(dest.path().join("project/build/stale.js"), "stale").unwrap();
+        fs::write(dest.path().join("project/other.js"), "other").unwrap();
+
+        let written = remote
+            .download_outputs(
+                &result,
+                dest.path(),
+                "project",
+                &["build".into()],
+                (
+                    &dest.path().join("stdout.log"),
+                    &dest.path().join("stderr.log"),
+                ),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(written.len(), 4);
+        assert_eq!(state.lock().unwrap().stream_reads, 1);
+        assert_eq!(
+            fs::read(dest.path().join("project/build/large.bin")).unwrap(),
+            large
+        );
+        assert_eq!(
+            fs::read_to_string(dest.path().join("project/build/small.js")).unwrap(),
+            "small"
+        );
+        assert_eq!(
+            fs::read_to_string(dest.path().join("project/build/copy.js")).unwrap(),
+            "small"
+        );
+        assert_eq!(
+            fs::read_to_string(dest.path().join("project/build/empty.js")).unwrap(),
+            ""
+        );
+        assert_eq!(
+            fs::read_to_string(dest.path().join("stdout.log")).unwrap(),
+            "out"
+        );
+        assert!(!dest.path().join("stderr.log").exists());
+
+        // Stale files within outputs are removed, but nothing else
+        assert!(!dest.path().join("project/build/stale.js").exists());
+        assert!(dest.path().join("project/other.js").exists());
+    }
+
+    #[tokio::test]
+    async fn rejects_paths_outside_the_workspace() {
+        let (remote, _) = start_server().await;
+        let root = create_temp_dir();
+        let dest = root.path().join("workspace");
+
+        fs::create_dir_all(&dest).unwrap();
+
+        for path in [
+            "../escape.txt",
+            "build/../../escape.txt",
+            "/tmp/escape.txt",
+            "./build/file.txt",
+            "build//file.txt",
+            "",
+        ] {
+            let result = ActionResult {
+                output_files: vec![OutputFile {
+                    path: path.into(),
+                    contents: b"escaped".to_vec(),
+                    ..OutputFile::default()
+                }],
+                ..ActionResult::default()
+            };
+
+            let error = remote
+                .download_outputs(
+                    &result,
+                    &dest,
+                    "project",
+                    &["build".into()],
+                    (&dest.join("stdout.log"), &dest.join("stderr.log")),
+                )
+                .await
+                .unwrap_err();
+
+            assert!(matches!(error, RemoteCacheError::InvalidOutputPath(_)));
+        }
+
+        assert!(!root.path().join("escape.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn doesnt_upload_existing_blobs() {
+        let (remote, state) = start_server().await;
+        let source = create_temp_dir();
+
+        fs::write(source.path().join("file.txt"), "content").unwrap();
+
+        for hash in ["a", "b"] {
+            remote
+                .upload_outputs(
+                    hash,
+                    source.path(),
+                    &[PathBuf::from("file.txt")],
+                    (
+                        &source.path().join("stdout.log"),
+                        &source.path().join("stderr.log"),
+                    ),
+                    0,
+                )
+                .await
+                .unwrap();
+        }
+
+        let state = state.lock().unwrap();
+
+        assert_eq!(state.blobs.len(), 1);
+        assert_eq!(state.action_results.len(), 2);
+    }
+}
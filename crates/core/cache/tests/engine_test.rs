@@ -226,7 +226,7 @@ mod cache_run_target_state {
 
         assert_eq!(
             fs::read_to_string(item.path).unwrap(),
-            r#"{"exitCode":123,"hash":"","lastRunTime":0,"target":"foo:bar"}"#
+            r#"{"exitCode":123,"hash":"","lastRunDuration":0,"lastRunTime":0,"target":"foo:bar"}"#
         );
 
         dir.close().unwrap();
@@ -245,7 +245,7 @@ mod cache_run_target_state {
 
         assert_eq!(
             fs::read_to_string(item.path).unwrap(),
-            r#"{"exitCode":123,"hash":"","lastRunTime":0,"target":"foo:bar"}"#
+            r#"{"exitCode":123,"hash":"","lastRunDuration":0,"lastRunTime":0,"target":"foo:bar"}"#
         );
 
         dir.close().unwrap();
@@ -536,3 +536,59 @@ mod create_hash_manifest {
         dir.close().unwrap();
     }
 }
+
+mod clean_unreferenced_blobs {
+    use super::*;
+    use moon_archive::CasArchiver;
+
+    #[test]
+    #[serial]
+    fn removes_blobs_not_in_a_manifest() {
+        let dir = create_temp_dir();
+        let cache = CacheEngine::load(dir.path()).unwrap();
+        let store = cache.get_output_store();
+
+        dir.child("kept.txt").write_str("kept").unwrap();
+        dir.child("removed.txt").write_str("removed").unwrap();
+
+        let kept_manifest = cache.get_hash_blobs_manifest_path("kept");
+        let mut cas = CasArchiver::new(dir.path(), &kept_manifest, &store);
+        cas.add_source(dir.path().join("kept.txt"), None);
+        let kept = cas.pack().unwrap();
+
+        let removed_manifest = cache.get_hash_blobs_manifest_path("removed");
+        let mut cas = CasArchiver::new(dir.path(), &removed_manifest, &store);
+        cas.add_source(dir.path().join("removed.txt"), None);
+        let removed = cas.pack().unwrap();
+
+        fs::remove_file(removed_manifest).unwrap();
+
+        assert_eq!(cache.clean_unreferenced_blobs().unwrap(), (1, 7));
+        assert!(store.get_blob_path(&kept.files[0].hash).exists());
+        assert!(!store.get_blob_path(&removed.files[0].hash).exists());
+
+        dir.close().unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn skips_unreadable_manifests() {
+        let dir = create_temp_dir();
+        let cache = CacheEngine::load(dir.path()).unwrap();
+        let store = cache.get_output_store();
+
+        dir.child("kept.txt").write_str("kept").unwrap();
+
+        let kept_manifest = cache.get_hash_blobs_manifest_path("kept");
+        let mut cas = CasArchiver::new(dir.path(), &kept_manifest, &store);
+        cas.add_source(dir.path().join("kept.txt"), None);
+        let kept = cas.pack().unwrap();
+
+        fs::write(cache.get_hash_blobs_manifest_path("broken"), "{ invalid").unwrap();
+
+        assert_eq!(cache.clean_unreferenced_blobs().unwrap(), (0, 0));
+        assert!(store.get_blob_path(&kept.files[0].hash).exists());
+
+        dir.close().unwrap();
+    }
+}
@@ -0,0 +1,208 @@
+use httpmock::{prelude::*, Method::HEAD};
+use moon_cache::RemoteCache;
+use moon_config::RemoteCacheConfig;
+use moon_test_utils::create_temp_dir;
+use rustc_hash::FxHashMap;
+use std::fs;
+
+fn create_remote(url: String) -> RemoteCache {
+    RemoteCache::from_config(&RemoteCacheConfig {
+        url: Some(url),
+        ..RemoteCacheConfig::default()
+    })
+    .unwrap()
+    .unwrap()
+}
+
+mod from_config {
+    use super::*;
+
+    #[test]
+    fn returns_none_without_url() {
+        assert!(RemoteCache::from_config(&RemoteCacheConfig::default())
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn strips_trailing_slash() {
+        let remote = create_remote("http://localhost/cache/".into());
+
+        assert_eq!(remote.url, "http://localhost/cache");
+        assert_eq!(
+            remote.get_artifact_url("abc123"),
+            "http://localhost/cache/abc123"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "InvalidHeader")]
+    fn errors_for_invalid_header() {
+        RemoteCache::from_config(&RemoteCacheConfig {
+            headers: FxHashMap::from_iter([("bad header".into(), "value".into())]),
+            url: Some("http://localhost".into()),
+            ..RemoteCacheConfig::default()
+        })
+        .unwrap();
+    }
+}
+
+mod has_artifact {
+    use super::*;
+
+    #[tokio::test]
+    async fn returns_true_if_exists() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(HEAD).path("/cache/abc123");
+            then.status(200);
+        });
+
+        let remote = create_remote(server.url("/cache"));
+
+        assert!(remote.has_artifact("abc123").await.unwrap());
+
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn returns_false_if_missing() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(HEAD).path("/cache/abc123");
+            then.status(404);
+        });
+
+        let remote = create_remote(server.url("/cache"));
+
+        assert!(!remote.has_artifact("abc123").await.unwrap());
+
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn sends_token_and_headers() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(HEAD)
+                .path("/abc123")
+                .header("Authorization", "Bearer secret")
+                .header("X-Team", "frontend");
+            then.status(200);
+        });
+
+        let remote = RemoteCache::from_config(&RemoteCacheConfig {
+            headers: FxHashMap::from_iter([("X-Team".into(), "frontend".into())]),
+            token: Some("secret".into()),
+            url: Some(server.url("")),
+            ..RemoteCacheConfig::default()
+        })
+        .unwrap()
+        .unwrap();
+
+        assert!(remote.has_artifact("abc123").await.unwrap());
+
+        mock.assert();
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "ArtifactCheckFailure")]
+    async fn errors_on_server_failure() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(HEAD).path("/abc123");
+            then.status(500);
+        });
+
+        create_remote(server.url(""))
+            .has_artifact("abc123")
+            .await
+            .unwrap();
+    }
+}
+
+mod download_artifact {
+    use super::*;
+
+    #[tokio::test]
+    async fn writes_to_destination() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/abc123");
+            then.status(200).body("archive");
+        });
+
+        let dir = create_temp_dir();
+        let dest = dir.path().join("abc123.tar.gz");
+        let remote = create_remote(server.url(""));
+
+        assert!(remote.download_artifact("abc123", &dest).await.unwrap());
+        assert_eq!(fs::read_to_string(dest).unwrap(), "archive");
+
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn returns_false_if_missing() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(GET).path("/abc123");
+            then.status(404);
+        });
+
+        let dir = create_temp_dir();
+        let dest = dir.path().join("abc123.tar.gz");
+        let remote = create_remote(server.url(""));
+
+        assert!(!remote.download_artifact("abc123", &dest).await.unwrap());
+        assert!(!dest.exists());
+    }
+}
+
+mod upload_artifact {
+    use super::*;
+
+    #[tokio::test]
+    async fn puts_archive_contents() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(PUT).path("/abc123").body("archive");
+            then.status(201);
+        });
+
+        let dir = create_temp_dir();
+        let archive = dir.path().join("abc123.tar.gz");
+
+        fs::write(&archive, "archive").unwrap();
+
+        create_remote(server.url(""))
+            .upload_artifact("abc123", &archive)
+            .await
+            .unwrap();
+
+        mock.assert();
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "ArtifactUploadFailure")]
+    async fn errors_on_server_failure() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(PUT).path("/abc123");
+            then.status(403);
+        });
+
+        let dir = create_temp_dir();
+        let archive = dir.path().join("abc123.tar.gz");
+
+        fs::write(&archive, "archive").unwrap();
+
+        create_remote(server.url(""))
+            .upload_artifact("abc123", &archive)
+            .await
+            .unwrap();
+    }
+}
@@ -24,6 +24,7 @@ type RuntimePair = (Runtime, Runtime);
 /// be confused with our tasks) or a "dependency graph".
 pub struct DepGraphBuilder<'ws> {
     all_query: Option<Criteria>,
+    all_query_touched_files: Option<TouchedFilePaths>,
     graph: DepGraphType,
     indices: IndicesType,
     platforms: &'ws PlatformManager,
@@ -37,6 +38,7 @@ impl<'ws> DepGraphBuilder<'ws> {
 
         DepGraphBuilder {
             all_query: None,
+            all_query_touched_files: None,
             graph: Graph::new(),
             indices: FxHashMap::default(),
             platforms,
@@ -55,6 +57,11 @@ impl<'ws> DepGraphBuilder<'ws> {
         Ok(())
     }
 
+    /// Set the touched files used to resolve `affected` conditions in the query.
+    pub fn set_query_touched_files(&mut self, touched_files: &TouchedFilePaths) {
+        self.all_query_touched_files = Some(touched_files.to_owned());
+    }
+
     pub fn get_index_from_node(&self, node: &ActionNode) -> Option<&NodeIndex> {
         self.indices.get(node)
     }
@@ -83,7 +90,7 @@ impl<'ws> DepGraphBuilder<'ws> {
             Some(task) => p.matches(&task.platform, None),
             None => p.matches(&project.language.clone().into(), None),
         }) {
-            project_runtime = platform.get_runtime_from_config(Some(&project.config));
+            project_runtime = platform.get_runtime_from_project(project);
             workspace_runtime = platform.get_runtime_from_config(None);
         }
 
@@ -104,7 +111,7 @@ impl<'ws> DepGraphBuilder<'ws> {
 
         // If project is NOT in the package manager workspace, then we should
         // install dependencies in the project, not the workspace root.
-        if let Ok(platform) = self.platforms.get(project.language.clone()) {
+        if let Ok(platform) = self.platforms.get_for_language(&project.language) {
             if !platform.is_project_in_dependency_workspace(project)? {
                 installs_in_project = true;
 
@@ -213,7 +220,10 @@ impl<'ws> DepGraphBuilder<'ws> {
                 let mut projects = vec![];
 
                 if let Some(all_query) = &self.all_query {
-                    projects.extend(self.project_graph.query(all_query)?);
+                    projects.extend(self.project_graph.query_with_touched_files(
+                        all_query,
+                        self.all_query_touched_files.as_ref(),
+                    )?);
                 } else {
                     projects.extend(self.project_graph.get_all()?);
                 };
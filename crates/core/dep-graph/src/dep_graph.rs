@@ -1,15 +1,19 @@
 use crate::errors::DepGraphError;
 use moon_action::ActionNode;
+use moon_target::Target;
 use petgraph::algo::toposort;
 use petgraph::dot::{Config, Dot};
 use petgraph::graph::DiGraph;
 use petgraph::graph::NodeIndex;
 use petgraph::visit::EdgeRef;
+use petgraph::Direction;
 use rustc_hash::{FxHashMap, FxHashSet};
+use std::time::Duration;
 
 pub type DepGraphType = DiGraph<ActionNode, ()>;
 pub type IndicesType = FxHashMap<ActionNode, NodeIndex>;
 pub type BatchedTopoSort = Vec<Vec<NodeIndex>>;
+pub type CriticalPaths = FxHashMap<NodeIndex, Duration>;
 
 /// A directed acyclic graph (DAG) for the work that needs to be processed, based on a
 /// project or task's dependency chain. This is also known as a "task graph" (not to
@@ -25,6 +29,51 @@ impl DepGraph {
         DepGraph { graph, indices }
     }
 
+    /// Calculate the critical path length of every node, which is the node's own weight
+    /// plus the longest weighted chain of dependents that must run after it.
+    pub fn calculate_critical_paths<F>(&self, weigh: F) -> Result<CriticalPaths, DepGraphError>
+    where
+        F: Fn(&ActionNode) -> Duration,
+    {
+        let mut paths = CriticalPaths::default();
+
+        let Ok(sorted) = self.sort_topological() else {
+            self.detect_cycle()?;
+
+            return Ok(paths);
+        };
+
+        // Dependents must be calculated before their dependencies
+        for ix in sorted.into_iter().rev() {
+            let longest_dependent = self
+                .get_dependents_of(&ix)
+                .iter()
+                .filter_map(|dep_ix| paths.get(dep_ix))
+                .max()
+                .copied()
+                .unwrap_or_default();
+
+            paths.insert(
+                ix,
+                weigh(self.graph.node_weight(ix).unwrap()) + longest_dependent,
+            );
+        }
+
+        Ok(paths)
+    }
+
+    pub fn get_dependencies_of(&self, index: &NodeIndex) -> Vec<NodeIndex> {
+        self.graph
+            .neighbors_directed(*index, Direction::Outgoing)
+            .collect()
+    }
+
+    pub fn get_dependents_of(&self, index: &NodeIndex) -> Vec<NodeIndex> {
+        self.graph
+            .neighbors_directed(*index, Direction::Incoming)
+            .collect()
+    }
+
     pub fn get_index_from_node(&self, node: &ActionNode) -> Option<&NodeIndex> {
         self.indices.get(node)
     }
@@ -37,6 +86,10 @@ impl DepGraph {
         self.graph.node_weight(*index)
     }
 
+    pub fn get_nodes(&self) -> Vec<&ActionNode> {
+        self.graph.node_weights().collect()
+    }
+
     pub fn sort_topological(&self) -> Result<Vec<NodeIndex>, DepGraphError> {
         let list = match toposort(&self.graph, None) {
             Ok(nodes) => nodes,
@@ -131,6 +184,36 @@ impl DepGraph {
         Ok(sorted_batches)
     }
 
+    /// Split persistent targets out of the graph and into their own graphs, so that they
+    /// can be ran (and restarted) independently. Persistent targets never complete, so nothing
+    /// waits on them, and the remaining graph still contains everything the targets require.
+    pub fn split_persistent_targets(self) -> (DepGraph, Vec<(Target, DepGraph)>) {
+        let mut persistent = vec![];
+
+        let graph = self.graph.filter_map(
+            |_, node| match node {
+                ActionNode::RunPersistentTarget(_, target) => {
+                    let mut graph = DepGraphType::new();
+                    let mut indices = IndicesType::default();
+
+                    indices.insert(node.to_owned(), graph.add_node(node.to_owned()));
+                    persistent.push((target.to_owned(), DepGraph::new(graph, indices)));
+
+                    None
+                }
+                _ => Some(node.to_owned()),
+            },
+            |_, _| Some(()),
+        );
+
+        let indices = graph
+            .node_indices()
+            .map(|index| (graph[index].to_owned(), index))
+            .collect();
+
+        (DepGraph::new(graph, indices), persistent)
+    }
+
     /// Get a labelled representation of the dep graph (which can be serialized easily).
     pub fn labeled_graph(&self) -> DiGraph<String, ()> {
         let graph = self.graph.clone();
@@ -1,4 +1,5 @@
 use moon::{build_dep_graph, generate_project_graph, load_workspace_from};
+use moon_action::ActionNode;
 use moon_config::{
     PartialInheritedTasksConfig, PartialNodeConfig, PartialToolchainConfig, PartialWorkspaceConfig,
     WorkspaceProjects,
@@ -13,6 +14,7 @@ use moon_workspace::Workspace;
 use petgraph::graph::NodeIndex;
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::path::PathBuf;
+use std::time::Duration;
 
 async fn create_project_graph() -> (Workspace, ProjectGraph, Sandbox) {
     let workspace_config = PartialWorkspaceConfig {
@@ -239,6 +241,46 @@ mod run_target {
         );
     }
 
+    #[tokio::test]
+    async fn calculates_critical_paths() {
+        let (workspace, projects, _sandbox) = create_tasks_project_graph().await;
+
+        let mut graph = build_dep_graph(&workspace, &projects);
+        graph
+            .run_target(&Target::new("basic", "test").unwrap(), None)
+            .unwrap();
+        graph
+            .run_target(&Target::new("chain", "a").unwrap(), None)
+            .unwrap();
+        let graph = graph.build();
+
+        let paths = graph
+            .calculate_critical_paths(|node| match node {
+                ActionNode::RunTarget(_, target) if target.task_id == "test" => {
+                    Duration::from_secs(10)
+                }
+                _ => Duration::from_secs(1),
+            })
+            .unwrap();
+
+        assert_eq!(
+            paths,
+            FxHashMap::from_iter([
+                (NodeIndex::new(0), Duration::from_secs(12)), // setup tool
+                (NodeIndex::new(1), Duration::from_secs(11)), // install deps
+                (NodeIndex::new(2), Duration::from_secs(11)), // sync project
+                (NodeIndex::new(3), Duration::from_secs(10)), // test
+                (NodeIndex::new(4), Duration::from_secs(7)),  // sync project
+                (NodeIndex::new(5), Duration::from_secs(1)),  // a
+                (NodeIndex::new(6), Duration::from_secs(2)),  // b
+                (NodeIndex::new(7), Duration::from_secs(3)),  // c
+                (NodeIndex::new(8), Duration::from_secs(4)),  // d
+                (NodeIndex::new(9), Duration::from_secs(5)),  // e
+                (NodeIndex::new(10), Duration::from_secs(6)), // f
+            ])
+        );
+    }
+
     #[tokio::test]
     async fn moves_persistent_tasks_last() {
         let (workspace, projects, _sandbox) = create_tasks_project_graph().await;
@@ -293,6 +335,47 @@ mod run_target {
         );
     }
 
+    #[tokio::test]
+    async fn splits_persistent_tasks_into_own_graphs() {
+        let (workspace, projects, _sandbox) = create_tasks_project_graph().await;
+
+        let mut graph = build_dep_graph(&workspace, &projects);
+        graph
+            .run_target(&Target::new("persistent", "dev").unwrap(), None)
+            .unwrap();
+        let (graph, persistent) = graph.build().split_persistent_targets();
+
+        assert_eq!(graph.get_node_count(), 10);
+        assert!(graph
+            .get_nodes()
+            .iter()
+            .all(|node| !matches!(node, ActionNode::RunPersistentTarget(_, _))));
+        assert_eq!(
+            sort_batches(graph.sort_batched_topological().unwrap()).len(),
+            4
+        );
+
+        let mut targets = persistent
+            .iter()
+            .map(|(target, graph)| {
+                assert_eq!(graph.get_node_count(), 1);
+
+                target.id.as_str()
+            })
+            .collect::<Vec<_>>();
+        targets.sort();
+
+        assert_eq!(
+            targets,
+            vec![
+                "persistent:dev",
+                "persistent:devMiddleman",
+                "persistent:local",
+                "persistent:persistent"
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn avoids_dupe_targets() {
         let (workspace, projects, _sandbox) = create_project_graph().await;
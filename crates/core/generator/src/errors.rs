@@ -1,7 +1,10 @@
 use miette::Diagnostic;
+use moon_archive::ArchiveError;
+use moon_args::ArgsSplitError;
 use moon_common::consts;
 use moon_config::ConfigError;
 use moon_error::MoonError;
+use moon_process::ProcessError;
 use starbase_styles::{Style, Stylize};
 use starbase_utils::{fs::FsError, json::JsonError, yaml::YamlError};
 use std::path::PathBuf;
@@ -10,9 +13,15 @@ use thiserror::Error;
 
 #[derive(Error, Debug, Diagnostic)]
 pub enum GeneratorError {
+    #[error("Template {} cannot extend {}, as it would create a cycle.", .0.style(Style::Id), .1.style(Style::Id))]
+    CyclicTemplateExtends(String, String),
+
     #[error("A template with the name {} already exists at {}.", .0.style(Style::Id), .1.style(Style::Path))]
     ExistingTemplate(String, PathBuf),
 
+    #[error("Failed to download template archive {}: {1}", .0.style(Style::Url))]
+    FailedToDownloadArchive(String, String),
+
     #[error("Failed to parse variable argument --{0}: {1}")]
     FailedToParseArgVar(String, String),
 
@@ -22,9 +31,29 @@ pub enum GeneratorError {
     )]
     InvalidConfigFile(String),
 
+    #[error(
+        "Unable to render template file {} for each element of {}, as the variable does not exist or is not an array.",
+        .1.style(Style::File),
+        .0.style(Style::Id),
+    )]
+    InvalidForEachVariable(String, String),
+
+    #[error("Failed to parse TOML file {}.", .0.style(Style::Path))]
+    InvalidTomlFile(PathBuf, #[source] toml_edit::TomlError),
+
+    #[error("Unable to create a new template, as all configured template locations are remote. A local file system path is required.")]
+    NoLocalTemplateLocation,
+
     #[error("No template with the name {} could not be found at any of the configured template paths.", .0.style(Style::Id))]
     MissingTemplate(String),
 
+    #[error(transparent)]
+    Archive(#[from] ArchiveError),
+
+    #[diagnostic(transparent)]
+    #[error(transparent)]
+    ArgsSplit(#[from] ArgsSplitError),
+
     #[diagnostic(transparent)]
     #[error(transparent)]
     Config(#[from] ConfigError),
@@ -33,6 +62,10 @@ pub enum GeneratorError {
     #[error(transparent)]
     Moon(#[from] MoonError),
 
+    #[diagnostic(transparent)]
+    #[error(transparent)]
+    Process(#[from] ProcessError),
+
     #[error(transparent)]
     Tera(#[from] TeraError),
 
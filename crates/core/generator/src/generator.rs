@@ -1,18 +1,54 @@
 use crate::errors::GeneratorError;
-use crate::template::Template;
-use moon_common::consts::CONFIG_TEMPLATE_FILENAME;
-use moon_config::{load_template_config_template, GeneratorConfig};
-use moon_logger::{debug, map_list, trace};
+use crate::merge::merge_three_way;
+use crate::template::{FileState, Template, TemplateFile};
+use convert_case::{Case, Casing};
+use moon_archive::{untar, unzip};
+use moon_args::split_args;
+use moon_common::consts::{CONFIG_DIRNAME, CONFIG_TEMPLATE_FILENAME};
+use moon_config::{load_template_config_template, GeneratorConfig, TemplateLocator};
+use moon_logger::{debug, map_list, trace, warn};
+use moon_process::Command;
 use moon_utils::{path, regex::clean_id};
+use sha2::{Digest, Sha256};
 use starbase_styles::color;
-use starbase_utils::fs;
+use starbase_utils::{fs, json::JsonValue};
 use std::path::{Path, PathBuf};
+use tera::Context;
 
 const LOG_TARGET: &str = "moon:generator";
 
+fn hash<T: AsRef<[u8]>>(value: T) -> String {
+    let mut sha = Sha256::new();
+    sha.update(value.as_ref());
+
+    format!("{:x}", sha.finalize())
+}
+
+async fn exec_git(cwd: &Path, args: &[&str]) -> Result<(), GeneratorError> {
+    Command::new("git")
+        .args(args)
+        .cwd(cwd)
+        .create_async()
+        .exec_capture_output()
+        .await?;
+
+    Ok(())
+}
+
 pub struct Generator {
     config: GeneratorConfig,
 
+    /// Local file system path for each configured template location.
+    /// Remote locations point to their fetched copy in the cache.
+    template_paths: Vec<PathBuf>,
+
+    /// The `.moon/cache/templates/.rendered` directory. Stores the last rendered
+    /// content of files that are three-way merged, keyed by destination.
+    rendered_dir: PathBuf,
+
+    /// The `.moon/cache/templates` directory. Stores fetched remote templates.
+    templates_dir: PathBuf,
+
     workspace_root: PathBuf,
 }
 
@@ -20,20 +56,40 @@ impl Generator {
     pub fn load(workspace_root: &Path, config: &GeneratorConfig) -> Result<Self, GeneratorError> {
         debug!(target: LOG_TARGET, "Creating generator");
 
+        let templates_dir = workspace_root.join(CONFIG_DIRNAME).join("cache/templates");
+        let template_paths = config
+            .templates
+            .iter()
+            .map(|locator| match locator {
+                TemplateLocator::File { path } => path::normalize(workspace_root.join(path)),
+                _ => templates_dir.join(hash(locator.to_string())),
+            })
+            .collect();
+
         Ok(Generator {
             config: config.to_owned(),
+            rendered_dir: templates_dir.join(".rendered"),
+            template_paths,
+            templates_dir,
             workspace_root: workspace_root.to_path_buf(),
         })
     }
 
-    /// Create a new template with a schema, using the first configured template path.
+    /// Create a new template with a schema, using the first configured local template path.
     /// Will error if a template of the same name already exists.
     pub fn create_template(&self, name: &str) -> Result<Template, GeneratorError> {
         let name = clean_id(name);
-        let root = self
-            .workspace_root
-            .join(&self.config.templates[0])
-            .join(&name);
+
+        let Some(TemplateLocator::File { path: templates_path }) = self
+            .config
+            .templates
+            .iter()
+            .find(|locator| !locator.is_remote())
+        else {
+            return Err(GeneratorError::NoLocalTemplateLocation);
+        };
+
+        let root = self.workspace_root.join(templates_path).join(&name);
 
         if root.exists() {
             return Err(GeneratorError::ExistingTemplate(name, root));
@@ -57,19 +113,53 @@ impl Generator {
     }
 
     /// Load the template with the provided name, using the first match amongst
-    /// the list of template paths. Will error if no match is found.
+    /// the list of template paths, and extend it with all of its configured
+    /// templates. Will error if no match is found.
     pub fn load_template(&self, name: &str) -> Result<Template, GeneratorError> {
+        self.load_template_with_extends(name, &mut vec![])
+    }
+
+    fn load_template_with_extends(
+        &self,
+        name: &str,
+        chain: &mut Vec<String>,
+    ) -> Result<Template, GeneratorError> {
+        let mut template = self.find_template(name)?;
+
+        chain.push(template.name.clone());
+
+        for extends_name in template.config.extends.clone() {
+            let extends_name = clean_id(&extends_name);
+
+            if chain.contains(&extends_name) {
+                return Err(GeneratorError::CyclicTemplateExtends(
+                    template.name,
+                    extends_name,
+                ));
+            }
+
+            let extended_template = self.load_template_with_extends(&extends_name, chain)?;
+
+            template.extend_template(extended_template);
+        }
+
+        chain.pop();
+
+        Ok(template)
+    }
+
+    fn find_template(&self, name: &str) -> Result<Template, GeneratorError> {
         let name = clean_id(name);
 
         trace!(
             target: LOG_TARGET,
             "Finding template {} from configured locations: {}",
             color::id(&name),
-            map_list(&self.config.templates, |t| color::file(t))
+            map_list(&self.config.templates, |t| color::file(t.to_string()))
         );
 
-        for template_path in &self.config.templates {
-            let root = path::normalize(self.workspace_root.join(template_path).join(&name));
+        for template_path in &self.template_paths {
+            let root = template_path.join(&name);
 
             if root.exists() {
                 trace!(target: LOG_TARGET, "Found at {}", color::path(&root));
@@ -81,6 +171,99 @@ impl Generator {
         Err(GeneratorError::MissingTemplate(name))
     }
 
+    /// Run the post-generate commands of the template in the destination directory.
+    /// The template name, destination, and all variables are passed as environment
+    /// variables, with variables in the format of `MOON_VAR_<NAME>`.
+    pub async fn run_post_generate(
+        &self,
+        template: &Template,
+        dest: &Path,
+        context: &Context,
+    ) -> Result<(), GeneratorError> {
+        if template.post_generate.is_empty() {
+            return Ok(());
+        }
+
+        let mut env = vec![
+            ("MOON_TEMPLATE_NAME".to_owned(), template.name.clone()),
+            ("MOON_TEMPLATE_DEST".to_owned(), path::to_string(dest)?),
+            (
+                "MOON_WORKSPACE_ROOT".to_owned(),
+                path::to_string(&self.workspace_root)?,
+            ),
+        ];
+
+        if let JsonValue::Object(vars) = context.clone().into_json() {
+            for (name, value) in vars {
+                env.push((
+                    format!("MOON_VAR_{}", name.to_case(Case::UpperSnake)),
+                    match value {
+                        JsonValue::String(value) => value,
+                        value => value.to_string(),
+                    },
+                ));
+            }
+        }
+
+        for command_line in &template.post_generate {
+            let args = split_args(command_line)?;
+
+            if args.is_empty() {
+                continue;
+            }
+
+            debug!(
+                target: LOG_TARGET,
+                "Running post-generate command {}",
+                color::shell(command_line)
+            );
+
+            Command::new(&args[0])
+                .args(&args[1..])
+                .cwd(dest)
+                .envs(env.clone())
+                .with_shell()
+                .set_print_command(true)
+                .create_async()
+                .exec_stream_output()
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Fetch all remote template locations into the cache, if they have not been
+    /// fetched before. When `refresh` is true, all remote locations are fetched again.
+    pub async fn resolve_remote_templates(&self, refresh: bool) -> Result<(), GeneratorError> {
+        for (locator, dest) in self.config.templates.iter().zip(&self.template_paths) {
+            if !locator.is_remote() || (dest.exists() && !refresh) {
+                continue;
+            }
+
+            debug!(
+                target: LOG_TARGET,
+                "Fetching template location {} into {}",
+                color::url(locator.to_string()),
+                color::path(dest)
+            );
+
+            match locator {
+                TemplateLocator::Archive { url } => {
+                    self.fetch_archive(url, dest).await?;
+                }
+                TemplateLocator::Git {
+                    remote_url,
+                    revision,
+                } => {
+                    self.fetch_git(remote_url, revision, dest).await?;
+                }
+                TemplateLocator::File { .. } => {}
+            };
+        }
+
+        Ok(())
+    }
+
     pub fn generate(&self, template: &Template) -> Result<(), GeneratorError> {
         debug!(
             target: LOG_TARGET,
@@ -89,13 +272,216 @@ impl Generator {
         );
 
         for file in &template.files {
-            if file.should_write() {
+            if !file.should_write() {
+                continue;
+            }
+
+            if matches!(file.state, FileState::Merge)
+                && file.is_three_way_merge()
+                && self.has_previous_render(file)
+            {
+                self.write_three_way_file(file)?;
+            } else {
                 template.write_file(file)?;
             }
+
+            // Store the rendered content, to be used as the base of the next merge
+            if file.is_three_way_merge() {
+                fs::write_file(self.get_rendered_path(file), &file.content)?;
+            }
         }
 
         debug!(target: LOG_TARGET, "Generation complete!");
 
         Ok(())
     }
+
+    /// Return true if the file was previously generated with a three-way merge strategy,
+    /// and its rendered content can be used as the base of a merge.
+    pub fn has_previous_render(&self, file: &TemplateFile) -> bool {
+        self.get_rendered_path(file).exists()
+    }
+
+    fn get_rendered_path(&self, file: &TemplateFile) -> PathBuf {
+        let dest = file
+            .dest_path
+            .strip_prefix(&self.workspace_root)
+            .unwrap_or(&file.dest_path);
+
+        self.rendered_dir
+            .join(hash(dest.to_string_lossy().as_bytes()))
+    }
+
+    /// Merge the changes made to the existing file since it was last generated,
+    /// with the changes made to the template since then.
+    fn write_three_way_file(&self, file: &TemplateFile) -> Result<(), GeneratorError> {
+        trace!(
+            target: LOG_TARGET,
+            "Three-way merging template file {} with {}",
+            color::file(&file.name),
+            color::path(&file.dest_path)
+        );
+
+        let result = merge_three_way(
+            &fs::read_file(self.get_rendered_path(file))?,
+            &fs::read_file(&file.dest_path)?,
+            &file.content,
+        );
+
+        if result.conflicts > 0 {
+            warn!(
+                target: LOG_TARGET,
+                "Merged {} with {} conflict(s), which must be resolved manually",
+                color::path(&file.dest_path),
+                result.conflicts,
+            );
+        }
+
+        fs::write_file(&file.dest_path, result.content)?;
+
+        Ok(())
+    }
+
+    /// Fetch into a staging directory, so that a failed fetch never
+    /// clobbers a previously fetched copy of the location.
+    fn create_staging_dir(&self, dest: &Path) -> Result<PathBuf, GeneratorError> {
+        let staging_dir = self
+            .templates_dir
+            .join(".staging")
+            .join(dest.file_name().unwrap());
+
+        if staging_dir.exists() {
+            fs::remove_dir_all(&staging_dir)?;
+        }
+
+        fs::create_dir_all(&staging_dir)?;
+
+        Ok(staging_dir)
+    }
+
+    fn replace_dest(&self, source: &Path, dest: &Path) -> Result<(), GeneratorError> {
+        if dest.exists() {
+            fs::remove_dir_all(dest)?;
+        }
+
+        fs::rename(source, dest)?;
+
+        Ok(())
+    }
+
+    async fn fetch_archive(&self, url: &str, dest: &Path) -> Result<(), GeneratorError> {
+        let staging_dir = self.create_staging_dir(dest)?;
+
+        match self.unpack_archive(url, &staging_dir).await {
+            Ok(root) => {
+                self.replace_dest(&root, dest)?;
+            }
+            Err(error) => {
+                fs::remove_dir_all(&staging_dir)?;
+
+                return Err(error);
+            }
+        };
+
+        if staging_dir.exists() {
+            fs::remove_dir_all(&staging_dir)?;
+        }
+
+        Ok(())
+    }
+
+    /// Unpack the archive into the staging directory, and return the root of its
+    /// contents. Archives that wrap everything in a single folder are unwrapped.
+    async fn unpack_archive(
+        &self,
+        url: &str,
+        staging_dir: &Path,
+    ) -> Result<PathBuf, GeneratorError> {
+        let unpack_dir = staging_dir.join("contents");
+
+        let archive_file = match url.strip_prefix("file://") {
+            Some(file) => self.workspace_root.join(file),
+            None => {
+                let file = staging_dir.join(url.rsplit('/').next().unwrap_or_default());
+                let response = reqwest::get(url).await.map_err(|error| {
+                    GeneratorError::FailedToDownloadArchive(url.to_owned(), error.to_string())
+                })?;
+
+                if !response.status().is_success() {
+                    return Err(GeneratorError::FailedToDownloadArchive(
+                        url.to_owned(),
+                        response.status().to_string(),
+                    ));
+                }
+
+                let bytes = response.bytes().await.map_err(|error| {
+                    GeneratorError::FailedToDownloadArchive(url.to_owned(), error.to_string())
+                })?;
+
+                fs::write_file(&file, bytes)?;
+
+                file
+            }
+        };
+
+        if url.ends_with(".zip") {
+            unzip(&archive_file, &unpack_dir, None)?;
+        } else {
+            untar(&archive_file, &unpack_dir, None)?;
+        }
+
+        let entries = fs::read_dir(&unpack_dir)?;
+
+        if entries.len() == 1 && entries[0].path().is_dir() {
+            return Ok(entries[0].path());
+        }
+
+        Ok(unpack_dir)
+    }
+
+    async fn fetch_git(
+        &self,
+        remote_url: &str,
+        revision: &str,
+        dest: &Path,
+    ) -> Result<(), GeneratorError> {
+        // Already cloned, so only fetch and checkout the latest revision
+        if dest.join(".git").exists() {
+            return self.checkout_git_revision(revision, dest).await;
+        }
+
+        let staging_dir = self.create_staging_dir(dest)?;
+        let mut result = exec_git(&staging_dir, &["init", "--quiet"]).await;
+
+        if result.is_ok() {
+            result = exec_git(&staging_dir, &["remote", "add", "origin", remote_url]).await;
+        }
+
+        if result.is_ok() {
+            result = self.checkout_git_revision(revision, &staging_dir).await;
+        }
+
+        if result.is_ok() {
+            self.replace_dest(&staging_dir, dest)?;
+        } else {
+            fs::remove_dir_all(&staging_dir)?;
+        }
+
+        result
+    }
+
+    async fn checkout_git_revision(
+        &self,
+        revision: &str,
+        dir: &Path,
+    ) -> Result<(), GeneratorError> {
+        exec_git(
+            dir,
+            &["fetch", "--quiet", "--depth", "1", "origin", revision],
+        )
+        .await?;
+        exec_git(dir, &["checkout", "--quiet", "--force", "FETCH_HEAD"]).await?;
+
+        Ok(())
+    }
 }
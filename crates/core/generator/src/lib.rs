@@ -1,9 +1,11 @@
 mod errors;
 mod filters;
 mod generator;
+mod merge;
 mod template;
 
 pub use errors::GeneratorError;
 pub use generator::Generator;
+pub use merge::*;
 pub use template::*;
 pub use tera::Context as TemplateContext;
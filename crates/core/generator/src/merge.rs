@@ -0,0 +1,117 @@
+use similar::{capture_diff_slices, Algorithm, DiffOp};
+
+pub const CONFLICT_START: &str = "<<<<<<< existing";
+pub const CONFLICT_SEPARATOR: &str = "=======";
+pub const CONFLICT_END: &str = ">>>>>>> template";
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct MergeResult {
+    pub content: String,
+
+    /// Number of chunks that changed in both the existing file and the
+    /// template, and were written with conflict markers.
+    pub conflicts: usize,
+}
+
+/// For each line in the base, map it to the matching line in the other content.
+fn match_lines(base: &[&str], other: &[&str]) -> Vec<Option<usize>> {
+    let mut matches = vec![None; base.len()];
+
+    for op in capture_diff_slices(Algorithm::Myers, base, other) {
+        if let DiffOp::Equal {
+            old_index,
+            new_index,
+            len,
+        } = op
+        {
+            for offset in 0..len {
+                matches[old_index + offset] = Some(new_index + offset);
+            }
+        }
+    }
+
+    matches
+}
+
+fn push_lines(content: &mut String, lines: &[&str]) {
+    for line in lines {
+        content.push_str(line);
+    }
+}
+
+fn push_conflict(content: &mut String, ours: &[&str], theirs: &[&str]) {
+    let push_side = |content: &mut String, lines: &[&str]| {
+        push_lines(content, lines);
+
+        if !content.ends_with('\n') {
+            content.push('\n');
+        }
+    };
+
+    content.push_str(CONFLICT_START);
+    content.push('\n');
+    push_side(content, ours);
+    content.push_str(CONFLICT_SEPARATOR);
+    content.push('\n');
+    push_side(content, theirs);
+    content.push_str(CONFLICT_END);
+    content.push('\n');
+}
+
+/// Merge the changes between the base and the existing file (ours), and the
+/// base and the newly rendered file (theirs), line by line, in the style of diff3.
+/// Chunks that were changed differently on both sides are wrapped in conflict markers.
+pub fn merge_three_way(base: &str, ours: &str, theirs: &str) -> MergeResult {
+    let base_lines = base.split_inclusive('\n').collect::<Vec<_>>();
+    let our_lines = ours.split_inclusive('\n').collect::<Vec<_>>();
+    let their_lines = theirs.split_inclusive('\n').collect::<Vec<_>>();
+
+    let our_matches = match_lines(&base_lines, &our_lines);
+    let their_matches = match_lines(&base_lines, &their_lines);
+
+    let mut result = MergeResult {
+        content: String::with_capacity(ours.len().max(theirs.len())),
+        conflicts: 0,
+    };
+
+    let (mut b, mut o, mut t) = (0, 0, 0);
+
+    loop {
+        // Find the next base line that is unchanged on both sides,
+        // or the end of all content when there are none left
+        let stable = (b..base_lines.len()).find_map(|index| {
+            match (our_matches[index], their_matches[index]) {
+                (Some(our_index), Some(their_index)) => Some((index, our_index, their_index)),
+                _ => None,
+            }
+        });
+
+        let (b_end, o_end, t_end) =
+            stable.unwrap_or((base_lines.len(), our_lines.len(), their_lines.len()));
+
+        let base_chunk = &base_lines[b..b_end];
+        let our_chunk = &our_lines[o..o_end];
+        let their_chunk = &their_lines[t..t_end];
+
+        if our_chunk == base_chunk || our_chunk == their_chunk {
+            push_lines(&mut result.content, their_chunk);
+        } else if their_chunk == base_chunk {
+            push_lines(&mut result.content, our_chunk);
+        } else {
+            push_conflict(&mut result.content, our_chunk, their_chunk);
+            result.conflicts += 1;
+        }
+
+        let Some((b_end, o_end, t_end)) = stable else {
+            break;
+        };
+
+        result.content.push_str(base_lines[b_end]);
+
+        b = b_end + 1;
+        o = o_end + 1;
+        t = t_end + 1;
+    }
+
+    result
+}
@@ -1,20 +1,82 @@
 use crate::filters;
 use crate::GeneratorError;
 use moon_common::consts::CONFIG_TEMPLATE_FILENAME;
-use moon_config::{ConfigError, TemplateConfig, TemplateFrontmatterConfig};
+use moon_config::{ConfigError, TemplateConfig, TemplateFrontmatterConfig, TemplateMergeStrategy};
 use moon_logger::{debug, trace};
 use moon_utils::{path, regex};
 use once_cell::sync::Lazy;
 use starbase_styles::color;
-use starbase_utils::{fs, json, yaml};
+use starbase_utils::{
+    fs,
+    json::{self, JsonError, JsonValue},
+    yaml,
+    yaml::YamlError,
+};
 use std::path::{Path, PathBuf};
 use tera::{Context, Tera};
+use toml_edit::{Document, TableLike};
 
 static PATH_VAR: Lazy<regex::Regex> =
     Lazy::new(|| regex::create_regex(r#"\[([A-Za-z0-9_]+)\]"#).unwrap());
 
 const LOG_TARGET: &str = "moon:generator:template";
 
+fn parse_json(content: &str, path: &Path) -> Result<json::JsonValue, JsonError> {
+    json::from_str(content).map_err(|error| JsonError::ReadFile {
+        path: path.to_path_buf(),
+        error,
+    })
+}
+
+fn parse_yaml(content: &str, path: &Path) -> Result<yaml::YamlValue, YamlError> {
+    yaml::from_str(content).map_err(|error| YamlError::ReadFile {
+        path: path.to_path_buf(),
+        error,
+    })
+}
+
+/// Extract the frontmatter from unrendered content, including any tags (like `{% set %}`)
+/// that precede it, so that it can be rendered and parsed before the rest of the file.
+fn extract_frontmatter(content: &str) -> Option<&str> {
+    let mut rest = content.trim_start();
+
+    while rest.starts_with("{%") || rest.starts_with("{#") {
+        let end = rest.find(if rest.starts_with("{%") { "%}" } else { "#}" })?;
+
+        rest = rest[(end + 2)..].trim_start();
+    }
+
+    if !rest.starts_with("---") {
+        return None;
+    }
+
+    let start_index = content.len() - rest.len();
+    let end_index = rest.get(4..)?.find("---")? + 4 + 3;
+
+    Some(&content[..(start_index + end_index)])
+}
+
+fn parse_toml(content: &str, path: &Path) -> Result<Document, GeneratorError> {
+    content
+        .parse::<Document>()
+        .map_err(|error| GeneratorError::InvalidTomlFile(path.to_path_buf(), error))
+}
+
+/// Recursively merge TOML tables, with values from next overwriting previous.
+/// Unlike JSON and YAML, the formatting and comments of previous are preserved.
+fn merge_toml(prev: &mut dyn TableLike, next: &dyn TableLike) {
+    for (key, next_item) in next.iter() {
+        match (prev.get_mut(key), next_item.as_table_like()) {
+            (Some(prev_item), Some(next_table)) if prev_item.is_table_like() => {
+                merge_toml(prev_item.as_table_like_mut().unwrap(), next_table);
+            }
+            _ => {
+                prev.insert(key, next_item.clone());
+            }
+        };
+    }
+}
+
 #[derive(Debug, Eq, PartialEq)]
 pub enum FileState {
     Create,
@@ -69,6 +131,8 @@ impl TemplateFile {
             return Some("json");
         } else if ext.ends_with("yaml") || ext.ends_with("yml") {
             return Some("yaml");
+        } else if ext.ends_with("toml") {
+            return Some("toml");
         }
 
         None
@@ -81,6 +145,13 @@ impl TemplateFile {
         }
     }
 
+    pub fn is_three_way_merge(&self) -> bool {
+        match &self.config {
+            Some(cfg) => matches!(cfg.merge, Some(TemplateMergeStrategy::ThreeWay)),
+            None => false,
+        }
+    }
+
     pub fn is_skipped(&self) -> bool {
         match &self.config {
             Some(cfg) => cfg.skip,
@@ -127,14 +198,97 @@ impl TemplateFile {
     pub fn should_write(&self) -> bool {
         !matches!(self.state, FileState::Skip)
     }
+
+    /// Layer the content of this file on top of a file (with the same destination)
+    /// from an extended template. Mergeable files are deep merged, while other files
+    /// replace the previous content entirely.
+    pub fn layer_over(&mut self, prev: &TemplateFile) -> Result<(), GeneratorError> {
+        match self.is_mergeable() {
+            Some("json") => {
+                trace!(
+                    target: LOG_TARGET,
+                    "Merging template file {} into extended file {}",
+                    color::file(&self.name),
+                    color::path(&prev.source_path)
+                );
+
+                let merged = json::merge(
+                    &parse_json(&prev.content, &prev.source_path)?,
+                    &parse_json(&self.content, &self.source_path)?,
+                );
+
+                self.content =
+                    json::to_string_pretty(&merged).map_err(|error| JsonError::StringifyFile {
+                        path: self.source_path.clone(),
+                        error,
+                    })?;
+            }
+            Some("yaml") => {
+                trace!(
+                    target: LOG_TARGET,
+                    "Merging template file {} into extended file {}",
+                    color::file(&self.name),
+                    color::path(&prev.source_path)
+                );
+
+                let merged = yaml::merge(
+                    &parse_yaml(&prev.content, &prev.source_path)?,
+                    &parse_yaml(&self.content, &self.source_path)?,
+                );
+
+                self.content =
+                    yaml::to_string(&merged).map_err(|error| YamlError::StringifyFile {
+                        path: self.source_path.clone(),
+                        error,
+                    })?;
+            }
+            Some("toml") => {
+                trace!(
+                    target: LOG_TARGET,
+                    "Merging template file {} into extended file {}",
+                    color::file(&self.name),
+                    color::path(&prev.source_path)
+                );
+
+                let mut merged = parse_toml(&prev.content, &prev.source_path)?;
+
+                merge_toml(
+                    merged.as_table_mut(),
+                    parse_toml(&self.content, &self.source_path)?.as_table(),
+                );
+
+                self.content = merged.to_string();
+            }
+            _ => {
+                trace!(
+                    target: LOG_TARGET,
+                    "Replacing extended file {} with template file {}",
+                    color::path(&prev.source_path),
+                    color::file(&self.name)
+                );
+            }
+        };
+
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
 pub struct Template {
     pub config: TemplateConfig,
     pub engine: Tera,
+
+    /// Name and root of all templates this template extends (including transitively),
+    /// in the order their files are layered. Does not include this template.
+    pub extends: Vec<(String, PathBuf)>,
+
     pub files: Vec<TemplateFile>,
     pub name: String,
+
+    /// Commands to run after generating, including those of extended templates
+    /// (which run first), in the order they should run.
+    pub post_generate: Vec<String>,
+
     pub root: PathBuf,
 }
 
@@ -159,54 +313,116 @@ impl Template {
         engine.register_filter("path_join", filters::path_join);
         engine.register_filter("path_relative", filters::path_relative);
 
+        let config = TemplateConfig::load_from(&root)?;
+
         Ok(Template {
-            config: TemplateConfig::load_from(&root)?,
+            post_generate: config.post_generate.clone(),
+            config,
             engine,
+            extends: vec![],
             files: vec![],
             name,
             root,
         })
     }
 
-    /// Load all template files from the source directory and return a list
-    /// of template file structs. These will later be used for rendering and generating.
-    pub fn load_files(&mut self, dest: &Path, context: &Context) -> Result<(), GeneratorError> {
-        let mut files = vec![];
+    /// Inherit the files, variables, and commands of the provided template. Files of extended
+    /// templates are layered first, and variables of this template take precedence.
+    pub fn extend_template(&mut self, template: Template) {
+        debug!(
+            target: LOG_TARGET,
+            "Extending template {} from {}",
+            color::id(&template.name),
+            color::id(&self.name)
+        );
 
-        for entry in fs::read_dir_all(&self.root)? {
-            // This is moon's schema, so skip it
-            if entry.file_name() == CONFIG_TEMPLATE_FILENAME {
-                continue;
+        for layer in template
+            .extends
+            .into_iter()
+            .chain([(template.name, template.root)])
+        {
+            if !self.extends.iter().any(|(name, _)| name == &layer.0) {
+                self.extends.push(layer);
             }
+        }
 
-            let source_path = entry.path();
-            let name =
-                self.interpolate_path(source_path.strip_prefix(&self.root).unwrap(), context)?;
+        for (name, variable) in template.config.variables {
+            self.config.variables.entry(name).or_insert(variable);
+        }
 
-            self.engine.add_template_file(&source_path, Some(&name))?;
+        // Inherited commands run after previously inherited commands,
+        // but before the commands of this template
+        let index = self.post_generate.len() - self.config.post_generate.len();
 
-            // Add partials to Tera, but skip copying them
-            if name.contains("partial") {
-                continue;
-            }
+        self.post_generate
+            .splice(index..index, template.post_generate);
 
-            trace!(
-                target: LOG_TARGET,
-                "Loading template file {} (source = {})",
-                color::file(&name),
-                color::path(&source_path),
-            );
+        if template.config.register_project {
+            self.config.register_project = true;
+        }
+    }
+
+    /// Load all template files from the source directory (and the directories of
+    /// extended templates), and return a list of template file structs. These will
+    /// later be used for rendering and generating.
+    pub fn load_files(&mut self, dest: &Path, context: &Context) -> Result<(), GeneratorError> {
+        let mut engine_files = vec![];
+        let mut sources = vec![];
+        let layers = self
+            .extends
+            .iter()
+            .cloned()
+            .chain([(self.name.clone(), self.root.clone())])
+            .collect::<Vec<_>>();
+
+        for (template_name, root) in layers {
+            for entry in fs::read_dir_all(&root)? {
+                // This is moon's schema, so skip it
+                if entry.file_name() == CONFIG_TEMPLATE_FILENAME {
+                    continue;
+                }
+
+                let source_path = entry.path();
+                let name =
+                    self.interpolate_path(source_path.strip_prefix(&root).unwrap(), context)?;
+
+                // Files are also registered with a template prefix, so that files of the same
+                // name can be included from a specific template. Otherwise the last layer wins.
+                let engine_name = format!("{template_name}/{name}");
+
+                engine_files.push((source_path.clone(), Some(name.clone())));
+                engine_files.push((source_path.clone(), Some(engine_name.clone())));
 
-            files.push(TemplateFile::load(name, source_path));
+                // Add partials to Tera, but skip copying them
+                if name.contains("partial") {
+                    continue;
+                }
+
+                trace!(
+                    target: LOG_TARGET,
+                    "Loading template file {} (source = {})",
+                    color::file(&name),
+                    color::path(&source_path),
+                );
+
+                sources.push((engine_name, TemplateFile::load(name, source_path)));
+            }
         }
 
+        self.engine.add_template_files(engine_files)?;
+
         // Do a second pass and render the content
-        for file in &mut files {
-            // if let Err(e) = self.engine.render(&file.name, context) {
-            //     dbg!(e);
-            // }
+        let mut files: Vec<TemplateFile> = vec![];
 
-            file.set_content(self.engine.render(&file.name, context)?, dest)?;
+        for (engine_name, file) in sources {
+            for mut file in self.render_file(&engine_name, file, dest, context)? {
+                // Layer on top of a file with the same destination from an extended template
+                if let Some(index) = files.iter().position(|f| f.dest_path == file.dest_path) {
+                    file.layer_over(&files.remove(index))?;
+                }
+
+                files.push(file);
+            }
         }
 
         // Sort so files are deterministic
@@ -217,6 +433,117 @@ impl Template {
         Ok(())
     }
 
+    /// Render the template file with the provided context. Files with an `if` condition
+    /// are only rendered when the condition is truthy, while files with `for_each` are
+    /// rendered once for each element of the array variable, available as `item`.
+    fn render_file(
+        &mut self,
+        engine_name: &str,
+        mut file: TemplateFile,
+        dest: &Path,
+        context: &Context,
+    ) -> Result<Vec<TemplateFile>, GeneratorError> {
+        let source = fs::read_file(&file.source_path)?;
+
+        // Frontmatter determines whether the file is rendered and how many times,
+        // so it must be rendered and parsed before the rest of the file
+        let config = match extract_frontmatter(&source) {
+            Some(frontmatter) => {
+                let mut content = self.engine.render_str(frontmatter, context)?;
+                content.push('\n');
+
+                let mut frontmatter_file = TemplateFile::load(file.name.clone(), PathBuf::new());
+                frontmatter_file.set_content(content, dest)?;
+                frontmatter_file.config
+            }
+            None => None,
+        };
+
+        let (condition, for_each) = match config {
+            Some(cfg) if cfg.condition.is_some() || cfg.for_each.is_some() => {
+                (cfg.condition, cfg.for_each)
+            }
+            _ => {
+                file.set_content(self.engine.render(engine_name, context)?, dest)?;
+
+                return Ok(vec![file]);
+            }
+        };
+
+        let contexts = match &for_each {
+            Some(var) => {
+                let Some(JsonValue::Array(items)) = context.get(var) else {
+                    return Err(GeneratorError::InvalidForEachVariable(
+                        var.to_owned(),
+                        file.name,
+                    ));
+                };
+
+                trace!(
+                    target: LOG_TARGET,
+                    "Rendering template file {} for each element of {} ({} elements)",
+                    color::file(&file.name),
+                    color::id(var),
+                    items.len(),
+                );
+
+                items
+                    .iter()
+                    .map(|item| {
+                        let mut item_context = context.clone();
+                        item_context.insert("item", item);
+                        item_context
+                    })
+                    .collect::<Vec<_>>()
+            }
+            None => vec![context.clone()],
+        };
+
+        let mut files = vec![];
+
+        for context in contexts {
+            if let Some(condition) = &condition {
+                if !self.evaluate_condition(condition, &context)? {
+                    trace!(
+                        target: LOG_TARGET,
+                        "Condition {} for template file {} is falsy, skipping",
+                        color::symbol(condition),
+                        color::file(&file.name),
+                    );
+
+                    continue;
+                }
+            }
+
+            let name = if for_each.is_some() {
+                self.interpolate_path(Path::new(&file.name), &context)?
+            } else {
+                file.name.clone()
+            };
+
+            let mut item_file = TemplateFile::load(name, file.source_path.clone());
+            item_file.set_content(self.engine.render(engine_name, &context)?, dest)?;
+
+            files.push(item_file);
+        }
+
+        Ok(files)
+    }
+
+    /// Evaluate a Tera expression against the context, and return its truthiness.
+    fn evaluate_condition(
+        &mut self,
+        condition: &str,
+        context: &Context,
+    ) -> Result<bool, GeneratorError> {
+        let result = self.engine.render_str(
+            &format!("{{% if {condition} %}}true{{% else %}}false{{% endif %}}"),
+            context,
+        )?;
+
+        Ok(result == "true")
+    }
+
     /// Tera *does not* support iterating over the context, so we're unable
     /// to interpolate a path ourselves. Instead, let's use Tera and its
     /// template rendering to handle this.
@@ -290,16 +617,24 @@ impl Template {
             match file.is_mergeable() {
                 Some("json") => {
                     let prev: json::JsonValue = json::read_file(&file.dest_path)?;
-                    let next: json::JsonValue = json::read_file(&file.source_path)?;
+                    let next = parse_json(&file.content, &file.source_path)?;
 
                     json::write_file(&file.dest_path, &json::merge(&prev, &next), true)?;
                 }
                 Some("yaml") => {
                     let prev: yaml::YamlValue = yaml::read_file(&file.dest_path)?;
-                    let next: yaml::YamlValue = yaml::read_file(&file.source_path)?;
+                    let next = parse_yaml(&file.content, &file.source_path)?;
 
                     yaml::write_file(&file.dest_path, &yaml::merge(&prev, &next))?;
                 }
+                Some("toml") => {
+                    let mut prev = parse_toml(&fs::read_file(&file.dest_path)?, &file.dest_path)?;
+                    let next = parse_toml(&file.content, &file.source_path)?;
+
+                    merge_toml(prev.as_table_mut(), next.as_table());
+
+                    fs::write_file(&file.dest_path, prev.to_string())?;
+                }
                 _ => {}
             }
         } else {
@@ -1,6 +1,8 @@
-use moon_config::{FilePath, GeneratorConfig};
+use moon_archive::tar;
+use moon_config::{FilePath, GeneratorConfig, TemplateLocator};
 use moon_generator::Generator;
 use moon_test_utils::create_sandbox;
+use std::fs;
 
 mod create_template {
     use super::*;
@@ -42,7 +44,9 @@ mod create_template {
         let template = Generator::load(
             sandbox.path(),
             &GeneratorConfig {
-                templates: vec![FilePath("./scaffolding".to_owned())],
+                templates: vec![TemplateLocator::File {
+                    path: FilePath("./scaffolding".to_owned()),
+                }],
             },
         )
         .unwrap()
@@ -92,3 +96,376 @@ mod create_template {
         );
     }
 }
+
+mod remote_templates {
+    use super::*;
+
+    fn create_config(locator: &str) -> GeneratorConfig {
+        GeneratorConfig {
+            templates: vec![TemplateLocator::try_from(locator).unwrap()],
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "NoLocalTemplateLocation")]
+    fn errors_when_creating_without_local_location() {
+        let sandbox = create_sandbox("generator");
+
+        Generator::load(
+            sandbox.path(),
+            &create_config("git+https://github.com/moonrepo/templates.git"),
+        )
+        .unwrap()
+        .create_template("new-template")
+        .unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "MissingTemplate(\"standard\")")]
+    fn errors_when_not_resolved() {
+        let sandbox = create_sandbox("generator");
+        sandbox.create_file("archive.tar.gz", "");
+
+        Generator::load(sandbox.path(), &create_config("file://./archive.tar.gz"))
+            .unwrap()
+            .load_template("standard")
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn loads_from_archive() {
+        let sandbox = create_sandbox("generator");
+
+        tar(
+            sandbox.path(),
+            &["templates".to_owned()],
+            sandbox.path().join("archive.tar.gz"),
+            None,
+        )
+        .unwrap();
+
+        let generator =
+            Generator::load(sandbox.path(), &create_config("file://./archive.tar.gz")).unwrap();
+
+        generator.resolve_remote_templates(false).await.unwrap();
+
+        let template = generator.load_template("standard").unwrap();
+
+        assert!(template
+            .root
+            .starts_with(sandbox.path().join(".moon/cache/templates")));
+        assert!(template.root.ends_with("standard"));
+        assert!(template.root.join("template.yml").exists());
+        assert!(
+            fs::read_dir(sandbox.path().join(".moon/cache/templates/.staging"))
+                .unwrap()
+                .next()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn loads_from_git() {
+        let sandbox = create_sandbox("generator");
+        let remote = create_sandbox("generator/templates");
+        remote.enable_git();
+
+        let generator = Generator::load(
+            sandbox.path(),
+            &create_config(&format!("git+file://{}#master", remote.path().display())),
+        )
+        .unwrap();
+
+        generator.resolve_remote_templates(false).await.unwrap();
+
+        let template = generator.load_template("standard").unwrap();
+
+        assert!(template
+            .root
+            .starts_with(sandbox.path().join(".moon/cache/templates")));
+        assert!(template.root.join("template.yml").exists());
+    }
+
+    #[tokio::test]
+    async fn refreshes_git_on_demand() {
+        let sandbox = create_sandbox("generator");
+        let remote = create_sandbox("generator/templates");
+        remote.enable_git();
+
+        let generator = Generator::load(
+            sandbox.path(),
+            &create_config(&format!("git+file://{}#master", remote.path().display())),
+        )
+        .unwrap();
+
+        generator.resolve_remote_templates(false).await.unwrap();
+
+        remote.create_file("added/template.yml", "title: Added\ndescription: Added");
+        remote.run_git(|cmd| {
+            cmd.args(["add", "--all", "."]);
+        });
+        remote.run_git(|cmd| {
+            cmd.args(["commit", "-m", "Add template"])
+                .env("GIT_AUTHOR_NAME", "moon tests")
+                .env("GIT_AUTHOR_EMAIL", "fakeemail@moonrepo.dev")
+                .env("GIT_COMMITTER_NAME", "moon tests")
+                .env("GIT_COMMITTER_EMAIL", "fakeemail@moonrepo.dev");
+        });
+
+        // Uses the cached copy
+        generator.resolve_remote_templates(false).await.unwrap();
+
+        assert!(generator.load_template("added").is_err());
+
+        generator.resolve_remote_templates(true).await.unwrap();
+
+        assert!(generator.load_template("added").is_ok());
+    }
+
+    #[tokio::test]
+    async fn errors_for_unknown_git_revision() {
+        let sandbox = create_sandbox("generator");
+        let remote = create_sandbox("generator/templates");
+        remote.enable_git();
+
+        let generator = Generator::load(
+            sandbox.path(),
+            &create_config(&format!(
+                "git+file://{}#unknown-branch",
+                remote.path().display()
+            )),
+        )
+        .unwrap();
+
+        assert!(generator.resolve_remote_templates(false).await.is_err());
+        assert!(generator.load_template("standard").is_err());
+    }
+}
+
+mod load_template {
+    use super::*;
+
+    #[test]
+    fn loads_extended_templates() {
+        let sandbox = create_sandbox("generator");
+
+        let template = Generator::load(sandbox.path(), &GeneratorConfig::default())
+            .unwrap()
+            .load_template("extends-child")
+            .unwrap();
+
+        assert_eq!(template.name, "extends-child");
+        assert_eq!(
+            template.extends,
+            vec![(
+                "extends-base".to_owned(),
+                sandbox.path().join("templates/extends-base")
+            )]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "CyclicTemplateExtends(\"extends-cycle-b\", \"extends-cycle-a\")")]
+    fn errors_on_cyclic_extends() {
+        let sandbox = create_sandbox("generator");
+
+        Generator::load(sandbox.path(), &GeneratorConfig::default())
+            .unwrap()
+            .load_template("extends-cycle-a")
+            .unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "MissingTemplate(\"unknown\")")]
+    fn errors_on_missing_extends() {
+        let sandbox = create_sandbox("generator");
+        sandbox.create_file(
+            "templates/extends-unknown/template.yml",
+            "title: Title\ndescription: Description\nextends: [unknown]",
+        );
+
+        Generator::load(sandbox.path(), &GeneratorConfig::default())
+            .unwrap()
+            .load_template("extends-unknown")
+            .unwrap();
+    }
+}
+
+mod run_post_generate {
+    use super::*;
+    use moon_generator::TemplateContext;
+
+    #[tokio::test]
+    async fn runs_commands_with_variables() {
+        let sandbox = create_sandbox("generator");
+        let generator = Generator::load(sandbox.path(), &GeneratorConfig::default()).unwrap();
+        let template = generator.load_template("hooks").unwrap();
+        let dest = sandbox.path().join("templates/hooks");
+
+        let mut context = TemplateContext::new();
+        context.insert("name", "example");
+
+        generator
+            .run_post_generate(&template, &dest, &context)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(dest.join("hook-output.txt")).unwrap(),
+            "hooks example\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn errors_on_failed_command() {
+        let sandbox = create_sandbox("generator");
+        sandbox.create_file(
+            "templates/failing/template.yml",
+            "title: Title\ndescription: Description\npostGenerate: ['bash -c \"exit 1\"']",
+        );
+
+        let generator = Generator::load(sandbox.path(), &GeneratorConfig::default()).unwrap();
+        let template = generator.load_template("failing").unwrap();
+
+        assert!(generator
+            .run_post_generate(&template, sandbox.path(), &TemplateContext::new())
+            .await
+            .is_err());
+    }
+}
+
+mod three_way_merge {
+    use super::*;
+    use moon_generator::{FileState, TemplateContext};
+
+    fn generate(generator: &Generator, sandbox_path: &std::path::Path, merge: bool) {
+        let mut template = generator.load_template("three-way").unwrap();
+        template
+            .load_files(&sandbox_path.join("dest"), &TemplateContext::new())
+            .unwrap();
+
+        for file in &mut template.files {
+            if merge && generator.has_previous_render(file) {
+                file.state = FileState::Merge;
+            }
+        }
+
+        generator.generate(&template).unwrap();
+    }
+
+    #[test]
+    fn merges_local_and_template_changes() {
+        let sandbox = create_sandbox("generator");
+        sandbox.create_file(
+            "templates/three-way/template.yml",
+            "title: Title\ndescription: Description",
+        );
+        sandbox.create_file(
+            "templates/three-way/file.txt",
+            "---\nmerge: three-way\n---\none\ntwo\nthree\n",
+        );
+
+        let generator = Generator::load(sandbox.path(), &GeneratorConfig::default()).unwrap();
+
+        generate(&generator, sandbox.path(), true);
+
+        // Change both the generated file and the template
+        sandbox.create_file("dest/file.txt", "one\ntwo\nthree\nlocal\n");
+        sandbox.create_file(
+            "templates/three-way/file.txt",
+            "---\nmerge: three-way\n---\nONE\ntwo\nthree\n",
+        );
+
+        generate(&generator, sandbox.path(), true);
+
+        assert_eq!(
+            fs::read_to_string(sandbox.path().join("dest/file.txt")).unwrap(),
+            "ONE\ntwo\nthree\nlocal\n"
+        );
+    }
+
+    #[test]
+    fn writes_conflict_markers() {
+        let sandbox = create_sandbox("generator");
+        sandbox.create_file(
+            "templates/three-way/template.yml",
+            "title: Title\ndescription: Description",
+        );
+        sandbox.create_file(
+            "templates/three-way/file.txt",
+            "---\nmerge: three-way\n---\nvalue\n",
+        );
+
+        let generator = Generator::load(sandbox.path(), &GeneratorConfig::default()).unwrap();
+
+        generate(&generator, sandbox.path(), true);
+
+        sandbox.create_file("dest/file.txt", "local\n");
+        sandbox.create_file(
+            "templates/three-way/file.txt",
+            "---\nmerge: three-way\n---\ntemplate\n",
+        );
+
+        generate(&generator, sandbox.path(), true);
+
+        assert_eq!(
+            fs::read_to_string(sandbox.path().join("dest/file.txt")).unwrap(),
+            "<<<<<<< existing\nlocal\n=======\ntemplate\n>>>>>>> template\n"
+        );
+    }
+
+    #[test]
+    fn only_stores_renders_for_three_way_files() {
+        let sandbox = create_sandbox("generator");
+        sandbox.create_file(
+            "templates/three-way/template.yml",
+            "title: Title\ndescription: Description",
+        );
+        sandbox.create_file("templates/three-way/file.txt", "value\n");
+
+        let generator = Generator::load(sandbox.path(), &GeneratorConfig::default()).unwrap();
+
+        generate(&generator, sandbox.path(), false);
+
+        assert!(!sandbox
+            .path()
+            .join(".moon/cache/templates/.rendered")
+            .exists());
+    }
+}
+
+mod merge_files {
+    use super::*;
+    use moon_generator::{FileState, TemplateContext};
+
+    #[test]
+    fn merges_toml_into_existing_file() {
+        let sandbox = create_sandbox("generator");
+        sandbox.create_file(
+            "templates/toml/template.yml",
+            "title: Title\ndescription: Description",
+        );
+        sandbox.create_file(
+            "templates/toml/Cargo.toml",
+            "[package]\nedition = \"2021\"\n\n[dependencies]\nserde = \"1\"\n",
+        );
+        sandbox.create_file(
+            "dest/Cargo.toml",
+            "[package]\nname = \"dest\" # Comment\n\n[dependencies]\nserde = \"0.9\"\n",
+        );
+
+        let generator = Generator::load(sandbox.path(), &GeneratorConfig::default()).unwrap();
+        let mut template = generator.load_template("toml").unwrap();
+        template
+            .load_files(&sandbox.path().join("dest"), &TemplateContext::new())
+            .unwrap();
+        template.files[0].state = FileState::Merge;
+
+        generator.generate(&template).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(sandbox.path().join("dest/Cargo.toml")).unwrap(),
+            "[package]\nname = \"dest\" # Comment\nedition = \"2021\"\n\n[dependencies]\nserde = \"1\"\n"
+        );
+    }
+}
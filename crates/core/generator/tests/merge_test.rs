@@ -0,0 +1,87 @@
+use moon_generator::{merge_three_way, MergeResult};
+
+fn merge(base: &str, ours: &str, theirs: &str) -> MergeResult {
+    merge_three_way(base, ours, theirs)
+}
+
+mod merge_three_way {
+    use super::*;
+
+    #[test]
+    fn returns_theirs_when_unchanged() {
+        let result = merge("a\nb\nc\n", "a\nb\nc\n", "a\nB\nc\n");
+
+        assert_eq!(result.content, "a\nB\nc\n");
+        assert_eq!(result.conflicts, 0);
+    }
+
+    #[test]
+    fn returns_ours_when_template_unchanged() {
+        let result = merge("a\nb\nc\n", "a\nB\nc\n", "a\nb\nc\n");
+
+        assert_eq!(result.content, "a\nB\nc\n");
+        assert_eq!(result.conflicts, 0);
+    }
+
+    #[test]
+    fn combines_non_overlapping_changes() {
+        let result = merge(
+            "one\ntwo\nthree\nfour\nfive\n",
+            "ONE\ntwo\nthree\nfour\nfive\nsix\n",
+            "one\ntwo\nthree\nFOUR\nfive\n",
+        );
+
+        assert_eq!(result.content, "ONE\ntwo\nthree\nFOUR\nfive\nsix\n");
+        assert_eq!(result.conflicts, 0);
+    }
+
+    #[test]
+    fn combines_removed_lines() {
+        let result = merge("a\nb\nc\nd\n", "a\nc\nd\n", "a\nb\nc\n");
+
+        assert_eq!(result.content, "a\nc\n");
+        assert_eq!(result.conflicts, 0);
+    }
+
+    #[test]
+    fn accepts_identical_changes() {
+        let result = merge("a\nb\nc\n", "a\nX\nc\n", "a\nX\nc\n");
+
+        assert_eq!(result.content, "a\nX\nc\n");
+        assert_eq!(result.conflicts, 0);
+    }
+
+    #[test]
+    fn marks_conflicting_changes() {
+        let result = merge("a\nb\nc\n", "a\nours\nc\n", "a\ntheirs\nc\n");
+
+        assert_eq!(
+            result.content,
+            "a\n<<<<<<< existing\nours\n=======\ntheirs\n>>>>>>> template\nc\n"
+        );
+        assert_eq!(result.conflicts, 1);
+    }
+
+    #[test]
+    fn marks_conflicts_without_trailing_newline() {
+        let result = merge("a\nb", "a\nours", "a\ntheirs");
+
+        assert_eq!(
+            result.content,
+            "a\n<<<<<<< existing\nours\n=======\ntheirs\n>>>>>>> template\n"
+        );
+        assert_eq!(result.conflicts, 1);
+    }
+
+    #[test]
+    fn handles_empty_base() {
+        let result = merge("", "ours\n", "theirs\n");
+
+        assert_eq!(result.conflicts, 1);
+
+        let result = merge("", "", "theirs\n");
+
+        assert_eq!(result.content, "theirs\n");
+        assert_eq!(result.conflicts, 0);
+    }
+}
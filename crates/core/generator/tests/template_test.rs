@@ -1,5 +1,7 @@
 use moon_common::consts::CONFIG_TEMPLATE_FILENAME;
-use moon_config::TemplateFrontmatterConfig;
+use moon_config::{
+    TemplateFrontmatterConfig, TemplateMergeStrategy, TemplateVariable, TemplateVariableSetting,
+};
 use moon_generator::{Template, TemplateContext, TemplateFile};
 use moon_test_utils::get_fixtures_path;
 use std::path::PathBuf;
@@ -42,6 +44,262 @@ mod load_files {
 
         assert!(!has_schema);
     }
+
+    #[test]
+    fn layers_extended_template_files() {
+        let mut template = Template::new(
+            "extends-child".into(),
+            get_fixtures_path("generator/templates/extends-child"),
+        )
+        .unwrap();
+
+        template.extend_template(
+            Template::new(
+                "extends-base".into(),
+                get_fixtures_path("generator/templates/extends-base"),
+            )
+            .unwrap(),
+        );
+
+        let mut context = create_context();
+        context.insert("name", "child");
+        context.insert("private", &true);
+
+        template
+            .load_files(&get_fixtures_path("generator"), &context)
+            .unwrap();
+
+        let get_content = |name: &str| {
+            template
+                .files
+                .iter()
+                .find(|f| f.name == name)
+                .unwrap()
+                .content
+                .clone()
+        };
+
+        assert_eq!(
+            template
+                .files
+                .iter()
+                .map(|f| f.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["README.md", "package.json", "src/base.ts", "src/index.ts"]
+        );
+
+        // Partials from the child take precedence
+        assert_eq!(get_content("README.md"), "# child\n\nChild footer\n\n");
+
+        // Mergeable files are deep merged
+        assert_eq!(
+            get_content("package.json"),
+            "{\n  \"name\": \"child\",\n  \"private\": true,\n  \"scripts\": {\n    \"build\": \"tsc\"\n  }\n}"
+        );
+
+        // Other files are replaced
+        assert_eq!(get_content("src/index.ts"), "export const child = true;\n");
+        assert_eq!(get_content("src/base.ts"), "export const base = true;\n");
+    }
+}
+
+mod render_conditions {
+    use super::*;
+    use moon_test_utils::{create_sandbox, Sandbox};
+
+    fn create_conditions_template() -> (Sandbox, Template) {
+        let sandbox = create_sandbox("generator");
+        sandbox.create_file(
+            "templates/conditions/template.yml",
+            "title: Title\ndescription: Description",
+        );
+        sandbox.create_file(
+            "templates/conditions/tests.ts",
+            "---\nif: with_tests\n---\ntest();",
+        );
+        sandbox.create_file(
+            "templates/conditions/docs.md",
+            "---\nif: not with_tests and string == 'string'\n---\n# Docs",
+        );
+        sandbox.create_file(
+            "templates/conditions/handlers/[item].ts",
+            "---\nfor_each: services\n---\nexport const {{ item | camel_case }}Handler = true;",
+        );
+        sandbox.create_file(
+            "templates/conditions/filtered/[item].ts",
+            "{% set upper = string | upper_case %}\n---\nfor_each: services\nif: item != 'billing'\n---\n{{ upper }}",
+        );
+
+        let template = Template::new(
+            "conditions".into(),
+            sandbox.path().join("templates/conditions"),
+        )
+        .unwrap();
+
+        (sandbox, template)
+    }
+
+    fn get_names(template: &Template) -> Vec<&str> {
+        template.files.iter().map(|f| f.name.as_str()).collect()
+    }
+
+    #[test]
+    fn renders_when_condition_truthy() {
+        let (sandbox, mut template) = create_conditions_template();
+
+        let mut context = create_context();
+        context.insert("with_tests", &true);
+        context.insert("services", &Vec::<String>::new());
+
+        template.load_files(sandbox.path(), &context).unwrap();
+
+        assert_eq!(get_names(&template), vec!["tests.ts"]);
+    }
+
+    #[test]
+    fn skips_when_condition_falsy() {
+        let (sandbox, mut template) = create_conditions_template();
+
+        let mut context = create_context();
+        context.insert("with_tests", &false);
+        context.insert("services", &Vec::<String>::new());
+
+        template.load_files(sandbox.path(), &context).unwrap();
+
+        assert_eq!(get_names(&template), vec!["docs.md"]);
+    }
+
+    #[test]
+    fn renders_for_each_element() {
+        let (sandbox, mut template) = create_conditions_template();
+
+        let mut context = create_context();
+        context.insert("with_tests", &false);
+        context.insert("services", &vec!["auth", "billing-api"]);
+
+        template.load_files(sandbox.path(), &context).unwrap();
+
+        assert_eq!(
+            get_names(&template),
+            vec![
+                "docs.md",
+                "filtered/auth.ts",
+                "filtered/billing-api.ts",
+                "handlers/auth.ts",
+                "handlers/billing-api.ts"
+            ]
+        );
+
+        let file = template
+            .files
+            .iter()
+            .find(|f| f.name == "handlers/billing-api.ts")
+            .unwrap();
+
+        assert_eq!(file.content, "export const billingApiHandler = true;");
+        assert_eq!(
+            file.dest_path,
+            sandbox.path().join("handlers/billing-api.ts")
+        );
+    }
+
+    #[test]
+    fn filters_elements_with_condition() {
+        let (sandbox, mut template) = create_conditions_template();
+
+        let mut context = create_context();
+        context.insert("with_tests", &false);
+        context.insert("services", &vec!["auth", "billing"]);
+
+        template.load_files(sandbox.path(), &context).unwrap();
+
+        let file = template
+            .files
+            .iter()
+            .find(|f| f.name == "filtered/auth.ts")
+            .unwrap();
+
+        assert_eq!(file.content, "STRING");
+        assert!(!get_names(&template).contains(&"filtered/billing.ts"));
+    }
+
+    #[test]
+    #[should_panic(expected = "InvalidForEachVariable(\"services\"")]
+    fn errors_when_for_each_not_an_array() {
+        let (sandbox, mut template) = create_conditions_template();
+
+        let mut context = create_context();
+        context.insert("with_tests", &false);
+        context.insert("services", "auth");
+
+        template.load_files(sandbox.path(), &context).unwrap();
+    }
+}
+
+mod extend_template {
+    use super::*;
+
+    #[test]
+    fn orders_post_generate_commands() {
+        let sandbox = moon_test_utils::create_sandbox("generator");
+        sandbox.create_file(
+            "templates/a/template.yml",
+            "title: A\ndescription: A\npostGenerate: [a]",
+        );
+        sandbox.create_file(
+            "templates/b/template.yml",
+            "title: B\ndescription: B\npostGenerate: [b1, b2]",
+        );
+        sandbox.create_file(
+            "templates/c/template.yml",
+            "title: C\ndescription: C\npostGenerate: [c]\nregisterProject: true",
+        );
+
+        let load = |name: &str| {
+            Template::new(name.into(), sandbox.path().join("templates").join(name)).unwrap()
+        };
+
+        let mut template = load("c");
+        template.extend_template(load("a"));
+        template.extend_template(load("b"));
+
+        assert_eq!(template.post_generate, vec!["a", "b1", "b2", "c"]);
+        assert!(template.config.register_project);
+    }
+
+    #[test]
+    fn inherits_variables() {
+        let mut template = Template::new(
+            "extends-child".into(),
+            get_fixtures_path("generator/templates/extends-child"),
+        )
+        .unwrap();
+
+        template.extend_template(
+            Template::new(
+                "extends-base".into(),
+                get_fixtures_path("generator/templates/extends-base"),
+            )
+            .unwrap(),
+        );
+
+        assert_eq!(
+            template.extends,
+            vec![(
+                "extends-base".to_owned(),
+                get_fixtures_path("generator/templates/extends-base")
+            )]
+        );
+        assert!(template.config.variables.contains_key("private"));
+        assert_eq!(
+            template.config.variables.get("name").unwrap(),
+            &TemplateVariable::String(TemplateVariableSetting {
+                default: "child".into(),
+                prompt: Some("Name?".into()),
+                required: None,
+            })
+        );
+    }
 }
 
 mod interpolate_path {
@@ -199,4 +457,77 @@ mod set_content {
         assert!(file.config.unwrap().skip);
         assert_eq!(file.content, "Content".to_owned());
     }
+
+    #[test]
+    fn merge_field() {
+        let mut file = create_template_file();
+        file.set_content("---\nmerge: three-way\n---\nContent", &PathBuf::from("."))
+            .unwrap();
+
+        assert!(file.is_three_way_merge());
+        assert_eq!(
+            file.config.unwrap().merge,
+            Some(TemplateMergeStrategy::ThreeWay)
+        );
+        assert_eq!(file.content, "Content".to_owned());
+    }
+}
+
+mod is_mergeable {
+    use super::*;
+
+    #[test]
+    fn supports_json_yaml_toml() {
+        for (name, ext) in [
+            ("package.json", Some("json")),
+            ("config.yml", Some("yaml")),
+            ("config.yaml", Some("yaml")),
+            ("Cargo.toml", Some("toml")),
+            ("pyproject.toml", Some("toml")),
+            ("README.md", None),
+        ] {
+            let file = TemplateFile::load(name.into(), PathBuf::from("."));
+
+            assert_eq!(file.is_mergeable(), ext);
+        }
+    }
+
+    #[test]
+    fn uses_to_field() {
+        let mut file = TemplateFile::load("cargo.tera".into(), PathBuf::from("."));
+        file.set_content("---\nto: Cargo.toml\n---\n", &PathBuf::from("."))
+            .unwrap();
+
+        assert_eq!(file.is_mergeable(), Some("toml"));
+    }
+}
+
+mod layer_over {
+    use super::*;
+
+    #[test]
+    fn merges_toml_and_preserves_order() {
+        let mut prev = TemplateFile::load("Cargo.toml".into(), PathBuf::from("."));
+        prev.content = "[package]\nname = \"base\"\nedition = \"2021\"\n\n# Deps\n[dependencies]\nserde = \"1\"\n".into();
+
+        let mut file = TemplateFile::load("Cargo.toml".into(), PathBuf::from("."));
+        file.content = "[package]\nname = \"child\"\n\n[dependencies]\ntokio = \"1\"\n".into();
+        file.layer_over(&prev).unwrap();
+
+        assert_eq!(
+            file.content,
+            "[package]\nname = \"child\"\nedition = \"2021\"\n\n# Deps\n[dependencies]\nserde = \"1\"\ntokio = \"1\"\n"
+        );
+    }
+
+    #[test]
+    fn errors_for_invalid_toml() {
+        let mut prev = TemplateFile::load("Cargo.toml".into(), PathBuf::from("."));
+        prev.content = "[package".into();
+
+        let mut file = TemplateFile::load("Cargo.toml".into(), PathBuf::from("."));
+        file.content = "[package]".into();
+
+        assert!(file.layer_over(&prev).is_err());
+    }
 }
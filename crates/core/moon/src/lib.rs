@@ -1,8 +1,13 @@
+use moon_bun_platform::BunPlatform;
 use moon_deno_platform::DenoPlatform;
 use moon_dep_graph::DepGraphBuilder;
 use moon_error::MoonError;
+use moon_go_platform::GoPlatform;
 use moon_node_platform::NodePlatform;
+use moon_php_platform::PhpPlatform;
 use moon_project_graph::{ProjectGraph, ProjectGraphBuilder, ProjectGraphError};
+use moon_python_platform::PythonPlatform;
+use moon_ruby_platform::RubyPlatform;
 use moon_rust_platform::RustPlatform;
 use moon_system_platform::SystemPlatform;
 use moon_utils::{is_ci, is_test_env};
@@ -61,6 +66,14 @@ pub async fn load_workspace_from(path: &Path) -> Result<Workspace, WorkspaceErro
         }
     };
 
+    if let Some(bun_config) = &workspace.toolchain_config.bun {
+        workspace.register_platform(Box::new(BunPlatform::new(
+            bun_config,
+            &workspace.toolchain_config.typescript,
+            &workspace.root,
+        )));
+    }
+
     if let Some(deno_config) = &workspace.toolchain_config.deno {
         workspace.register_platform(Box::new(DenoPlatform::new(
             deno_config,
@@ -69,6 +82,10 @@ pub async fn load_workspace_from(path: &Path) -> Result<Workspace, WorkspaceErro
         )));
     }
 
+    if let Some(go_config) = &workspace.toolchain_config.go {
+        workspace.register_platform(Box::new(GoPlatform::new(go_config, &workspace.root)));
+    }
+
     if let Some(node_config) = &workspace.toolchain_config.node {
         workspace.register_platform(Box::new(NodePlatform::new(
             node_config,
@@ -77,6 +94,21 @@ pub async fn load_workspace_from(path: &Path) -> Result<Workspace, WorkspaceErro
         )));
     }
 
+    if let Some(php_config) = &workspace.toolchain_config.php {
+        workspace.register_platform(Box::new(PhpPlatform::new(php_config, &workspace.root)));
+    }
+
+    if let Some(python_config) = &workspace.toolchain_config.python {
+        workspace.register_platform(Box::new(PythonPlatform::new(
+            python_config,
+            &workspace.root,
+        )));
+    }
+
+    if let Some(ruby_config) = &workspace.toolchain_config.ruby {
+        workspace.register_platform(Box::new(RubyPlatform::new(ruby_config, &workspace.root)));
+    }
+
     if let Some(rust_config) = &workspace.toolchain_config.rust {
         workspace.register_platform(Box::new(RustPlatform::new(rust_config, &workspace.root)));
     }
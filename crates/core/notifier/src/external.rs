@@ -0,0 +1,241 @@
+use crate::webhooks::{is_event_allowed, WebhookPayload};
+use ci_env::{get_environment, CiEnvironment};
+use moon_config::SubscriberConfig;
+use moon_emitter::{Event, EventFlow, Subscriber};
+use moon_error::MoonError;
+use moon_logger::{error, trace, warn};
+use moon_utils::time::now_timestamp;
+use moon_workspace::Workspace;
+use serde::Deserialize;
+use starbase_styles::color;
+use std::io;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use tokio::time::timeout;
+use uuid::Uuid;
+
+const LOG_TARGET: &str = "moon:notifier:external";
+
+#[derive(Default, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExternalFlow {
+    Break,
+    #[default]
+    Continue,
+}
+
+#[derive(Default, Deserialize)]
+pub struct ExternalResponse {
+    #[serde(default)]
+    pub flow: ExternalFlow,
+}
+
+struct ExternalProcess {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: Lines<BufReader<ChildStdout>>,
+}
+
+/// Streams events to an external process over stdin, as a JSON payload per line,
+/// and waits for the process to respond with a JSON line on stdout. The process
+/// is spawned on the first event, and is shutdown when the pipeline ends.
+pub struct ExternalSubscriber {
+    args: Vec<String>,
+    command: String,
+    enabled: bool,
+    environment: Option<CiEnvironment>,
+    events: Vec<String>,
+    process: Option<ExternalProcess>,
+    timeout: Duration,
+    uuid: String,
+}
+
+impl ExternalSubscriber {
+    pub fn new(config: &SubscriberConfig) -> Self {
+        ExternalSubscriber {
+            args: config.args.clone(),
+            command: config.command.clone(),
+            enabled: true,
+            environment: get_environment(),
+            events: config.events.clone(),
+            process: None,
+            timeout: Duration::from_secs(config.timeout),
+            uuid: Uuid::new_v4().to_string(),
+        }
+    }
+
+    fn spawn(&self, workspace: &Workspace) -> io::Result<ExternalProcess> {
+        trace!(
+            target: LOG_TARGET,
+            "Spawning subscriber process {}",
+            color::shell(&self.command),
+        );
+
+        let mut child = Command::new(&self.command)
+            .args(&self.args)
+            .current_dir(&workspace.root)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .kill_on_drop(true)
+            .spawn()?;
+
+        let stdin = child.stdin.take().expect("Missing stdin.");
+        let stdout = child.stdout.take().expect("Missing stdout.");
+
+        Ok(ExternalProcess {
+            child,
+            stdin,
+            stdout: BufReader::new(stdout).lines(),
+        })
+    }
+
+    async fn send(&mut self, body: String) -> io::Result<ExternalResponse> {
+        let process = self.process.as_mut().unwrap();
+
+        process.stdin.write_all(body.as_bytes()).await?;
+        process.stdin.write_all(b"\n").await?;
+        process.stdin.flush().await?;
+
+        // The emitter is locked while waiting, so a process that never
+        // responds must not be allowed to hang the entire pipeline
+        let Ok(line) = timeout(self.timeout, process.stdout.next_line()).await else {
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("no response within {} seconds", self.timeout.as_secs()),
+            ));
+        };
+
+        let Some(line) = line? else {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "process exited without responding",
+            ));
+        };
+
+        if line.trim().is_empty() {
+            return Ok(ExternalResponse::default());
+        }
+
+        Ok(serde_json::from_str(&line).unwrap_or_else(|error| {
+            warn!(
+                target: LOG_TARGET,
+                "Subscriber {} responded with an invalid payload, continuing: {}",
+                color::shell(&self.command),
+                color::muted_light(error.to_string()),
+            );
+
+            ExternalResponse::default()
+        }))
+    }
+
+    async fn shutdown(&mut self) {
+        if let Some(process) = self.process.take() {
+            let ExternalProcess {
+                mut child, stdin, ..
+            } = process;
+
+            // Closing stdin signals to the process that no more events will be sent
+            drop(stdin);
+
+            // A process that ignores the closed stdin must not hang moon on exit
+            if timeout(self.timeout, child.wait()).await.is_err() {
+                warn!(
+                    target: LOG_TARGET,
+                    "Subscriber {} did not exit within {} seconds, killing",
+                    color::shell(&self.command),
+                    self.timeout.as_secs(),
+                );
+
+                let _ = child.kill().await;
+            }
+        }
+    }
+
+    fn disable(&mut self, message: String) {
+        self.enabled = false;
+        self.process = None;
+
+        error!(
+            target: LOG_TARGET,
+            "Subscriber {} failed: {}. Subsequent events will not be sent to it.",
+            color::shell(&self.command),
+            message,
+        );
+    }
+}
+
+#[async_trait::async_trait]
+impl Subscriber for ExternalSubscriber {
+    async fn on_emit<'a>(
+        &mut self,
+        event: &Event<'a>,
+        workspace: &Workspace,
+    ) -> Result<EventFlow, MoonError> {
+        let type_of = event.get_type();
+
+        if self.enabled && is_event_allowed(&self.events, &type_of) {
+            if self.process.is_none() {
+                match self.spawn(workspace) {
+                    Ok(process) => {
+                        self.process = Some(process);
+                    }
+                    Err(error) => {
+                        self.disable(error.to_string());
+                    }
+                };
+            }
+
+            if self.process.is_some() {
+                trace!(
+                    target: LOG_TARGET,
+                    "Sending event {} to subscriber {}",
+                    color::id(&type_of),
+                    color::shell(&self.command),
+                );
+
+                let body = serde_json::to_string(&WebhookPayload {
+                    created_at: now_timestamp(),
+                    environment: self.environment.clone(),
+                    event,
+                    type_of,
+                    uuid: self.uuid.clone(),
+                })
+                .unwrap();
+
+                match self.send(body).await {
+                    Ok(response) => {
+                        if response.flow == ExternalFlow::Break {
+                            // Breaking is only supported before the pipeline has started,
+                            // as other events are required by the caching layer
+                            if matches!(event, Event::PipelineStarted { .. }) {
+                                self.enabled = false;
+                                self.shutdown().await;
+
+                                return Ok(EventFlow::Break);
+                            }
+
+                            warn!(
+                                target: LOG_TARGET,
+                                "Subscriber {} can only break the {} event, ignoring",
+                                color::shell(&self.command),
+                                color::id("pipeline.started"),
+                            );
+                        }
+                    }
+                    Err(error) => {
+                        self.disable(error.to_string());
+                    }
+                };
+            }
+        }
+
+        if event.is_end() {
+            self.shutdown().await;
+        }
+
+        Ok(EventFlow::Continue)
+    }
+}
@@ -1,3 +1,5 @@
+mod external;
 mod webhooks;
 
+pub use external::*;
 pub use webhooks::*;
@@ -1,12 +1,17 @@
 use ci_env::{get_environment, CiEnvironment};
+use hmac::{Hmac, Mac};
 use moon_emitter::{Event, EventFlow, Subscriber};
 use moon_error::MoonError;
-use moon_logger::{error, trace};
+use moon_logger::{error, trace, warn};
 use moon_utils::time::{chrono::prelude::*, now_timestamp};
 use moon_workspace::Workspace;
 use serde::{Deserialize, Serialize};
+use sha2::Sha256;
 use starbase_styles::color;
+use std::time::Duration;
+use tokio::sync::mpsc::{channel, Sender};
 use tokio::task::JoinHandle;
+use tokio::time::sleep;
 use uuid::Uuid;
 
 const LOG_TARGET: &str = "moon:notifier:webhooks";
@@ -28,41 +33,169 @@ pub struct WebhookPayload<T: Serialize> {
     pub uuid: String,
 }
 
+const MAX_RETRIES: u32 = 3;
+
+const QUEUE_SIZE: usize = 100;
+
+const RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// Return true if the event type is allowed by the list of filters.
+/// An empty list will allow all events.
+pub fn is_event_allowed(events: &[String], type_of: &str) -> bool {
+    events.is_empty() || events.iter().any(|event| event == type_of)
+}
+
+/// Sign the body with an HMAC-SHA256 of the shared secret,
+/// formatted as `sha256=<hex digest>`.
+pub fn sign_payload(secret: &str, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size.");
+
+    mac.update(body.as_bytes());
+
+    format!("sha256={:x}", mac.finalize().into_bytes())
+}
+
 pub async fn notify_webhook(
-    url: String,
+    client: &reqwest::Client,
+    url: &str,
     body: String,
+    type_of: &str,
+    secret: Option<&str>,
 ) -> Result<reqwest::Response, reqwest::Error> {
-    reqwest::Client::new()
+    let mut request = client
         .post(url)
-        .body(body)
         .header("Accept", "application/json")
         .header("Content-Type", "application/json")
         .header("Connection", "keep-alive")
         .header("Keep-Alive", "timeout=30, max=120")
-        .send()
-        .await
+        .header("X-Moon-Event", type_of);
+
+    if let Some(secret) = secret {
+        request = request.header("X-Moon-Signature-256", sign_payload(secret, &body));
+    }
+
+    request.body(body).send().await
+}
+
+struct WebhookRequest {
+    body: String,
+    type_of: String,
+}
+
+#[derive(Clone)]
+struct WebhookDelivery {
+    client: reqwest::Client,
+    secret: Option<String>,
+    url: String,
+}
+
+impl WebhookDelivery {
+    /// Send the request, and retry with an exponential backoff when the
+    /// request failed to send, or the endpoint responded with a server error.
+    async fn send(&self, request: &WebhookRequest) -> bool {
+        let mut attempt = 0;
+
+        loop {
+            let response = notify_webhook(
+                &self.client,
+                &self.url,
+                request.body.clone(),
+                &request.type_of,
+                self.secret.as_deref(),
+            )
+            .await;
+
+            let retryable = match response {
+                Ok(res) if res.status().is_success() => return true,
+                Ok(res) => {
+                    res.status().is_server_error()
+                        || res.status() == reqwest::StatusCode::TOO_MANY_REQUESTS
+                }
+                Err(_) => true,
+            };
+
+            if !retryable || attempt >= MAX_RETRIES {
+                return false;
+            }
+
+            trace!(
+                target: LOG_TARGET,
+                "Failed to send webhook event {}, retrying (attempt {})",
+                color::id(&request.type_of),
+                attempt + 1,
+            );
+
+            sleep(RETRY_DELAY * 2u32.pow(attempt)).await;
+            attempt += 1;
+        }
+    }
 }
 
 pub struct WebhooksSubscriber {
+    delivery: WebhookDelivery,
     enabled: bool,
     environment: Option<CiEnvironment>,
-    requests: Vec<JoinHandle<()>>,
-    url: String,
+    events: Vec<String>,
+    queue: Option<Sender<WebhookRequest>>,
     uuid: String,
+    worker: Option<JoinHandle<()>>,
 }
 
 impl WebhooksSubscriber {
-    pub fn new(url: String) -> Self {
+    pub fn new(url: String, events: Vec<String>, secret: Option<String>) -> Self {
         WebhooksSubscriber {
             enabled: true,
             environment: get_environment(),
-            requests: vec![],
+            events,
+            queue: None,
             uuid: if url.contains("127.0.0.1") {
                 "XXXX-XXXX-XXXX-XXXX".into()
             } else {
                 Uuid::new_v4().to_string()
             },
-            url,
+            delivery: WebhookDelivery {
+                client: reqwest::Client::new(),
+                secret,
+                url,
+            },
+            worker: None,
+        }
+    }
+
+    /// Requests are sent in order from a background worker, through a bounded
+    /// queue. When the queue is full, the pipeline will wait for a free slot.
+    async fn enqueue(&mut self, request: WebhookRequest) {
+        let queue = self.queue.get_or_insert_with(|| {
+            let (sender, mut receiver) = channel::<WebhookRequest>(QUEUE_SIZE);
+            let delivery = self.delivery.clone();
+
+            self.worker = Some(tokio::spawn(async move {
+                while let Some(request) = receiver.recv().await {
+                    if !delivery.send(&request).await {
+                        warn!(
+                            target: LOG_TARGET,
+                            "Failed to send webhook event {} to {}",
+                            color::id(&request.type_of),
+                            color::url(&delivery.url),
+                        );
+                    }
+                }
+            }));
+
+            sender
+        });
+
+        let _ = queue.send(request).await;
+    }
+
+    /// Close the queue and wait for all pending requests to be sent,
+    /// otherwise they will be dropped when the process exits!
+    async fn flush(&mut self) {
+        self.queue = None;
+
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.await;
         }
     }
 }
@@ -74,59 +207,51 @@ impl Subscriber for WebhooksSubscriber {
         event: &Event<'a>,
         _workspace: &Workspace,
     ) -> Result<EventFlow, MoonError> {
-        if !self.enabled {
-            return Ok(EventFlow::Continue);
-        }
+        let type_of = event.get_type();
 
-        let payload = WebhookPayload {
-            created_at: now_timestamp(),
-            environment: self.environment.clone(),
-            event,
-            type_of: event.get_type(),
-            uuid: self.uuid.clone(),
-        };
-
-        trace!(
-            target: LOG_TARGET,
-            "Posting event {} to webhook endpoint",
-            color::id(&payload.type_of),
-        );
-
-        let body = serde_json::to_string(&payload).unwrap();
-
-        // For the first event, we want to ensure that the webhook URL is valid
-        // by sending the request and checking for a failure. If failed,
-        // we will disable subsequent requests from being called.
-        if matches!(event, Event::PipelineStarted { .. }) {
-            let response = notify_webhook(self.url.to_owned(), body).await;
-
-            if response.is_err() || !response.unwrap().status().is_success() {
-                self.enabled = false;
-
-                error!(
-                    target: LOG_TARGET,
-                    "Failed to send webhook event to {}. Subsequent webhook requests will be disabled.",
-                    color::url(&self.url),
-                );
-            }
+        if self.enabled && is_event_allowed(&self.events, &type_of) {
+            let payload = WebhookPayload {
+                created_at: now_timestamp(),
+                environment: self.environment.clone(),
+                event,
+                type_of,
+                uuid: self.uuid.clone(),
+            };
 
-            // For every other event, we will make the request and ignore the result.
-            // We will also avoid awaiting the request to not slow down the overall runner.
-        } else {
-            let url = self.url.to_owned();
+            trace!(
+                target: LOG_TARGET,
+                "Posting event {} to webhook endpoint",
+                color::id(&payload.type_of),
+            );
 
-            self.requests.push(tokio::spawn(async {
-                let _ = notify_webhook(url, body).await;
-            }));
+            let request = WebhookRequest {
+                body: serde_json::to_string(&payload).unwrap(),
+                type_of: payload.type_of,
+            };
+
+            // For the first event, we want to ensure that the webhook URL is valid
+            // by sending the request and checking for a failure. If failed,
+            // we will disable subsequent requests from being called.
+            if matches!(event, Event::PipelineStarted { .. }) {
+                if !self.delivery.send(&request).await {
+                    self.enabled = false;
+
+                    error!(
+                        target: LOG_TARGET,
+                        "Failed to send webhook event to {}. Subsequent webhook requests will be disabled.",
+                        color::url(&self.delivery.url),
+                    );
+                }
+
+                // For every other event, we will queue the request and send it in the
+                // background, to avoid slowing down the overall runner.
+            } else {
+                self.enqueue(request).await;
+            }
         }
 
-        // For the last event, we want to ensure that all webhook requests have
-        // actually sent, otherwise, when the program exists all of these requests
-        // will be dropped!
         if event.is_end() {
-            for future in self.requests.drain(0..) {
-                let _ = future.await;
-            }
+            self.flush().await;
         }
 
         Ok(EventFlow::Continue)
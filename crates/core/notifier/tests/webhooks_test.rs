@@ -0,0 +1,38 @@
+use moon_notifier::{is_event_allowed, sign_payload};
+
+mod event_filters {
+    use super::*;
+
+    #[test]
+    fn allows_all_when_empty() {
+        assert!(is_event_allowed(&[], "pipeline.started"));
+        assert!(is_event_allowed(&[], "target.ran"));
+    }
+
+    #[test]
+    fn only_allows_listed_events() {
+        let events = vec!["pipeline.finished".to_owned(), "target.ran".to_owned()];
+
+        assert!(is_event_allowed(&events, "pipeline.finished"));
+        assert!(is_event_allowed(&events, "target.ran"));
+        assert!(!is_event_allowed(&events, "pipeline.started"));
+        assert!(!is_event_allowed(&events, "target.running"));
+    }
+}
+
+mod signing {
+    use super::*;
+
+    #[test]
+    fn signs_with_hmac_sha256() {
+        assert_eq!(
+            sign_payload("key", "The quick brown fox jumps over the lazy dog"),
+            "sha256=f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+        );
+    }
+
+    #[test]
+    fn changes_with_secret() {
+        assert_ne!(sign_payload("a", "{}"), sign_payload("b", "{}"));
+    }
+}
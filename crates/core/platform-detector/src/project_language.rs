@@ -5,7 +5,7 @@ use moon_go_lang::{G, GOENV, GOMOD, GVM};
 use moon_lang::{is_using_dependency_manager, is_using_version_manager};
 use moon_node_lang::{NODENV, NPM, NVM, PNPM, YARN};
 use moon_php_lang::{COMPOSER, PHPBREW, PHPENV};
-use moon_python_lang::{PIP, PIPENV, PYENV};
+use moon_python_lang::{PIP, PIPENV, POETRY, PYENV, UV};
 use moon_ruby_lang::{BUNDLER, RBENV, RVM};
 use moon_rust_lang::{CARGO, RUSTUP, RUSTUP_LEGACY};
 use std::path::Path;
@@ -31,6 +31,8 @@ pub fn detect_project_language(root: &Path) -> LanguageType {
     // Python
     if is_using_dependency_manager(root, &PIP, true)
         || is_using_dependency_manager(root, &PIPENV, true)
+        || is_using_dependency_manager(root, &POETRY, true)
+        || is_using_dependency_manager(root, &UV, true)
         || is_using_version_manager(root, &PYENV)
     {
         return LanguageType::Python;
@@ -2,8 +2,22 @@ use moon_config::{LanguageType, PlatformType, ToolchainConfig};
 use moon_utils::regex::{self, UNIX_SYSTEM_COMMAND, WINDOWS_SYSTEM_COMMAND};
 use once_cell::sync::Lazy;
 
+static BUN_COMMANDS: Lazy<regex::Regex> =
+    Lazy::new(|| regex::create_regex("^(bun|bunx)$").unwrap());
+
 static DENO_COMMANDS: Lazy<regex::Regex> = Lazy::new(|| regex::create_regex("^(deno)$").unwrap());
 
+static GO_COMMANDS: Lazy<regex::Regex> = Lazy::new(|| regex::create_regex("^(go|gofmt)$").unwrap());
+
+static PHP_COMMANDS: Lazy<regex::Regex> =
+    Lazy::new(|| regex::create_regex("^(php|composer)$").unwrap());
+
+static PYTHON_COMMANDS: Lazy<regex::Regex> =
+    Lazy::new(|| regex::create_regex("^(python|python3|pip|pip3|poetry|uv)$").unwrap());
+
+static RUBY_COMMANDS: Lazy<regex::Regex> =
+    Lazy::new(|| regex::create_regex("^(ruby|bundle|gem|rake)$").unwrap());
+
 static RUST_COMMANDS: Lazy<regex::Regex> =
     Lazy::new(|| regex::create_regex("^(rust-|rustc|rustdoc|rustfmt|rustup|cargo)").unwrap());
 
@@ -16,8 +30,13 @@ fn use_platform_if_enabled(
     toolchain_config: &ToolchainConfig,
 ) -> PlatformType {
     match platform {
+        PlatformType::Bun if toolchain_config.bun.is_some() => return platform,
         PlatformType::Deno if toolchain_config.deno.is_some() => return platform,
+        PlatformType::Go if toolchain_config.go.is_some() => return platform,
         PlatformType::Node if toolchain_config.node.is_some() => return platform,
+        PlatformType::Php if toolchain_config.php.is_some() => return platform,
+        PlatformType::Python if toolchain_config.python.is_some() => return platform,
+        PlatformType::Ruby if toolchain_config.ruby.is_some() => return platform,
         PlatformType::Rust if toolchain_config.rust.is_some() => return platform,
         _ => {}
     };
@@ -30,6 +49,10 @@ pub fn detect_task_platform(
     language: &LanguageType,
     toolchain_config: &ToolchainConfig,
 ) -> PlatformType {
+    if BUN_COMMANDS.is_match(command) {
+        return use_platform_if_enabled(PlatformType::Bun, toolchain_config);
+    }
+
     if DENO_COMMANDS.is_match(command) {
         return use_platform_if_enabled(PlatformType::Deno, toolchain_config);
     }
@@ -38,6 +61,22 @@ pub fn detect_task_platform(
         return use_platform_if_enabled(PlatformType::Node, toolchain_config);
     }
 
+    if GO_COMMANDS.is_match(command) {
+        return use_platform_if_enabled(PlatformType::Go, toolchain_config);
+    }
+
+    if PHP_COMMANDS.is_match(command) {
+        return use_platform_if_enabled(PlatformType::Php, toolchain_config);
+    }
+
+    if PYTHON_COMMANDS.is_match(command) {
+        return use_platform_if_enabled(PlatformType::Python, toolchain_config);
+    }
+
+    if RUBY_COMMANDS.is_match(command) {
+        return use_platform_if_enabled(PlatformType::Ruby, toolchain_config);
+    }
+
     if RUST_COMMANDS.is_match(command) {
         return use_platform_if_enabled(PlatformType::Rust, toolchain_config);
     }
@@ -53,5 +92,13 @@ pub fn detect_task_platform(
         return PlatformType::System;
     }
 
+    // JavaScript and TypeScript projects run with Bun when Node.js is not enabled
+    if matches!(platform, PlatformType::Node)
+        && toolchain_config.node.is_none()
+        && toolchain_config.bun.is_some()
+    {
+        return PlatformType::Bun;
+    }
+
     use_platform_if_enabled(platform, toolchain_config)
 }
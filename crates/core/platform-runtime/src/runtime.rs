@@ -6,8 +6,13 @@ use std::fmt::{self, Debug};
 #[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 #[serde(tag = "platform", content = "version")]
 pub enum Runtime {
+    Bun(Version),
     Deno(Version),
+    Go(Version),
     Node(Version),
+    Php(Version),
+    Python(Version),
+    Ruby(Version),
     Rust(Version),
     System,
 }
@@ -15,8 +20,13 @@ pub enum Runtime {
 impl Runtime {
     pub fn label(&self) -> String {
         match self {
+            Runtime::Bun(version) => format!("Bun {version}"),
             Runtime::Deno(version) => format!("Deno {version}"),
+            Runtime::Go(version) => format!("Go {version}"),
             Runtime::Node(version) => format!("Node.js {version}"),
+            Runtime::Php(version) => format!("PHP {version}"),
+            Runtime::Python(version) => format!("Python {version}"),
+            Runtime::Ruby(version) => format!("Ruby {version}"),
             Runtime::Rust(version) => format!("Rust {version}"),
             Runtime::System => "system".into(),
         }
@@ -24,9 +34,14 @@ impl Runtime {
 
     pub fn version(&self) -> Version {
         match self {
-            Runtime::Deno(version) | Runtime::Node(version) | Runtime::Rust(version) => {
-                version.to_owned()
-            }
+            Runtime::Bun(version)
+            | Runtime::Deno(version)
+            | Runtime::Go(version)
+            | Runtime::Node(version)
+            | Runtime::Php(version)
+            | Runtime::Python(version)
+            | Runtime::Ruby(version)
+            | Runtime::Rust(version) => version.to_owned(),
             Runtime::System => Version::new("latest"),
         }
     }
@@ -36,8 +51,13 @@ impl fmt::Display for Runtime {
     // Primarily used in action graph node labels
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            Runtime::Bun(_) => write!(f, "Bun"),
             Runtime::Deno(_) => write!(f, "Deno"),
+            Runtime::Go(_) => write!(f, "Go"),
             Runtime::Node(_) => write!(f, "Node"),
+            Runtime::Php(_) => write!(f, "PHP"),
+            Runtime::Python(_) => write!(f, "Python"),
+            Runtime::Ruby(_) => write!(f, "Ruby"),
             Runtime::Rust(_) => write!(f, "Rust"),
             Runtime::System => write!(f, "System"),
         }
@@ -47,8 +67,13 @@ impl fmt::Display for Runtime {
 impl From<&Runtime> for PlatformType {
     fn from(value: &Runtime) -> Self {
         match value {
+            Runtime::Bun(_) => PlatformType::Bun,
             Runtime::Deno(_) => PlatformType::Deno,
+            Runtime::Go(_) => PlatformType::Go,
             Runtime::Node(_) => PlatformType::Node,
+            Runtime::Php(_) => PlatformType::Php,
+            Runtime::Python(_) => PlatformType::Python,
+            Runtime::Ruby(_) => PlatformType::Ruby,
             Runtime::Rust(_) => PlatformType::Rust,
             Runtime::System => PlatformType::System,
         }
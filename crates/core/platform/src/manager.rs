@@ -1,5 +1,5 @@
 use crate::platform::Platform;
-use moon_config::PlatformType;
+use moon_config::{LanguageType, PlatformType};
 use moon_error::MoonError;
 use rustc_hash::FxHashMap;
 
@@ -26,6 +26,20 @@ impl PlatformManager {
             .ok_or_else(|| MoonError::UnsupportedPlatform(type_of.to_string()))
     }
 
+    /// Return the platform for the provided language. JavaScript and TypeScript
+    /// are handled by Node.js, but will fallback to Bun when Node.js is not enabled.
+    pub fn get_for_language(&self, language: &LanguageType) -> Result<&BoxedPlatform, MoonError> {
+        let type_of: PlatformType = language.clone().into();
+
+        if matches!(type_of, PlatformType::Node) && !self.cache.contains_key(&type_of) {
+            if let Some(bun) = self.cache.get(&PlatformType::Bun) {
+                return Ok(bun);
+            }
+        }
+
+        self.get(type_of)
+    }
+
     pub fn get_mut<T: Into<PlatformType>>(
         &mut self,
         type_of: T,
@@ -25,6 +25,12 @@ pub trait Platform: Debug + Send + Sync {
     /// Return a runtime with an appropriate version based on the provided configs.
     fn get_runtime_from_config(&self, project_config: Option<&ProjectConfig>) -> Runtime;
 
+    /// Return a runtime with an appropriate version for the provided project.
+    /// Defaults to the project's config, but platforms may also inspect its files.
+    fn get_runtime_from_project(&self, project: &Project) -> Runtime {
+        self.get_runtime_from_config(Some(&project.config))
+    }
+
     /// Return true if the current platform is for the provided project or runtime.
     fn matches(&self, platform: &PlatformType, runtime: Option<&Runtime>) -> bool;
 
@@ -117,7 +117,7 @@ impl<'ws> ProjectGraphBuilder<'ws> {
             }
         }
 
-        if let Ok(platform) = self.workspace.platforms.get(project.language.clone()) {
+        if let Ok(platform) = self.workspace.platforms.get_for_language(&project.language) {
             // Inherit implicit dependencies
             for dep_config in
                 platform.load_project_implicit_dependencies(&project, &self.aliases)?
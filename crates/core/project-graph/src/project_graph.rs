@@ -2,15 +2,19 @@ use moon_common::Id;
 use moon_config::{ProjectsAliasesMap, ProjectsSourcesMap};
 use moon_logger::debug;
 use moon_project::{Project, ProjectError};
-use moon_query::{Criteria, Queryable};
+use moon_query::{
+    ComparisonOperator, Condition, Criteria, Field, GraphDepth, QueryError, Queryable,
+};
+use moon_task::{Task, TouchedFilePaths};
 use moon_utils::{get_workspace_root, path};
 use petgraph::dot::{Config, Dot};
 use petgraph::graph::{DiGraph, NodeIndex};
 use petgraph::visit::EdgeRef;
 use petgraph::Direction;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use serde::{Deserialize, Serialize};
 use starbase_styles::color;
+use starbase_utils::glob::GlobSet;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
 
@@ -64,10 +68,23 @@ impl ProjectGraph {
 
     /// Return all projects that match the query criteria.
     pub fn query<Q: AsRef<Criteria>>(&self, query: Q) -> Result<Vec<&Project>, ProjectError> {
+        self.query_with_touched_files(query, None)
+    }
+
+    /// Return all projects that match the query criteria, using the
+    /// touched files to determine which projects are affected.
+    pub fn query_with_touched_files<Q: AsRef<Criteria>>(
+        &self,
+        query: Q,
+        touched_files: Option<&TouchedFilePaths>,
+    ) -> Result<Vec<&Project>, ProjectError> {
         let query = query.as_ref();
         let query_input = query.input.as_ref().unwrap();
 
-        {
+        // Affected results depend on the touched files, so can't be cached by input
+        let cacheable = !query.requires_touched_files();
+
+        if cacheable {
             if let Some(project_ids) = self.query_cache.read().unwrap().get(query_input) {
                 return Ok(project_ids.iter().map(|id| self.get(id).unwrap()).collect());
             }
@@ -79,11 +96,12 @@ impl ProjectGraph {
             color::shell(query_input)
         );
 
+        let query = self.resolve_graph_criteria(query, touched_files)?;
         let mut filtered_projects = vec![];
         let mut project_ids = vec![];
 
         for project in self.get_all()? {
-            if project.matches_criteria(query)? {
+            if project.matches_criteria(&query)? {
                 debug!(
                     target: LOG_TARGET,
                     "{} did match the criteria",
@@ -102,7 +120,7 @@ impl ProjectGraph {
             }
         }
 
-        {
+        if cacheable {
             self.query_cache
                 .write()
                 .unwrap()
@@ -112,6 +130,41 @@ impl ProjectGraph {
         Ok(filtered_projects)
     }
 
+    /// Return all tasks that match the query criteria, grouped by their project.
+    /// Task fields are matched against each task individually.
+    pub fn query_tasks<Q: AsRef<Criteria>>(
+        &self,
+        query: Q,
+        touched_files: Option<&TouchedFilePaths>,
+    ) -> Result<Vec<(&Project, Vec<&Task>)>, ProjectError> {
+        let query = query.as_ref();
+
+        debug!(
+            target: LOG_TARGET,
+            "Filtering tasks using query {}",
+            color::shell(query.input.as_ref().unwrap())
+        );
+
+        let query = self.resolve_graph_criteria(query, touched_files)?;
+        let mut filtered_tasks = vec![];
+
+        for project in self.get_all()? {
+            let mut tasks = vec![];
+
+            for task in project.tasks.values() {
+                if project.matches_task_criteria(task, &query)? {
+                    tasks.push(task);
+                }
+            }
+
+            if !tasks.is_empty() {
+                filtered_tasks.push((project, tasks));
+            }
+        }
+
+        Ok(filtered_tasks)
+    }
+
     /// Return a project with the associated ID. If the project does not
     /// exist or has been misconfigured, return an error.
     pub fn get(&self, alias_or_id: &str) -> Result<&Project, ProjectError> {
@@ -202,6 +255,126 @@ impl ProjectGraph {
         Ok(deps)
     }
 
+    /// Replace graph related conditions (affected, dependencies, dependents)
+    /// with a condition that matches against the resolved project IDs.
+    fn resolve_graph_criteria(
+        &self,
+        criteria: &Criteria,
+        touched_files: Option<&TouchedFilePaths>,
+    ) -> Result<Criteria, ProjectError> {
+        let mut conditions = vec![];
+
+        for condition in &criteria.conditions {
+            conditions.push(match condition {
+                Condition::Field { field, op } => match field {
+                    Field::Affected { affected, depth } => {
+                        let touched_files = touched_files.ok_or(QueryError::MissingTouchedFiles)?;
+                        let roots = self
+                            .get_all()?
+                            .into_iter()
+                            .filter(|project| project.is_affected(touched_files))
+                            .map(|project| project.id.clone())
+                            .collect::<Vec<_>>();
+
+                        // Dependents of an affected project are also affected
+                        let mut ids = self.traverse(&roots, Direction::Incoming, *depth);
+                        ids.extend(roots);
+
+                        // `affected=false` is the same as `affected!=true`
+                        let is_match = *affected == matches!(op, ComparisonOperator::Equal);
+
+                        create_project_condition(ids, is_match)
+                    }
+                    Field::DependencyOf { ids, depth } => create_project_condition(
+                        self.traverse(
+                            &self.find_query_roots(ids, op)?,
+                            Direction::Outgoing,
+                            *depth,
+                        ),
+                        is_positive_operator(op),
+                    ),
+                    Field::DependentOf { ids, depth } => create_project_condition(
+                        self.traverse(
+                            &self.find_query_roots(ids, op)?,
+                            Direction::Incoming,
+                            *depth,
+                        ),
+                        is_positive_operator(op),
+                    ),
+                    _ => condition.clone(),
+                },
+                Condition::Criteria { criteria } => Condition::Criteria {
+                    criteria: self.resolve_graph_criteria(criteria, touched_files)?,
+                },
+                Condition::Not { criteria } => Condition::Not {
+                    criteria: self.resolve_graph_criteria(criteria, touched_files)?,
+                },
+            });
+        }
+
+        Ok(Criteria {
+            op: criteria.op.clone(),
+            conditions,
+            input: criteria.input.clone(),
+        })
+    }
+
+    /// Find the projects to start a graph traversal from. Equality operators
+    /// match against project IDs and aliases, while like operators match globs.
+    fn find_query_roots(
+        &self,
+        values: &[String],
+        op: &ComparisonOperator,
+    ) -> Result<Vec<Id>, ProjectError> {
+        if matches!(op, ComparisonOperator::Like | ComparisonOperator::NotLike) {
+            let globset = GlobSet::new(values).map_err(QueryError::Glob)?;
+
+            return Ok(self
+                .get_all()?
+                .into_iter()
+                .filter(|project| globset.is_match(project.id.as_str()))
+                .map(|project| project.id.clone())
+                .collect());
+        }
+
+        values
+            .iter()
+            .map(|value| self.get(value).map(|project| project.id.clone()))
+            .collect()
+    }
+
+    /// Walk the graph in the provided direction, starting from the root projects,
+    /// and return the IDs of all projects found within the depth. The root projects
+    /// are not included, unless they are found through a cycle.
+    fn traverse(&self, roots: &[Id], direction: Direction, depth: GraphDepth) -> Vec<Id> {
+        let mut found = FxHashSet::default();
+        let mut queue = roots
+            .iter()
+            .filter_map(|id| self.indices.get(id).copied())
+            .collect::<Vec<_>>();
+        let mut level = 0;
+
+        while !queue.is_empty() && depth.map_or(true, |max| level < max) {
+            let mut next_queue = vec![];
+
+            for index in queue {
+                for neighbor in self.graph.neighbors_directed(index, direction) {
+                    if found.insert(neighbor) {
+                        next_queue.push(neighbor);
+                    }
+                }
+            }
+
+            queue = next_queue;
+            level += 1;
+        }
+
+        found
+            .into_iter()
+            .map(|index| self.graph.node_weight(index).unwrap().id.clone())
+            .collect()
+    }
+
     /// Get a labelled representation of the dep graph (which can be serialized easily).
     pub fn labeled_graph(&self) -> DiGraph<String, ()> {
         let graph = self.graph.clone();
@@ -237,3 +410,18 @@ impl ProjectGraph {
         format!("{dot:?}")
     }
 }
+
+fn is_positive_operator(op: &ComparisonOperator) -> bool {
+    matches!(op, ComparisonOperator::Equal | ComparisonOperator::Like)
+}
+
+fn create_project_condition(ids: Vec<Id>, is_match: bool) -> Condition {
+    Condition::Field {
+        field: Field::Project(ids.into_iter().map(|id| id.to_string()).collect()),
+        op: if is_match {
+            ComparisonOperator::Equal
+        } else {
+            ComparisonOperator::NotEqual
+        },
+    }
+}
@@ -11,10 +11,10 @@ use moon_test_utils::{
     assert_snapshot, create_sandbox_with_config, get_project_graph_aliases_fixture_configs, Sandbox,
 };
 use moon_utils::string_vec;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use std::fs::OpenOptions;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 pub fn append_file<P: AsRef<Path>>(path: P, data: &str) {
     let mut file = OpenOptions::new()
@@ -828,6 +828,112 @@ mod query {
         ids
     }
 
+    #[tokio::test]
+    async fn by_affected() {
+        let (graph, _sandbox) = get_queries_graph().await;
+        let touched_files = FxHashSet::from_iter([PathBuf::from("d/file.go")]);
+
+        let projects = graph
+            .query_with_touched_files(build_query("affected=true").unwrap(), Some(&touched_files))
+            .unwrap();
+
+        assert_eq!(get_ids(&projects), vec!["d"]);
+
+        let projects = graph
+            .query_with_touched_files(
+                build_query("affected:*=true").unwrap(),
+                Some(&touched_files),
+            )
+            .unwrap();
+
+        assert_eq!(get_ids(&projects), vec!["a", "b", "d"]);
+
+        let projects = graph
+            .query_with_touched_files(
+                build_query("affected:1=false").unwrap(),
+                Some(&touched_files),
+            )
+            .unwrap();
+
+        assert_eq!(get_ids(&projects), vec!["a", "c"]);
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "MissingTouchedFiles")]
+    async fn by_affected_requires_touched_files() {
+        let (graph, _sandbox) = get_queries_graph().await;
+
+        graph.query(build_query("affected=true").unwrap()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn by_dependency_of() {
+        let (graph, _sandbox) = get_queries_graph().await;
+
+        let projects = graph.query(build_query("dependencyOf=a").unwrap()).unwrap();
+
+        assert_eq!(get_ids(&projects), vec!["b"]);
+
+        let projects = graph
+            .query(build_query("dependencyOf:*=a").unwrap())
+            .unwrap();
+
+        assert_eq!(get_ids(&projects), vec!["b", "d"]);
+    }
+
+    #[tokio::test]
+    async fn by_dependent_of() {
+        let (graph, _sandbox) = get_queries_graph().await;
+
+        let projects = graph.query(build_query("dependentOf=d").unwrap()).unwrap();
+
+        assert_eq!(get_ids(&projects), vec!["b"]);
+
+        let projects = graph
+            .query(build_query("dependentOf:*=d").unwrap())
+            .unwrap();
+
+        assert_eq!(get_ids(&projects), vec!["a", "b"]);
+
+        let projects = graph
+            .query(build_query("dependentOf:*!=d").unwrap())
+            .unwrap();
+
+        assert_eq!(get_ids(&projects), vec!["c", "d"]);
+    }
+
+    #[tokio::test]
+    async fn by_dependent_of_with_like() {
+        let (graph, _sandbox) = get_queries_graph().await;
+
+        let projects = graph
+            .query(build_query("dependentOf~{b,d}").unwrap())
+            .unwrap();
+
+        assert_eq!(get_ids(&projects), vec!["a", "b"]);
+    }
+
+    #[tokio::test]
+    async fn by_dependent_of_with_other_fields() {
+        let (graph, _sandbox) = get_queries_graph().await;
+
+        let projects = graph
+            .query(build_query("dependentOf:*=d && taskType=build").unwrap())
+            .unwrap();
+
+        assert_eq!(get_ids(&projects), vec!["a"]);
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "UnconfiguredID(\"unknown\")")]
+    async fn by_dependent_of_errors_for_unknown_project() {
+        let (graph, _sandbox) = get_queries_graph().await;
+
+        graph
+            .query(build_query("dependentOf=unknown").unwrap())
+            .unwrap();
+    }
+
     #[tokio::test]
     async fn by_language() {
         let (graph, _sandbox) = get_queries_graph().await;
@@ -881,6 +987,20 @@ mod query {
         assert_eq!(get_ids(&projects), vec!["b", "c"]);
     }
 
+    #[tokio::test]
+    async fn by_tag_negated() {
+        let (graph, _sandbox) = get_queries_graph().await;
+
+        // Projects with any of the tags are excluded
+        let projects = graph.query(build_query("tag!=three").unwrap()).unwrap();
+
+        assert_eq!(get_ids(&projects), vec!["a", "d"]);
+
+        let projects = graph.query(build_query("tag!~t*").unwrap()).unwrap();
+
+        assert_eq!(get_ids(&projects), vec!["a", "d"]);
+    }
+
     #[tokio::test]
     async fn by_task() {
         let (graph, _sandbox) = get_queries_graph().await;
@@ -892,6 +1012,131 @@ mod query {
         assert_eq!(get_ids(&projects), vec!["a", "c", "d"]);
     }
 
+    #[tokio::test]
+    async fn by_task_command() {
+        let (graph, _sandbox) = get_queries_graph().await;
+
+        let projects = graph
+            .query(build_query("taskCommand~{dev,lint}").unwrap())
+            .unwrap();
+
+        assert_eq!(get_ids(&projects), vec!["a", "b"]);
+    }
+
+    #[tokio::test]
+    async fn by_task_dep() {
+        let (graph, _sandbox) = get_queries_graph().await;
+
+        let projects = graph.query(build_query("taskDep=b:lint").unwrap()).unwrap();
+
+        assert_eq!(get_ids(&projects), vec!["a"]);
+    }
+
+    #[tokio::test]
+    async fn by_task_dep_negated() {
+        let (graph, _sandbox) = get_queries_graph().await;
+
+        let projects = graph
+            .query(build_query("task=build && taskDep!=b:lint").unwrap())
+            .unwrap();
+
+        // Project `a` still matches through its `dev` task
+        assert_eq!(get_ids(&projects), vec!["a", "d"]);
+
+        let projects = graph
+            .query(build_query("taskOutput!=dist").unwrap())
+            .unwrap();
+
+        assert_eq!(get_ids(&projects), vec!["a", "b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn by_task_env() {
+        let (graph, _sandbox) = get_queries_graph().await;
+
+        let projects = graph
+            .query(build_query("taskEnv~PYTHON*").unwrap())
+            .unwrap();
+
+        assert_eq!(get_ids(&projects), vec!["c"]);
+    }
+
+    #[tokio::test]
+    async fn by_task_input_and_output() {
+        let (graph, _sandbox) = get_queries_graph().await;
+
+        let projects = graph
+            .query(build_query("taskInput~tests/**").unwrap())
+            .unwrap();
+
+        assert_eq!(get_ids(&projects), vec!["c"]);
+
+        let projects = graph
+            .query(build_query("taskOutput=dist").unwrap())
+            .unwrap();
+
+        assert_eq!(get_ids(&projects), vec!["a", "d"]);
+    }
+
+    #[tokio::test]
+    async fn by_task_option() {
+        let (graph, _sandbox) = get_queries_graph().await;
+
+        let projects = graph
+            .query(build_query("taskOption.cache=false").unwrap())
+            .unwrap();
+
+        assert_eq!(get_ids(&projects), vec!["a", "c"]);
+
+        let projects = graph
+            .query(build_query("taskOption.runInCI=false && taskOption.cache=false").unwrap())
+            .unwrap();
+
+        assert_eq!(get_ids(&projects), vec!["a"]);
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "UnknownTaskOption(\"unknown\")")]
+    async fn by_task_option_errors_for_unknown() {
+        let (graph, _sandbox) = get_queries_graph().await;
+
+        graph
+            .query(build_query("taskOption.unknown=true").unwrap())
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn tasks_by_task_option() {
+        let (graph, _sandbox) = get_queries_graph().await;
+
+        let mut tasks = graph
+            .query_tasks(build_query("taskOption.cache=false").unwrap(), None)
+            .unwrap()
+            .into_iter()
+            .flat_map(|(_, tasks)| tasks.into_iter().map(|task| task.target.id.clone()))
+            .collect::<Vec<_>>();
+        tasks.sort();
+
+        assert_eq!(tasks, vec!["a:dev", "c:test"]);
+    }
+
+    #[tokio::test]
+    async fn tasks_with_project_fields() {
+        let (graph, _sandbox) = get_queries_graph().await;
+
+        let tasks = graph
+            .query_tasks(
+                build_query("dependentOf=b && (taskType=build || task=dev)").unwrap(),
+                None,
+            )
+            .unwrap()
+            .into_iter()
+            .flat_map(|(_, tasks)| tasks.into_iter().map(|task| task.target.id.clone()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(tasks, vec!["a:build", "a:dev"]);
+    }
+
     #[tokio::test]
     async fn by_task_platform() {
         let (graph, _sandbox) = get_queries_graph().await;
@@ -940,6 +1185,72 @@ mod query {
         assert_eq!(get_ids(&projects), vec!["a", "b"]);
     }
 
+    #[tokio::test]
+    async fn by_metadata() {
+        let (graph, _sandbox) = get_queries_graph().await;
+
+        let projects = graph.query(build_query("owner~*-team").unwrap()).unwrap();
+
+        assert_eq!(get_ids(&projects), vec!["a", "b"]);
+
+        let projects = graph
+            .query(build_query("maintainers=carol").unwrap())
+            .unwrap();
+
+        assert_eq!(get_ids(&projects), vec!["b"]);
+
+        // Projects maintained by any of the values are excluded
+        let projects = graph
+            .query(build_query("maintainers!=alice").unwrap())
+            .unwrap();
+
+        assert_eq!(get_ids(&projects), vec!["b"]);
+
+        let projects = graph
+            .query(build_query("maintainers!=[bob,carol]").unwrap())
+            .unwrap();
+
+        assert!(projects.is_empty());
+
+        let projects = graph.query(build_query("channel=#web").unwrap()).unwrap();
+
+        assert_eq!(get_ids(&projects), vec!["a"]);
+    }
+
+    #[tokio::test]
+    async fn with_mixed_conditions() {
+        let (graph, _sandbox) = get_queries_graph().await;
+
+        let projects = graph
+            .query(build_query("language=go || projectType=library && tag=two").unwrap())
+            .unwrap();
+
+        assert_eq!(get_ids(&projects), vec!["b", "d"]);
+
+        let projects = graph
+            .query(build_query("tag=one && language=python || tag=four").unwrap())
+            .unwrap();
+
+        assert_eq!(get_ids(&projects), vec!["c", "d"]);
+    }
+
+    #[tokio::test]
+    async fn with_not_conditions() {
+        let (graph, _sandbox) = get_queries_graph().await;
+
+        let projects = graph
+            .query(build_query("!(language=javascript || language=typescript)").unwrap())
+            .unwrap();
+
+        assert_eq!(get_ids(&projects), vec!["c", "d"]);
+
+        let projects = graph
+            .query(build_query("projectType=library AND NOT (dependentOf=d)").unwrap())
+            .unwrap();
+
+        assert_eq!(get_ids(&projects), vec!["d"]);
+    }
+
     #[tokio::test]
     async fn with_nested_conditions() {
         let (graph, _sandbox) = get_queries_graph().await;
@@ -2,7 +2,7 @@ use crate::errors::ProjectError;
 use moon_common::{cacheable, consts, Id};
 use moon_config::{
     DependencyConfig, InheritedTasksConfig, InheritedTasksManager, LanguageType, ProjectConfig,
-    ProjectDependsOn, ProjectType,
+    ProjectDependsOn, ProjectMetadataConfig, ProjectType,
 };
 use moon_file_group::{FileGroup, FileGroupError};
 use moon_logger::{debug, trace, Logable};
@@ -379,9 +379,22 @@ impl Project {
     }
 }
 
-impl Queryable for Project {
-    /// Return true if this project matches the given query criteria.
-    fn matches_criteria(&self, query: &Criteria) -> Result<bool, QueryError> {
+impl Project {
+    /// Return true if the task within this project matches the given query criteria.
+    /// Task fields are matched against the provided task only, instead of all tasks.
+    pub fn matches_task_criteria(&self, task: &Task, query: &Criteria) -> Result<bool, QueryError> {
+        self.matches_criteria_for_tasks(query, &[task])
+    }
+
+    fn get_metadata(&self) -> Option<&ProjectMetadataConfig> {
+        self.config.project.as_ref()
+    }
+
+    fn matches_criteria_for_tasks(
+        &self,
+        query: &Criteria,
+        tasks: &[&Task],
+    ) -> Result<bool, QueryError> {
         let match_all = matches!(query.op, LogicalOperator::And);
         let mut matched_any = false;
 
@@ -389,7 +402,30 @@ impl Queryable for Project {
             let matches = match condition {
                 Condition::Field { field, .. } => {
                     let result = match field {
+                        // Graph fields are resolved to project IDs by the project graph
+                        Field::Affected { .. }
+                        | Field::DependencyOf { .. }
+                        | Field::DependentOf { .. } => Ok(false),
+                        Field::Channel(channels) => match self.get_metadata() {
+                            Some(ProjectMetadataConfig {
+                                channel: Some(channel),
+                                ..
+                            }) => condition.matches(channels, channel),
+                            _ => Ok(false),
+                        },
                         Field::Language(langs) => condition.matches_enum(langs, &self.language),
+                        Field::Maintainers(maintainers) => match self.get_metadata() {
+                            Some(metadata) => {
+                                condition.matches_list(maintainers, &metadata.maintainers)
+                            }
+                            None => Ok(false),
+                        },
+                        Field::Owner(owners) => match self.get_metadata() {
+                            Some(ProjectMetadataConfig {
+                                owner: Some(owner), ..
+                            }) => condition.matches(owners, owner),
+                            _ => Ok(false),
+                        },
                         Field::Project(ids) => condition.matches(ids, &self.id),
                         Field::ProjectAlias(aliases) => {
                             if let Some(alias) = &self.alias {
@@ -409,25 +445,26 @@ impl Queryable for Project {
                                 .map(|t| t.to_string())
                                 .collect::<Vec<_>>(),
                         ),
-                        Field::Task(ids) => Ok(self
-                            .tasks
-                            .values()
-                            .any(|task| condition.matches(ids, &task.id).unwrap_or_default())),
-                        Field::TaskPlatform(platforms) => Ok(self.tasks.values().any(|task| {
-                            condition
-                                .matches_enum(platforms, &task.platform)
-                                .unwrap_or_default()
-                        })),
-                        Field::TaskType(types) => Ok(self.tasks.values().any(|task| {
-                            condition
-                                .matches_enum(types, &task.type_of)
-                                .unwrap_or_default()
-                        })),
+                        _ => {
+                            let mut matches = false;
+
+                            for task in tasks {
+                                if task_matches_condition(task, condition, field)? {
+                                    matches = true;
+                                    break;
+                                }
+                            }
+
+                            Ok(matches)
+                        }
                     };
 
                     result?
                 }
-                Condition::Criteria { criteria } => self.matches_criteria(criteria)?,
+                Condition::Criteria { criteria } => {
+                    self.matches_criteria_for_tasks(criteria, tasks)?
+                }
+                Condition::Not { criteria } => !self.matches_criteria_for_tasks(criteria, tasks)?,
             };
 
             if matches {
@@ -451,3 +488,46 @@ impl Queryable for Project {
         Ok(true)
     }
 }
+
+fn task_matches_condition(
+    task: &Task,
+    condition: &Condition,
+    field: &Field,
+) -> Result<bool, QueryError> {
+    Ok(match field {
+        Field::Task(ids) => condition.matches(ids, &task.id)?,
+        Field::TaskCommand(commands) => condition.matches(commands, &task.command)?,
+        Field::TaskDep(targets) => condition.matches_list(
+            targets,
+            &task
+                .deps
+                .iter()
+                .map(|dep| dep.id.clone())
+                .collect::<Vec<_>>(),
+        )?,
+        Field::TaskEnv(keys) => {
+            condition.matches_list(keys, &task.env.keys().cloned().collect::<Vec<_>>())?
+        }
+        Field::TaskInput(inputs) => condition.matches_list(inputs, &task.inputs)?,
+        Field::TaskOption { option, values } => {
+            let value = task
+                .options
+                .get_value(option)
+                .ok_or_else(|| QueryError::UnknownTaskOption(option.to_owned()))?;
+
+            condition.matches(values, &value)?
+        }
+        Field::TaskOutput(outputs) => condition.matches_list(outputs, &task.outputs)?,
+        Field::TaskPlatform(platforms) => condition.matches_enum(platforms, &task.platform)?,
+        Field::TaskType(types) => condition.matches_enum(types, &task.type_of)?,
+        _ => false,
+    })
+}
+
+impl Queryable for Project {
+    /// Return true if this project matches the given query criteria.
+    /// Task fields match if any task within the project matches.
+    fn matches_criteria(&self, query: &Criteria) -> Result<bool, QueryError> {
+        self.matches_criteria_for_tasks(query, &self.tasks.values().collect::<Vec<_>>())
+    }
+}
@@ -10,7 +10,7 @@ use moon_error::MoonError;
 use moon_hasher::HashSet;
 use moon_logger::{debug, warn};
 use moon_platform_runtime::Runtime;
-use moon_process::{args, output_to_error, output_to_string, Command, Output};
+use moon_process::{args, output_to_error, output_to_string, Command, Output, ProcessError};
 use moon_project::Project;
 use moon_target::{TargetError, TargetScope};
 use moon_task::{Task, TaskError};
@@ -35,6 +35,9 @@ pub enum HydrateFrom {
 pub struct Runner<'a> {
     pub cache: RunTargetState,
 
+    /// Attempts of a process that ran and failed, as they are not returned with the error.
+    pub failed_attempts: Vec<Attempt>,
+
     emitter: &'a Emitter,
 
     project: &'a Project,
@@ -58,6 +61,7 @@ impl<'a> Runner<'a> {
         Ok(Runner {
             cache: workspace.cache.cache_run_target_state(&task.target)?,
             emitter,
+            failed_attempts: vec![],
             project,
             stderr: Term::buffered_stderr(),
             stdout: Term::buffered_stdout(),
@@ -563,6 +567,21 @@ impl<'a> Runner<'a> {
             }
         });
 
+        // Timeouts are validated when the config is loaded
+        if let Some(timeout) = self
+            .task
+            .options
+            .timeout
+            .as_ref()
+            .and_then(|timeout| time::parse_duration(timeout).ok())
+        {
+            command.set_timeout(timeout);
+
+            // Isolate the process so that anything it spawns is also terminated on timeout,
+            // but keep processes that interact with the terminal in its foreground group
+            command.set_process_group(!is_interactive && !is_persistent);
+        }
+
         loop {
             let mut attempt = Attempt::new(attempt_index);
 
@@ -610,6 +629,13 @@ impl<'a> Runner<'a> {
                     } else if attempt_index >= attempt_total {
                         interval_handle.abort();
 
+                        // Persist the logs of the failed run, so that reporters can use them
+                        self.cache.save_output_logs(
+                            output_to_string(&out.stdout),
+                            output_to_string(&out.stderr),
+                        )?;
+                        self.failed_attempts = attempts;
+
                         return Err(RunnerError::Process(output_to_error(
                             self.task.command.clone(),
                             &out,
@@ -626,6 +652,47 @@ impl<'a> Runner<'a> {
                         );
                     }
                 }
+                // process ran longer than its timeout and was terminated
+                Err(ProcessError::TimedOut {
+                    output: out,
+                    timeout,
+                    ..
+                }) => {
+                    attempt.done(ActionStatus::TimedOut);
+
+                    if should_stream_output {
+                        self.handle_streamed_output(&attempt, attempt_total, &out)?;
+                    } else {
+                        self.handle_captured_output(&attempt, attempt_total, &out)?;
+                    }
+
+                    attempts.push(attempt);
+
+                    if attempt_index >= attempt_total {
+                        interval_handle.abort();
+
+                        self.cache.save_output_logs(
+                            output_to_string(&out.stdout),
+                            output_to_string(&out.stderr),
+                        )?;
+                        self.failed_attempts = attempts;
+
+                        return Err(RunnerError::Process(ProcessError::TimedOut {
+                            bin: self.task.command.clone(),
+                            output: out,
+                            timeout,
+                        }));
+                    } else {
+                        attempt_index += 1;
+
+                        warn!(
+                            target: LOG_TARGET,
+                            "Target {} timed out, running again with attempt {}",
+                            color::label(&self.task.target),
+                            attempt_index
+                        );
+                    }
+                }
                 // process itself failed
                 Err(error) => {
                     attempt.done(ActionStatus::Failed);
@@ -672,6 +739,11 @@ impl<'a> Runner<'a> {
             self.run_command(context, &mut command).await?
         };
 
+        self.cache.last_run_duration = attempts
+            .iter()
+            .filter_map(|attempt| attempt.duration)
+            .sum::<Duration>()
+            .as_millis();
         self.cache.last_run_time = time::now_millis();
         self.cache.save()?;
 
@@ -817,6 +889,10 @@ impl<'a> Runner<'a> {
             comments.push(time::elapsed(duration));
         }
 
+        if matches!(attempt.status, ActionStatus::TimedOut) {
+            comments.push("timed out".to_owned());
+        }
+
         if self.should_print_short_hash() && attempt.finished_at.is_some() {
             comments.push(self.get_short_hash().to_owned());
         }
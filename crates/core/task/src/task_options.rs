@@ -36,6 +36,8 @@ cacheable!(
         pub run_from_workspace_root: bool,
 
         pub shell: bool,
+
+        pub timeout: Option<String>,
     }
 );
 
@@ -57,11 +59,30 @@ impl Default for TaskOptions {
             run_in_ci: true,
             run_from_workspace_root: false,
             shell: true,
+            timeout: None,
         }
     }
 }
 
 impl TaskOptions {
+    /// Return the value of an option by its name (case-insensitive), formatted as a string.
+    /// Unset options are an empty string, while unknown options are `None`.
+    pub fn get_value(&self, name: &str) -> Option<String> {
+        let Ok(serde_json::Value::Object(options)) = serde_json::to_value(self) else {
+            return None;
+        };
+
+        let (_, value) = options
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))?;
+
+        Some(match value {
+            serde_json::Value::Null => String::new(),
+            serde_json::Value::String(value) => value.to_owned(),
+            value => value.to_string(),
+        })
+    }
+
     pub fn merge(&mut self, config: &TaskOptionsConfig) {
         if let Some(affected_files) = &config.affected_files {
             self.affected_files = Some(affected_files.to_owned());
@@ -122,6 +143,10 @@ impl TaskOptions {
         if let Some(shell) = &config.shell {
             self.shell = *shell;
         }
+
+        if let Some(timeout) = &config.timeout {
+            self.timeout = Some(timeout.to_owned());
+        }
     }
 
     pub fn from_config(config: TaskOptionsConfig, is_local: bool) -> TaskOptions {
@@ -145,6 +170,7 @@ impl TaskOptions {
             run_in_ci: config.run_in_ci.unwrap_or(!is_local),
             run_from_workspace_root: config.run_from_workspace_root.unwrap_or_default(),
             shell: config.shell.unwrap_or(true),
+            timeout: config.timeout,
         }
     }
 
@@ -198,6 +224,10 @@ impl TaskOptions {
             config.shell = Some(self.shell);
         }
 
+        if let Some(timeout) = &self.timeout {
+            config.timeout = Some(timeout.to_owned());
+        }
+
         config
     }
 }
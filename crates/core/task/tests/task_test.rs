@@ -43,6 +43,7 @@ mod from_config {
                 run_in_ci: true,
                 run_from_workspace_root: false,
                 shell: true,
+                timeout: None,
             }
         )
     }
@@ -689,3 +690,28 @@ mod is_affected {
         assert!(task.is_affected(&set).unwrap());
     }
 }
+
+mod option_values {
+    use super::*;
+
+    #[test]
+    fn returns_formatted_values() {
+        let options = TaskOptions {
+            cache: false,
+            retry_count: 3,
+            timeout: Some("10m".into()),
+            ..TaskOptions::default()
+        };
+
+        assert_eq!(options.get_value("cache"), Some("false".into()));
+        assert_eq!(options.get_value("retryCount"), Some("3".into()));
+        assert_eq!(options.get_value("timeout"), Some("10m".into()));
+        assert_eq!(options.get_value("runInCI"), Some("true".into()));
+        assert_eq!(options.get_value("envFile"), Some("".into()));
+    }
+
+    #[test]
+    fn returns_none_for_unknown() {
+        assert_eq!(TaskOptions::default().get_value("unknown"), None);
+    }
+}
@@ -0,0 +1,67 @@
+/// A single directive within a `go.mod` or `go.work` file. Directives within
+/// a block, like `require ( ... )`, are expanded into a directive per line.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct Directive {
+    pub verb: String,
+
+    pub args: Vec<String>,
+
+    /// The trailing comment of the line, if any (e.g. `indirect`).
+    pub comment: Option<String>,
+}
+
+fn parse_line(line: &str) -> (Vec<String>, Option<String>) {
+    let (line, comment) = match line.find("//") {
+        Some(index) => (&line[0..index], Some(line[index + 2..].trim().to_owned())),
+        None => (line, None),
+    };
+
+    let args = line
+        .split_whitespace()
+        .map(|arg| arg.trim_matches(|c| c == '"' || c == '`').to_owned())
+        .collect();
+
+    (args, comment)
+}
+
+/// Parse the directives of a `go.mod` or `go.work` file.
+pub fn parse_directives(content: &str) -> Vec<Directive> {
+    let mut directives = vec![];
+    let mut block_verb: Option<String> = None;
+
+    for line in content.lines() {
+        let (mut args, comment) = parse_line(line);
+
+        if args.is_empty() {
+            continue;
+        }
+
+        if let Some(verb) = &block_verb {
+            if args[0] == ")" {
+                block_verb = None;
+            } else {
+                directives.push(Directive {
+                    verb: verb.to_owned(),
+                    args,
+                    comment,
+                });
+            }
+
+            continue;
+        }
+
+        let verb = args.remove(0);
+
+        if args.len() == 1 && args[0] == "(" {
+            block_verb = Some(verb);
+        } else {
+            directives.push(Directive {
+                verb,
+                args,
+                comment,
+            });
+        }
+    }
+
+    directives
+}
@@ -0,0 +1,108 @@
+use crate::directives::parse_directives;
+use crate::GOMOD;
+use cached::proc_macro::cached;
+use moon_error::MoonError;
+use moon_lang::config_cache;
+use starbase_utils::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct GoModRequire {
+    pub module: String,
+
+    pub version: String,
+
+    pub indirect: bool,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct GoModReplace {
+    pub module: String,
+
+    pub version: Option<String>,
+
+    /// Either a module path, or a file system path to a module directory.
+    pub replacement: String,
+
+    pub replacement_version: Option<String>,
+}
+
+impl GoModReplace {
+    /// Replacements that start with `./`, `../`, or are absolute,
+    /// point to a module directory on the file system.
+    pub fn is_local(&self) -> bool {
+        self.replacement.starts_with("./")
+            || self.replacement.starts_with("../")
+            || Path::new(&self.replacement).is_absolute()
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct GoMod {
+    pub module: String,
+
+    pub go: Option<String>,
+
+    pub require: Vec<GoModRequire>,
+
+    pub replace: Vec<GoModReplace>,
+
+    pub path: PathBuf,
+}
+
+/// Parse the contents of a `go.mod` file. Only the directives
+/// required for dependency resolution are extracted.
+pub fn parse_go_mod(content: &str) -> GoMod {
+    let mut go_mod = GoMod::default();
+
+    for directive in parse_directives(content) {
+        let mut args = directive.args.into_iter();
+
+        match directive.verb.as_str() {
+            "module" => {
+                go_mod.module = args.next().unwrap_or_default();
+            }
+            "go" => {
+                go_mod.go = args.next();
+            }
+            "require" => {
+                if let (Some(module), Some(version)) = (args.next(), args.next()) {
+                    go_mod.require.push(GoModRequire {
+                        module,
+                        version,
+                        indirect: directive.comment.as_deref() == Some("indirect"),
+                    });
+                }
+            }
+            "replace" => {
+                let args = args.collect::<Vec<_>>();
+
+                let Some(index) = args.iter().position(|arg| arg == "=>") else {
+                    continue;
+                };
+
+                let (source, target) = (&args[0..index], &args[index + 1..]);
+
+                if source.is_empty() || target.is_empty() {
+                    continue;
+                }
+
+                go_mod.replace.push(GoModReplace {
+                    module: source[0].to_owned(),
+                    version: source.get(1).cloned(),
+                    replacement: target[0].to_owned(),
+                    replacement_version: target.get(1).cloned(),
+                });
+            }
+            _ => {}
+        };
+    }
+
+    go_mod
+}
+
+fn read_go_mod(path: &Path) -> Result<GoMod, MoonError> {
+    Ok(parse_go_mod(&fs::read_file(path)?))
+}
+
+config_cache!(GoMod, GOMOD.manifest, read_go_mod);
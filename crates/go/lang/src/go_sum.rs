@@ -0,0 +1,35 @@
+use cached::proc_macro::cached;
+use moon_error::MoonError;
+use moon_lang::LockfileDependencyVersions;
+use rustc_hash::FxHashMap;
+use starbase_utils::fs;
+use std::path::PathBuf;
+
+/// Parse the contents of a `go.sum` file into a map of module paths to their
+/// checksummed versions. Each version is in the format of `<version> <hash>`.
+pub fn parse_go_sum(content: &str) -> LockfileDependencyVersions {
+    let mut deps: LockfileDependencyVersions = FxHashMap::default();
+
+    for line in content.lines() {
+        let mut parts = line.split_whitespace();
+
+        if let (Some(module), Some(version), Some(hash)) =
+            (parts.next(), parts.next(), parts.next())
+        {
+            deps.entry(module.to_owned())
+                .or_default()
+                .push(format!("{version} {hash}"));
+        }
+    }
+
+    deps
+}
+
+#[cached(result)]
+pub fn load_go_sum_dependencies(path: PathBuf) -> Result<LockfileDependencyVersions, MoonError> {
+    if !path.exists() {
+        return Ok(FxHashMap::default());
+    }
+
+    Ok(parse_go_sum(&fs::read_file(path)?))
+}
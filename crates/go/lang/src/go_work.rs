@@ -0,0 +1,52 @@
+use crate::directives::parse_directives;
+use crate::GOMOD;
+use cached::proc_macro::cached;
+use moon_error::MoonError;
+use moon_lang::config_cache;
+use starbase_utils::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct GoWork {
+    pub go: Option<String>,
+
+    /// Relative paths to module directories within the workspace.
+    pub modules: Vec<String>,
+
+    pub path: PathBuf,
+}
+
+impl GoWork {
+    /// Return absolute paths to all module directories within the workspace.
+    pub fn get_module_dirs(&self, workspace_root: &Path) -> Vec<PathBuf> {
+        self.modules
+            .iter()
+            .map(|dir| workspace_root.join(dir))
+            .collect()
+    }
+}
+
+/// Parse the contents of a `go.work` file.
+pub fn parse_go_work(content: &str) -> GoWork {
+    let mut go_work = GoWork::default();
+
+    for directive in parse_directives(content) {
+        match directive.verb.as_str() {
+            "go" => {
+                go_work.go = directive.args.into_iter().next();
+            }
+            "use" => {
+                go_work.modules.extend(directive.args);
+            }
+            _ => {}
+        };
+    }
+
+    go_work
+}
+
+fn read_go_work(path: &Path) -> Result<GoWork, MoonError> {
+    Ok(parse_go_work(&fs::read_file(path)?))
+}
+
+config_cache!(GoWork, GOMOD.config_files[0], read_go_work);
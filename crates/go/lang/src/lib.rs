@@ -1,3 +1,8 @@
+mod directives;
+pub mod go_mod;
+pub mod go_sum;
+pub mod go_work;
+
 use moon_lang::{DependencyManager, Language, VersionManager};
 
 pub const GO: Language = Language {
@@ -11,7 +16,7 @@ pub const GO: Language = Language {
 
 pub const GOMOD: DependencyManager = DependencyManager {
     binary: "go mod",
-    config_files: &[],
+    config_files: &["go.work"],
     lockfile: "go.sum",
     manifest: "go.mod",
 };
@@ -0,0 +1,88 @@
+use moon_go_lang::go_mod::*;
+use moon_test_utils::create_sandbox;
+
+#[test]
+fn parses_module_and_directives() {
+    let sandbox = create_sandbox("go");
+    let go_mod = GoMod::read(sandbox.path().join("api")).unwrap().unwrap();
+
+    assert_eq!(go_mod.module, "example.com/acme/api");
+    assert_eq!(go_mod.go.as_deref(), Some("1.20"));
+    assert_eq!(
+        go_mod.require,
+        vec![
+            GoModRequire {
+                module: "example.com/acme/shared".into(),
+                version: "v0.0.0".into(),
+                indirect: false,
+            },
+            GoModRequire {
+                module: "github.com/google/uuid".into(),
+                version: "v1.3.0".into(),
+                indirect: false,
+            },
+            GoModRequire {
+                module: "golang.org/x/text".into(),
+                version: "v0.9.0".into(),
+                indirect: true,
+            },
+            GoModRequire {
+                module: "example.com/acme/legacy".into(),
+                version: "v1.0.0".into(),
+                indirect: false,
+            },
+        ]
+    );
+    assert_eq!(
+        go_mod.replace,
+        vec![GoModReplace {
+            module: "example.com/acme/legacy".into(),
+            version: None,
+            replacement: "../utils".into(),
+            replacement_version: None,
+        }]
+    );
+}
+
+#[test]
+fn supports_quoted_module_paths() {
+    let sandbox = create_sandbox("go");
+    let go_mod = GoMod::read(sandbox.path().join("tool")).unwrap().unwrap();
+
+    assert_eq!(go_mod.module, "example.com/acme/tool");
+}
+
+#[test]
+fn parses_replace_blocks_with_versions() {
+    let go_mod = parse_go_mod(
+        r#"
+module example.com/foo
+
+replace (
+    example.com/a v1.0.0 => example.com/b v1.1.0 // fork
+    example.com/c => /abs/path/c
+)
+"#,
+    );
+
+    assert_eq!(
+        go_mod.replace,
+        vec![
+            GoModReplace {
+                module: "example.com/a".into(),
+                version: Some("v1.0.0".into()),
+                replacement: "example.com/b".into(),
+                replacement_version: Some("v1.1.0".into()),
+            },
+            GoModReplace {
+                module: "example.com/c".into(),
+                version: None,
+                replacement: "/abs/path/c".into(),
+                replacement_version: None,
+            },
+        ]
+    );
+
+    assert!(!go_mod.replace[0].is_local());
+    assert!(go_mod.replace[1].is_local());
+}
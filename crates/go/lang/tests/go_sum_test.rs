@@ -0,0 +1,25 @@
+use moon_go_lang::go_sum::*;
+use moon_test_utils::create_sandbox;
+
+#[test]
+fn groups_checksums_by_module() {
+    let sandbox = create_sandbox("go");
+    let deps = load_go_sum_dependencies(sandbox.path().join("api/go.sum")).unwrap();
+
+    assert_eq!(deps.len(), 2);
+    assert_eq!(
+        deps.get("github.com/google/uuid").unwrap(),
+        &vec![
+            "v1.3.0 h1:t6JiXgmwXMjEs8VusXIJk2BXHsn+wx8BZdTaoZ5fu7I=".to_owned(),
+            "v1.3.0/go.mod h1:TIyPZe4MgqvfeYDBFedMoGGpEw/LqOeaOT+nhxU+yHo=".to_owned(),
+        ]
+    );
+}
+
+#[test]
+fn returns_empty_if_missing() {
+    let sandbox = create_sandbox("go");
+    let deps = load_go_sum_dependencies(sandbox.path().join("shared/go.sum")).unwrap();
+
+    assert!(deps.is_empty());
+}
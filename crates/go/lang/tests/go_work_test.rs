@@ -0,0 +1,18 @@
+use moon_go_lang::go_work::*;
+use moon_test_utils::create_sandbox;
+
+#[test]
+fn parses_use_directives() {
+    let sandbox = create_sandbox("go");
+    let go_work = GoWork::read(sandbox.path()).unwrap().unwrap();
+
+    assert_eq!(go_work.go.as_deref(), Some("1.20"));
+    assert_eq!(go_work.modules, vec!["./api", "./shared", "./utils"]);
+}
+
+#[test]
+fn supports_single_use_directives() {
+    let go_work = parse_go_work("go 1.20\n\nuse ./a\nuse ./b // comment\n");
+
+    assert_eq!(go_work.modules, vec!["./a", "./b"]);
+}
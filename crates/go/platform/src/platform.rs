@@ -0,0 +1,370 @@
+use crate::target_hasher::GoTargetHasher;
+use moon_action_context::ActionContext;
+use moon_common::Id;
+use moon_config::{
+    DependencyConfig, DependencyScope, DependencySource, GoConfig, HasherConfig, PlatformType,
+    ProjectConfig, ProjectsAliasesMap, ProjectsSourcesMap,
+};
+use moon_error::MoonError;
+use moon_go_lang::{go_mod::GoMod, go_sum::load_go_sum_dependencies, go_work::GoWork, GOMOD};
+use moon_go_tool::GoTool;
+use moon_hasher::{DepsHasher, HashSet};
+use moon_logger::{debug, warn};
+use moon_platform::{Platform, Runtime, Version};
+use moon_process::Command;
+use moon_project::Project;
+use moon_task::Task;
+use moon_terminal::{print_checkpoint, Checkpoint};
+use moon_tool::{Tool, ToolError, ToolManager};
+use moon_utils::{async_trait, path};
+use rustc_hash::{FxHashMap, FxHashSet};
+use starbase_styles::color;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+const LOG_TARGET: &str = "moon:go-platform";
+
+#[derive(Debug)]
+pub struct GoPlatform {
+    pub config: GoConfig,
+
+    module_paths: FxHashMap<String, Id>,
+
+    toolchain: ToolManager<GoTool>,
+
+    workspace_root: PathBuf,
+}
+
+impl GoPlatform {
+    pub fn new(config: &GoConfig, workspace_root: &Path) -> Self {
+        GoPlatform {
+            config: config.to_owned(),
+            module_paths: FxHashMap::default(),
+            toolchain: ToolManager::new(Runtime::Go(Version::new_global())),
+            workspace_root: workspace_root.to_path_buf(),
+        }
+    }
+
+    fn get_tool(&self, runtime: &Runtime) -> Result<&GoTool, ToolError> {
+        self.toolchain.get_for_version(runtime.version())
+    }
+}
+
+#[async_trait]
+impl Platform for GoPlatform {
+    fn get_type(&self) -> PlatformType {
+        PlatformType::Go
+    }
+
+    fn get_runtime_from_config(&self, _project_config: Option<&ProjectConfig>) -> Runtime {
+        Runtime::Go(Version::new_global())
+    }
+
+    fn matches(&self, platform: &PlatformType, runtime: Option<&Runtime>) -> bool {
+        if matches!(platform, PlatformType::Go) {
+            return true;
+        }
+
+        if let Some(runtime) = &runtime {
+            return matches!(runtime, Runtime::Go(_));
+        }
+
+        false
+    }
+
+    // PROJECT GRAPH
+
+    fn is_project_in_dependency_workspace(&self, project: &Project) -> Result<bool, MoonError> {
+        if project.root == self.workspace_root {
+            return Ok(true);
+        }
+
+        // Modules listed in `go.work` share the workspace's module cache
+        let Some(go_work) = GoWork::read(&self.workspace_root)? else {
+            return Ok(false);
+        };
+
+        Ok(go_work
+            .get_module_dirs(&self.workspace_root)
+            .into_iter()
+            .any(|dir| path::normalize(dir) == project.root))
+    }
+
+    fn load_project_graph_aliases(
+        &mut self,
+        projects_map: &ProjectsSourcesMap,
+        aliases_map: &mut ProjectsAliasesMap,
+    ) -> Result<(), MoonError> {
+        debug!(
+            target: LOG_TARGET,
+            "Loading module paths (aliases) from project {}'s",
+            color::file(GOMOD.manifest)
+        );
+
+        for (project_id, project_source) in projects_map {
+            let Some(go_mod) = GoMod::read(self.workspace_root.join(project_source))? else {
+                continue;
+            };
+
+            if go_mod.module.is_empty() {
+                continue;
+            }
+
+            let alias = go_mod.module;
+
+            self.module_paths
+                .insert(alias.clone(), project_id.to_owned());
+
+            if let Some(existing_source) = projects_map.get(&alias) {
+                if existing_source != project_source {
+                    warn!(
+                        target: LOG_TARGET,
+                        "A project already exists with the ID {} ({}), skipping alias of the same name ({})",
+                        color::id(&alias),
+                        color::file(existing_source),
+                        color::file(project_source)
+                    );
+
+                    continue;
+                }
+            }
+
+            if let Some(existing_id) = aliases_map.get(&alias) {
+                warn!(
+                    target: LOG_TARGET,
+                    "A project already exists with the alias {} (for ID {}), skipping conflicting alias (from {})",
+                    color::id(alias),
+                    color::id(existing_id),
+                    color::file(project_source)
+                );
+
+                continue;
+            }
+
+            aliases_map.insert(alias, project_id.to_owned());
+        }
+
+        Ok(())
+    }
+
+    fn load_project_implicit_dependencies(
+        &self,
+        project: &Project,
+        _aliases_map: &ProjectsAliasesMap,
+    ) -> Result<Vec<DependencyConfig>, MoonError> {
+        let mut implicit_deps = vec![];
+
+        debug!(
+            target: LOG_TARGET,
+            "Scanning {} for implicit dependency relations",
+            color::id(&project.id),
+        );
+
+        let Some(go_mod) = GoMod::read(&project.root)? else {
+            return Ok(implicit_deps);
+        };
+
+        let mut found_ids = FxHashSet::default();
+        let mut add_implicit_relation = |dep_project_id: &Id, module: &str| {
+            if dep_project_id != &project.id && found_ids.insert(dep_project_id.to_owned()) {
+                implicit_deps.push(DependencyConfig {
+                    id: dep_project_id.to_owned(),
+                    scope: DependencyScope::Production,
+                    source: DependencySource::Implicit,
+                    via: Some(module.to_owned()),
+                });
+            }
+        };
+
+        for require in &go_mod.require {
+            if let Some(dep_project_id) = self.module_paths.get(&require.module) {
+                add_implicit_relation(dep_project_id, &require.module);
+            }
+        }
+
+        // Local replacements point to a module directory, which may be
+        // a project whose module path differs from the replaced module
+        for replace in &go_mod.replace {
+            if !replace.is_local() {
+                continue;
+            }
+
+            let module_dir = path::normalize(project.root.join(&replace.replacement));
+
+            if let Some(local_go_mod) = GoMod::read(module_dir)? {
+                if let Some(dep_project_id) = self.module_paths.get(&local_go_mod.module) {
+                    add_implicit_relation(dep_project_id, &replace.module);
+                }
+            }
+        }
+
+        Ok(implicit_deps)
+    }
+
+    // TOOLCHAIN
+
+    fn is_toolchain_enabled(&self) -> Result<bool, ToolError> {
+        Ok(false)
+    }
+
+    fn get_tool(&self) -> Result<Box<&dyn Tool>, ToolError> {
+        let tool = self.toolchain.get()?;
+
+        Ok(Box::new(tool))
+    }
+
+    fn get_tool_for_version(&self, version: Version) -> Result<Box<&dyn Tool>, ToolError> {
+        let tool = self.toolchain.get_for_version(&version)?;
+
+        Ok(Box::new(tool))
+    }
+
+    fn get_dependency_configs(&self) -> Result<Option<(String, String)>, ToolError> {
+        Ok(Some((GOMOD.lockfile.to_owned(), GOMOD.manifest.to_owned())))
+    }
+
+    async fn setup_toolchain(&mut self) -> Result<(), ToolError> {
+        let version = Version::new_global();
+        let mut last_versions = FxHashMap::default();
+
+        if !self.toolchain.has(&version) {
+            self.toolchain
+                .register(&version, GoTool::new(&self.config, &version)?);
+        }
+
+        self.toolchain.setup(&version, &mut last_versions).await?;
+
+        Ok(())
+    }
+
+    async fn teardown_toolchain(&mut self) -> Result<(), ToolError> {
+        self.toolchain.teardown_all().await?;
+
+        Ok(())
+    }
+
+    // ACTIONS
+
+    async fn setup_tool(
+        &mut self,
+        _context: &ActionContext,
+        runtime: &Runtime,
+        last_versions: &mut FxHashMap<String, String>,
+    ) -> Result<u8, ToolError> {
+        let version = runtime.version();
+
+        if !self.toolchain.has(&version) {
+            self.toolchain
+                .register(&version, GoTool::new(&self.config, &version)?);
+        }
+
+        Ok(self.toolchain.setup(&version, last_versions).await?)
+    }
+
+    async fn install_deps(
+        &self,
+        _context: &ActionContext,
+        runtime: &Runtime,
+        working_dir: &Path,
+    ) -> Result<(), ToolError> {
+        // A `go.work` without a `go.mod` is valid, and will download for all modules
+        if !working_dir.join(GOMOD.manifest).exists()
+            && !working_dir.join(GOMOD.config_files[0]).exists()
+        {
+            return Ok(());
+        }
+
+        let tool = self.get_tool(runtime)?;
+
+        debug!(target: LOG_TARGET, "Installing dependencies");
+
+        print_checkpoint("go mod download", Checkpoint::Setup);
+
+        Command::new(tool.get_bin_path()?)
+            .args(["mod", "download"])
+            .cwd(working_dir)
+            .create_async()
+            .exec_stream_output()
+            .await?;
+
+        if self.config.tidy_on_install && working_dir.join(GOMOD.manifest).exists() {
+            print_checkpoint("go mod tidy", Checkpoint::Setup);
+
+            Command::new(tool.get_bin_path()?)
+                .args(["mod", "tidy"])
+                .cwd(working_dir)
+                .create_async()
+                .exec_stream_output()
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn hash_manifest_deps(
+        &self,
+        manifest_path: &Path,
+        hashset: &mut HashSet,
+        _hasher_config: &HasherConfig,
+    ) -> Result<(), ToolError> {
+        let mut hasher = DepsHasher::new("go".into());
+
+        if let Some(go_mod) = GoMod::read(manifest_path)? {
+            for require in &go_mod.require {
+                hasher.hash_dep(&require.module, &require.version);
+            }
+        }
+
+        // The checksums pin the exact contents of each module
+        let go_sum_path = manifest_path.parent().unwrap().join(GOMOD.lockfile);
+
+        for (module, versions) in load_go_sum_dependencies(go_sum_path)? {
+            hasher.hash_dep(format!("{module} (sum)"), versions.join(","));
+        }
+
+        hashset.hash(hasher);
+
+        Ok(())
+    }
+
+    async fn hash_run_target(
+        &self,
+        project: &Project,
+        _runtime: &Runtime,
+        hashset: &mut HashSet,
+        _hasher_config: &HasherConfig,
+    ) -> Result<(), ToolError> {
+        let mut go_sum_path = project.root.join(GOMOD.lockfile);
+
+        if !go_sum_path.exists() {
+            go_sum_path = self.workspace_root.join(GOMOD.lockfile);
+        }
+
+        if !go_sum_path.exists() {
+            return Ok(());
+        }
+
+        let mut hasher = GoTargetHasher::new(None);
+
+        hasher.locked_dependencies = BTreeMap::from_iter(load_go_sum_dependencies(go_sum_path)?);
+
+        hashset.hash(hasher);
+
+        Ok(())
+    }
+
+    async fn create_run_target_command(
+        &self,
+        _context: &ActionContext,
+        _project: &Project,
+        task: &Task,
+        _runtime: &Runtime,
+        working_dir: &Path,
+    ) -> Result<Command, ToolError> {
+        let mut command = Command::new(&task.command);
+
+        command.args(&task.args).envs(&task.env).cwd(working_dir);
+
+        Ok(command)
+    }
+}
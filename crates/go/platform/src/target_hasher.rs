@@ -0,0 +1,39 @@
+use moon_hasher::{Digest, Hasher, Sha256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoTargetHasher {
+    pub go_version: String,
+
+    // All the modules (and their checksums) of the project or workspace
+    pub locked_dependencies: BTreeMap<String, Vec<String>>,
+}
+
+impl GoTargetHasher {
+    pub fn new(go_version: Option<String>) -> Self {
+        GoTargetHasher {
+            go_version: go_version.unwrap_or_else(|| "unknown".into()),
+            ..GoTargetHasher::default()
+        }
+    }
+}
+
+impl Hasher for GoTargetHasher {
+    fn hash(&self, sha: &mut Sha256) {
+        sha.update(self.go_version.as_bytes());
+
+        for (name, versions) in &self.locked_dependencies {
+            sha.update(name.as_bytes());
+
+            for version in versions {
+                sha.update(version.as_bytes());
+            }
+        }
+    }
+
+    fn serialize(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap()
+    }
+}
@@ -0,0 +1,197 @@
+use moon::{generate_project_graph, load_workspace_from};
+use moon_config::{
+    GoConfig, HasherConfig, PartialGoConfig, PartialToolchainConfig, PartialWorkspaceConfig,
+    WorkspaceProjects,
+};
+use moon_go_platform::GoPlatform;
+use moon_hasher::HashSet;
+use moon_platform::Platform;
+use moon_project::Project;
+use moon_project_graph::ProjectGraph;
+use moon_test_utils::{create_sandbox, create_sandbox_with_config, Sandbox};
+use rustc_hash::FxHashMap;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn create_project(root: PathBuf) -> Project {
+    Project {
+        root,
+        ..Project::default()
+    }
+}
+
+async fn get_go_graph() -> (ProjectGraph, Sandbox) {
+    let workspace_config = PartialWorkspaceConfig {
+        projects: Some(WorkspaceProjects::Sources(FxHashMap::from_iter([
+            ("api".into(), "api".to_owned()),
+            ("shared".into(), "shared".to_owned()),
+            ("tool".into(), "tool".to_owned()),
+            ("utils".into(), "utils".to_owned()),
+        ]))),
+        ..PartialWorkspaceConfig::default()
+    };
+
+    let toolchain_config = PartialToolchainConfig {
+        go: Some(PartialGoConfig::default()),
+        ..PartialToolchainConfig::default()
+    };
+
+    let sandbox =
+        create_sandbox_with_config("go", Some(workspace_config), Some(toolchain_config), None);
+
+    let mut workspace = load_workspace_from(sandbox.path()).await.unwrap();
+    let graph = generate_project_graph(&mut workspace).await.unwrap();
+
+    (graph, sandbox)
+}
+
+fn get_dependencies(graph: &ProjectGraph, id: &str) -> BTreeMap<String, Option<String>> {
+    graph
+        .get(id)
+        .unwrap()
+        .dependencies
+        .iter()
+        .map(|(id, dep)| (id.to_string(), dep.via.clone()))
+        .collect()
+}
+
+mod project_graph {
+    use super::*;
+
+    #[tokio::test]
+    async fn loads_module_paths_as_aliases() {
+        let (graph, _sandbox) = get_go_graph().await;
+
+        assert_eq!(
+            graph.aliases,
+            FxHashMap::from_iter([
+                ("example.com/acme/api".into(), "api".into()),
+                ("example.com/acme/shared".into(), "shared".into()),
+                ("example.com/acme/tool".into(), "tool".into()),
+                ("example.com/acme/utils".into(), "utils".into()),
+            ])
+        );
+
+        assert_eq!(graph.get("example.com/acme/shared").unwrap().id, "shared");
+    }
+
+    #[tokio::test]
+    async fn infers_dependencies_from_require() {
+        let (graph, _sandbox) = get_go_graph().await;
+
+        assert_eq!(
+            get_dependencies(&graph, "tool"),
+            BTreeMap::from_iter([("api".into(), Some("example.com/acme/api".into()))])
+        );
+    }
+
+    #[tokio::test]
+    async fn infers_dependencies_from_local_replace() {
+        let (graph, _sandbox) = get_go_graph().await;
+
+        assert_eq!(
+            get_dependencies(&graph, "api"),
+            BTreeMap::from_iter([
+                ("shared".into(), Some("example.com/acme/shared".into())),
+                ("utils".into(), Some("example.com/acme/legacy".into())),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn ignores_modules_without_workspace_deps() {
+        let (graph, _sandbox) = get_go_graph().await;
+
+        assert!(get_dependencies(&graph, "shared").is_empty());
+    }
+}
+
+mod dependency_configs {
+    use super::*;
+
+    #[test]
+    fn uses_go_mod_and_go_sum() {
+        let platform = GoPlatform::new(&GoConfig::default(), &PathBuf::new());
+
+        assert_eq!(
+            platform.get_dependency_configs().unwrap(),
+            Some(("go.sum".into(), "go.mod".into()))
+        );
+    }
+}
+
+mod dependency_workspace {
+    use super::*;
+
+    #[test]
+    fn root_is_in_workspace() {
+        let sandbox = create_sandbox("go");
+        let platform = GoPlatform::new(&GoConfig::default(), sandbox.path());
+
+        assert!(platform
+            .is_project_in_dependency_workspace(&create_project(sandbox.path().to_path_buf()))
+            .unwrap());
+    }
+
+    #[test]
+    fn used_module_is_in_workspace() {
+        let sandbox = create_sandbox("go");
+        let platform = GoPlatform::new(&GoConfig::default(), sandbox.path());
+
+        assert!(platform
+            .is_project_in_dependency_workspace(&create_project(sandbox.path().join("api")))
+            .unwrap());
+    }
+
+    #[test]
+    fn unused_module_is_not_in_workspace() {
+        let sandbox = create_sandbox("go");
+        let platform = GoPlatform::new(&GoConfig::default(), sandbox.path());
+
+        assert!(!platform
+            .is_project_in_dependency_workspace(&create_project(sandbox.path().join("tool")))
+            .unwrap());
+    }
+}
+
+mod hash_manifest_deps {
+    use super::*;
+
+    async fn hash_manifest(platform: &GoPlatform, manifest_path: &Path) -> String {
+        let mut hashset = HashSet::default();
+
+        platform
+            .hash_manifest_deps(manifest_path, &mut hashset, &HasherConfig::default())
+            .await
+            .unwrap();
+
+        hashset.generate()
+    }
+
+    #[tokio::test]
+    async fn changes_when_go_sum_changes() {
+        let sandbox = create_sandbox("go");
+        let platform = GoPlatform::new(&GoConfig::default(), sandbox.path());
+
+        let before = hash_manifest(&platform, &sandbox.path().join("api/go.mod")).await;
+
+        // Cached by path, so use a copy of the module
+        fs::create_dir_all(sandbox.path().join("api-copy")).unwrap();
+        fs::copy(
+            sandbox.path().join("api/go.mod"),
+            sandbox.path().join("api-copy/go.mod"),
+        )
+        .unwrap();
+        fs::write(
+            sandbox.path().join("api-copy/go.sum"),
+            "github.com/google/uuid v1.3.1 h1:KjJaJ9iWZ3jOFZIf1Lqf4laDRCasjl0BCmnEGxkdLb4=\n",
+        )
+        .unwrap();
+
+        assert_ne!(
+            before,
+            hash_manifest(&platform, &sandbox.path().join("api-copy/go.mod")).await
+        );
+    }
+}
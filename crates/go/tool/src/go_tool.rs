@@ -0,0 +1,34 @@
+use moon_config::GoConfig;
+use moon_go_lang::GO;
+use moon_platform_runtime::Version;
+use moon_tool::{Tool, ToolError};
+use proto::async_trait;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub struct GoTool {
+    pub config: GoConfig,
+
+    pub global: bool,
+}
+
+impl GoTool {
+    pub fn new(config: &GoConfig, _version: &Version) -> Result<GoTool, ToolError> {
+        // Go is not managed by proto, so always use the binary on PATH
+        Ok(GoTool {
+            config: config.to_owned(),
+            global: true,
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for GoTool {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn get_bin_path(&self) -> Result<PathBuf, ToolError> {
+        Ok(PathBuf::from(GO.binary))
+    }
+}
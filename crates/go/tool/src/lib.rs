@@ -0,0 +1,3 @@
+mod go_tool;
+
+pub use go_tool::*;
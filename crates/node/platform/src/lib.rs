@@ -9,15 +9,16 @@ pub use target_hasher::NodeTargetHasher;
 use moon_common::Id;
 use moon_config::PartialTaskConfig;
 use moon_node_lang::PackageJson;
-use moon_task::TaskError;
+use moon_task::{PlatformType, TaskError};
 use std::collections::BTreeMap;
 use task::ScriptParser;
 
 pub fn create_tasks_from_scripts(
     project_id: &str,
     package_json: &mut PackageJson,
+    platform: PlatformType,
 ) -> Result<BTreeMap<Id, PartialTaskConfig>, TaskError> {
-    let mut parser = ScriptParser::new(project_id);
+    let mut parser = ScriptParser::new(project_id, platform);
 
     parser.parse_scripts(package_json)?;
     parser.update_package(package_json)?;
@@ -28,8 +29,9 @@ pub fn create_tasks_from_scripts(
 pub fn infer_tasks_from_scripts(
     project_id: &str,
     package_json: &PackageJson,
+    platform: PlatformType,
 ) -> Result<BTreeMap<Id, PartialTaskConfig>, TaskError> {
-    let mut parser = ScriptParser::new(project_id);
+    let mut parser = ScriptParser::new(project_id, platform);
 
     parser.infer_scripts(package_json)?;
 
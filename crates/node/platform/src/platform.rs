@@ -223,8 +223,9 @@ impl Platform for NodePlatform {
         );
 
         if let Some(package_json) = PackageJson::read(&project.root)? {
-            for (id, partial_task) in infer_tasks_from_scripts(&project.id, &package_json)
-                .map_err(|e| MoonError::Generic(e.to_string()))?
+            for (id, partial_task) in
+                infer_tasks_from_scripts(&project.id, &package_json, PlatformType::Node)
+                    .map_err(|e| MoonError::Generic(e.to_string()))?
             {
                 tasks.insert(id, TaskConfig::from_partial(partial_task));
             }
@@ -34,7 +34,7 @@ static DEV_COMMAND: Lazy<regex::Regex> = Lazy::new(|| {
 });
 
 static DEV_COMMAND_SOLO: Lazy<regex::Regex> = Lazy::new(|| {
-    regex::create_regex(r#"^(npx |yarn dlx |pnpm dlx )?(parcel|vite|webpack)$"#).unwrap()
+    regex::create_regex(r#"^(npx |bunx |yarn dlx |pnpm dlx )?(parcel|vite|webpack)$"#).unwrap()
 });
 
 static DEV_ONLY_NAME: Lazy<regex::Regex> = Lazy::new(|| {
@@ -46,7 +46,7 @@ static DEV_ONLY_NAME: Lazy<regex::Regex> = Lazy::new(|| {
 
 // Special package manager handling
 static PM_RUN_COMMAND: Lazy<regex::Regex> = Lazy::new(|| {
-    regex::create_regex(r#"(?:npm|pnpm|yarn) run ([a-zA-Z0-9:-_]+)([^&]+)?"#).unwrap()
+    regex::create_regex(r#"(?:bun|npm|pnpm|yarn) run ([a-zA-Z0-9:-_]+)([^&]+)?"#).unwrap()
 });
 
 static PM_LIFE_CYCLES: Lazy<regex::Regex> = Lazy::new(|| {
@@ -123,7 +123,7 @@ fn clean_script_name(name: &str) -> String {
     ID_CLEAN.replace_all(name, "-").to_string()
 }
 
-fn detect_platform_type(command: &str) -> PlatformType {
+fn detect_platform_type(command: &str, platform: PlatformType) -> PlatformType {
     if UNIX_SYSTEM_COMMAND.is_match(command)
         || WINDOWS_SYSTEM_COMMAND.is_match(command)
         || command == "noop"
@@ -131,7 +131,7 @@ fn detect_platform_type(command: &str) -> PlatformType {
         return PlatformType::System;
     }
 
-    PlatformType::Node
+    platform
 }
 
 pub enum TaskContext {
@@ -145,6 +145,7 @@ pub fn create_task(
     script_name: &str,
     script: &str,
     context: TaskContext,
+    platform: PlatformType,
 ) -> Result<PartialTaskConfig, TaskError> {
     let is_wrapping = matches!(context, TaskContext::WrapRunScript);
     let script_args = split_args(script).unwrap();
@@ -180,19 +181,24 @@ pub fn create_task(
     }
 
     if is_wrapping {
-        task_config.platform = Some(PlatformType::Node);
-        task_config.command = Some(TaskCommandArgs::Sequence(string_vec![
-            "moon",
-            "node",
-            "run-script",
-            script_name
-        ]));
+        task_config.platform = Some(platform);
+        task_config.command = Some(TaskCommandArgs::Sequence(match platform {
+            // Bun can run package scripts directly
+            PlatformType::Bun => string_vec!["bun", "run", script_name],
+            _ => string_vec!["moon", "node", "run-script", script_name],
+        }));
     } else {
         if let Some(command) = args.get(0) {
             if is_bash_script(command) {
                 args.insert(0, "bash".to_owned());
             } else if is_node_script(command) {
-                args.insert(0, "node".to_owned());
+                args.insert(
+                    0,
+                    match platform {
+                        PlatformType::Bun => "bun".to_owned(),
+                        _ => "node".to_owned(),
+                    },
+                );
             } else {
                 // Already there
             }
@@ -200,7 +206,7 @@ pub fn create_task(
             args.insert(0, "noop".to_owned());
         }
 
-        task_config.platform = Some(detect_platform_type(&args[0]));
+        task_config.platform = Some(detect_platform_type(&args[0], platform));
         task_config.command = Some(if args.len() == 1 {
             TaskCommandArgs::String(args.remove(0))
         } else {
@@ -237,6 +243,9 @@ pub struct ScriptParser<'a> {
     /// Script names -> task IDs.
     names_to_ids: FxHashMap<String, Id>,
 
+    /// Platform to run the tasks with.
+    platform: PlatformType,
+
     /// Scripts that started with "post".
     post: ScriptsMap,
 
@@ -257,10 +266,11 @@ pub struct ScriptParser<'a> {
 }
 
 impl<'a> ScriptParser<'a> {
-    pub fn new(project_id: &'a str) -> Self {
+    pub fn new(project_id: &'a str, platform: PlatformType) -> Self {
         ScriptParser {
             life_cycles: FxHashMap::default(),
             names_to_ids: FxHashMap::default(),
+            platform,
             post: FxHashMap::default(),
             pre: FxHashMap::default(),
             project_id,
@@ -289,7 +299,13 @@ impl<'a> ScriptParser<'a> {
 
             self.tasks.insert(
                 Id::new(&task_id)?,
-                create_task(&target_id, name, script, TaskContext::WrapRunScript)?,
+                create_task(
+                    &target_id,
+                    name,
+                    script,
+                    TaskContext::WrapRunScript,
+                    self.platform,
+                )?,
             );
         }
 
@@ -477,7 +493,13 @@ impl<'a> ScriptParser<'a> {
 
         self.tasks.insert(
             task_id.clone(),
-            create_task(&target_id, name, value, TaskContext::ConvertToTask)?,
+            create_task(
+                &target_id,
+                name,
+                value,
+                TaskContext::ConvertToTask,
+                self.platform,
+            )?,
         );
 
         Ok(task_id)
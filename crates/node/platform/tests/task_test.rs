@@ -170,6 +170,7 @@ mod create_task {
                 "script",
                 "bash scripts/setup.sh",
                 TaskContext::ConvertToTask,
+                PlatformType::Node,
             )
             .unwrap();
 
@@ -193,6 +194,7 @@ mod create_task {
                 "script",
                 "scripts/setup.sh",
                 TaskContext::ConvertToTask,
+                PlatformType::Node,
             )
             .unwrap();
 
@@ -216,6 +218,7 @@ mod create_task {
                 "script",
                 "node scripts/test.js",
                 TaskContext::ConvertToTask,
+                PlatformType::Node,
             )
             .unwrap();
 
@@ -242,6 +245,7 @@ mod create_task {
                     "script",
                     candidate,
                     TaskContext::ConvertToTask,
+                    PlatformType::Node,
                 )
                 .unwrap();
 
@@ -255,6 +259,30 @@ mod create_task {
                 )
             }
         }
+
+        #[test]
+        fn handles_bun_without_command() {
+            let task = create_task(
+                "project:task",
+                "script",
+                "scripts/test.js",
+                TaskContext::ConvertToTask,
+                PlatformType::Bun,
+            )
+            .unwrap();
+
+            assert_eq!(
+                task,
+                PartialTaskConfig {
+                    command: Some(TaskCommandArgs::Sequence(string_vec![
+                        "bun",
+                        "scripts/test.js"
+                    ])),
+                    platform: Some(PlatformType::Bun),
+                    ..PartialTaskConfig::default()
+                }
+            )
+        }
     }
 
     mod env_vars {
@@ -267,6 +295,7 @@ mod create_task {
                 "script",
                 "KEY=VALUE yarn install",
                 TaskContext::ConvertToTask,
+                PlatformType::Node,
             )
             .unwrap();
 
@@ -291,6 +320,7 @@ mod create_task {
                 "script",
                 "KEY1=VAL1 KEY2=VAL2 yarn install",
                 TaskContext::ConvertToTask,
+                PlatformType::Node,
             )
             .unwrap();
 
@@ -315,6 +345,7 @@ mod create_task {
                 "script",
                 "KEY1=VAL1; KEY2=VAL2; yarn install",
                 TaskContext::ConvertToTask,
+                PlatformType::Node,
             )
             .unwrap();
 
@@ -339,6 +370,7 @@ mod create_task {
                 "script",
                 "NODE_OPTIONS='-f -b' yarn",
                 TaskContext::ConvertToTask,
+                PlatformType::Node,
             )
             .unwrap();
 
@@ -385,6 +417,7 @@ mod create_task {
                     "script",
                     &format!("tool build {} {}", candidate.0, candidate.1),
                     TaskContext::ConvertToTask,
+                    PlatformType::Node,
                 )
                 .unwrap();
 
@@ -413,6 +446,7 @@ mod create_task {
                 "script",
                 "build --out ../parent/dir",
                 TaskContext::ConvertToTask,
+                PlatformType::Node,
             )
             .unwrap();
         }
@@ -425,6 +459,7 @@ mod create_task {
                 "script",
                 "build --out /abs/dir",
                 TaskContext::ConvertToTask,
+                PlatformType::Node,
             )
             .unwrap();
         }
@@ -437,6 +472,7 @@ mod create_task {
                 "script",
                 "build --out C:\\\\abs\\\\dir",
                 TaskContext::ConvertToTask,
+                PlatformType::Node,
             )
             .unwrap();
         }
@@ -461,7 +497,7 @@ mod infer_tasks_from_scripts {
             ..PackageJson::default()
         };
 
-        let tasks = infer_tasks_from_scripts("project", &pkg).unwrap();
+        let tasks = infer_tasks_from_scripts("project", &pkg, PlatformType::Node).unwrap();
 
         assert_eq!(
             tasks,
@@ -536,6 +572,48 @@ mod infer_tasks_from_scripts {
             ])
         )
     }
+
+    #[test]
+    fn wraps_scripts_with_bun() {
+        let pkg = PackageJson {
+            scripts: Some(BTreeMap::from([
+                (
+                    "build".into(),
+                    "bun build ./index.ts --outdir ./dist".into(),
+                ),
+                ("dev".into(), "bun --watch index.ts".into()),
+            ])),
+            ..PackageJson::default()
+        };
+
+        let tasks = infer_tasks_from_scripts("project", &pkg, PlatformType::Bun).unwrap();
+
+        assert_eq!(
+            tasks,
+            BTreeMap::from([
+                (
+                    "build".into(),
+                    PartialTaskConfig {
+                        command: Some(TaskCommandArgs::Sequence(string_vec![
+                            "bun", "run", "build"
+                        ])),
+                        outputs: Some(string_vec!["dist"]),
+                        platform: Some(PlatformType::Bun),
+                        ..PartialTaskConfig::default()
+                    }
+                ),
+                (
+                    "dev".into(),
+                    PartialTaskConfig {
+                        command: Some(TaskCommandArgs::Sequence(string_vec!["bun", "run", "dev"])),
+                        local: Some(true),
+                        platform: Some(PlatformType::Bun),
+                        ..PartialTaskConfig::default()
+                    }
+                ),
+            ])
+        )
+    }
 }
 
 mod create_tasks_from_scripts {
@@ -556,7 +634,7 @@ mod create_tasks_from_scripts {
             ..PackageJson::default()
         };
 
-        let tasks = create_tasks_from_scripts("project", &mut pkg).unwrap();
+        let tasks = create_tasks_from_scripts("project", &mut pkg, PlatformType::Node).unwrap();
 
         assert!(tasks.is_empty());
     }
@@ -578,7 +656,7 @@ mod create_tasks_from_scripts {
             ..PackageJson::default()
         };
 
-        let tasks = create_tasks_from_scripts("project", &mut pkg).unwrap();
+        let tasks = create_tasks_from_scripts("project", &mut pkg, PlatformType::Node).unwrap();
 
         assert_eq!(
             tasks.keys().map(|t| t.to_string()).collect::<Vec<String>>(),
@@ -605,7 +683,7 @@ mod create_tasks_from_scripts {
             ..PackageJson::default()
         };
 
-        let tasks = create_tasks_from_scripts("project", &mut pkg).unwrap();
+        let tasks = create_tasks_from_scripts("project", &mut pkg, PlatformType::Node).unwrap();
 
         assert_eq!(pkg.scripts, None);
 
@@ -657,7 +735,7 @@ mod create_tasks_from_scripts {
                 ..PackageJson::default()
             };
 
-            let tasks = create_tasks_from_scripts("project", &mut pkg).unwrap();
+            let tasks = create_tasks_from_scripts("project", &mut pkg, PlatformType::Node).unwrap();
 
             assert_eq!(pkg.scripts, None);
 
@@ -707,7 +785,7 @@ mod create_tasks_from_scripts {
                 ..PackageJson::default()
             };
 
-            let tasks = create_tasks_from_scripts("project", &mut pkg).unwrap();
+            let tasks = create_tasks_from_scripts("project", &mut pkg, PlatformType::Node).unwrap();
 
             assert_eq!(pkg.scripts, None);
 
@@ -757,7 +835,7 @@ mod create_tasks_from_scripts {
                 ..PackageJson::default()
             };
 
-            let tasks = create_tasks_from_scripts("project", &mut pkg).unwrap();
+            let tasks = create_tasks_from_scripts("project", &mut pkg, PlatformType::Node).unwrap();
 
             assert_eq!(pkg.scripts, None);
 
@@ -806,7 +884,7 @@ mod create_tasks_from_scripts {
                 ..PackageJson::default()
             };
 
-            let tasks = create_tasks_from_scripts("project", &mut pkg).unwrap();
+            let tasks = create_tasks_from_scripts("project", &mut pkg, PlatformType::Node).unwrap();
 
             assert_eq!(pkg.scripts, None);
 
@@ -851,7 +929,7 @@ mod create_tasks_from_scripts {
                 ..PackageJson::default()
             };
 
-            let tasks = create_tasks_from_scripts("project", &mut pkg).unwrap();
+            let tasks = create_tasks_from_scripts("project", &mut pkg, PlatformType::Node).unwrap();
 
             assert_eq!(pkg.scripts, None);
 
@@ -888,7 +966,8 @@ mod create_tasks_from_scripts {
                     ..PackageJson::default()
                 };
 
-                let tasks = create_tasks_from_scripts("project", &mut pkg).unwrap();
+                let tasks =
+                    create_tasks_from_scripts("project", &mut pkg, PlatformType::Node).unwrap();
 
                 assert_eq!(pkg.scripts, None);
 
@@ -941,7 +1020,8 @@ mod create_tasks_from_scripts {
                     ..PackageJson::default()
                 };
 
-                let tasks = create_tasks_from_scripts("project", &mut pkg).unwrap();
+                let tasks =
+                    create_tasks_from_scripts("project", &mut pkg, PlatformType::Node).unwrap();
 
                 assert_eq!(pkg.scripts, None);
 
@@ -998,7 +1078,7 @@ mod create_tasks_from_scripts {
                 ..PackageJson::default()
             };
 
-            let tasks = create_tasks_from_scripts("project", &mut pkg).unwrap();
+            let tasks = create_tasks_from_scripts("project", &mut pkg, PlatformType::Node).unwrap();
 
             assert_eq!(pkg.scripts, None);
 
@@ -1094,7 +1174,7 @@ mod create_tasks_from_scripts {
                 ..PackageJson::default()
             };
 
-            let tasks = create_tasks_from_scripts("project", &mut pkg).unwrap();
+            let tasks = create_tasks_from_scripts("project", &mut pkg, PlatformType::Node).unwrap();
 
             assert_eq!(
                 pkg.scripts,
@@ -1180,7 +1260,7 @@ mod create_tasks_from_scripts {
                 ..PackageJson::default()
             };
 
-            let tasks = create_tasks_from_scripts("project", &mut pkg).unwrap();
+            let tasks = create_tasks_from_scripts("project", &mut pkg, PlatformType::Node).unwrap();
 
             assert_eq!(
                 pkg.scripts,
@@ -1324,7 +1404,7 @@ mod create_tasks_from_scripts {
                     ..PackageJson::default()
                 };
 
-            let tasks = create_tasks_from_scripts("project", &mut pkg).unwrap();
+            let tasks = create_tasks_from_scripts("project", &mut pkg, PlatformType::Node).unwrap();
 
             assert_eq!(pkg.scripts, None);
 
@@ -1640,7 +1720,7 @@ mod create_tasks_from_scripts {
                     ..PackageJson::default()
                 };
 
-            let tasks = create_tasks_from_scripts("project", &mut pkg).unwrap();
+            let tasks = create_tasks_from_scripts("project", &mut pkg, PlatformType::Node).unwrap();
 
             assert_eq!(
                 pkg.scripts,
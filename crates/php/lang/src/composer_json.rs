@@ -0,0 +1,84 @@
+// composer.json
+
+use crate::COMPOSER;
+use cached::proc_macro::cached;
+use moon_error::MoonError;
+use moon_lang::config_cache;
+use serde::{Deserialize, Serialize};
+use starbase_utils::glob;
+use starbase_utils::json::{read_file as read_json, JsonValue};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+config_cache!(ComposerJson, COMPOSER.manifest, read_json);
+
+pub type DepsSet = BTreeMap<String, String>;
+
+// Only define fields we interact with and care about!
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct ComposerJson {
+    pub name: Option<String>,
+
+    // Can be a list or a map of repositories, and entries can be
+    // `false` to disable a repository, so keep the raw value
+    pub repositories: Option<JsonValue>,
+
+    pub require: Option<DepsSet>,
+
+    #[serde(rename = "require-dev")]
+    pub require_dev: Option<DepsSet>,
+
+    #[serde(skip)]
+    pub path: PathBuf,
+}
+
+impl ComposerJson {
+    /// Return the `url` of all repositories with a `path` type.
+    /// These may be a glob, relative to the `composer.json`.
+    pub fn get_path_repository_urls(&self) -> Vec<String> {
+        let repositories = match &self.repositories {
+            Some(JsonValue::Array(list)) => list.iter().collect::<Vec<_>>(),
+            Some(JsonValue::Object(map)) => map.values().collect::<Vec<_>>(),
+            _ => vec![],
+        };
+
+        repositories
+            .into_iter()
+            .filter(|repo| repo.get("type").and_then(|t| t.as_str()) == Some("path"))
+            .filter_map(|repo| repo.get("url").and_then(|url| url.as_str()))
+            .map(|url| url.to_owned())
+            .collect()
+    }
+
+    /// Return absolute paths to all package directories provided
+    /// by `path` repositories, with globs expanded.
+    pub fn get_path_repository_dirs(&self, root_dir: &Path) -> Result<Vec<PathBuf>, MoonError> {
+        let mut dirs = vec![];
+
+        for url in self.get_path_repository_urls() {
+            if glob::is_glob(&url) {
+                // Globs can't traverse upwards, so walk from the static
+                // prefix of the URL instead (`../packages/*` -> `../packages`)
+                let mut base_dir = root_dir.to_path_buf();
+                let mut parts = url.split('/').peekable();
+
+                while let Some(part) = parts.next_if(|part| !glob::is_glob(part)) {
+                    base_dir = base_dir.join(part);
+                }
+
+                let pattern = parts.collect::<Vec<_>>().join("/");
+
+                for dir in glob::walk(&base_dir, [&pattern])? {
+                    if dir.is_dir() {
+                        dirs.push(dir);
+                    }
+                }
+            } else {
+                dirs.push(root_dir.join(url));
+            }
+        }
+
+        Ok(dirs)
+    }
+}
@@ -0,0 +1,68 @@
+// composer.lock
+
+use crate::COMPOSER;
+use cached::proc_macro::cached;
+use moon_error::MoonError;
+use moon_lang::{config_cache, LockfileDependencyVersions};
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use starbase_utils::json::read_file as read_json;
+use std::path::{Path, PathBuf};
+
+config_cache!(ComposerLock, COMPOSER.lockfile, read_json);
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct ComposerLockPackageDist {
+    pub reference: Option<String>,
+
+    pub shasum: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct ComposerLockPackage {
+    pub name: String,
+
+    pub version: String,
+
+    pub dist: Option<ComposerLockPackageDist>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct ComposerLock {
+    pub packages: Vec<ComposerLockPackage>,
+
+    #[serde(rename = "packages-dev")]
+    pub packages_dev: Vec<ComposerLockPackage>,
+
+    #[serde(skip)]
+    pub path: PathBuf,
+}
+
+#[cached(result)]
+pub fn load_lockfile_dependencies(path: PathBuf) -> Result<LockfileDependencyVersions, MoonError> {
+    let mut deps: LockfileDependencyVersions = FxHashMap::default();
+
+    if let Some(lockfile) = ComposerLock::read(path)? {
+        for package in lockfile.packages.into_iter().chain(lockfile.packages_dev) {
+            // Prefer the dist reference (commit or checksum) when available,
+            // as versions of path and VCS packages are not unique
+            let hash = package.dist.and_then(|dist| {
+                dist.shasum
+                    .filter(|shasum| !shasum.is_empty())
+                    .or(dist.reference)
+            });
+
+            let version = match hash {
+                Some(hash) => format!("{} {hash}", package.version),
+                None => package.version,
+            };
+
+            deps.entry(package.name).or_default().push(version);
+        }
+    }
+
+    Ok(deps)
+}
@@ -1,4 +1,8 @@
+pub mod composer_json;
+pub mod composer_lock;
+
 use moon_lang::{DependencyManager, Language, VersionManager};
+use std::path::{Path, PathBuf};
 
 pub const PHP: Language = Language {
     binary: "php",
@@ -27,3 +31,28 @@ pub const PHPBREW: VersionManager = VersionManager {
     binary: "phpbrew",
     version_file: ".phpbrewrc", // Contains "phpbrew use <version>"
 };
+
+/// Find a binary installed by Composer within `vendor/bin`, starting from the
+/// provided directory, and traversing upwards until found.
+pub fn find_vendor_bin<P: AsRef<Path>, B: AsRef<str>>(
+    starting_dir: P,
+    bin_name: B,
+) -> Option<PathBuf> {
+    let starting_dir = starting_dir.as_ref();
+    let bin_name = bin_name.as_ref();
+    let bin_path = starting_dir
+        .join(PHP.vendor_bins_dir.unwrap())
+        .join(if cfg!(windows) {
+            format!("{bin_name}.bat")
+        } else {
+            bin_name.to_owned()
+        });
+
+    if bin_path.exists() {
+        return Some(bin_path);
+    }
+
+    starting_dir
+        .parent()
+        .and_then(|dir| find_vendor_bin(dir, bin_name))
+}
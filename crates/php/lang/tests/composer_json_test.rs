@@ -0,0 +1,48 @@
+use moon_php_lang::composer_json::*;
+use moon_test_utils::create_sandbox;
+
+#[test]
+fn reads_path_repositories_from_list() {
+    let sandbox = create_sandbox("php");
+    let composer_json = ComposerJson::read(sandbox.path().join("app"))
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(composer_json.name.as_deref(), Some("acme/app"));
+    assert_eq!(
+        composer_json.get_path_repository_urls(),
+        vec!["../utils", "../packages/*"]
+    );
+}
+
+#[test]
+fn reads_path_repositories_from_map() {
+    let sandbox = create_sandbox("php");
+    let composer_json = ComposerJson::read(sandbox.path().join("packages/testing"))
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        composer_json.get_path_repository_urls(),
+        vec!["../../utils"]
+    );
+}
+
+#[test]
+fn expands_globs_in_path_repositories() {
+    let sandbox = create_sandbox("php");
+    let root = sandbox.path().join("app");
+    let composer_json = ComposerJson::read(&root).unwrap().unwrap();
+    let dirs = composer_json.get_path_repository_dirs(&root).unwrap();
+
+    assert_eq!(dirs.len(), 2);
+    assert_eq!(dirs[0], root.join("../utils"));
+    assert!(dirs[1].ends_with("packages/testing"));
+}
+
+#[test]
+fn returns_none_if_missing() {
+    let sandbox = create_sandbox("php");
+
+    assert!(ComposerJson::read(sandbox.path()).unwrap().is_none());
+}
@@ -0,0 +1,37 @@
+use moon_php_lang::composer_lock::*;
+use moon_test_utils::create_sandbox;
+
+#[test]
+fn loads_packages_and_dev_packages() {
+    let sandbox = create_sandbox("php");
+    let deps = load_lockfile_dependencies(sandbox.path().join("app/composer.lock")).unwrap();
+
+    assert_eq!(deps.len(), 3);
+    assert_eq!(
+        deps.get("acme/utils").unwrap(),
+        &vec!["dev-main 7c1a5dfe0b1d3f8e2c5a3b1f0e9d8c7b6a5f4e3d".to_owned()]
+    );
+}
+
+#[test]
+fn prefers_shasum_over_reference() {
+    let sandbox = create_sandbox("php");
+    let deps = load_lockfile_dependencies(sandbox.path().join("app/composer.lock")).unwrap();
+
+    assert_eq!(
+        deps.get("monolog/monolog").unwrap(),
+        &vec!["3.3.1 9b5daeaffce5b926cac47923798bba91059e60e2".to_owned()]
+    );
+    assert_eq!(
+        deps.get("phpunit/phpunit").unwrap(),
+        &vec!["10.1.2 3a4b2c1d".to_owned()]
+    );
+}
+
+#[test]
+fn returns_empty_if_missing() {
+    let sandbox = create_sandbox("php");
+    let deps = load_lockfile_dependencies(sandbox.path().join("utils/composer.lock")).unwrap();
+
+    assert!(deps.is_empty());
+}
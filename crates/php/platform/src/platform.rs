@@ -0,0 +1,374 @@
+use crate::target_hasher::PhpTargetHasher;
+use moon_action_context::ActionContext;
+use moon_common::Id;
+use moon_config::{
+    DependencyConfig, DependencyScope, DependencySource, HasherConfig, PhpConfig, PlatformType,
+    ProjectConfig, ProjectsAliasesMap, ProjectsSourcesMap,
+};
+use moon_error::MoonError;
+use moon_hasher::{DepsHasher, HashSet};
+use moon_logger::{debug, warn};
+use moon_php_lang::{
+    composer_json::ComposerJson, composer_lock::load_lockfile_dependencies, find_vendor_bin,
+    COMPOSER, PHP,
+};
+use moon_php_tool::PhpTool;
+use moon_platform::{Platform, Runtime, Version};
+use moon_process::Command;
+use moon_project::Project;
+use moon_task::Task;
+use moon_terminal::{print_checkpoint, Checkpoint};
+use moon_tool::{Tool, ToolError, ToolManager};
+use moon_utils::{async_trait, path};
+use rustc_hash::{FxHashMap, FxHashSet};
+use starbase_styles::color;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+const LOG_TARGET: &str = "moon:php-platform";
+
+#[derive(Debug)]
+pub struct PhpPlatform {
+    pub config: PhpConfig,
+
+    package_names: FxHashMap<String, Id>,
+
+    toolchain: ToolManager<PhpTool>,
+
+    workspace_root: PathBuf,
+}
+
+impl PhpPlatform {
+    pub fn new(config: &PhpConfig, workspace_root: &Path) -> Self {
+        PhpPlatform {
+            config: config.to_owned(),
+            package_names: FxHashMap::default(),
+            toolchain: ToolManager::new(Runtime::Php(Version::new_global())),
+            workspace_root: workspace_root.to_path_buf(),
+        }
+    }
+
+    fn get_tool(&self, runtime: &Runtime) -> Result<&PhpTool, ToolError> {
+        self.toolchain.get_for_version(runtime.version())
+    }
+}
+
+#[async_trait]
+impl Platform for PhpPlatform {
+    fn get_type(&self) -> PlatformType {
+        PlatformType::Php
+    }
+
+    fn get_runtime_from_config(&self, _project_config: Option<&ProjectConfig>) -> Runtime {
+        Runtime::Php(Version::new_global())
+    }
+
+    fn matches(&self, platform: &PlatformType, runtime: Option<&Runtime>) -> bool {
+        if matches!(platform, PlatformType::Php) {
+            return true;
+        }
+
+        if let Some(runtime) = &runtime {
+            return matches!(runtime, Runtime::Php(_));
+        }
+
+        false
+    }
+
+    // PROJECT GRAPH
+
+    fn is_project_in_dependency_workspace(&self, project: &Project) -> Result<bool, MoonError> {
+        if project.root == self.workspace_root {
+            return Ok(true);
+        }
+
+        // Projects with their own manifest install into their own `vendor` directory
+        Ok(!project.root.join(COMPOSER.manifest).exists())
+    }
+
+    fn load_project_graph_aliases(
+        &mut self,
+        projects_map: &ProjectsSourcesMap,
+        aliases_map: &mut ProjectsAliasesMap,
+    ) -> Result<(), MoonError> {
+        debug!(
+            target: LOG_TARGET,
+            "Loading names (aliases) from project {}'s",
+            color::file(COMPOSER.manifest)
+        );
+
+        for (project_id, project_source) in projects_map {
+            let Some(composer_json) = ComposerJson::read(self.workspace_root.join(project_source))? else {
+                continue;
+            };
+
+            let Some(alias) = composer_json.name else {
+                continue;
+            };
+
+            self.package_names
+                .insert(alias.clone(), project_id.to_owned());
+
+            if let Some(existing_source) = projects_map.get(&alias) {
+                if existing_source != project_source {
+                    warn!(
+                        target: LOG_TARGET,
+                        "A project already exists with the ID {} ({}), skipping alias of the same name ({})",
+                        color::id(&alias),
+                        color::file(existing_source),
+                        color::file(project_source)
+                    );
+
+                    continue;
+                }
+            }
+
+            if let Some(existing_id) = aliases_map.get(&alias) {
+                warn!(
+                    target: LOG_TARGET,
+                    "A project already exists with the alias {} (for ID {}), skipping conflicting alias (from {})",
+                    color::id(alias),
+                    color::id(existing_id),
+                    color::file(project_source)
+                );
+
+                continue;
+            }
+
+            aliases_map.insert(alias, project_id.to_owned());
+        }
+
+        Ok(())
+    }
+
+    fn load_project_implicit_dependencies(
+        &self,
+        project: &Project,
+        _aliases_map: &ProjectsAliasesMap,
+    ) -> Result<Vec<DependencyConfig>, MoonError> {
+        let mut implicit_deps = vec![];
+
+        debug!(
+            target: LOG_TARGET,
+            "Scanning {} for implicit dependency relations",
+            color::id(&project.id),
+        );
+
+        let Some(composer_json) = ComposerJson::read(&project.root)? else {
+            return Ok(implicit_deps);
+        };
+
+        // Only packages provided by a local `path` repository
+        // are considered a relation to another project
+        let mut local_packages = FxHashSet::default();
+
+        for dir in composer_json.get_path_repository_dirs(&project.root)? {
+            if let Some(local_composer_json) = ComposerJson::read(path::normalize(dir))? {
+                if let Some(name) = local_composer_json.name {
+                    local_packages.insert(name);
+                }
+            }
+        }
+
+        for (deps, scope) in [
+            (&composer_json.require, DependencyScope::Production),
+            (&composer_json.require_dev, DependencyScope::Development),
+        ] {
+            let Some(deps) = deps else {
+                continue;
+            };
+
+            for name in deps.keys() {
+                if !local_packages.contains(name) {
+                    continue;
+                }
+
+                if let Some(dep_project_id) = self.package_names.get(name) {
+                    if dep_project_id != &project.id {
+                        implicit_deps.push(DependencyConfig {
+                            id: dep_project_id.to_owned(),
+                            scope,
+                            source: DependencySource::Implicit,
+                            via: Some(name.to_owned()),
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(implicit_deps)
+    }
+
+    // TOOLCHAIN
+
+    fn is_toolchain_enabled(&self) -> Result<bool, ToolError> {
+        Ok(false)
+    }
+
+    fn get_tool(&self) -> Result<Box<&dyn Tool>, ToolError> {
+        let tool = self.toolchain.get()?;
+
+        Ok(Box::new(tool))
+    }
+
+    fn get_tool_for_version(&self, version: Version) -> Result<Box<&dyn Tool>, ToolError> {
+        let tool = self.toolchain.get_for_version(&version)?;
+
+        Ok(Box::new(tool))
+    }
+
+    fn get_dependency_configs(&self) -> Result<Option<(String, String)>, ToolError> {
+        Ok(Some((
+            COMPOSER.lockfile.to_owned(),
+            COMPOSER.manifest.to_owned(),
+        )))
+    }
+
+    async fn setup_toolchain(&mut self) -> Result<(), ToolError> {
+        let version = Version::new_global();
+        let mut last_versions = FxHashMap::default();
+
+        if !self.toolchain.has(&version) {
+            self.toolchain
+                .register(&version, PhpTool::new(&self.config, &version)?);
+        }
+
+        self.toolchain.setup(&version, &mut last_versions).await?;
+
+        Ok(())
+    }
+
+    async fn teardown_toolchain(&mut self) -> Result<(), ToolError> {
+        self.toolchain.teardown_all().await?;
+
+        Ok(())
+    }
+
+    // ACTIONS
+
+    async fn setup_tool(
+        &mut self,
+        _context: &ActionContext,
+        runtime: &Runtime,
+        last_versions: &mut FxHashMap<String, String>,
+    ) -> Result<u8, ToolError> {
+        let version = runtime.version();
+
+        if !self.toolchain.has(&version) {
+            self.toolchain
+                .register(&version, PhpTool::new(&self.config, &version)?);
+        }
+
+        Ok(self.toolchain.setup(&version, last_versions).await?)
+    }
+
+    async fn install_deps(
+        &self,
+        _context: &ActionContext,
+        runtime: &Runtime,
+        working_dir: &Path,
+    ) -> Result<(), ToolError> {
+        if !working_dir.join(COMPOSER.manifest).exists() {
+            return Ok(());
+        }
+
+        // Ensure PHP is available, as Composer requires it
+        self.get_tool(runtime)?;
+
+        debug!(target: LOG_TARGET, "Installing dependencies");
+
+        print_checkpoint("composer install", Checkpoint::Setup);
+
+        Command::new(COMPOSER.binary)
+            .args(["install", "--no-interaction"])
+            .cwd(working_dir)
+            .create_async()
+            .exec_stream_output()
+            .await?;
+
+        Ok(())
+    }
+
+    async fn hash_manifest_deps(
+        &self,
+        manifest_path: &Path,
+        hashset: &mut HashSet,
+        _hasher_config: &HasherConfig,
+    ) -> Result<(), ToolError> {
+        let mut hasher = DepsHasher::new("php".into());
+
+        if let Some(composer_json) = ComposerJson::read(manifest_path)? {
+            if let Some(deps) = &composer_json.require {
+                hasher.hash_deps(deps);
+            }
+
+            if let Some(dev_deps) = &composer_json.require_dev {
+                hasher.hash_deps(dev_deps);
+            }
+        }
+
+        // The dist references pin the exact contents of each package
+        let lockfile_path = manifest_path.parent().unwrap().join(COMPOSER.lockfile);
+
+        for (name, versions) in load_lockfile_dependencies(lockfile_path)? {
+            hasher.hash_dep(format!("{name} (lock)"), versions.join(","));
+        }
+
+        hashset.hash(hasher);
+
+        Ok(())
+    }
+
+    async fn hash_run_target(
+        &self,
+        project: &Project,
+        _runtime: &Runtime,
+        hashset: &mut HashSet,
+        _hasher_config: &HasherConfig,
+    ) -> Result<(), ToolError> {
+        let mut lockfile_path = project.root.join(COMPOSER.lockfile);
+
+        if !lockfile_path.exists() {
+            lockfile_path = self.workspace_root.join(COMPOSER.lockfile);
+        }
+
+        if !lockfile_path.exists() {
+            return Ok(());
+        }
+
+        let mut hasher = PhpTargetHasher::new(None);
+
+        hasher.locked_dependencies =
+            BTreeMap::from_iter(load_lockfile_dependencies(lockfile_path)?);
+
+        hashset.hash(hasher);
+
+        Ok(())
+    }
+
+    async fn create_run_target_command(
+        &self,
+        _context: &ActionContext,
+        project: &Project,
+        task: &Task,
+        _runtime: &Runtime,
+        working_dir: &Path,
+    ) -> Result<Command, ToolError> {
+        let mut command = if task.command == PHP.binary || task.command == COMPOSER.binary {
+            Command::new(&task.command)
+        } else {
+            // Binaries installed by Composer (phpunit, phpstan, etc) are not on PATH,
+            // so resolve them from the closest `vendor/bin`, up to the workspace root
+            match find_vendor_bin(&project.root, &task.command)
+                .filter(|bin| bin.starts_with(&self.workspace_root))
+            {
+                Some(bin) => Command::new(bin),
+                None => Command::new(&task.command),
+            }
+        };
+
+        command.args(&task.args).envs(&task.env).cwd(working_dir);
+
+        Ok(command)
+    }
+}
@@ -0,0 +1,39 @@
+use moon_hasher::{Digest, Hasher, Sha256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhpTargetHasher {
+    pub php_version: String,
+
+    // All the packages (and their references) of the project or workspace
+    pub locked_dependencies: BTreeMap<String, Vec<String>>,
+}
+
+impl PhpTargetHasher {
+    pub fn new(php_version: Option<String>) -> Self {
+        PhpTargetHasher {
+            php_version: php_version.unwrap_or_else(|| "unknown".into()),
+            ..PhpTargetHasher::default()
+        }
+    }
+}
+
+impl Hasher for PhpTargetHasher {
+    fn hash(&self, sha: &mut Sha256) {
+        sha.update(self.php_version.as_bytes());
+
+        for (name, versions) in &self.locked_dependencies {
+            sha.update(name.as_bytes());
+
+            for version in versions {
+                sha.update(version.as_bytes());
+            }
+        }
+    }
+
+    fn serialize(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap()
+    }
+}
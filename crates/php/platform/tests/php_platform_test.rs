@@ -0,0 +1,286 @@
+use moon::{generate_project_graph, load_workspace_from};
+use moon_action_context::ActionContext;
+use moon_config::{
+    HasherConfig, PartialPhpConfig, PartialToolchainConfig, PartialWorkspaceConfig, PhpConfig,
+    PlatformType, WorkspaceProjects,
+};
+use moon_hasher::HashSet;
+use moon_php_platform::PhpPlatform;
+use moon_platform::{Platform, Runtime, Version};
+use moon_process::Command;
+use moon_project::Project;
+use moon_project_graph::ProjectGraph;
+use moon_task::Task;
+use moon_test_utils::{create_sandbox, create_sandbox_with_config, Sandbox};
+use moon_utils::string_vec;
+use rustc_hash::FxHashMap;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+fn create_project(root: PathBuf) -> Project {
+    Project {
+        root,
+        ..Project::default()
+    }
+}
+
+async fn create_target_command(platform: &PhpPlatform, project: &Project, bin: &str) -> Command {
+    let task = Task {
+        command: bin.into(),
+        args: string_vec!["--colors"],
+        platform: PlatformType::Php,
+        ..Task::default()
+    };
+
+    platform
+        .create_run_target_command(
+            &ActionContext::default(),
+            project,
+            &task,
+            &Runtime::Php(Version::new_global()),
+            &PathBuf::from("cwd"),
+        )
+        .await
+        .unwrap()
+}
+
+async fn get_php_graph() -> (ProjectGraph, Sandbox) {
+    let workspace_config = PartialWorkspaceConfig {
+        projects: Some(WorkspaceProjects::Sources(FxHashMap::from_iter([
+            ("api".into(), "api".to_owned()),
+            ("app".into(), "app".to_owned()),
+            ("testing".into(), "packages/testing".to_owned()),
+            ("utils".into(), "utils".to_owned()),
+        ]))),
+        ..PartialWorkspaceConfig::default()
+    };
+
+    let toolchain_config = PartialToolchainConfig {
+        php: Some(PartialPhpConfig::default()),
+        ..PartialToolchainConfig::default()
+    };
+
+    let sandbox =
+        create_sandbox_with_config("php", Some(workspace_config), Some(toolchain_config), None);
+
+    let mut workspace = load_workspace_from(sandbox.path()).await.unwrap();
+    let graph = generate_project_graph(&mut workspace).await.unwrap();
+
+    (graph, sandbox)
+}
+
+fn get_dependencies(graph: &ProjectGraph, id: &str) -> BTreeMap<String, Option<String>> {
+    graph
+        .get(id)
+        .unwrap()
+        .dependencies
+        .iter()
+        .map(|(id, dep)| (id.to_string(), dep.via.clone()))
+        .collect()
+}
+
+mod project_graph {
+    use super::*;
+    use moon_config::DependencyScope;
+
+    #[tokio::test]
+    async fn loads_package_names_as_aliases() {
+        let (graph, _sandbox) = get_php_graph().await;
+
+        assert_eq!(
+            graph.aliases,
+            FxHashMap::from_iter([
+                ("acme/api".into(), "api".into()),
+                ("acme/app".into(), "app".into()),
+                ("acme/testing".into(), "testing".into()),
+                ("acme/utils".into(), "utils".into()),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn infers_dependencies_from_path_repositories() {
+        let (graph, _sandbox) = get_php_graph().await;
+
+        assert_eq!(
+            get_dependencies(&graph, "app"),
+            BTreeMap::from_iter([
+                ("testing".into(), Some("acme/testing".into())),
+                ("utils".into(), Some("acme/utils".into())),
+            ])
+        );
+
+        let app = graph.get("app").unwrap();
+
+        assert_eq!(
+            app.dependencies.get("testing").unwrap().scope,
+            DependencyScope::Development
+        );
+        assert_eq!(
+            app.dependencies.get("utils").unwrap().scope,
+            DependencyScope::Production
+        );
+    }
+
+    #[tokio::test]
+    async fn infers_dependencies_from_repository_map() {
+        let (graph, _sandbox) = get_php_graph().await;
+
+        assert_eq!(
+            get_dependencies(&graph, "testing"),
+            BTreeMap::from_iter([("utils".into(), Some("acme/utils".into()))])
+        );
+    }
+
+    #[tokio::test]
+    async fn ignores_packages_not_from_path_repositories() {
+        let (graph, _sandbox) = get_php_graph().await;
+
+        assert!(!get_dependencies(&graph, "app").contains_key("api"));
+        assert!(get_dependencies(&graph, "api").is_empty());
+    }
+}
+
+mod dependency_configs {
+    use super::*;
+
+    #[test]
+    fn uses_composer_json_and_lock() {
+        let platform = PhpPlatform::new(&PhpConfig::default(), &PathBuf::new());
+
+        assert_eq!(
+            platform.get_dependency_configs().unwrap(),
+            Some(("composer.lock".into(), "composer.json".into()))
+        );
+    }
+}
+
+mod dependency_workspace {
+    use super::*;
+
+    #[test]
+    fn root_is_in_workspace() {
+        let sandbox = create_sandbox("php");
+        let platform = PhpPlatform::new(&PhpConfig::default(), sandbox.path());
+
+        assert!(platform
+            .is_project_in_dependency_workspace(&create_project(sandbox.path().to_path_buf()))
+            .unwrap());
+    }
+
+    #[test]
+    fn project_with_manifest_is_not_in_workspace() {
+        let sandbox = create_sandbox("php");
+        let platform = PhpPlatform::new(&PhpConfig::default(), sandbox.path());
+
+        assert!(!platform
+            .is_project_in_dependency_workspace(&create_project(sandbox.path().join("app")))
+            .unwrap());
+    }
+}
+
+mod hash_manifest_deps {
+    use super::*;
+
+    async fn hash_manifest(platform: &PhpPlatform, manifest_path: &Path) -> String {
+        let mut hashset = HashSet::default();
+
+        platform
+            .hash_manifest_deps(manifest_path, &mut hashset, &HasherConfig::default())
+            .await
+            .unwrap();
+
+        hashset.generate()
+    }
+
+    #[tokio::test]
+    async fn includes_lockfile_packages() {
+        let sandbox = create_sandbox("php");
+        let platform = PhpPlatform::new(&PhpConfig::default(), sandbox.path());
+
+        // Same manifest, but without a lockfile
+        sandbox.create_file(
+            "app-copy/composer.json",
+            std::fs::read_to_string(sandbox.path().join("app/composer.json")).unwrap(),
+        );
+
+        assert_ne!(
+            hash_manifest(&platform, &sandbox.path().join("app/composer.json")).await,
+            hash_manifest(&platform, &sandbox.path().join("app-copy/composer.json")).await
+        );
+    }
+}
+
+mod target_command {
+    use super::*;
+
+    #[tokio::test]
+    async fn runs_php_as_is() {
+        let sandbox = create_sandbox("php");
+        sandbox.create_file("app/vendor/bin/php", "");
+
+        let platform = PhpPlatform::new(&PhpConfig::default(), sandbox.path());
+        let command = create_target_command(
+            &platform,
+            &create_project(sandbox.path().join("app")),
+            "php",
+        )
+        .await;
+
+        assert_eq!(command.bin, "php");
+        assert_eq!(command.args, &["--colors"]);
+    }
+
+    #[tokio::test]
+    async fn resolves_project_vendor_bin() {
+        let sandbox = create_sandbox("php");
+        sandbox.create_file("vendor/bin/phpunit", "");
+        sandbox.create_file("app/vendor/bin/phpunit", "");
+
+        let platform = PhpPlatform::new(&PhpConfig::default(), sandbox.path());
+        let command = create_target_command(
+            &platform,
+            &create_project(sandbox.path().join("app")),
+            "phpunit",
+        )
+        .await;
+
+        assert_eq!(
+            PathBuf::from(command.bin),
+            sandbox.path().join("app/vendor/bin/phpunit")
+        );
+    }
+
+    #[tokio::test]
+    async fn resolves_workspace_vendor_bin() {
+        let sandbox = create_sandbox("php");
+        sandbox.create_file("vendor/bin/phpunit", "");
+
+        let platform = PhpPlatform::new(&PhpConfig::default(), sandbox.path());
+        let command = create_target_command(
+            &platform,
+            &create_project(sandbox.path().join("app")),
+            "phpunit",
+        )
+        .await;
+
+        assert_eq!(
+            PathBuf::from(command.bin),
+            sandbox.path().join("vendor/bin/phpunit")
+        );
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_command_if_not_installed() {
+        let sandbox = create_sandbox("php");
+        let platform = PhpPlatform::new(&PhpConfig::default(), sandbox.path());
+        let command = create_target_command(
+            &platform,
+            &create_project(sandbox.path().join("app")),
+            "phpstan",
+        )
+        .await;
+
+        assert_eq!(command.bin, "phpstan");
+    }
+}
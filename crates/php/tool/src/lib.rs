@@ -0,0 +1,3 @@
+mod php_tool;
+
+pub use php_tool::*;
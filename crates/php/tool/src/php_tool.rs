@@ -0,0 +1,34 @@
+use moon_config::PhpConfig;
+use moon_php_lang::PHP;
+use moon_platform_runtime::Version;
+use moon_tool::{Tool, ToolError};
+use proto::async_trait;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub struct PhpTool {
+    pub config: PhpConfig,
+
+    pub global: bool,
+}
+
+impl PhpTool {
+    pub fn new(config: &PhpConfig, _version: &Version) -> Result<PhpTool, ToolError> {
+        // PHP is not managed by proto, so always use the binary on PATH
+        Ok(PhpTool {
+            config: config.to_owned(),
+            global: true,
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for PhpTool {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn get_bin_path(&self) -> Result<PathBuf, ToolError> {
+        Ok(PathBuf::from(PHP.binary))
+    }
+}
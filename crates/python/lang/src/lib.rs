@@ -1,3 +1,6 @@
+pub mod python_lock;
+pub mod requirements;
+
 use moon_lang::{DependencyManager, Language, VersionManager};
 
 pub const PYTHON: Language = Language {
@@ -23,6 +26,20 @@ pub const PIPENV: DependencyManager = DependencyManager {
     manifest: "Pipfile",
 };
 
+pub const POETRY: DependencyManager = DependencyManager {
+    binary: "poetry",
+    config_files: &["poetry.toml"],
+    lockfile: "poetry.lock",
+    manifest: "pyproject.toml",
+};
+
+pub const UV: DependencyManager = DependencyManager {
+    binary: "uv",
+    config_files: &["uv.toml"],
+    lockfile: "uv.lock",
+    manifest: "pyproject.toml",
+};
+
 // Version managers
 
 pub const PYENV: VersionManager = VersionManager {
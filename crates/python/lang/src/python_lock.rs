@@ -0,0 +1,49 @@
+use crate::POETRY;
+use cached::proc_macro::cached;
+use moon_error::MoonError;
+use moon_lang::{config_cache, LockfileDependencyVersions};
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use starbase_utils::toml::read_file as read_toml;
+use std::path::{Path, PathBuf};
+
+// Poetry and uv lockfiles share the same `[[package]]` structure,
+// so we only need to support a single format.
+config_cache!(PythonLock, POETRY.lockfile, read_toml);
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct PythonLockPackage {
+    pub name: String,
+    pub version: String,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct PythonLock {
+    pub package: Vec<PythonLockPackage>,
+
+    #[serde(skip)]
+    pub path: PathBuf,
+}
+
+#[cached(result)]
+pub fn load_lockfile_dependencies(path: PathBuf) -> Result<LockfileDependencyVersions, MoonError> {
+    let mut deps: LockfileDependencyVersions = FxHashMap::default();
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    if let Some(lockfile) = PythonLock::read_with_name(&path, name)? {
+        for package in lockfile.package {
+            deps.entry(package.name)
+                .and_modify(|dep| {
+                    dep.push(package.version.clone());
+                })
+                .or_insert_with(|| vec![package.version]);
+        }
+    }
+
+    Ok(deps)
+}
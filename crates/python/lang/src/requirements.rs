@@ -0,0 +1,48 @@
+use moon_error::MoonError;
+use starbase_utils::fs;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Normalize a package name as per PEP 503, so that `Foo_Bar` and `foo-bar` are equal.
+pub fn normalize_package_name(name: &str) -> String {
+    name.trim().to_lowercase().replace(['_', '.'], "-")
+}
+
+/// Parse the contents of a `requirements.txt` file into a map of package names
+/// to their version specifiers. Options (`-r`, `--index-url`, etc), comments,
+/// and empty lines are ignored.
+pub fn parse_requirements(content: &str) -> BTreeMap<String, String> {
+    let mut deps = BTreeMap::new();
+
+    for line in content.lines() {
+        let line = match line.find(" #") {
+            Some(index) => &line[0..index],
+            None => line,
+        }
+        .trim();
+
+        if line.is_empty() || line.starts_with('#') || line.starts_with('-') {
+            continue;
+        }
+
+        let (name, spec) = match line.find(|c: char| "=<>!~[;@ ".contains(c)) {
+            Some(index) => (&line[0..index], line[index..].trim()),
+            None => (line, ""),
+        };
+
+        deps.insert(
+            normalize_package_name(name),
+            if spec.is_empty() {
+                "*".to_owned()
+            } else {
+                spec.to_owned()
+            },
+        );
+    }
+
+    deps
+}
+
+pub fn load_requirements(path: &Path) -> Result<BTreeMap<String, String>, MoonError> {
+    Ok(parse_requirements(&fs::read_file(path)?))
+}
@@ -0,0 +1,30 @@
+use moon_python_lang::python_lock::*;
+use moon_test_utils::create_sandbox;
+
+#[test]
+fn resolves_poetry_lockfile_deps() {
+    let sandbox = create_sandbox("python");
+    let deps = load_lockfile_dependencies(sandbox.path().join("poetry/poetry.lock")).unwrap();
+
+    assert_eq!(deps.len(), 2);
+    assert_eq!(deps.get("certifi").unwrap(), &vec!["2023.5.7".to_owned()]);
+    assert_eq!(deps.get("requests").unwrap(), &vec!["2.31.0".to_owned()]);
+}
+
+#[test]
+fn resolves_uv_lockfile_deps() {
+    let sandbox = create_sandbox("python");
+    let deps = load_lockfile_dependencies(sandbox.path().join("uv/uv.lock")).unwrap();
+
+    assert_eq!(deps.len(), 2);
+    assert_eq!(deps.get("requests").unwrap(), &vec!["2.31.0".to_owned()]);
+    assert_eq!(deps.get("uv-project").unwrap(), &vec!["0.1.0".to_owned()]);
+}
+
+#[test]
+fn returns_empty_for_missing_lockfile() {
+    let sandbox = create_sandbox("python");
+    let deps = load_lockfile_dependencies(sandbox.path().join("pip/poetry.lock")).unwrap();
+
+    assert!(deps.is_empty());
+}
@@ -0,0 +1,34 @@
+use moon_python_lang::requirements::*;
+use moon_test_utils::create_sandbox;
+use std::collections::BTreeMap;
+
+#[test]
+fn parses_specs_and_ignores_options() {
+    let sandbox = create_sandbox("python");
+    let deps = load_requirements(&sandbox.path().join("pip/requirements.txt")).unwrap();
+
+    assert_eq!(
+        deps,
+        BTreeMap::from_iter([
+            ("flask".to_owned(), ">=2.0,<3.0".to_owned()),
+            ("python-dateutil".to_owned(), "*".to_owned()),
+            ("requests".to_owned(), "==2.31.0".to_owned()),
+        ])
+    );
+}
+
+#[test]
+fn supports_extras_and_markers() {
+    let deps = parse_requirements(
+        "uvicorn[standard]==0.22.0\npywin32 ; sys_platform == 'win32'\npkg @ https://example.com/pkg.whl",
+    );
+
+    assert_eq!(deps.get("uvicorn").unwrap(), "[standard]==0.22.0");
+    assert_eq!(deps.get("pywin32").unwrap(), "; sys_platform == 'win32'");
+    assert_eq!(deps.get("pkg").unwrap(), "@ https://example.com/pkg.whl");
+}
+
+#[test]
+fn normalizes_names() {
+    assert_eq!(normalize_package_name("Foo_Bar.baz"), "foo-bar-baz");
+}
@@ -0,0 +1,337 @@
+use crate::target_hasher::PythonTargetHasher;
+use moon_action_context::ActionContext;
+use moon_config::{HasherConfig, PlatformType, ProjectConfig, PythonConfig, PythonPackageManager};
+use moon_error::MoonError;
+use moon_hasher::{DepsHasher, HashSet};
+use moon_logger::debug;
+use moon_platform::{Platform, Runtime, Version};
+use moon_process::Command;
+use moon_project::Project;
+use moon_python_lang::{
+    python_lock::load_lockfile_dependencies, requirements::load_requirements, PIP, POETRY, UV,
+};
+use moon_python_tool::{get_venv_bin_dir, PythonTool};
+use moon_task::Task;
+use moon_terminal::{print_checkpoint, Checkpoint};
+use moon_tool::{get_path_env_var, Tool, ToolError, ToolManager};
+use moon_utils::async_trait;
+use proto::get_sha256_hash_of_file;
+use rustc_hash::FxHashMap;
+use starbase_styles::color;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+const LOG_TARGET: &str = "moon:python-platform";
+
+#[derive(Debug)]
+pub struct PythonPlatform {
+    pub config: PythonConfig,
+
+    toolchain: ToolManager<PythonTool>,
+
+    workspace_root: PathBuf,
+}
+
+impl PythonPlatform {
+    pub fn new(config: &PythonConfig, workspace_root: &Path) -> Self {
+        PythonPlatform {
+            config: config.to_owned(),
+            toolchain: ToolManager::new(Runtime::Python(Version::new_global())),
+            workspace_root: workspace_root.to_path_buf(),
+        }
+    }
+
+    /// Return the lockfile and manifest file names for the configured package manager.
+    /// Since pip has no lockfile, `requirements.txt` is used for both.
+    pub fn get_dependency_files(&self) -> (&'static str, &'static str) {
+        match self.config.package_manager {
+            PythonPackageManager::Pip => (PIP.manifest, PIP.manifest),
+            PythonPackageManager::Poetry => (POETRY.lockfile, POETRY.manifest),
+            PythonPackageManager::Uv => (UV.lockfile, UV.manifest),
+        }
+    }
+
+    /// Return the virtual environment to run the project's tasks in. If the project
+    /// has its own virtual environment, use it, otherwise use the workspace's.
+    pub fn get_venv_dir(&self, project: &Project) -> PathBuf {
+        let project_venv_dir = project.root.join(&self.config.venv_name);
+
+        if !self.config.root_venv_only && project_venv_dir.exists() {
+            return project_venv_dir;
+        }
+
+        self.workspace_root.join(&self.config.venv_name)
+    }
+
+    fn get_tool(&self, runtime: &Runtime) -> Result<&PythonTool, ToolError> {
+        self.toolchain.get_for_version(runtime.version())
+    }
+}
+
+#[async_trait]
+impl Platform for PythonPlatform {
+    fn get_type(&self) -> PlatformType {
+        PlatformType::Python
+    }
+
+    fn get_runtime_from_config(&self, _project_config: Option<&ProjectConfig>) -> Runtime {
+        Runtime::Python(Version::new_global())
+    }
+
+    fn matches(&self, platform: &PlatformType, runtime: Option<&Runtime>) -> bool {
+        if matches!(platform, PlatformType::Python) {
+            return true;
+        }
+
+        if let Some(runtime) = &runtime {
+            return matches!(runtime, Runtime::Python(_));
+        }
+
+        false
+    }
+
+    // PROJECT GRAPH
+
+    fn is_project_in_dependency_workspace(&self, project: &Project) -> Result<bool, MoonError> {
+        if self.config.root_venv_only || project.root == self.workspace_root {
+            return Ok(true);
+        }
+
+        // Projects with their own manifest are installed into their own virtual environment
+        let (_, manifest) = self.get_dependency_files();
+
+        Ok(!project.root.join(manifest).exists())
+    }
+
+    // TOOLCHAIN
+
+    fn is_toolchain_enabled(&self) -> Result<bool, ToolError> {
+        Ok(false)
+    }
+
+    fn get_tool(&self) -> Result<Box<&dyn Tool>, ToolError> {
+        let tool = self.toolchain.get()?;
+
+        Ok(Box::new(tool))
+    }
+
+    fn get_tool_for_version(&self, version: Version) -> Result<Box<&dyn Tool>, ToolError> {
+        let tool = self.toolchain.get_for_version(&version)?;
+
+        Ok(Box::new(tool))
+    }
+
+    fn get_dependency_configs(&self) -> Result<Option<(String, String)>, ToolError> {
+        let (lockfile, manifest) = self.get_dependency_files();
+
+        Ok(Some((lockfile.to_owned(), manifest.to_owned())))
+    }
+
+    async fn setup_toolchain(&mut self) -> Result<(), ToolError> {
+        let version = Version::new_global();
+        let mut last_versions = FxHashMap::default();
+
+        if !self.toolchain.has(&version) {
+            self.toolchain
+                .register(&version, PythonTool::new(&self.config, &version)?);
+        }
+
+        self.toolchain.setup(&version, &mut last_versions).await?;
+
+        Ok(())
+    }
+
+    async fn teardown_toolchain(&mut self) -> Result<(), ToolError> {
+        self.toolchain.teardown_all().await?;
+
+        Ok(())
+    }
+
+    // ACTIONS
+
+    async fn setup_tool(
+        &mut self,
+        _context: &ActionContext,
+        runtime: &Runtime,
+        last_versions: &mut FxHashMap<String, String>,
+    ) -> Result<u8, ToolError> {
+        let version = runtime.version();
+
+        if !self.toolchain.has(&version) {
+            self.toolchain
+                .register(&version, PythonTool::new(&self.config, &version)?);
+        }
+
+        Ok(self.toolchain.setup(&version, last_versions).await?)
+    }
+
+    async fn install_deps(
+        &self,
+        _context: &ActionContext,
+        runtime: &Runtime,
+        working_dir: &Path,
+    ) -> Result<(), ToolError> {
+        let tool = self.get_tool(runtime)?;
+        let venv_dir = working_dir.join(&self.config.venv_name);
+        let venv_bin_dir = get_venv_bin_dir(&venv_dir);
+        let (_, manifest) = self.get_dependency_files();
+
+        if !venv_dir.exists() {
+            debug!(
+                target: LOG_TARGET,
+                "Creating virtual environment {}",
+                color::path(&venv_dir)
+            );
+
+            let mut command = if matches!(self.config.package_manager, PythonPackageManager::Uv) {
+                print_checkpoint("uv venv", Checkpoint::Setup);
+
+                let mut command = Command::new(UV.binary);
+                command.args(["venv", &self.config.venv_name]);
+                command
+            } else {
+                print_checkpoint("python -m venv", Checkpoint::Setup);
+
+                let mut command = Command::new(tool.get_bin_path()?);
+                command.args(["-m", "venv", &self.config.venv_name]);
+                command
+            };
+
+            command
+                .cwd(working_dir)
+                .create_async()
+                .exec_stream_output()
+                .await?;
+        }
+
+        if !working_dir.join(manifest).exists() {
+            return Ok(());
+        }
+
+        debug!(target: LOG_TARGET, "Installing dependencies");
+
+        let mut command = match self.config.package_manager {
+            PythonPackageManager::Pip => {
+                print_checkpoint("pip install", Checkpoint::Setup);
+
+                let mut command = Command::new(venv_bin_dir.join("python"));
+                command.args(["-m", "pip", "install", "-r", PIP.manifest]);
+                command
+            }
+            PythonPackageManager::Poetry => {
+                print_checkpoint("poetry install", Checkpoint::Setup);
+
+                let mut command = Command::new(POETRY.binary);
+                command.arg("install");
+                command
+            }
+            PythonPackageManager::Uv => {
+                print_checkpoint("uv sync", Checkpoint::Setup);
+
+                let mut command = Command::new(UV.binary);
+                command.arg("sync").env("UV_PROJECT_ENVIRONMENT", &venv_dir);
+                command
+            }
+        };
+
+        // Package managers install into the active virtual environment
+        command
+            .env("PATH", get_path_env_var(&venv_bin_dir))
+            .env("VIRTUAL_ENV", &venv_dir)
+            .cwd(working_dir)
+            .create_async()
+            .exec_stream_output()
+            .await?;
+
+        Ok(())
+    }
+
+    async fn hash_manifest_deps(
+        &self,
+        manifest_path: &Path,
+        hashset: &mut HashSet,
+        _hasher_config: &HasherConfig,
+    ) -> Result<(), ToolError> {
+        let mut hasher = DepsHasher::new("python".into());
+
+        match self.config.package_manager {
+            PythonPackageManager::Pip => {
+                hasher.hash_deps(&load_requirements(manifest_path)?);
+            }
+            PythonPackageManager::Poetry | PythonPackageManager::Uv => {
+                let (lockfile, manifest) = self.get_dependency_files();
+                let lockfile_path = manifest_path.parent().unwrap().join(lockfile);
+
+                // The manifest is not a list of dependencies (it also includes build
+                // settings), so hash the file contents, and the resolved versions
+                hasher.hash_dep(manifest, get_sha256_hash_of_file(manifest_path)?);
+
+                for (name, versions) in load_lockfile_dependencies(lockfile_path)? {
+                    hasher.hash_dep(name, versions.join(","));
+                }
+            }
+        };
+
+        hashset.hash(hasher);
+
+        Ok(())
+    }
+
+    async fn hash_run_target(
+        &self,
+        project: &Project,
+        _runtime: &Runtime,
+        hashset: &mut HashSet,
+        _hasher_config: &HasherConfig,
+    ) -> Result<(), ToolError> {
+        let (lockfile, _) = self.get_dependency_files();
+        let mut lockfile_path = project.root.join(lockfile);
+
+        if self.config.root_venv_only || !lockfile_path.exists() {
+            lockfile_path = self.workspace_root.join(lockfile);
+        }
+
+        if !lockfile_path.exists() {
+            return Ok(());
+        }
+
+        let mut hasher = PythonTargetHasher::new(None);
+
+        hasher.locked_dependencies = match self.config.package_manager {
+            PythonPackageManager::Pip => load_requirements(&lockfile_path)?
+                .into_iter()
+                .map(|(name, spec)| (name, vec![spec]))
+                .collect(),
+            PythonPackageManager::Poetry | PythonPackageManager::Uv => {
+                BTreeMap::from_iter(load_lockfile_dependencies(lockfile_path)?)
+            }
+        };
+
+        hashset.hash(hasher);
+
+        Ok(())
+    }
+
+    async fn create_run_target_command(
+        &self,
+        _context: &ActionContext,
+        project: &Project,
+        task: &Task,
+        _runtime: &Runtime,
+        working_dir: &Path,
+    ) -> Result<Command, ToolError> {
+        let venv_dir = self.get_venv_dir(project);
+        let mut command = Command::new(&task.command);
+
+        // Run within the virtual environment, so that its binaries
+        // and packages take precedence over the global ones
+        command
+            .args(&task.args)
+            .env("PATH", get_path_env_var(&get_venv_bin_dir(&venv_dir)))
+            .env("VIRTUAL_ENV", &venv_dir)
+            .envs(&task.env)
+            .cwd(working_dir);
+
+        Ok(command)
+    }
+}
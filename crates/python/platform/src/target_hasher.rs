@@ -0,0 +1,39 @@
+use moon_hasher::{Digest, Hasher, Sha256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PythonTargetHasher {
+    pub python_version: String,
+
+    // All the dependencies (and their versions) of the project or workspace
+    pub locked_dependencies: BTreeMap<String, Vec<String>>,
+}
+
+impl PythonTargetHasher {
+    pub fn new(python_version: Option<String>) -> Self {
+        PythonTargetHasher {
+            python_version: python_version.unwrap_or_else(|| "unknown".into()),
+            ..PythonTargetHasher::default()
+        }
+    }
+}
+
+impl Hasher for PythonTargetHasher {
+    fn hash(&self, sha: &mut Sha256) {
+        sha.update(self.python_version.as_bytes());
+
+        for (name, versions) in &self.locked_dependencies {
+            sha.update(name.as_bytes());
+
+            for version in versions {
+                sha.update(version.as_bytes());
+            }
+        }
+    }
+
+    fn serialize(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap()
+    }
+}
@@ -0,0 +1,245 @@
+use moon_action_context::ActionContext;
+use moon_config::{HasherConfig, PlatformType, PythonConfig, PythonPackageManager};
+use moon_hasher::HashSet;
+use moon_platform::{Platform, Runtime, Version};
+use moon_process::Command;
+use moon_project::Project;
+use moon_python_platform::PythonPlatform;
+use moon_task::Task;
+use moon_test_utils::create_sandbox;
+use moon_utils::string_vec;
+use std::env;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn create_platform(package_manager: PythonPackageManager, workspace_root: &Path) -> PythonPlatform {
+    PythonPlatform::new(
+        &PythonConfig {
+            package_manager,
+            ..PythonConfig::default()
+        },
+        workspace_root,
+    )
+}
+
+fn create_project(root: PathBuf) -> Project {
+    Project {
+        root,
+        ..Project::default()
+    }
+}
+
+async fn create_target_command(platform: &PythonPlatform, project: &Project) -> Command {
+    let task = Task {
+        command: "pytest".into(),
+        args: string_vec!["-v"],
+        platform: PlatformType::Python,
+        ..Task::default()
+    };
+
+    platform
+        .create_run_target_command(
+            &ActionContext::default(),
+            project,
+            &task,
+            &Runtime::Python(Version::new_global()),
+            &PathBuf::from("cwd"),
+        )
+        .await
+        .unwrap()
+}
+
+fn get_first_path(command: &Command) -> PathBuf {
+    env::split_paths(command.env.get(OsStr::new("PATH")).unwrap())
+        .next()
+        .unwrap()
+}
+
+mod dependency_configs {
+    use super::*;
+
+    #[test]
+    fn uses_requirements_for_pip() {
+        let platform = create_platform(PythonPackageManager::Pip, &PathBuf::new());
+
+        assert_eq!(
+            platform.get_dependency_configs().unwrap(),
+            Some(("requirements.txt".into(), "requirements.txt".into()))
+        );
+    }
+
+    #[test]
+    fn uses_lockfile_for_poetry() {
+        let platform = create_platform(PythonPackageManager::Poetry, &PathBuf::new());
+
+        assert_eq!(
+            platform.get_dependency_configs().unwrap(),
+            Some(("poetry.lock".into(), "pyproject.toml".into()))
+        );
+    }
+
+    #[test]
+    fn uses_lockfile_for_uv() {
+        let platform = create_platform(PythonPackageManager::Uv, &PathBuf::new());
+
+        assert_eq!(
+            platform.get_dependency_configs().unwrap(),
+            Some(("uv.lock".into(), "pyproject.toml".into()))
+        );
+    }
+}
+
+mod dependency_workspace {
+    use super::*;
+
+    #[test]
+    fn root_is_in_workspace() {
+        let sandbox = create_sandbox("python");
+        let platform = create_platform(PythonPackageManager::Pip, sandbox.path());
+
+        assert!(platform
+            .is_project_in_dependency_workspace(&create_project(sandbox.path().to_path_buf()))
+            .unwrap());
+    }
+
+    #[test]
+    fn project_with_manifest_is_not_in_workspace() {
+        let sandbox = create_sandbox("python");
+        let platform = create_platform(PythonPackageManager::Pip, sandbox.path());
+
+        assert!(!platform
+            .is_project_in_dependency_workspace(&create_project(sandbox.path().join("pip")))
+            .unwrap());
+    }
+
+    #[test]
+    fn project_without_manifest_is_in_workspace() {
+        let sandbox = create_sandbox("python");
+        let platform = create_platform(PythonPackageManager::Pip, sandbox.path());
+
+        assert!(platform
+            .is_project_in_dependency_workspace(&create_project(sandbox.path().join("poetry")))
+            .unwrap());
+    }
+
+    #[test]
+    fn all_projects_in_workspace_when_root_only() {
+        let sandbox = create_sandbox("python");
+        let platform = PythonPlatform::new(
+            &PythonConfig {
+                root_venv_only: true,
+                ..PythonConfig::default()
+            },
+            sandbox.path(),
+        );
+
+        assert!(platform
+            .is_project_in_dependency_workspace(&create_project(sandbox.path().join("pip")))
+            .unwrap());
+    }
+}
+
+mod hash_manifest_deps {
+    use super::*;
+
+    async fn hash_manifest(platform: &PythonPlatform, manifest_path: &Path) -> String {
+        let mut hashset = HashSet::default();
+
+        platform
+            .hash_manifest_deps(manifest_path, &mut hashset, &HasherConfig::default())
+            .await
+            .unwrap();
+
+        hashset.generate()
+    }
+
+    #[tokio::test]
+    async fn changes_when_requirements_change() {
+        let sandbox = create_sandbox("python");
+        let platform = create_platform(PythonPackageManager::Pip, sandbox.path());
+        let manifest_path = sandbox.path().join("pip/requirements.txt");
+
+        let before = hash_manifest(&platform, &manifest_path).await;
+
+        fs::write(&manifest_path, "requests==2.30.0").unwrap();
+
+        assert_ne!(before, hash_manifest(&platform, &manifest_path).await);
+    }
+
+    #[tokio::test]
+    async fn ignores_comments_in_requirements() {
+        let sandbox = create_sandbox("python");
+        let platform = create_platform(PythonPackageManager::Pip, sandbox.path());
+        let manifest_path = sandbox.path().join("pip/requirements.txt");
+
+        fs::write(&manifest_path, "requests==2.31.0").unwrap();
+
+        let before = hash_manifest(&platform, &manifest_path).await;
+
+        fs::write(&manifest_path, "# Comment\nrequests==2.31.0 # http\n").unwrap();
+
+        assert_eq!(before, hash_manifest(&platform, &manifest_path).await);
+    }
+
+    #[tokio::test]
+    async fn supports_lockfiles() {
+        let sandbox = create_sandbox("python");
+        let poetry = create_platform(PythonPackageManager::Poetry, sandbox.path());
+        let uv = create_platform(PythonPackageManager::Uv, sandbox.path());
+
+        assert_ne!(
+            hash_manifest(&poetry, &sandbox.path().join("poetry/pyproject.toml")).await,
+            hash_manifest(&uv, &sandbox.path().join("uv/pyproject.toml")).await
+        );
+    }
+}
+
+mod target_command {
+    use super::*;
+
+    #[tokio::test]
+    async fn runs_command_as_is() {
+        let sandbox = create_sandbox("python");
+        let platform = create_platform(PythonPackageManager::Pip, sandbox.path());
+        let command =
+            create_target_command(&platform, &create_project(sandbox.path().join("pip"))).await;
+
+        assert_eq!(command.bin, "pytest");
+        assert_eq!(command.args, &["-v"]);
+    }
+
+    #[tokio::test]
+    async fn uses_workspace_venv() {
+        let sandbox = create_sandbox("python");
+        let platform = create_platform(PythonPackageManager::Pip, sandbox.path());
+        let command =
+            create_target_command(&platform, &create_project(sandbox.path().join("pip"))).await;
+
+        let venv_dir = sandbox.path().join(".venv");
+
+        assert_eq!(
+            command.env.get(OsStr::new("VIRTUAL_ENV")).unwrap(),
+            venv_dir.as_os_str()
+        );
+        assert!(get_first_path(&command).starts_with(venv_dir));
+    }
+
+    #[tokio::test]
+    async fn uses_project_venv_if_exists() {
+        let sandbox = create_sandbox("python");
+        sandbox.create_file("pip/.venv/pyvenv.cfg", "");
+
+        let platform = create_platform(PythonPackageManager::Pip, sandbox.path());
+        let command =
+            create_target_command(&platform, &create_project(sandbox.path().join("pip"))).await;
+
+        let venv_dir = sandbox.path().join("pip/.venv");
+
+        assert_eq!(
+            command.env.get(OsStr::new("VIRTUAL_ENV")).unwrap(),
+            venv_dir.as_os_str()
+        );
+        assert!(get_first_path(&command).starts_with(venv_dir));
+    }
+}
@@ -0,0 +1,3 @@
+mod python_tool;
+
+pub use python_tool::*;
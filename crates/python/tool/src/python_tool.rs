@@ -0,0 +1,57 @@
+use moon_config::{PythonConfig, PythonPackageManager};
+use moon_platform_runtime::Version;
+use moon_python_lang::{PIP, POETRY, UV};
+use moon_tool::{Tool, ToolError};
+use proto::async_trait;
+use std::path::{Path, PathBuf};
+
+/// Return the directory that contains executables within a virtual environment.
+pub fn get_venv_bin_dir(venv_dir: &Path) -> PathBuf {
+    if cfg!(windows) {
+        venv_dir.join("Scripts")
+    } else {
+        venv_dir.join("bin")
+    }
+}
+
+#[derive(Debug)]
+pub struct PythonTool {
+    pub config: PythonConfig,
+
+    pub global: bool,
+}
+
+impl PythonTool {
+    pub fn new(config: &PythonConfig, _version: &Version) -> Result<PythonTool, ToolError> {
+        // Python is not managed by proto, so always use the binary on PATH
+        Ok(PythonTool {
+            config: config.to_owned(),
+            global: true,
+        })
+    }
+
+    /// Return the binary name of the configured package manager.
+    pub fn get_package_manager_bin(&self) -> &'static str {
+        match self.config.package_manager {
+            PythonPackageManager::Pip => PIP.binary,
+            PythonPackageManager::Poetry => POETRY.binary,
+            PythonPackageManager::Uv => UV.binary,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for PythonTool {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn get_bin_path(&self) -> Result<PathBuf, ToolError> {
+        // Most unix systems only ship a `python3` binary
+        Ok(PathBuf::from(if cfg!(windows) {
+            "python"
+        } else {
+            "python3"
+        }))
+    }
+}
@@ -0,0 +1,69 @@
+use cached::proc_macro::cached;
+use moon_error::MoonError;
+use moon_lang::LockfileDependencyVersions;
+use rustc_hash::FxHashMap;
+use starbase_utils::fs;
+use std::path::PathBuf;
+
+/// Parse the contents of a `Gemfile.lock` file into a map of gem names to their
+/// locked versions. Gems from a `GIT` source are in the format of `<version> <revision>`,
+/// while gems with native extensions may be locked to multiple platform versions.
+pub fn parse_gemfile_lock(content: &str) -> LockfileDependencyVersions {
+    let mut deps: LockfileDependencyVersions = FxHashMap::default();
+    let mut in_source = false;
+    let mut in_specs = false;
+    let mut revision = None;
+
+    for line in content.lines() {
+        // Sections are not indented: GEM, GIT, PATH, PLATFORMS, DEPENDENCIES, etc
+        if !line.starts_with(' ') {
+            in_source = matches!(line.trim(), "GEM" | "GIT" | "PATH");
+            in_specs = false;
+            revision = None;
+            continue;
+        }
+
+        if !in_source {
+            continue;
+        }
+
+        // Only gems are indented by 4 spaces, their dependencies by 6
+        if let Some(spec) = line.strip_prefix("    ") {
+            if !in_specs || spec.starts_with(' ') {
+                continue;
+            }
+
+            if let Some((name, version)) = spec.split_once(" (") {
+                let version = version.trim_end_matches(')');
+
+                deps.entry(name.to_owned())
+                    .or_default()
+                    .push(match &revision {
+                        Some(revision) => format!("{version} {revision}"),
+                        None => version.to_owned(),
+                    });
+            }
+
+            continue;
+        }
+
+        let option = line.trim();
+
+        if option == "specs:" {
+            in_specs = true;
+        } else if let Some(rev) = option.strip_prefix("revision: ") {
+            revision = Some(rev.to_owned());
+        }
+    }
+
+    deps
+}
+
+#[cached(result)]
+pub fn load_lockfile_dependencies(path: PathBuf) -> Result<LockfileDependencyVersions, MoonError> {
+    if !path.exists() {
+        return Ok(FxHashMap::default());
+    }
+
+    Ok(parse_gemfile_lock(&fs::read_file(path)?))
+}
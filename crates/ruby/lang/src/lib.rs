@@ -1,4 +1,8 @@
+pub mod gemfile_lock;
+
 use moon_lang::{DependencyManager, Language, VersionManager};
+use starbase_utils::fs;
+use std::path::Path;
 
 pub const RUBY: Language = Language {
     binary: "ruby",
@@ -27,3 +31,23 @@ pub const RBENV: VersionManager = VersionManager {
     binary: "rbenv",
     version_file: ".ruby-version",
 };
+
+/// Detect the Ruby version from a `.ruby-version` file in the provided directory.
+/// Version managers also support a `ruby-` prefix, which is stripped.
+pub fn detect_ruby_version<P: AsRef<Path>>(dir: P) -> Option<String> {
+    let version_path = dir.as_ref().join(RBENV.version_file);
+
+    if !version_path.exists() {
+        return None;
+    }
+
+    let content = fs::read_file(version_path).ok()?;
+    let version = content.trim();
+    let version = version.strip_prefix("ruby-").unwrap_or(version);
+
+    if version.is_empty() {
+        None
+    } else {
+        Some(version.to_owned())
+    }
+}
@@ -0,0 +1,55 @@
+use moon_ruby_lang::gemfile_lock::*;
+use moon_test_utils::create_sandbox;
+
+#[test]
+fn loads_gems_from_all_sources() {
+    let sandbox = create_sandbox("ruby");
+    let deps = load_lockfile_dependencies(sandbox.path().join("web/Gemfile.lock")).unwrap();
+
+    assert_eq!(deps.len(), 9);
+    assert_eq!(deps.get("auth").unwrap(), &vec!["0.1.0".to_owned()]);
+    assert_eq!(deps.get("rails").unwrap(), &vec!["7.0.5".to_owned()]);
+}
+
+#[test]
+fn includes_git_revision() {
+    let sandbox = create_sandbox("ruby");
+    let deps = load_lockfile_dependencies(sandbox.path().join("web/Gemfile.lock")).unwrap();
+
+    assert_eq!(
+        deps.get("sidekiq").unwrap(),
+        &vec!["7.1.1 5c8b3d8e6e0c4d3e7a1b9f2c0d4e6a8b1c3d5e7f".to_owned()]
+    );
+}
+
+#[test]
+fn includes_all_platform_versions() {
+    let sandbox = create_sandbox("ruby");
+    let deps = load_lockfile_dependencies(sandbox.path().join("web/Gemfile.lock")).unwrap();
+
+    assert_eq!(
+        deps.get("nokogiri").unwrap(),
+        &vec![
+            "1.15.2-arm64-darwin".to_owned(),
+            "1.15.2-x86_64-linux".to_owned()
+        ]
+    );
+}
+
+#[test]
+fn ignores_other_sections() {
+    let deps = parse_gemfile_lock(
+        "GEM\n  specs:\n    rake (13.0.6)\n\nDEPENDENCIES\n  rake (~> 13.0)\n\nBUNDLED WITH\n   2.4.10\n",
+    );
+
+    assert_eq!(deps.len(), 1);
+    assert!(!deps.contains_key("2.4.10"));
+}
+
+#[test]
+fn returns_empty_if_missing() {
+    let sandbox = create_sandbox("ruby");
+    let deps = load_lockfile_dependencies(sandbox.path().join("lib/Gemfile.lock")).unwrap();
+
+    assert!(deps.is_empty());
+}
@@ -0,0 +1,27 @@
+use moon_ruby_lang::detect_ruby_version;
+use moon_test_utils::create_sandbox;
+
+#[test]
+fn strips_ruby_prefix() {
+    let sandbox = create_sandbox("ruby");
+
+    assert_eq!(detect_ruby_version(sandbox.path()), Some("3.2.2".into()));
+}
+
+#[test]
+fn supports_plain_versions() {
+    let sandbox = create_sandbox("ruby");
+    sandbox.create_file("web/.ruby-version", "3.1.4\n");
+
+    assert_eq!(
+        detect_ruby_version(sandbox.path().join("web")),
+        Some("3.1.4".into())
+    );
+}
+
+#[test]
+fn returns_none_if_missing() {
+    let sandbox = create_sandbox("ruby");
+
+    assert_eq!(detect_ruby_version(sandbox.path().join("lib")), None);
+}
@@ -0,0 +1,5 @@
+mod platform;
+mod target_hasher;
+
+pub use platform::*;
+pub use target_hasher::*;
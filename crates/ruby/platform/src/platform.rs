@@ -0,0 +1,274 @@
+use crate::target_hasher::RubyTargetHasher;
+use moon_action_context::ActionContext;
+use moon_config::{HasherConfig, PlatformType, ProjectConfig, RubyConfig};
+use moon_error::MoonError;
+use moon_hasher::{DepsHasher, HashSet};
+use moon_logger::debug;
+use moon_platform::{Platform, Runtime, Version};
+use moon_process::Command;
+use moon_project::Project;
+use moon_ruby_lang::{detect_ruby_version, gemfile_lock::load_lockfile_dependencies, BUNDLER};
+use moon_ruby_tool::RubyTool;
+use moon_task::Task;
+use moon_terminal::{print_checkpoint, Checkpoint};
+use moon_tool::{Tool, ToolError, ToolManager};
+use moon_utils::async_trait;
+use proto::get_sha256_hash_of_file;
+use rustc_hash::FxHashMap;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+const LOG_TARGET: &str = "moon:ruby-platform";
+
+/// Gems typically provide an executable of the same name (`rake`, `rubocop`),
+/// or are split into a separate core gem (`rspec` is provided by `rspec-core`).
+fn is_gem_executable(command: &str, gems: &FxHashMap<String, Vec<String>>) -> bool {
+    gems.contains_key(command) || gems.contains_key(&format!("{command}-core"))
+}
+
+#[derive(Debug)]
+pub struct RubyPlatform {
+    pub config: RubyConfig,
+
+    toolchain: ToolManager<RubyTool>,
+
+    workspace_root: PathBuf,
+}
+
+impl RubyPlatform {
+    pub fn new(config: &RubyConfig, workspace_root: &Path) -> Self {
+        RubyPlatform {
+            config: config.to_owned(),
+            toolchain: ToolManager::new(Runtime::Ruby(Version::new_global())),
+            workspace_root: workspace_root.to_path_buf(),
+        }
+    }
+
+    /// Return the directory containing the `Gemfile` for the project's gems.
+    /// If the project has its own `Gemfile`, use it, otherwise use the workspace's.
+    pub fn get_gemfile_dir(&self, project: &Project) -> PathBuf {
+        if project.root.join(BUNDLER.manifest).exists() {
+            return project.root.clone();
+        }
+
+        self.workspace_root.clone()
+    }
+
+    fn get_tool(&self, runtime: &Runtime) -> Result<&RubyTool, ToolError> {
+        self.toolchain.get_for_version(runtime.version())
+    }
+}
+
+#[async_trait]
+impl Platform for RubyPlatform {
+    fn get_type(&self) -> PlatformType {
+        PlatformType::Ruby
+    }
+
+    fn get_runtime_from_config(&self, _project_config: Option<&ProjectConfig>) -> Runtime {
+        // Ruby is not managed by proto, but version managers (rbenv, rvm, etc)
+        // select the version from this file, so use it for accurate hashing
+        if let Some(version) = detect_ruby_version(&self.workspace_root) {
+            return Runtime::Ruby(Version::new(&version));
+        }
+
+        Runtime::Ruby(Version::new_global())
+    }
+
+    fn get_runtime_from_project(&self, project: &Project) -> Runtime {
+        // Projects may select their own version with a `.ruby-version` file
+        if let Some(version) = detect_ruby_version(&project.root) {
+            return Runtime::Ruby(Version::new_override(&version));
+        }
+
+        self.get_runtime_from_config(Some(&project.config))
+    }
+
+    fn matches(&self, platform: &PlatformType, runtime: Option<&Runtime>) -> bool {
+        if matches!(platform, PlatformType::Ruby) {
+            return true;
+        }
+
+        if let Some(runtime) = &runtime {
+            return matches!(runtime, Runtime::Ruby(_));
+        }
+
+        false
+    }
+
+    // PROJECT GRAPH
+
+    fn is_project_in_dependency_workspace(&self, project: &Project) -> Result<bool, MoonError> {
+        if project.root == self.workspace_root {
+            return Ok(true);
+        }
+
+        // Projects with their own `Gemfile` are bundled separately
+        Ok(!project.root.join(BUNDLER.manifest).exists())
+    }
+
+    // TOOLCHAIN
+
+    fn is_toolchain_enabled(&self) -> Result<bool, ToolError> {
+        Ok(false)
+    }
+
+    fn get_tool(&self) -> Result<Box<&dyn Tool>, ToolError> {
+        let tool = self.toolchain.get()?;
+
+        Ok(Box::new(tool))
+    }
+
+    fn get_tool_for_version(&self, version: Version) -> Result<Box<&dyn Tool>, ToolError> {
+        let tool = self.toolchain.get_for_version(&version)?;
+
+        Ok(Box::new(tool))
+    }
+
+    fn get_dependency_configs(&self) -> Result<Option<(String, String)>, ToolError> {
+        Ok(Some((
+            BUNDLER.lockfile.to_owned(),
+            BUNDLER.manifest.to_owned(),
+        )))
+    }
+
+    async fn setup_toolchain(&mut self) -> Result<(), ToolError> {
+        let version = self.get_runtime_from_config(None).version();
+        let mut last_versions = FxHashMap::default();
+
+        if !self.toolchain.has(&version) {
+            self.toolchain
+                .register(&version, RubyTool::new(&self.config, &version)?);
+        }
+
+        self.toolchain.setup(&version, &mut last_versions).await?;
+
+        Ok(())
+    }
+
+    async fn teardown_toolchain(&mut self) -> Result<(), ToolError> {
+        self.toolchain.teardown_all().await?;
+
+        Ok(())
+    }
+
+    // ACTIONS
+
+    async fn setup_tool(
+        &mut self,
+        _context: &ActionContext,
+        runtime: &Runtime,
+        last_versions: &mut FxHashMap<String, String>,
+    ) -> Result<u8, ToolError> {
+        let version = runtime.version();
+
+        if !self.toolchain.has(&version) {
+            self.toolchain
+                .register(&version, RubyTool::new(&self.config, &version)?);
+        }
+
+        Ok(self.toolchain.setup(&version, last_versions).await?)
+    }
+
+    async fn install_deps(
+        &self,
+        _context: &ActionContext,
+        runtime: &Runtime,
+        working_dir: &Path,
+    ) -> Result<(), ToolError> {
+        if !working_dir.join(BUNDLER.manifest).exists() {
+            return Ok(());
+        }
+
+        // Ensure Ruby is available, as Bundler requires it
+        self.get_tool(runtime)?;
+
+        debug!(target: LOG_TARGET, "Installing dependencies");
+
+        print_checkpoint("bundle install", Checkpoint::Setup);
+
+        Command::new(BUNDLER.binary)
+            .arg("install")
+            .cwd(working_dir)
+            .create_async()
+            .exec_stream_output()
+            .await?;
+
+        Ok(())
+    }
+
+    async fn hash_manifest_deps(
+        &self,
+        manifest_path: &Path,
+        hashset: &mut HashSet,
+        _hasher_config: &HasherConfig,
+    ) -> Result<(), ToolError> {
+        let mut hasher = DepsHasher::new("ruby".into());
+
+        // The `Gemfile` is Ruby code and not a list of dependencies,
+        // so hash the file contents, and the locked versions
+        hasher.hash_dep(BUNDLER.manifest, get_sha256_hash_of_file(manifest_path)?);
+
+        let lockfile_path = manifest_path.parent().unwrap().join(BUNDLER.lockfile);
+
+        for (name, versions) in load_lockfile_dependencies(lockfile_path)? {
+            hasher.hash_dep(name, versions.join(","));
+        }
+
+        hashset.hash(hasher);
+
+        Ok(())
+    }
+
+    async fn hash_run_target(
+        &self,
+        project: &Project,
+        runtime: &Runtime,
+        hashset: &mut HashSet,
+        _hasher_config: &HasherConfig,
+    ) -> Result<(), ToolError> {
+        let lockfile_path = self.get_gemfile_dir(project).join(BUNDLER.lockfile);
+
+        if !lockfile_path.exists() {
+            return Ok(());
+        }
+
+        let mut hasher = RubyTargetHasher::new(Some(runtime.version().number));
+
+        hasher.locked_dependencies =
+            BTreeMap::from_iter(load_lockfile_dependencies(lockfile_path)?);
+
+        hashset.hash(hasher);
+
+        Ok(())
+    }
+
+    async fn create_run_target_command(
+        &self,
+        _context: &ActionContext,
+        project: &Project,
+        task: &Task,
+        _runtime: &Runtime,
+        working_dir: &Path,
+    ) -> Result<Command, ToolError> {
+        let gemfile_dir = self.get_gemfile_dir(project);
+        let gems = load_lockfile_dependencies(gemfile_dir.join(BUNDLER.lockfile))?;
+
+        // Gem executables must run through Bundler, so that
+        // the locked versions of all gems are activated
+        let mut command = if is_gem_executable(&task.command, &gems) {
+            let mut command = Command::new(BUNDLER.binary);
+            command
+                .arg("exec")
+                .arg(&task.command)
+                .env("BUNDLE_GEMFILE", gemfile_dir.join(BUNDLER.manifest));
+            command
+        } else {
+            Command::new(&task.command)
+        };
+
+        command.args(&task.args).envs(&task.env).cwd(working_dir);
+
+        Ok(command)
+    }
+}
@@ -0,0 +1,39 @@
+use moon_hasher::{Digest, Hasher, Sha256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RubyTargetHasher {
+    pub ruby_version: String,
+
+    // All the gems (and their versions) of the project or workspace
+    pub locked_dependencies: BTreeMap<String, Vec<String>>,
+}
+
+impl RubyTargetHasher {
+    pub fn new(ruby_version: Option<String>) -> Self {
+        RubyTargetHasher {
+            ruby_version: ruby_version.unwrap_or_else(|| "unknown".into()),
+            ..RubyTargetHasher::default()
+        }
+    }
+}
+
+impl Hasher for RubyTargetHasher {
+    fn hash(&self, sha: &mut Sha256) {
+        sha.update(self.ruby_version.as_bytes());
+
+        for (name, versions) in &self.locked_dependencies {
+            sha.update(name.as_bytes());
+
+            for version in versions {
+                sha.update(version.as_bytes());
+            }
+        }
+    }
+
+    fn serialize(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap()
+    }
+}
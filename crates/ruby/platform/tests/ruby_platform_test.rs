@@ -0,0 +1,309 @@
+use moon_action_context::ActionContext;
+use moon_config::{HasherConfig, PlatformType, RubyConfig};
+use moon_hasher::HashSet;
+use moon_platform::{Platform, Runtime, Version};
+use moon_process::Command;
+use moon_project::Project;
+use moon_ruby_platform::RubyPlatform;
+use moon_task::Task;
+use moon_test_utils::create_sandbox;
+use moon_utils::string_vec;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn create_project(root: PathBuf) -> Project {
+    Project {
+        root,
+        ..Project::default()
+    }
+}
+
+async fn create_target_command(platform: &RubyPlatform, project: &Project, bin: &str) -> Command {
+    let task = Task {
+        command: bin.into(),
+        args: string_vec!["--verbose"],
+        platform: PlatformType::Ruby,
+        ..Task::default()
+    };
+
+    platform
+        .create_run_target_command(
+            &ActionContext::default(),
+            project,
+            &task,
+            &Runtime::Ruby(Version::new_global()),
+            &PathBuf::from("cwd"),
+        )
+        .await
+        .unwrap()
+}
+
+mod runtime {
+    use super::*;
+
+    #[test]
+    fn uses_ruby_version_file() {
+        let sandbox = create_sandbox("ruby");
+        let platform = RubyPlatform::new(&RubyConfig::default(), sandbox.path());
+
+        assert_eq!(
+            platform.get_runtime_from_config(None),
+            Runtime::Ruby(Version::new("3.2.2"))
+        );
+    }
+
+    #[test]
+    fn uses_global_if_no_version_file() {
+        let sandbox = create_sandbox("ruby");
+        fs::remove_file(sandbox.path().join(".ruby-version")).unwrap();
+
+        let platform = RubyPlatform::new(&RubyConfig::default(), sandbox.path());
+
+        assert_eq!(
+            platform.get_runtime_from_config(None),
+            Runtime::Ruby(Version::new_global())
+        );
+    }
+
+    #[test]
+    fn uses_project_ruby_version_file() {
+        let sandbox = create_sandbox("ruby");
+        sandbox.create_file("web/.ruby-version", "ruby-3.1.4");
+
+        let platform = RubyPlatform::new(&RubyConfig::default(), sandbox.path());
+
+        assert_eq!(
+            platform.get_runtime_from_project(&create_project(sandbox.path().join("web"))),
+            Runtime::Ruby(Version::new_override("3.1.4"))
+        );
+    }
+
+    #[test]
+    fn falls_back_to_workspace_ruby_version_file() {
+        let sandbox = create_sandbox("ruby");
+        let platform = RubyPlatform::new(&RubyConfig::default(), sandbox.path());
+
+        assert_eq!(
+            platform.get_runtime_from_project(&create_project(sandbox.path().join("web"))),
+            Runtime::Ruby(Version::new("3.2.2"))
+        );
+    }
+}
+
+mod dependency_configs {
+    use super::*;
+
+    #[test]
+    fn uses_gemfile_and_lock() {
+        let platform = RubyPlatform::new(&RubyConfig::default(), &PathBuf::new());
+
+        assert_eq!(
+            platform.get_dependency_configs().unwrap(),
+            Some(("Gemfile.lock".into(), "Gemfile".into()))
+        );
+    }
+}
+
+mod dependency_workspace {
+    use super::*;
+
+    #[test]
+    fn root_is_in_workspace() {
+        let sandbox = create_sandbox("ruby");
+        let platform = RubyPlatform::new(&RubyConfig::default(), sandbox.path());
+
+        assert!(platform
+            .is_project_in_dependency_workspace(&create_project(sandbox.path().to_path_buf()))
+            .unwrap());
+    }
+
+    #[test]
+    fn project_with_gemfile_is_not_in_workspace() {
+        let sandbox = create_sandbox("ruby");
+        let platform = RubyPlatform::new(&RubyConfig::default(), sandbox.path());
+
+        assert!(!platform
+            .is_project_in_dependency_workspace(&create_project(sandbox.path().join("web")))
+            .unwrap());
+    }
+
+    #[test]
+    fn project_without_gemfile_is_in_workspace() {
+        let sandbox = create_sandbox("ruby");
+        let platform = RubyPlatform::new(&RubyConfig::default(), sandbox.path());
+
+        assert!(platform
+            .is_project_in_dependency_workspace(&create_project(sandbox.path().join("lib")))
+            .unwrap());
+    }
+}
+
+mod hash_manifest_deps {
+    use super::*;
+
+    async fn hash_manifest(platform: &RubyPlatform, manifest_path: &Path) -> String {
+        let mut hashset = HashSet::default();
+
+        platform
+            .hash_manifest_deps(manifest_path, &mut hashset, &HasherConfig::default())
+            .await
+            .unwrap();
+
+        hashset.generate()
+    }
+
+    #[tokio::test]
+    async fn changes_when_gemfile_changes() {
+        let sandbox = create_sandbox("ruby");
+        let platform = RubyPlatform::new(&RubyConfig::default(), sandbox.path());
+        let manifest_path = sandbox.path().join("web/Gemfile");
+
+        let before = hash_manifest(&platform, &manifest_path).await;
+
+        fs::write(
+            &manifest_path,
+            "source \"https://rubygems.org\"\n\ngem \"rails\"\n",
+        )
+        .unwrap();
+
+        assert_ne!(before, hash_manifest(&platform, &manifest_path).await);
+    }
+
+    #[tokio::test]
+    async fn includes_locked_gems() {
+        let sandbox = create_sandbox("ruby");
+        let platform = RubyPlatform::new(&RubyConfig::default(), sandbox.path());
+
+        // Same manifest, but without a lockfile
+        sandbox.create_file(
+            "web-copy/Gemfile",
+            fs::read_to_string(sandbox.path().join("web/Gemfile")).unwrap(),
+        );
+
+        assert_ne!(
+            hash_manifest(&platform, &sandbox.path().join("web/Gemfile")).await,
+            hash_manifest(&platform, &sandbox.path().join("web-copy/Gemfile")).await
+        );
+    }
+}
+
+mod hash_run_target {
+    use super::*;
+
+    async fn hash_target(platform: &RubyPlatform, project: &Project, runtime: &Runtime) -> String {
+        let mut hashset = HashSet::default();
+
+        platform
+            .hash_run_target(project, runtime, &mut hashset, &HasherConfig::default())
+            .await
+            .unwrap();
+
+        hashset.generate()
+    }
+
+    #[tokio::test]
+    async fn changes_with_ruby_version() {
+        let sandbox = create_sandbox("ruby");
+        let platform = RubyPlatform::new(&RubyConfig::default(), sandbox.path());
+        let project = create_project(sandbox.path().join("lib"));
+
+        assert_ne!(
+            hash_target(&platform, &project, &Runtime::Ruby(Version::new("3.2.2"))).await,
+            hash_target(&platform, &project, &Runtime::Ruby(Version::new("3.1.4"))).await
+        );
+    }
+
+    #[tokio::test]
+    async fn uses_project_lockfile() {
+        let sandbox = create_sandbox("ruby");
+        let platform = RubyPlatform::new(&RubyConfig::default(), sandbox.path());
+        let runtime = Runtime::Ruby(Version::new("3.2.2"));
+
+        assert_ne!(
+            hash_target(
+                &platform,
+                &create_project(sandbox.path().join("lib")),
+                &runtime
+            )
+            .await,
+            hash_target(
+                &platform,
+                &create_project(sandbox.path().join("web")),
+                &runtime
+            )
+            .await
+        );
+    }
+}
+
+mod target_command {
+    use super::*;
+
+    #[tokio::test]
+    async fn runs_ruby_as_is() {
+        let sandbox = create_sandbox("ruby");
+        let platform = RubyPlatform::new(&RubyConfig::default(), sandbox.path());
+        let command = create_target_command(
+            &platform,
+            &create_project(sandbox.path().join("lib")),
+            "ruby",
+        )
+        .await;
+
+        assert_eq!(command.bin, "ruby");
+        assert_eq!(command.args, &["--verbose"]);
+    }
+
+    #[tokio::test]
+    async fn runs_gem_executables_with_bundler() {
+        let sandbox = create_sandbox("ruby");
+        let platform = RubyPlatform::new(&RubyConfig::default(), sandbox.path());
+        let command = create_target_command(
+            &platform,
+            &create_project(sandbox.path().join("lib")),
+            "rubocop",
+        )
+        .await;
+
+        assert_eq!(command.bin, "bundle");
+        assert_eq!(command.args, &["exec", "rubocop", "--verbose"]);
+        assert_eq!(
+            command.env.get(OsStr::new("BUNDLE_GEMFILE")).unwrap(),
+            sandbox.path().join("Gemfile").as_os_str()
+        );
+    }
+
+    #[tokio::test]
+    async fn runs_core_gem_executables_with_bundler() {
+        let sandbox = create_sandbox("ruby");
+        let platform = RubyPlatform::new(&RubyConfig::default(), sandbox.path());
+        let command = create_target_command(
+            &platform,
+            &create_project(sandbox.path().join("web")),
+            "rspec",
+        )
+        .await;
+
+        assert_eq!(command.bin, "bundle");
+        assert_eq!(command.args, &["exec", "rspec", "--verbose"]);
+        assert_eq!(
+            command.env.get(OsStr::new("BUNDLE_GEMFILE")).unwrap(),
+            sandbox.path().join("web/Gemfile").as_os_str()
+        );
+    }
+
+    #[tokio::test]
+    async fn runs_unknown_commands_as_is() {
+        let sandbox = create_sandbox("ruby");
+        let platform = RubyPlatform::new(&RubyConfig::default(), sandbox.path());
+        let command = create_target_command(
+            &platform,
+            &create_project(sandbox.path().join("web")),
+            "rubocop",
+        )
+        .await;
+
+        assert_eq!(command.bin, "rubocop");
+    }
+}
@@ -0,0 +1,3 @@
+mod ruby_tool;
+
+pub use ruby_tool::*;
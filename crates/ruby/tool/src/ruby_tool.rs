@@ -0,0 +1,34 @@
+use moon_config::RubyConfig;
+use moon_platform_runtime::Version;
+use moon_ruby_lang::RUBY;
+use moon_tool::{Tool, ToolError};
+use proto::async_trait;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub struct RubyTool {
+    pub config: RubyConfig,
+
+    pub global: bool,
+}
+
+impl RubyTool {
+    pub fn new(config: &RubyConfig, _version: &Version) -> Result<RubyTool, ToolError> {
+        // Ruby is not managed by proto, so always use the binary on PATH
+        Ok(RubyTool {
+            config: config.to_owned(),
+            global: true,
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for RubyTool {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn get_bin_path(&self) -> Result<PathBuf, ToolError> {
+        Ok(PathBuf::from(RUBY.binary))
+    }
+}
@@ -2,17 +2,18 @@ use crate::{bins_hasher::RustBinsHasher, target_hasher::RustTargetHasher};
 use moon_action_context::ActionContext;
 use moon_common::Id;
 use moon_config::{
-    HasherConfig, PlatformType, ProjectConfig, ProjectsAliasesMap, ProjectsSourcesMap, RustConfig,
+    DependencyConfig, DependencyScope, DependencySource, HasherConfig, PlatformType, ProjectConfig,
+    ProjectsAliasesMap, ProjectsSourcesMap, RustConfig,
 };
 use moon_error::MoonError;
 use moon_hasher::HashSet;
-use moon_logger::{debug, map_list};
+use moon_logger::{debug, map_list, warn};
 use moon_platform::{Platform, Runtime, Version};
 use moon_process::Command;
 use moon_project::{Project, ProjectError};
 use moon_rust_lang::{
     cargo_lock::load_lockfile_dependencies,
-    cargo_toml::CargoTomlCache,
+    cargo_toml::{CargoTomlCache, CargoTomlExt, Dependency, DepsSet, Manifest},
     toolchain_toml::{ToolchainToml, ToolchainTomlCache},
     CARGO, RUSTUP, RUSTUP_LEGACY,
 };
@@ -20,7 +21,7 @@ use moon_rust_tool::RustTool;
 use moon_task::Task;
 use moon_terminal::{print_checkpoint, Checkpoint};
 use moon_tool::{Tool, ToolError, ToolManager};
-use moon_utils::{async_trait, string_vec};
+use moon_utils::{async_trait, path, string_vec};
 use proto::{rust::RustLanguage, Executable, Proto};
 use rustc_hash::FxHashMap;
 use starbase_styles::color;
@@ -32,10 +33,30 @@ use std::{
 
 const LOG_TARGET: &str = "moon:rust-platform";
 
+// Implicit relations and aliases are inferred on a best effort basis, so a manifest
+// that fails to parse is logged and skipped, instead of failing the project graph.
+fn read_cargo_toml(dir: &Path) -> Option<Manifest> {
+    match CargoTomlCache::read(dir) {
+        Ok(manifest) => manifest,
+        Err(error) => {
+            warn!(
+                target: LOG_TARGET,
+                "Failed to parse {}, skipping: {}",
+                color::path(dir.join(CARGO.manifest)),
+                error
+            );
+
+            None
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct RustPlatform {
     pub config: RustConfig,
 
+    package_names: FxHashMap<String, Id>,
+
     toolchain: ToolManager<RustTool>,
 
     #[allow(dead_code)]
@@ -46,10 +67,57 @@ impl RustPlatform {
     pub fn new(config: &RustConfig, workspace_root: &Path) -> Self {
         RustPlatform {
             config: config.to_owned(),
+            package_names: FxHashMap::default(),
             toolchain: ToolManager::new(Runtime::Rust(Version::new_global())),
             workspace_root: workspace_root.to_path_buf(),
         }
     }
+
+    fn find_implicit_relations(
+        &self,
+        project: &Project,
+        cargo_root: &Path,
+        cargo_workspace_toml: Option<&Manifest>,
+        deps: &DepsSet,
+        scope: DependencyScope,
+        implicit_deps: &mut Vec<DependencyConfig>,
+    ) {
+        for (dep_name, dep) in deps {
+            // Only path dependencies can point to another project. Paths of
+            // inherited dependencies are relative to the Cargo workspace root.
+            let dep_root = match dep {
+                Dependency::Simple(_) => None,
+                Dependency::Detailed(detail) if !detail.inherited => detail
+                    .path
+                    .as_ref()
+                    .map(|dep_path| project.root.join(dep_path)),
+                _ => cargo_workspace_toml
+                    .and_then(|ws| ws.get_detailed_workspace_dependency(dep_name))
+                    .and_then(|detail| detail.path)
+                    .map(|dep_path| cargo_root.join(dep_path)),
+            };
+
+            let Some(dep_root) = dep_root else {
+                continue;
+            };
+
+            if let Some(dep_cargo_toml) = read_cargo_toml(&path::normalize(dep_root)) {
+                if let Some(dep_project_id) = dep_cargo_toml
+                    .package
+                    .and_then(|package| self.package_names.get(&package.name))
+                {
+                    if dep_project_id != &project.id {
+                        implicit_deps.push(DependencyConfig {
+                            id: dep_project_id.to_owned(),
+                            scope,
+                            source: DependencySource::Implicit,
+                            via: Some(dep_name.to_owned()),
+                        });
+                    }
+                }
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -98,6 +166,15 @@ impl Platform for RustPlatform {
         projects_map: &ProjectsSourcesMap,
         aliases_map: &mut ProjectsAliasesMap,
     ) -> Result<(), MoonError> {
+        // Map crate names to projects, so that we can infer implicit dependencies
+        for (id, source) in projects_map {
+            if let Some(cargo_toml) = read_cargo_toml(&self.workspace_root.join(source)) {
+                if let Some(package) = cargo_toml.package {
+                    self.package_names.insert(package.name, id.to_owned());
+                }
+            }
+        }
+
         // Extract the alias from the Cargo project relative to the lockfile
         for (id, source) in projects_map {
             let project_root = self.workspace_root.join(source);
@@ -106,7 +183,7 @@ impl Platform for RustPlatform {
                 continue;
             }
 
-            if let Some(cargo_toml) = CargoTomlCache::read(project_root)? {
+            if let Some(cargo_toml) = read_cargo_toml(&project_root) {
                 if let Some(package) = cargo_toml.package {
                     if package.name != id.as_str() {
                         debug!(
@@ -127,6 +204,61 @@ impl Platform for RustPlatform {
         Ok(())
     }
 
+    fn load_project_implicit_dependencies(
+        &self,
+        project: &Project,
+        _aliases_map: &ProjectsAliasesMap,
+    ) -> Result<Vec<DependencyConfig>, MoonError> {
+        let mut implicit_deps = vec![];
+
+        let Some(cargo_toml) = read_cargo_toml(&project.root) else {
+            return Ok(implicit_deps);
+        };
+
+        debug!(
+            target: LOG_TARGET,
+            "Scanning {} for implicit dependency relations",
+            color::id(&project.id),
+        );
+
+        // The Cargo workspace is not always the moon workspace, so locate it
+        // from the lockfile, which is only created at the Cargo workspace root
+        let cargo_root = fs::find_upwards(CARGO.lockfile, &project.root)
+            .and_then(|lockfile| lockfile.parent().map(Path::to_path_buf))
+            .unwrap_or_else(|| self.workspace_root.clone());
+        let cargo_workspace_toml = read_cargo_toml(&cargo_root);
+        let ws = cargo_workspace_toml.as_ref();
+
+        // Build dependencies are required to compile the crate, so are not
+        // scoped as development, unlike dev dependencies (tests, examples, etc)
+        for (deps, scope) in [
+            (&cargo_toml.dependencies, DependencyScope::Production),
+            (&cargo_toml.build_dependencies, DependencyScope::Production),
+            (&cargo_toml.dev_dependencies, DependencyScope::Development),
+        ] {
+            self.find_implicit_relations(project, &cargo_root, ws, deps, scope, &mut implicit_deps);
+        }
+
+        for target in cargo_toml.target.values() {
+            for (deps, scope) in [
+                (&target.dependencies, DependencyScope::Production),
+                (&target.build_dependencies, DependencyScope::Production),
+                (&target.dev_dependencies, DependencyScope::Development),
+            ] {
+                self.find_implicit_relations(
+                    project,
+                    &cargo_root,
+                    ws,
+                    deps,
+                    scope,
+                    &mut implicit_deps,
+                );
+            }
+        }
+
+        Ok(implicit_deps)
+    }
+
     // TOOLCHAIN
 
     fn is_toolchain_enabled(&self) -> Result<bool, ToolError> {
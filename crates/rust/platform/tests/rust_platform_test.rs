@@ -1,13 +1,19 @@
+use moon::{generate_project_graph, load_workspace_from};
 use moon_action_context::ActionContext;
-use moon_config::{PlatformType, RustConfig};
+use moon_config::{
+    DependencyScope, PartialRustConfig, PartialToolchainConfig, PartialWorkspaceConfig,
+    PlatformType, ProjectsSourcesMap, RustConfig, WorkspaceProjects,
+};
 use moon_platform::{Platform, Runtime, Version};
 use moon_process::Command;
 use moon_project::Project;
+use moon_project_graph::ProjectGraph;
 use moon_rust_platform::RustPlatform;
 use moon_task::Task;
-use moon_test_utils::create_sandbox;
+use moon_test_utils::{create_sandbox, create_sandbox_with_config, Sandbox};
 use moon_utils::string_vec;
 use rustc_hash::FxHashMap;
+use std::collections::BTreeMap;
 use std::env;
 use std::fs;
 use std::path::PathBuf;
@@ -351,3 +357,149 @@ mod target_command {
         env::remove_var("CARGO_HOME");
     }
 }
+
+mod implicit_dependencies {
+    use super::*;
+
+    fn create_deps_sandbox() -> Sandbox {
+        create_deps_sandbox_with_projects(FxHashMap::from_iter([
+            ("app".into(), "crates/app".to_owned()),
+            ("build-utils".into(), "crates/build-utils".to_owned()),
+            ("common".into(), "crates/common".to_owned()),
+            ("macros".into(), "crates/macros".to_owned()),
+            ("test-utils".into(), "crates/test-utils".to_owned()),
+        ]))
+    }
+
+    fn create_deps_sandbox_with_projects(projects: ProjectsSourcesMap) -> Sandbox {
+        let workspace_config = PartialWorkspaceConfig {
+            projects: Some(WorkspaceProjects::Sources(projects)),
+            ..PartialWorkspaceConfig::default()
+        };
+
+        let toolchain_config = PartialToolchainConfig {
+            rust: Some(PartialRustConfig::default()),
+            ..PartialToolchainConfig::default()
+        };
+
+        create_sandbox_with_config(
+            "rust/deps",
+            Some(workspace_config),
+            Some(toolchain_config),
+            None,
+        )
+    }
+
+    async fn get_deps_graph() -> (ProjectGraph, Sandbox) {
+        let sandbox = create_deps_sandbox();
+        let mut workspace = load_workspace_from(sandbox.path()).await.unwrap();
+        let graph = generate_project_graph(&mut workspace).await.unwrap();
+
+        (graph, sandbox)
+    }
+
+    fn get_dependencies(
+        graph: &ProjectGraph,
+        id: &str,
+    ) -> BTreeMap<String, (DependencyScope, Option<String>)> {
+        graph
+            .get(id)
+            .unwrap()
+            .dependencies
+            .iter()
+            .map(|(id, dep)| (id.to_string(), (dep.scope, dep.via.clone())))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn infers_from_all_dependency_types() {
+        let (graph, _sandbox) = get_deps_graph().await;
+
+        assert_eq!(
+            get_dependencies(&graph, "app"),
+            BTreeMap::from_iter([
+                (
+                    "build-utils".into(),
+                    (DependencyScope::Production, Some("build-utils".into()))
+                ),
+                (
+                    "common".into(),
+                    (DependencyScope::Production, Some("common".into()))
+                ),
+                (
+                    "macros".into(),
+                    (DependencyScope::Development, Some("macros".into()))
+                ),
+                (
+                    "test-utils".into(),
+                    (DependencyScope::Development, Some("test-utils".into()))
+                ),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn infers_from_target_dependencies() {
+        let (graph, _sandbox) = get_deps_graph().await;
+
+        assert_eq!(
+            get_dependencies(&graph, "macros"),
+            BTreeMap::from_iter([(
+                "common".into(),
+                (DependencyScope::Production, Some("common".into()))
+            )])
+        );
+    }
+
+    #[tokio::test]
+    async fn ignores_crates_without_path_deps() {
+        let (graph, _sandbox) = get_deps_graph().await;
+
+        assert!(get_dependencies(&graph, "common").is_empty());
+    }
+
+    #[tokio::test]
+    async fn skips_manifests_that_fail_to_parse() {
+        let sandbox = create_deps_sandbox();
+        sandbox.create_file("crates/common/Cargo.toml", "[package");
+
+        let mut workspace = load_workspace_from(sandbox.path()).await.unwrap();
+        let graph = generate_project_graph(&mut workspace).await.unwrap();
+        let deps = get_dependencies(&graph, "app");
+
+        assert!(deps.contains_key("build-utils"));
+        assert!(!deps.contains_key("common"));
+    }
+
+    #[tokio::test]
+    async fn resolves_inherited_paths_from_the_cargo_workspace_root() {
+        let sandbox = create_deps_sandbox_with_projects(FxHashMap::from_iter([
+            ("app".into(), "nested/crates/app".to_owned()),
+            ("common".into(), "nested/crates/common".to_owned()),
+        ]));
+
+        for file in [
+            "Cargo.toml",
+            "crates/app/Cargo.toml",
+            "crates/common/Cargo.toml",
+        ] {
+            let content = fs::read_to_string(sandbox.path().join(file)).unwrap();
+
+            sandbox.create_file(&format!("nested/{file}"), content);
+        }
+
+        sandbox.create_file("nested/Cargo.lock", "");
+        sandbox.create_file("Cargo.toml", "[workspace]\nmembers = []");
+
+        let mut workspace = load_workspace_from(sandbox.path()).await.unwrap();
+        let graph = generate_project_graph(&mut workspace).await.unwrap();
+
+        assert_eq!(
+            get_dependencies(&graph, "app"),
+            BTreeMap::from_iter([(
+                "common".into(),
+                (DependencyScope::Production, Some("common".into()))
+            )])
+        );
+    }
+}
@@ -61,8 +61,13 @@ impl Serialize for LanguageType {
 derive_enum!(
     #[derive(ConfigEnum, Copy, Default, Hash)]
     pub enum PlatformType {
+        Bun,
         Deno,
+        Go,
         Node,
+        Php,
+        Python,
+        Ruby,
         Rust,
         System,
         #[default]
@@ -86,14 +91,14 @@ impl From<LanguageType> for PlatformType {
             LanguageType::Unknown => PlatformType::Unknown,
             LanguageType::Bash | LanguageType::Batch => PlatformType::System,
             // Deno and Bun are not covered here!
+            LanguageType::Go => PlatformType::Go,
             LanguageType::JavaScript | LanguageType::TypeScript => PlatformType::Node,
+            LanguageType::Php => PlatformType::Php,
+            LanguageType::Python => PlatformType::Python,
             LanguageType::Rust => PlatformType::Rust,
             // TODO: Move these to their own platform once it's been implemented!
-            LanguageType::Go
-            | LanguageType::Php
-            | LanguageType::Python
-            | LanguageType::Ruby
-            | LanguageType::Other(_) => PlatformType::System,
+            LanguageType::Ruby => PlatformType::Ruby,
+            LanguageType::Other(_) => PlatformType::System,
         }
     }
 }
@@ -11,6 +11,7 @@ mod types;
 mod validate;
 mod workspace;
 mod workspace_config;
+mod workspace_file;
 
 pub use inherited_tasks_config::*;
 pub use language_platform::*;
@@ -25,6 +26,7 @@ pub use toolchain_config::*;
 pub use types::*;
 pub use workspace::*;
 pub use workspace_config::*;
+pub use workspace_file::*;
 
 pub fn load_workspace_config_template() -> &'static str {
     include_str!("../templates/workspace.yml")
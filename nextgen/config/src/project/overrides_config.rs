@@ -25,6 +25,9 @@ cacheable!(
 cacheable!(
     #[derive(Clone, Config, Debug)]
     pub struct ProjectToolchainConfig {
+        #[setting(nested)]
+        pub bun: Option<ProjectToolchainCommonToolConfig>,
+
         #[setting(nested)]
         pub node: Option<ProjectToolchainCommonToolConfig>,
 
@@ -1,5 +1,6 @@
 use crate::portable_path::is_glob;
 use moon_common::cacheable;
+use moon_utils::time;
 use schematic::{derive_enum, Config, ConfigEnum, ValidateError};
 use serde::{de, Deserialize, Deserializer, Serialize};
 use serde_yaml::Value;
@@ -18,6 +19,16 @@ fn validate_env_file<D, C>(
     Ok(())
 }
 
+fn validate_timeout<D, C>(timeout: &str, _data: &D, _ctx: &C) -> Result<(), ValidateError> {
+    match time::parse_duration(timeout) {
+        Ok(duration) if duration.is_zero() => {
+            Err(ValidateError::new("timeout must be greater than 0"))
+        }
+        Ok(_) => Ok(()),
+        Err(error) => Err(ValidateError::new(format!("invalid duration: {error}"))),
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 #[serde(untagged, rename_all = "kebab-case")]
 pub enum TaskOptionAffectedFiles {
@@ -143,5 +154,8 @@ cacheable!(
         pub run_from_workspace_root: Option<bool>,
 
         pub shell: Option<bool>,
+
+        #[setting(validate = validate_timeout)]
+        pub timeout: Option<String>,
     }
 );
@@ -1,4 +1,11 @@
-use schematic::{Config, ConfigError, ConfigLoader, Format};
+use schematic::{derive_enum, Config, ConfigEnum, ConfigError, ConfigLoader, Format};
+
+derive_enum!(
+    #[derive(ConfigEnum, Copy)]
+    pub enum TemplateMergeStrategy {
+        ThreeWay,
+    }
+);
 
 /// Docs: https://moonrepo.dev/docs/config/template#frontmatter
 #[derive(Debug, Config, Eq, PartialEq)]
@@ -10,6 +17,14 @@ pub struct TemplateFrontmatterConfig {
     pub schema: String,
 
     pub force: bool,
+
+    #[setting(rename = "for_each")]
+    pub for_each: Option<String>,
+
+    #[setting(rename = "if")]
+    pub condition: Option<String>,
+
+    pub merge: Option<TemplateMergeStrategy>,
     pub to: Option<String>,
     pub skip: bool,
 }
@@ -57,6 +57,12 @@ pub struct TemplateConfig {
     #[setting(validate = validate::not_empty)]
     pub description: String,
 
+    pub extends: Vec<String>,
+
+    pub post_generate: Vec<String>,
+
+    pub register_project: bool,
+
     #[setting(validate = validate::not_empty)]
     pub title: String,
 
@@ -0,0 +1,12 @@
+use crate::validate::validate_semver;
+use schematic::Config;
+use serde::Serialize;
+
+/// Docs: https://moonrepo.dev/docs/config/toolchain#bun
+#[derive(Debug, Clone, Config, Serialize)]
+pub struct BunConfig {
+    pub infer_tasks_from_scripts: bool,
+
+    #[setting(env = "MOON_BUN_VERSION", validate = validate_semver)]
+    pub version: Option<String>,
+}
@@ -0,0 +1,9 @@
+use schematic::Config;
+use serde::Serialize;
+
+/// Docs: https://moonrepo.dev/docs/config/toolchain#go
+#[derive(Debug, Clone, Config, Serialize)]
+pub struct GoConfig {
+    /// Run `go mod tidy` after dependencies have been downloaded.
+    pub tidy_on_install: bool,
+}
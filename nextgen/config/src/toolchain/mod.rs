@@ -1,10 +1,20 @@
+mod bun_config;
 mod deno_config;
+mod go_config;
 mod node_config;
+mod php_config;
+mod python_config;
+mod ruby_config;
 mod rust_config;
 mod typescript_config;
 
+pub use bun_config::*;
 pub use deno_config::*;
+pub use go_config::*;
 pub use node_config::*;
+pub use php_config::*;
+pub use python_config::*;
+pub use ruby_config::*;
 pub use rust_config::*;
 pub use typescript_config::*;
 
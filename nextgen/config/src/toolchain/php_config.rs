@@ -0,0 +1,6 @@
+use schematic::Config;
+use serde::Serialize;
+
+/// Docs: https://moonrepo.dev/docs/config/toolchain#php
+#[derive(Debug, Clone, Config, Serialize)]
+pub struct PhpConfig {}
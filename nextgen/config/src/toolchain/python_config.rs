@@ -0,0 +1,23 @@
+use schematic::{derive_enum, Config, ConfigEnum};
+use serde::Serialize;
+
+derive_enum!(
+    #[derive(ConfigEnum, Copy, Default)]
+    pub enum PythonPackageManager {
+        #[default]
+        Pip,
+        Poetry,
+        Uv,
+    }
+);
+
+/// Docs: https://moonrepo.dev/docs/config/toolchain#python
+#[derive(Debug, Clone, Config, Serialize)]
+pub struct PythonConfig {
+    pub package_manager: PythonPackageManager,
+
+    pub root_venv_only: bool,
+
+    #[setting(default = ".venv")]
+    pub venv_name: String,
+}
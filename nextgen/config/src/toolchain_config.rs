@@ -20,12 +20,27 @@ pub struct ToolchainConfig {
     #[setting(extend, validate = validate::extends_string)]
     pub extends: Option<String>,
 
+    #[setting(nested)]
+    pub bun: Option<BunConfig>,
+
     #[setting(nested)]
     pub deno: Option<DenoConfig>,
 
+    #[setting(nested)]
+    pub go: Option<GoConfig>,
+
     #[setting(nested)]
     pub node: Option<NodeConfig>,
 
+    #[setting(nested)]
+    pub php: Option<PhpConfig>,
+
+    #[setting(nested)]
+    pub python: Option<PythonConfig>,
+
+    #[setting(nested)]
+    pub ruby: Option<RubyConfig>,
+
     #[setting(nested)]
     pub rust: Option<RustConfig>,
 
@@ -34,8 +49,18 @@ pub struct ToolchainConfig {
 }
 
 impl ToolchainConfig {
+    inherit_tool!(BunConfig, bun, "bun", inherit_proto_bun);
+
     inherit_tool_without_version!(DenoConfig, deno, "deno", inherit_proto_deno);
 
+    inherit_tool_without_version!(GoConfig, go, "go", inherit_proto_go);
+
+    inherit_tool_without_version!(PhpConfig, php, "php", inherit_proto_php);
+
+    inherit_tool_without_version!(PythonConfig, python, "python", inherit_proto_python);
+
+    inherit_tool_without_version!(RubyConfig, ruby, "ruby", inherit_proto_ruby);
+
     inherit_tool!(RustConfig, rust, "rust", inherit_proto_rust);
 
     inherit_tool!(NodeConfig, node, "node", inherit_proto_node);
@@ -48,7 +73,12 @@ impl ToolchainConfig {
     );
 
     pub fn inherit_proto(&mut self, proto_tools: &ToolsConfig) -> Result<(), ConfigError> {
+        self.inherit_proto_bun(proto_tools)?;
         self.inherit_proto_deno(proto_tools)?;
+        self.inherit_proto_go(proto_tools)?;
+        self.inherit_proto_php(proto_tools)?;
+        self.inherit_proto_python(proto_tools)?;
+        self.inherit_proto_ruby(proto_tools)?;
         self.inherit_proto_rust(proto_tools)?;
         self.inherit_proto_node(proto_tools)?;
         self.inherit_proto_typescript(proto_tools)?;
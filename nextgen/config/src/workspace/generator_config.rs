@@ -1,8 +1,134 @@
-use crate::portable_path::FilePath;
-use schematic::{validate, Config};
+use crate::portable_path::{FilePath, Portable};
+use schemars::JsonSchema;
+use schematic::{validate, Config, ValidateError};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
 
-fn default_templates<C>(_ctx: &C) -> Option<Vec<FilePath>> {
-    Some(vec![FilePath("./templates".into())])
+const ARCHIVE_EXTENSIONS: [&str; 3] = [".tar.gz", ".tgz", ".zip"];
+
+fn default_templates<C>(_ctx: &C) -> Option<Vec<TemplateLocator>> {
+    Some(vec![TemplateLocator::File {
+        path: FilePath("./templates".into()),
+    }])
+}
+
+/// A location in which templates can be found. Locations that are not on the
+/// local file system are fetched and cached under `.moon/cache/templates`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TemplateLocator {
+    /// A `.tar.gz`, `.tgz`, or `.zip` archive, either from a secure URL
+    /// or a `file://` path: `https://domain.com/templates.tar.gz`
+    Archive { url: String },
+
+    /// A file system path, relative from the workspace root: `./templates`
+    File { path: FilePath },
+
+    /// A git repository, with an optional branch, tag, or commit:
+    /// `git+https://github.com/org/templates.git#v1.0.0`
+    Git {
+        remote_url: String,
+        revision: String,
+    },
+}
+
+impl TemplateLocator {
+    pub fn is_remote(&self) -> bool {
+        !matches!(self, TemplateLocator::File { .. })
+    }
+}
+
+impl Portable for TemplateLocator {
+    fn from_str(value: &str) -> Result<Self, ValidateError> {
+        if let Some(git) = value.strip_prefix("git+") {
+            let (remote_url, revision) = match git.split_once('#') {
+                Some((url, revision)) => (url, revision),
+                None => (git, "HEAD"),
+            };
+
+            if remote_url.is_empty() || revision.is_empty() {
+                return Err(ValidateError::new(
+                    "git locations require a repository URL, for example git+https://github.com/org/templates.git#main",
+                ));
+            }
+
+            return Ok(TemplateLocator::Git {
+                remote_url: remote_url.to_owned(),
+                revision: revision.to_owned(),
+            });
+        }
+
+        if value.starts_with("http://") {
+            return Err(ValidateError::new(
+                "only secure URLs are supported for template archives",
+            ));
+        }
+
+        if value.starts_with("https://") || value.starts_with("file://") {
+            if !ARCHIVE_EXTENSIONS.iter().any(|ext| value.ends_with(ext)) {
+                return Err(ValidateError::new(
+                    "template archives must be a .tar.gz, .tgz, or .zip file",
+                ));
+            }
+
+            return Ok(TemplateLocator::Archive {
+                url: value.to_owned(),
+            });
+        }
+
+        Ok(TemplateLocator::File {
+            path: FilePath::from_str(value)?,
+        })
+    }
+}
+
+impl TryFrom<&str> for TemplateLocator {
+    type Error = ValidateError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        TemplateLocator::from_str(value)
+    }
+}
+
+impl fmt::Display for TemplateLocator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateLocator::Archive { url } => write!(f, "{url}"),
+            TemplateLocator::File { path } => write!(f, "{}", path.as_str()),
+            TemplateLocator::Git {
+                remote_url,
+                revision,
+            } => write!(f, "git+{remote_url}#{revision}"),
+        }
+    }
+}
+
+impl JsonSchema for TemplateLocator {
+    fn schema_name() -> String {
+        "TemplateLocator".to_owned()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        String::json_schema(gen)
+    }
+}
+
+impl<'de> Deserialize<'de> for TemplateLocator {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        TemplateLocator::from_str(&String::deserialize(deserializer)?)
+            .map_err(|error| de::Error::custom(error.message))
+    }
+}
+
+impl Serialize for TemplateLocator {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
 }
 
 #[derive(Clone, Config)]
@@ -11,5 +137,5 @@ pub struct GeneratorConfig {
         validate = validate::not_empty,
         default = default_templates
     )]
-    pub templates: Vec<FilePath>,
+    pub templates: Vec<TemplateLocator>,
 }
@@ -13,8 +13,29 @@ fn validate_webhook_url<T: AsRef<str>, D, C>(
     Ok(())
 }
 
+#[derive(Config)]
+pub struct SubscriberConfig {
+    pub args: Vec<String>,
+
+    #[setting(validate = validate::not_empty)]
+    pub command: String,
+
+    pub events: Vec<String>,
+
+    #[setting(default = 30)]
+    pub timeout: u64,
+}
+
 #[derive(Config)]
 pub struct NotifierConfig {
+    #[setting(nested)]
+    pub subscribers: Vec<SubscriberConfig>,
+
+    pub webhook_events: Vec<String>,
+
+    #[setting(env = "MOON_WEBHOOK_SECRET")]
+    pub webhook_secret: Option<String>,
+
     #[setting(validate = validate_webhook_url)]
     pub webhook_url: Option<String>,
 }
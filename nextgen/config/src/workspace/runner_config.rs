@@ -1,5 +1,52 @@
 use moon_target::Target;
-use schematic::Config;
+use rustc_hash::FxHashMap;
+use schematic::{derive_enum, validate, Config, ConfigEnum};
+
+derive_enum!(
+    #[derive(ConfigEnum, Copy, Default)]
+    pub enum OutputStoreLayout {
+        // A tarball per hash
+        #[default]
+        Archive,
+        // Content-addressed file blobs, with a manifest per hash
+        Cas,
+    }
+);
+
+#[derive(Config)]
+pub struct OutputStoreConfig {
+    pub hardlink: bool,
+
+    pub layout: OutputStoreLayout,
+}
+
+derive_enum!(
+    #[derive(ConfigEnum, Copy, Default)]
+    pub enum RemoteCacheProtocol {
+        // Bazel remote execution API (ActionCache + CAS)
+        Grpc,
+        #[default]
+        Http,
+    }
+);
+
+#[derive(Config)]
+pub struct RemoteCacheConfig {
+    pub headers: FxHashMap<String, String>,
+
+    pub instance_name: Option<String>,
+
+    pub protocol: RemoteCacheProtocol,
+
+    #[setting(env = "MOON_REMOTE_CACHE_TOKEN")]
+    pub token: Option<String>,
+
+    #[setting(validate = validate::url)]
+    pub url: Option<String>,
+
+    #[setting(default = true)]
+    pub writable: bool,
+}
 
 #[derive(Config)]
 pub struct RunnerConfig {
@@ -12,4 +59,10 @@ pub struct RunnerConfig {
     pub inherit_colors_for_piped_tasks: bool,
 
     pub log_running_command: bool,
+
+    #[setting(nested)]
+    pub output_store: OutputStoreConfig,
+
+    #[setting(nested)]
+    pub remote_cache: RemoteCacheConfig,
 }
@@ -0,0 +1,129 @@
+use std::ops::Range;
+
+fn get_indent(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+fn is_content_line(line: &str) -> bool {
+    let line = line.trim();
+
+    !line.is_empty() && !line.starts_with('#')
+}
+
+// A value that is nested on the following lines, instead of inline
+fn is_block_value(value: &str) -> bool {
+    value.is_empty() || value.starts_with('#')
+}
+
+/// Find the line of a mapping key at the provided indentation within the range,
+/// and return its index and the (trimmed) value that follows the key.
+fn find_key<'l>(
+    lines: &[&'l str],
+    key: &str,
+    indent: usize,
+    range: Range<usize>,
+) -> Option<(usize, &'l str)> {
+    for index in range {
+        let line = lines[index];
+
+        if !is_content_line(line) || get_indent(line) != indent {
+            continue;
+        }
+
+        if let Some(value) = line.trim().strip_prefix(key) {
+            if let Some(value) = value.strip_prefix(':') {
+                return Some((index, value.trim()));
+            }
+        }
+    }
+
+    None
+}
+
+/// Find the block that is nested under the header line at the provided index,
+/// and return the index of its last content line, and the indentation of its entries.
+fn find_block(lines: &[&str], header: usize) -> (usize, Option<usize>) {
+    let parent_indent = get_indent(lines[header]);
+    let mut last_line = header;
+    let mut indent = None;
+
+    for (index, line) in lines.iter().enumerate().skip(header + 1) {
+        if !is_content_line(line) {
+            continue;
+        }
+
+        if get_indent(line) <= parent_indent {
+            break;
+        }
+
+        last_line = index;
+        indent.get_or_insert(get_indent(line));
+    }
+
+    (last_line, indent)
+}
+
+/// Add a project to the `projects` setting (or `projects.sources`) of a workspace config
+/// file, by inserting the entry as text, so that comments and formatting are preserved.
+///
+/// Returns `None` when the sources are not declared inline in the file (only globs are
+/// configured, or projects are inherited through `extends`), or are written in a style
+/// that can't be safely edited, like an inline `{ ... }` mapping.
+pub fn add_workspace_project_source(content: &str, id: &str, source: &str) -> Option<String> {
+    let lines = content.split_inclusive('\n').collect::<Vec<_>>();
+
+    let (projects_line, value) = find_key(&lines, "projects", 0, 0..lines.len())?;
+
+    if !is_block_value(value) {
+        return None;
+    }
+
+    let (mut last_line, indent) = find_block(&lines, projects_line);
+    let mut indent = indent?;
+    let projects_range = (projects_line + 1)..(last_line + 1);
+
+    // A list of globs
+    if lines[projects_range.clone()]
+        .iter()
+        .find(|line| is_content_line(line))
+        .map(|line| line.trim_start().starts_with('-'))
+        .unwrap_or_default()
+    {
+        return None;
+    }
+
+    if let Some((sources_line, value)) = find_key(&lines, "sources", indent, projects_range.clone())
+    {
+        if !is_block_value(value) {
+            return None;
+        }
+
+        (last_line, indent) = match find_block(&lines, sources_line) {
+            (last_line, Some(indent)) => (last_line, indent),
+            _ => return None,
+        };
+    } else if find_key(&lines, "globs", indent, projects_range).is_some() {
+        return None;
+    }
+
+    let mut result = String::with_capacity(content.len() + id.len() + source.len() + 8);
+
+    for (index, line) in lines.iter().enumerate() {
+        result.push_str(line);
+
+        if index == last_line {
+            if !line.ends_with('\n') {
+                result.push('\n');
+            }
+
+            result.push_str(&format!(
+                "{}{}: '{}'\n",
+                " ".repeat(indent),
+                id,
+                source.replace('\'', "''")
+            ));
+        }
+    }
+
+    Some(result)
+}
@@ -298,7 +298,7 @@ outputs:
 
         #[test]
         #[should_panic(
-            expected = "unknown variant `perl`, expected one of `deno`, `node`, `rust`, `system`, `unknown`"
+            expected = "unknown variant `perl`, expected one of `bun`, `deno`, `go`, `node`, `php`, `python`, `ruby`, `rust`, `system`, `unknown`"
         )]
         fn errors_on_invalid_variant() {
             test_parse_config("platform: perl", |code| TaskConfig::parse(code));
@@ -529,5 +529,59 @@ options:
             //                 );
             //             }
         }
+
+        mod timeout {
+            use super::*;
+
+            #[test]
+            fn can_set_duration() {
+                let config = test_parse_config(
+                    r"
+options:
+  timeout: 10m
+",
+                    |code| TaskConfig::parse(code),
+                );
+
+                assert_eq!(config.options.timeout, Some("10m".into()));
+            }
+
+            #[test]
+            fn can_set_compound_duration() {
+                let config = test_parse_config(
+                    r"
+options:
+  timeout: 1h 30m
+",
+                    |code| TaskConfig::parse(code),
+                );
+
+                assert_eq!(config.options.timeout, Some("1h 30m".into()));
+            }
+
+            #[test]
+            #[should_panic(expected = "invalid duration")]
+            fn errors_on_invalid_duration() {
+                test_parse_config(
+                    r"
+options:
+  timeout: forever
+",
+                    |code| TaskConfig::parse(code),
+                );
+            }
+
+            #[test]
+            #[should_panic(expected = "timeout must be greater than 0")]
+            fn errors_on_zero() {
+                test_parse_config(
+                    r"
+options:
+  timeout: 0s
+",
+                    |code| TaskConfig::parse(code),
+                );
+            }
+        }
     }
 }
@@ -10,7 +10,7 @@ mod template_config {
 
     #[test]
     #[should_panic(
-        expected = "unknown field `unknown`, expected one of `$schema`, `description`, `title`, `variables`"
+        expected = "unknown field `unknown`, expected one of `$schema`, `description`, `extends`, `postGenerate`, `registerProject`, `title`, `variables`"
     )]
     fn error_unknown_field() {
         test_load_config(CONFIG_TEMPLATE_FILENAME, "unknown: 123", |path| {
@@ -28,6 +28,9 @@ mod template_config {
 
         assert_eq!(config.title, "title");
         assert_eq!(config.description, "description");
+        assert!(config.extends.is_empty());
+        assert!(config.post_generate.is_empty());
+        assert!(!config.register_project);
         assert_eq!(config.variables, FxHashMap::default());
     }
 
@@ -75,6 +78,57 @@ mod template_config {
         }
     }
 
+    mod extends {
+        use super::*;
+
+        #[test]
+        fn can_set_templates() {
+            let config = test_load_config(
+                CONFIG_TEMPLATE_FILENAME,
+                "title: title\ndescription: description\nextends: [base, base-ts-lib]",
+                |path| TemplateConfig::load_from(path),
+            );
+
+            assert_eq!(config.extends, vec!["base", "base-ts-lib"]);
+        }
+
+        #[test]
+        #[should_panic(expected = "invalid type: string \"base\", expected a sequence")]
+        fn invalid_type() {
+            test_load_config(
+                CONFIG_TEMPLATE_FILENAME,
+                "title: title\ndescription: description\nextends: base",
+                |path| TemplateConfig::load_from(path),
+            );
+        }
+    }
+
+    mod post_generate {
+        use super::*;
+
+        #[test]
+        fn can_set_commands() {
+            let config = test_load_config(
+                CONFIG_TEMPLATE_FILENAME,
+                "title: title\ndescription: description\npostGenerate: ['npm install', 'git init']\nregisterProject: true",
+                |path| TemplateConfig::load_from(path),
+            );
+
+            assert_eq!(config.post_generate, vec!["npm install", "git init"]);
+            assert!(config.register_project);
+        }
+
+        #[test]
+        #[should_panic(expected = "invalid type: string \"npm install\", expected a sequence")]
+        fn invalid_type() {
+            test_load_config(
+                CONFIG_TEMPLATE_FILENAME,
+                "title: title\ndescription: description\npostGenerate: npm install",
+                |path| TemplateConfig::load_from(path),
+            );
+        }
+    }
+
     mod variables {
         use super::*;
         use moon_config::{
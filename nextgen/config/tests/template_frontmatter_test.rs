@@ -1,6 +1,6 @@
 mod utils;
 
-use moon_config::TemplateFrontmatterConfig;
+use moon_config::{TemplateFrontmatterConfig, TemplateMergeStrategy};
 use utils::*;
 
 mod template_frontmatter {
@@ -8,7 +8,7 @@ mod template_frontmatter {
 
     #[test]
     #[should_panic(
-        expected = "unknown field `title`, expected one of `$schema`, `force`, `to`, `skip`"
+        expected = "unknown field `title`, expected one of `$schema`, `force`, `for_each`, `if`, `merge`, `to`, `skip`"
     )]
     fn error_unknown_field() {
         test_parse_config("title: test", |code| TemplateFrontmatterConfig::parse(code));
@@ -20,6 +20,9 @@ mod template_frontmatter {
 
         assert!(!config.force);
         assert!(!config.skip);
+        assert_eq!(config.condition, None);
+        assert_eq!(config.for_each, None);
+        assert_eq!(config.merge, None);
         assert_eq!(config.to, None);
     }
 
@@ -59,4 +62,54 @@ mod template_frontmatter {
     fn invalid_to() {
         test_parse_config("to: true", |code| TemplateFrontmatterConfig::parse(code));
     }
+
+    #[test]
+    fn can_set_merge() {
+        let config = test_parse_config("merge: three-way", |code| {
+            TemplateFrontmatterConfig::parse(code)
+        });
+
+        assert_eq!(config.merge, Some(TemplateMergeStrategy::ThreeWay));
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown variant `deep`, expected `three-way`")]
+    fn invalid_merge() {
+        test_parse_config("merge: deep", |code| TemplateFrontmatterConfig::parse(code));
+    }
+
+    #[test]
+    fn can_set_if() {
+        let config = test_parse_config("if: with_tests and name != 'foo'", |code| {
+            TemplateFrontmatterConfig::parse(code)
+        });
+
+        assert_eq!(
+            config.condition,
+            Some("with_tests and name != 'foo'".into())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid type: boolean `true`, expected a string")]
+    fn invalid_if() {
+        test_parse_config("if: true", |code| TemplateFrontmatterConfig::parse(code));
+    }
+
+    #[test]
+    fn can_set_for_each() {
+        let config = test_parse_config("for_each: services", |code| {
+            TemplateFrontmatterConfig::parse(code)
+        });
+
+        assert_eq!(config.for_each, Some("services".into()));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid type: sequence, expected a string")]
+    fn invalid_for_each() {
+        test_parse_config("for_each: [a, b]", |code| {
+            TemplateFrontmatterConfig::parse(code)
+        });
+    }
 }
@@ -1,6 +1,6 @@
 mod utils;
 
-use moon_config::{NodePackageManager, ToolchainConfig};
+use moon_config::{NodePackageManager, PythonPackageManager, ToolchainConfig};
 use proto::ToolsConfig;
 use starbase_sandbox::create_sandbox;
 use std::env;
@@ -13,7 +13,7 @@ mod toolchain_config {
 
     #[test]
     #[should_panic(
-        expected = "unknown field `unknown`, expected one of `$schema`, `extends`, `deno`, `node`, `rust`, `typescript`"
+        expected = "unknown field `unknown`, expected one of `$schema`, `extends`, `bun`, `deno`, `go`, `node`, `php`, `python`, `ruby`, `rust`, `typescript`"
     )]
     fn error_unknown_field() {
         test_load_config(FILENAME, "unknown: 123", |path| {
@@ -70,6 +70,66 @@ mod toolchain_config {
         }
     }
 
+    mod bun {
+        use super::*;
+
+        #[test]
+        fn uses_defaults() {
+            let config = test_load_config(FILENAME, "bun: {}", |path| {
+                ToolchainConfig::load_from(path, &ToolsConfig::default())
+            });
+
+            let cfg = config.bun.unwrap();
+
+            assert!(!cfg.infer_tasks_from_scripts);
+            assert_eq!(cfg.version, None);
+        }
+
+        #[test]
+        fn sets_values() {
+            let config = test_load_config(
+                FILENAME,
+                r"
+bun:
+  inferTasksFromScripts: true
+  version: 1.0.0
+",
+                |path| ToolchainConfig::load_from(path, &ToolsConfig::default()),
+            );
+
+            let cfg = config.bun.unwrap();
+
+            assert!(cfg.infer_tasks_from_scripts);
+            assert_eq!(cfg.version.unwrap(), "1.0.0");
+        }
+
+        #[test]
+        fn enables_via_proto() {
+            let config = test_load_config(FILENAME, "{}", |path| {
+                let mut proto = ToolsConfig::default();
+                proto.tools.insert("bun".into(), "1.0.0".into());
+
+                ToolchainConfig::load_from(path, &proto)
+            });
+
+            assert!(config.bun.is_some());
+            assert_eq!(config.bun.unwrap().version.unwrap(), "1.0.0");
+        }
+
+        #[test]
+        #[should_panic(expected = "not a valid semantic version")]
+        fn validates_version() {
+            test_load_config(
+                FILENAME,
+                r"
+bun:
+  version: '1'
+",
+                |path| ToolchainConfig::load_from(path, &ToolsConfig::default()),
+            );
+        }
+    }
+
     mod deno {
         use super::*;
 
@@ -117,6 +177,40 @@ deno:
         }
     }
 
+    mod go {
+        use super::*;
+
+        #[test]
+        fn uses_defaults() {
+            let config = test_load_config(FILENAME, "go: {}", |path| {
+                ToolchainConfig::load_from(path, &ToolsConfig::default())
+            });
+
+            assert!(!config.go.unwrap().tidy_on_install);
+        }
+
+        #[test]
+        fn sets_values() {
+            let config = test_load_config(FILENAME, "go:\n  tidyOnInstall: true", |path| {
+                ToolchainConfig::load_from(path, &ToolsConfig::default())
+            });
+
+            assert!(config.go.unwrap().tidy_on_install);
+        }
+
+        #[test]
+        fn enables_via_proto() {
+            let config = test_load_config(FILENAME, "{}", |path| {
+                let mut proto = ToolsConfig::default();
+                proto.tools.insert("go".into(), "1.20.0".into());
+
+                ToolchainConfig::load_from(path, &proto)
+            });
+
+            assert!(config.go.is_some());
+        }
+    }
+
     mod node {
         use super::*;
 
@@ -400,6 +494,105 @@ node:
         }
     }
 
+    mod php {
+        use super::*;
+
+        #[test]
+        fn uses_defaults() {
+            let config = test_load_config(FILENAME, "php: {}", |path| {
+                ToolchainConfig::load_from(path, &ToolsConfig::default())
+            });
+
+            assert!(config.php.is_some());
+        }
+
+        #[test]
+        fn enables_via_proto() {
+            let config = test_load_config(FILENAME, "{}", |path| {
+                let mut proto = ToolsConfig::default();
+                proto.tools.insert("php".into(), "8.2.0".into());
+
+                ToolchainConfig::load_from(path, &proto)
+            });
+
+            assert!(config.php.is_some());
+        }
+    }
+
+    mod python {
+        use super::*;
+
+        #[test]
+        fn uses_defaults() {
+            let config = test_load_config(FILENAME, "python: {}", |path| {
+                ToolchainConfig::load_from(path, &ToolsConfig::default())
+            });
+
+            let cfg = config.python.unwrap();
+
+            assert_eq!(cfg.package_manager, PythonPackageManager::Pip);
+            assert!(!cfg.root_venv_only);
+            assert_eq!(cfg.venv_name, ".venv");
+        }
+
+        #[test]
+        fn sets_values() {
+            let config = test_load_config(
+                FILENAME,
+                r"
+python:
+  packageManager: uv
+  rootVenvOnly: true
+  venvName: venv
+",
+                |path| ToolchainConfig::load_from(path, &ToolsConfig::default()),
+            );
+
+            let cfg = config.python.unwrap();
+
+            assert_eq!(cfg.package_manager, PythonPackageManager::Uv);
+            assert!(cfg.root_venv_only);
+            assert_eq!(cfg.venv_name, "venv");
+        }
+
+        #[test]
+        fn enables_via_proto() {
+            let config = test_load_config(FILENAME, "{}", |path| {
+                let mut proto = ToolsConfig::default();
+                proto.tools.insert("python".into(), "3.11.0".into());
+
+                ToolchainConfig::load_from(path, &proto)
+            });
+
+            assert!(config.python.is_some());
+        }
+    }
+
+    mod ruby {
+        use super::*;
+
+        #[test]
+        fn uses_defaults() {
+            let config = test_load_config(FILENAME, "ruby: {}", |path| {
+                ToolchainConfig::load_from(path, &ToolsConfig::default())
+            });
+
+            assert!(config.ruby.is_some());
+        }
+
+        #[test]
+        fn enables_via_proto() {
+            let config = test_load_config(FILENAME, "{}", |path| {
+                let mut proto = ToolsConfig::default();
+                proto.tools.insert("ruby".into(), "3.2.2".into());
+
+                ToolchainConfig::load_from(path, &proto)
+            });
+
+            assert!(config.ruby.is_some());
+        }
+    }
+
     mod rust {
         use super::*;
 
@@ -1,6 +1,9 @@
 mod utils;
 
-use moon_config::{FilePath, VcsManager, WorkspaceConfig, WorkspaceProjects};
+use moon_config::{
+    FilePath, OutputStoreLayout, RemoteCacheProtocol, TemplateLocator, VcsManager, WorkspaceConfig,
+    WorkspaceProjects,
+};
 use rustc_hash::FxHashMap;
 use starbase_sandbox::create_sandbox;
 use utils::*;
@@ -305,7 +308,9 @@ constraints:
 
             assert_eq!(
                 config.generator.templates,
-                vec![FilePath("./templates".into())]
+                vec![TemplateLocator::File {
+                    path: FilePath("./templates".into())
+                }]
             );
         }
 
@@ -327,14 +332,84 @@ generator:
             assert_eq!(
                 config.generator.templates,
                 vec![
-                    FilePath("custom/path".into()),
-                    FilePath("./rel/path".into()),
-                    FilePath("../parent/path".into()),
-                    FilePath("/abs/path".into())
+                    TemplateLocator::File {
+                        path: FilePath("custom/path".into())
+                    },
+                    TemplateLocator::File {
+                        path: FilePath("./rel/path".into())
+                    },
+                    TemplateLocator::File {
+                        path: FilePath("../parent/path".into())
+                    },
+                    TemplateLocator::File {
+                        path: FilePath("/abs/path".into())
+                    },
                 ]
             );
         }
 
+        #[test]
+        fn can_set_remote_templates() {
+            let config = test_load_config(
+                FILENAME,
+                r"
+generator:
+  templates:
+    - git+https://github.com/moonrepo/templates.git#v1.0.0
+    - git+ssh://git@github.com/moonrepo/templates.git
+    - https://domain.com/templates.tar.gz
+    - file://./archives/templates.zip
+",
+                |path| WorkspaceConfig::load_from(path),
+            );
+
+            assert_eq!(
+                config.generator.templates,
+                vec![
+                    TemplateLocator::Git {
+                        remote_url: "https://github.com/moonrepo/templates.git".into(),
+                        revision: "v1.0.0".into(),
+                    },
+                    TemplateLocator::Git {
+                        remote_url: "ssh://git@github.com/moonrepo/templates.git".into(),
+                        revision: "HEAD".into(),
+                    },
+                    TemplateLocator::Archive {
+                        url: "https://domain.com/templates.tar.gz".into(),
+                    },
+                    TemplateLocator::Archive {
+                        url: "file://./archives/templates.zip".into(),
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "only secure URLs are supported for template archives")]
+        fn errors_on_insecure_archive_url() {
+            test_load_config(
+                FILENAME,
+                r"
+generator:
+  templates: ['http://domain.com/templates.tar.gz']
+",
+                |path| WorkspaceConfig::load_from(path),
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "template archives must be a .tar.gz, .tgz, or .zip file")]
+        fn errors_on_unsupported_archive() {
+            test_load_config(
+                FILENAME,
+                r"
+generator:
+  templates: ['https://domain.com/templates.rar']
+",
+                |path| WorkspaceConfig::load_from(path),
+            );
+        }
+
         #[test]
         #[should_panic(expected = "globs are not supported, expected a literal file path")]
         fn errors_on_template_glob() {
@@ -421,6 +496,26 @@ notifier:
             );
         }
 
+        #[test]
+        fn can_set_webhook_delivery_settings() {
+            let config = test_load_config(
+                FILENAME,
+                r"
+notifier:
+  webhookEvents: ['pipeline.finished', 'target.ran']
+  webhookSecret: 'abc123'
+  webhookUrl: 'https://domain.com/some/url'
+",
+                |path| WorkspaceConfig::load_from(path),
+            );
+
+            assert_eq!(
+                config.notifier.webhook_events,
+                vec!["pipeline.finished", "target.ran"]
+            );
+            assert_eq!(config.notifier.webhook_secret, Some("abc123".into()));
+        }
+
         #[test]
         #[should_panic(expected = "not a valid url: relative URL without a base")]
         fn errors_on_invalid_url() {
@@ -442,6 +537,49 @@ notifier:
                 r"
 notifier:
   webhookUrl: 'http://domain.com/some/url'
+",
+                |path| WorkspaceConfig::load_from(path),
+            );
+        }
+
+        #[test]
+        fn can_set_subscribers() {
+            let config = test_load_config(
+                FILENAME,
+                r"
+notifier:
+  subscribers:
+    - command: node
+      args: ['./scripts/policy.js']
+      events: ['pipeline.started']
+      timeout: 5
+    - command: ./telemetry
+",
+                |path| WorkspaceConfig::load_from(path),
+            );
+
+            let subscribers = &config.notifier.subscribers;
+
+            assert_eq!(subscribers.len(), 2);
+            assert_eq!(subscribers[0].command, "node");
+            assert_eq!(subscribers[0].args, vec!["./scripts/policy.js"]);
+            assert_eq!(subscribers[0].events, vec!["pipeline.started"]);
+            assert_eq!(subscribers[0].timeout, 5);
+            assert_eq!(subscribers[1].command, "./telemetry");
+            assert!(subscribers[1].args.is_empty());
+            assert!(subscribers[1].events.is_empty());
+            assert_eq!(subscribers[1].timeout, 30);
+        }
+
+        #[test]
+        #[should_panic(expected = "must not be empty")]
+        fn errors_on_empty_subscriber_command() {
+            test_load_config(
+                FILENAME,
+                r"
+notifier:
+  subscribers:
+    - command: ''
 ",
                 |path| WorkspaceConfig::load_from(path),
             );
@@ -503,6 +641,144 @@ runner:
                 r"
 runner:
   archivableTargets: ['bad target']
+",
+                |path| WorkspaceConfig::load_from(path),
+            );
+        }
+
+        #[test]
+        fn loads_output_store_defaults() {
+            let config = test_load_config(FILENAME, "runner: {}", |path| {
+                WorkspaceConfig::load_from(path)
+            });
+
+            assert_eq!(
+                config.runner.output_store.layout,
+                OutputStoreLayout::Archive
+            );
+            assert!(!config.runner.output_store.hardlink);
+        }
+
+        #[test]
+        fn can_set_output_store() {
+            let config = test_load_config(
+                FILENAME,
+                r"
+runner:
+  outputStore:
+    layout: cas
+    hardlink: true
+",
+                |path| WorkspaceConfig::load_from(path),
+            );
+
+            assert_eq!(config.runner.output_store.layout, OutputStoreLayout::Cas);
+            assert!(config.runner.output_store.hardlink);
+        }
+
+        #[test]
+        #[should_panic(expected = "unknown variant `zip`, expected `archive` or `cas`")]
+        fn errors_on_invalid_output_store_layout() {
+            test_load_config(
+                FILENAME,
+                r"
+runner:
+  outputStore:
+    layout: zip
+",
+                |path| WorkspaceConfig::load_from(path),
+            );
+        }
+
+        #[test]
+        fn loads_remote_cache_defaults() {
+            let config = test_load_config(FILENAME, "runner: {}", |path| {
+                WorkspaceConfig::load_from(path)
+            });
+
+            assert!(config.runner.remote_cache.url.is_none());
+            assert!(config.runner.remote_cache.token.is_none());
+            assert!(config.runner.remote_cache.headers.is_empty());
+            assert!(config.runner.remote_cache.writable);
+            assert_eq!(
+                config.runner.remote_cache.protocol,
+                RemoteCacheProtocol::Http
+            );
+        }
+
+        #[test]
+        fn can_set_remote_cache_grpc() {
+            let config = test_load_config(
+                FILENAME,
+                r"
+runner:
+  remoteCache:
+    url: 'grpc://localhost:9092'
+    protocol: grpc
+    instanceName: main
+",
+                |path| WorkspaceConfig::load_from(path),
+            );
+
+            assert_eq!(
+                config.runner.remote_cache.protocol,
+                RemoteCacheProtocol::Grpc
+            );
+            assert_eq!(
+                config.runner.remote_cache.instance_name,
+                Some("main".into())
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "unknown variant `s3`, expected `grpc` or `http`")]
+        fn errors_on_invalid_remote_cache_protocol() {
+            test_load_config(
+                FILENAME,
+                r"
+runner:
+  remoteCache:
+    protocol: s3
+",
+                |path| WorkspaceConfig::load_from(path),
+            );
+        }
+
+        #[test]
+        fn can_set_remote_cache() {
+            let config = test_load_config(
+                FILENAME,
+                r"
+runner:
+  remoteCache:
+    url: 'http://localhost:8080/cache'
+    headers:
+      X-Team: frontend
+    writable: false
+",
+                |path| WorkspaceConfig::load_from(path),
+            );
+
+            assert_eq!(
+                config.runner.remote_cache.url,
+                Some("http://localhost:8080/cache".into())
+            );
+            assert_eq!(
+                config.runner.remote_cache.headers,
+                FxHashMap::from_iter([("X-Team".into(), "frontend".into())])
+            );
+            assert!(!config.runner.remote_cache.writable);
+        }
+
+        #[test]
+        #[should_panic(expected = "not a valid url")]
+        fn errors_on_invalid_remote_cache_url() {
+            test_load_config(
+                FILENAME,
+                r"
+runner:
+  remoteCache:
+    url: 'invalid value'
 ",
                 |path| WorkspaceConfig::load_from(path),
             );
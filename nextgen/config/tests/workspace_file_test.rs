@@ -0,0 +1,76 @@
+use moon_config::add_workspace_project_source;
+
+fn add(content: &str) -> Option<String> {
+    add_workspace_project_source(content, "app", "apps/app")
+}
+
+mod add_workspace_project_source {
+    use super::*;
+
+    #[test]
+    fn appends_to_sources() {
+        assert_eq!(
+            add("projects:\n  web: 'apps/web'\n").unwrap(),
+            "projects:\n  web: 'apps/web'\n  app: 'apps/app'\n"
+        );
+    }
+
+    #[test]
+    fn preserves_comments_and_formatting() {
+        assert_eq!(
+            add("# Projects\nprojects:\n    # Web\n    web: \"apps/web\"   # main\n\n# VCS\nvcs:\n  manager: 'git'\n").unwrap(),
+            "# Projects\nprojects:\n    # Web\n    web: \"apps/web\"   # main\n    app: 'apps/app'\n\n# VCS\nvcs:\n  manager: 'git'\n"
+        );
+    }
+
+    #[test]
+    fn handles_missing_trailing_newline() {
+        assert_eq!(
+            add("projects:\n  web: 'apps/web'").unwrap(),
+            "projects:\n  web: 'apps/web'\n  app: 'apps/app'\n"
+        );
+    }
+
+    #[test]
+    fn appends_to_nested_sources() {
+        assert_eq!(
+            add("projects:\n  globs:\n    - 'packages/*'\n  sources:\n    web: 'apps/web'\nrunner: {}\n").unwrap(),
+            "projects:\n  globs:\n    - 'packages/*'\n  sources:\n    web: 'apps/web'\n    app: 'apps/app'\nrunner: {}\n"
+        );
+    }
+
+    #[test]
+    fn escapes_quotes_in_source() {
+        assert_eq!(
+            add_workspace_project_source("projects:\n  web: 'apps/web'\n", "app", "it's/app")
+                .unwrap(),
+            "projects:\n  web: 'apps/web'\n  app: 'it''s/app'\n"
+        );
+    }
+
+    #[test]
+    fn skips_when_projects_are_missing() {
+        assert_eq!(add(""), None);
+        assert_eq!(add("{}\n"), None);
+        assert_eq!(add("extends: './shared.yml'\n"), None);
+    }
+
+    #[test]
+    fn skips_when_only_globs() {
+        assert_eq!(add("projects:\n  - 'apps/*'\n"), None);
+        assert_eq!(add("projects:\n  globs:\n    - 'apps/*'\n"), None);
+    }
+
+    #[test]
+    fn skips_when_not_block_style() {
+        assert_eq!(add("projects: { web: 'apps/web' }\n"), None);
+        assert_eq!(add("projects: ['apps/*']\n"), None);
+        assert_eq!(add("projects:\n  globs: ['apps/*']\n  sources: {}\n"), None);
+    }
+
+    #[test]
+    fn skips_when_empty() {
+        assert_eq!(add("projects:\n"), None);
+        assert_eq!(add("projects:\n  sources:\n"), None);
+    }
+}
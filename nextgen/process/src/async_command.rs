@@ -1,15 +1,28 @@
 use crate::command_inspector::CommandInspector;
 use crate::output_to_error;
 use crate::process_error::ProcessError;
-use std::process::{Output, Stdio};
+use moon_common::color;
+use std::io;
+use std::process::{ExitStatus, Output, Stdio};
 use std::sync::{Arc, RwLock};
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
 use tokio::process::{Child, Command};
-use tokio::task;
+use tokio::task::{self, JoinHandle};
+use tokio::time::timeout;
+use tracing::debug;
+
+// How long to wait for a process to exit after being terminated, before killing it
+const TERMINATE_GRACE_PERIOD: Duration = Duration::from_secs(10);
+
+// How long to wait for the output pipes to close after a process has been killed
+const PIPE_DRAIN_PERIOD: Duration = Duration::from_secs(1);
 
 pub struct AsyncCommand<'cmd> {
     pub inner: Command,
     pub inspector: CommandInspector<'cmd>,
+    pub process_group: bool,
+    pub timeout: Option<Duration>,
 }
 
 impl<'cmd> AsyncCommand<'cmd> {
@@ -19,9 +32,13 @@ impl<'cmd> AsyncCommand<'cmd> {
         let command = &mut self.inner;
         let output: Output;
 
-        if self.inspector.should_pass_stdin() {
+        if self.inspector.should_pass_stdin() || self.timeout.is_some() {
             let mut child = command
-                .stdin(Stdio::piped())
+                .stdin(if self.inspector.should_pass_stdin() {
+                    Stdio::piped()
+                } else {
+                    Stdio::null()
+                })
                 .stdout(Stdio::piped())
                 .stderr(Stdio::piped())
                 .spawn()
@@ -30,15 +47,46 @@ impl<'cmd> AsyncCommand<'cmd> {
                     error,
                 })?;
 
-            self.write_input_to_child(&mut child).await?;
+            if self.inspector.should_pass_stdin() {
+                self.write_input_to_child(&mut child).await?;
+            }
 
-            output = child
-                .wait_with_output()
-                .await
-                .map_err(|error| ProcessError::Capture {
-                    bin: self.get_bin_name(),
-                    error,
-                })?;
+            // Read both pipes while waiting, so that the process
+            // doesn't block on a full pipe buffer
+            let mut stderr = child.stderr.take().unwrap();
+            let mut stdout = child.stdout.take().unwrap();
+
+            let stderr_handle = task::spawn(async move {
+                let mut buffer = vec![];
+                let _ = stderr.read_to_end(&mut buffer).await;
+                buffer
+            });
+
+            let stdout_handle = task::spawn(async move {
+                let mut buffer = vec![];
+                let _ = stdout.read_to_end(&mut buffer).await;
+                buffer
+            });
+
+            let (status, timed_out) =
+                self.wait_for_child(&mut child)
+                    .await
+                    .map_err(|error| ProcessError::Capture {
+                        bin: self.get_bin_name(),
+                        error,
+                    })?;
+
+            output = Output {
+                status,
+                stderr: join_reader(stderr_handle, timed_out).await,
+                stdout: join_reader(stdout_handle, timed_out).await,
+            };
+
+            self.handle_timeout(output, timed_out).and_then(|output| {
+                self.handle_nonzero_status(&output, true)?;
+
+                Ok(output)
+            })
         } else {
             output = command
                 .output()
@@ -47,11 +95,11 @@ impl<'cmd> AsyncCommand<'cmd> {
                     bin: self.get_bin_name(),
                     error,
                 })?;
-        }
 
-        self.handle_nonzero_status(&output, true)?;
+            self.handle_nonzero_status(&output, true)?;
 
-        Ok(output)
+            Ok(output)
+        }
     }
 
     pub async fn exec_stream_output(&mut self) -> Result<Output, ProcessError> {
@@ -78,16 +126,22 @@ impl<'cmd> AsyncCommand<'cmd> {
             })?;
         };
 
-        let status = child.wait().await.map_err(|error| ProcessError::Stream {
-            bin: self.get_bin_name(),
-            error,
-        })?;
+        let (status, timed_out) =
+            self.wait_for_child(&mut child)
+                .await
+                .map_err(|error| ProcessError::Stream {
+                    bin: self.get_bin_name(),
+                    error,
+                })?;
 
-        let output = Output {
-            status,
-            stderr: vec![],
-            stdout: vec![],
-        };
+        let output = self.handle_timeout(
+            Output {
+                status,
+                stderr: vec![],
+                stdout: vec![],
+            },
+            timed_out,
+        )?;
 
         self.handle_nonzero_status(&output, false)?;
 
@@ -176,24 +230,27 @@ impl<'cmd> AsyncCommand<'cmd> {
                 .extend(captured_lines);
         }));
 
+        // Attempt to create the child output
+        let (status, timed_out) =
+            self.wait_for_child(&mut child)
+                .await
+                .map_err(|error| ProcessError::StreamCapture {
+                    bin: self.get_bin_name(),
+                    error,
+                })?;
+
         for handle in handles {
-            let _ = handle.await;
+            join_reader(handle, timed_out).await;
         }
 
-        // Attempt to create the child output
-        let status = child
-            .wait()
-            .await
-            .map_err(|error| ProcessError::StreamCapture {
-                bin: self.get_bin_name(),
-                error,
-            })?;
-
-        let output = Output {
-            status,
-            stdout: captured_stdout.read().unwrap().join("\n").into_bytes(),
-            stderr: captured_stderr.read().unwrap().join("\n").into_bytes(),
-        };
+        let output = self.handle_timeout(
+            Output {
+                status,
+                stdout: captured_stdout.read().unwrap().join("\n").into_bytes(),
+                stderr: captured_stderr.read().unwrap().join("\n").into_bytes(),
+            },
+            timed_out,
+        )?;
 
         self.handle_nonzero_status(&output, true)?;
 
@@ -208,6 +265,71 @@ impl<'cmd> AsyncCommand<'cmd> {
             .to_string()
     }
 
+    /// Wait for the child process to exit. If a timeout has been configured and the
+    /// process runs longer, terminate it (and its process group) gracefully (SIGTERM on Unix),
+    /// and then kill it after a grace period. Returns the exit status and whether it timed out.
+    async fn wait_for_child(&self, child: &mut Child) -> io::Result<(ExitStatus, bool)> {
+        let Some(duration) = self.timeout else {
+            return Ok((child.wait().await?, false));
+        };
+
+        if let Ok(status) = timeout(duration, child.wait()).await {
+            return Ok((status?, false));
+        }
+
+        debug!(
+            timeout = ?duration,
+            "Process {} timed out, terminating",
+            color::shell(self.get_bin_name())
+        );
+
+        #[cfg(unix)]
+        if let Some(pid) = child.id() {
+            // When the child was spawned as the leader of its own process group,
+            // signal the entire group to also reach any grandchildren
+            let target = if self.process_group {
+                -(pid as libc::pid_t)
+            } else {
+                pid as libc::pid_t
+            };
+
+            // SAFETY: The process is a child that has not been reaped yet
+            unsafe {
+                libc::kill(target, libc::SIGTERM);
+            }
+
+            if let Ok(status) = timeout(TERMINATE_GRACE_PERIOD, child.wait()).await {
+                return Ok((status?, true));
+            }
+
+            debug!(
+                "Process {} did not exit after being terminated, killing",
+                color::shell(self.get_bin_name())
+            );
+
+            // SAFETY: The process is a child that has not been reaped yet
+            unsafe {
+                libc::kill(target, libc::SIGKILL);
+            }
+        }
+
+        child.kill().await?;
+
+        Ok((child.wait().await?, true))
+    }
+
+    fn handle_timeout(&self, output: Output, timed_out: bool) -> Result<Output, ProcessError> {
+        if timed_out {
+            return Err(ProcessError::TimedOut {
+                bin: self.get_bin_name(),
+                output,
+                timeout: self.timeout.unwrap_or_default(),
+            });
+        }
+
+        Ok(output)
+    }
+
     fn handle_nonzero_status(
         &self,
         output: &Output,
@@ -240,3 +362,19 @@ impl<'cmd> AsyncCommand<'cmd> {
         Ok(())
     }
 }
+
+/// Wait for a pipe reader to finish. When the process was killed, descendants that
+/// escaped it may still hold the pipe open, so only wait for a short period.
+async fn join_reader<T: Default>(mut handle: JoinHandle<T>, timed_out: bool) -> T {
+    if !timed_out {
+        return handle.await.unwrap_or_default();
+    }
+
+    match timeout(PIPE_DRAIN_PERIOD, &mut handle).await {
+        Ok(result) => result.unwrap_or_default(),
+        Err(_) => {
+            handle.abort();
+            T::default()
+        }
+    }
+}
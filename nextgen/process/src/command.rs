@@ -4,6 +4,8 @@ use rustc_hash::FxHashMap;
 use std::{
     ffi::{OsStr, OsString},
     path::{Path, PathBuf},
+    process::Command as StdCommand,
+    time::Duration,
 };
 use tokio::process::Command as TokioCommand;
 
@@ -29,8 +31,16 @@ pub struct Command {
     /// Log the command to the terminal before running
     pub print_command: bool,
 
+    /// Run the process in its own process group (on Unix), so that any processes
+    /// it spawns are also terminated on timeout. This detaches it from the terminal,
+    /// so it will not receive Ctrl+C, and should not be used for interactive processes.
+    pub process_group: bool,
+
     /// Shell to wrap executing commands in
     pub shell: Option<shell::Shell>,
+
+    /// Terminate the process if it runs longer than this duration
+    pub timeout: Option<Duration>,
 }
 
 impl Command {
@@ -46,7 +56,9 @@ impl Command {
             input: vec![],
             prefix: None,
             print_command: false,
+            process_group: false,
             shell: None,
+            timeout: None,
         };
 
         // Referencing a batch script needs to be ran with a shell
@@ -94,7 +106,16 @@ impl Command {
         let inspector = self.inspect();
         let command_line = inspector.get_command_line();
 
-        let mut command = TokioCommand::new(&command_line.command[0]);
+        let mut std_command = StdCommand::new(&command_line.command[0]);
+
+        #[cfg(unix)]
+        if self.process_group {
+            use std::os::unix::process::CommandExt;
+
+            std_command.process_group(0);
+        }
+
+        let mut command = TokioCommand::from(std_command);
         command.args(&command_line.command[1..]);
         command.envs(&self.env);
         command.kill_on_drop(true);
@@ -106,6 +127,8 @@ impl Command {
         AsyncCommand {
             inner: command,
             inspector,
+            process_group: self.process_group,
+            timeout: self.timeout,
         }
     }
 
@@ -193,11 +216,21 @@ impl Command {
         self
     }
 
+    pub fn set_process_group(&mut self, state: bool) -> &mut Command {
+        self.process_group = state;
+        self
+    }
+
     pub fn set_shell(&mut self, shell: shell::Shell) -> &mut Command {
         self.shell = Some(shell);
         self
     }
 
+    pub fn set_timeout(&mut self, timeout: Duration) -> &mut Command {
+        self.timeout = Some(timeout);
+        self
+    }
+
     pub fn with_shell(&mut self) -> &mut Command {
         self.set_shell(shell::create_shell());
         self
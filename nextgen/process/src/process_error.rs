@@ -1,6 +1,9 @@
 use miette::Diagnostic;
 use moon_args::ArgsSplitError;
 use moon_common::{Style, Stylize};
+use moon_utils::time;
+use std::process::Output;
+use std::time::Duration;
 use thiserror::Error;
 
 #[derive(Error, Debug, Diagnostic)]
@@ -48,6 +51,18 @@ pub enum ProcessError {
         error: std::io::Error,
     },
 
+    #[diagnostic(code(process::timed_out))]
+    #[error(
+        "Process {} timed out after {}.",
+        .bin.style(Style::Shell),
+        time::format_duration(*.timeout).to_string().style(Style::Symbol),
+    )]
+    TimedOut {
+        bin: String,
+        output: Output,
+        timeout: Duration,
+    },
+
     #[diagnostic(code(process::stdin::failed))]
     #[error("Failed to write stdin to {}.", .bin.style(Style::Shell))]
     WriteInput {
@@ -0,0 +1,68 @@
+#![cfg(unix)]
+
+use moon_process::{Command, ProcessError};
+use std::time::{Duration, Instant};
+
+fn create_orphaning_command() -> Command {
+    let mut command = Command::new("bash");
+    command.args(["-c", "echo started; sleep 100 & wait"]);
+    command.set_timeout(Duration::from_millis(500));
+    command.set_process_group(true);
+    command
+}
+
+mod timeout {
+    use super::*;
+
+    #[tokio::test]
+    async fn terminates_descendants_when_capturing() {
+        let start = Instant::now();
+        let result = create_orphaning_command()
+            .create_async()
+            .exec_capture_output()
+            .await;
+
+        assert!(matches!(result, Err(ProcessError::TimedOut { .. })));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn terminates_descendants_when_streaming_and_capturing() {
+        let start = Instant::now();
+        let result = create_orphaning_command()
+            .create_async()
+            .exec_stream_and_capture_output()
+            .await;
+
+        let Err(ProcessError::TimedOut { output, .. }) = result else {
+            panic!("Expected a timeout error");
+        };
+
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "started");
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn terminates_process_without_process_group() {
+        let mut command = Command::new("sleep");
+        command.arg("100");
+        command.set_timeout(Duration::from_millis(500));
+
+        let start = Instant::now();
+        let result = command.create_async().exec_capture_output().await;
+
+        assert!(matches!(result, Err(ProcessError::TimedOut { .. })));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn returns_output_when_completed_in_time() {
+        let mut command = Command::new("bash");
+        command.args(["-c", "echo done"]);
+        command.set_timeout(Duration::from_secs(5));
+
+        let output = command.create_async().exec_capture_output().await.unwrap();
+
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "done\n");
+    }
+}
@@ -1,24 +1,41 @@
 use crate::parser::{parse_query, AstNode, ComparisonOperator, LogicalOperator};
 use crate::query_error::QueryError;
 use moon_config::{LanguageType, PlatformType, ProjectType, TaskType};
+use pest::error::InputLocation;
 use starbase_utils::glob::{GlobError, GlobSet};
 use std::cmp::PartialEq;
+use std::ops::Range;
 use std::str::FromStr;
 
-#[derive(Debug, PartialEq)]
+/// Depth of a graph traversal, where `None` is unlimited (transitive).
+pub type GraphDepth = Option<usize>;
+
+#[derive(Clone, Debug, PartialEq)]
 pub enum Field {
+    Affected { affected: bool, depth: GraphDepth },
+    Channel(Vec<String>),
+    DependencyOf { ids: Vec<String>, depth: GraphDepth },
+    DependentOf { ids: Vec<String>, depth: GraphDepth },
     Language(Vec<LanguageType>),
+    Maintainers(Vec<String>),
+    Owner(Vec<String>),
     Project(Vec<String>),
     ProjectAlias(Vec<String>),
     ProjectSource(Vec<String>),
     ProjectType(Vec<ProjectType>),
     Tag(Vec<String>),
     Task(Vec<String>),
+    TaskCommand(Vec<String>),
+    TaskDep(Vec<String>),
+    TaskEnv(Vec<String>),
+    TaskInput(Vec<String>),
+    TaskOption { option: String, values: Vec<String> },
+    TaskOutput(Vec<String>),
     TaskPlatform(Vec<PlatformType>),
     TaskType(Vec<TaskType>),
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Condition {
     Field {
         field: Field,
@@ -27,6 +44,9 @@ pub enum Condition {
     Criteria {
         criteria: Criteria,
     },
+    Not {
+        criteria: Criteria,
+    },
 }
 
 impl Condition {
@@ -38,18 +58,32 @@ impl Condition {
                 ComparisonOperator::Like => GlobSet::new(haystack)?.is_match(needle),
                 ComparisonOperator::NotLike => !GlobSet::new(haystack)?.is_match(needle),
             },
-            Condition::Criteria { .. } => false,
+            Condition::Criteria { .. } | Condition::Not { .. } => false,
         })
     }
 
+    /// Match a list of values against the condition. For negated operators, the
+    /// condition only matches when *none* of the values match the positive operator.
     pub fn matches_list(&self, haystack: &[String], needles: &[String]) -> Result<bool, GlobError> {
-        for needle in needles {
-            if self.matches(haystack, needle)? {
-                return Ok(true);
+        let Condition::Field { op, .. } = self else {
+            return Ok(false);
+        };
+
+        let matches_any = match op {
+            ComparisonOperator::Equal | ComparisonOperator::NotEqual => {
+                needles.iter().any(|needle| haystack.contains(needle))
             }
-        }
+            ComparisonOperator::Like | ComparisonOperator::NotLike => {
+                let globset = GlobSet::new(haystack)?;
+
+                needles.iter().any(|needle| globset.is_match(needle))
+            }
+        };
 
-        Ok(false)
+        Ok(match op {
+            ComparisonOperator::NotEqual | ComparisonOperator::NotLike => !matches_any,
+            _ => matches_any,
+        })
     }
 
     pub fn matches_enum<T: PartialEq>(
@@ -64,31 +98,86 @@ impl Condition {
                 // Like and NotLike are not supported for enums
                 _ => false,
             },
-            Condition::Criteria { .. } => false,
+            Condition::Criteria { .. } | Condition::Not { .. } => false,
         })
     }
 }
 
-#[derive(Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct Criteria {
     pub op: LogicalOperator,
     pub conditions: Vec<Condition>,
     pub input: Option<String>,
 }
 
+impl Criteria {
+    /// Return true if any condition, including nested conditions,
+    /// requires touched files to be resolved.
+    pub fn requires_touched_files(&self) -> bool {
+        self.conditions.iter().any(|condition| match condition {
+            Condition::Field { field, .. } => matches!(field, Field::Affected { .. }),
+            Condition::Criteria { criteria } | Condition::Not { criteria } => {
+                criteria.requires_touched_files()
+            }
+        })
+    }
+}
+
 impl AsRef<Criteria> for Criteria {
     fn as_ref(&self) -> &Criteria {
         self
     }
 }
 
+/// The location of a comparison within the query, for labeling errors.
+struct Location<'q> {
+    input: &'q str,
+    span: Range<usize>,
+}
+
+impl<'q> Location<'q> {
+    fn unknown_field(&self, field: &str) -> QueryError {
+        QueryError::UnknownField {
+            field: field.to_owned(),
+            input: self.input.to_owned(),
+            span: self.span.clone().into(),
+        }
+    }
+
+    fn unknown_field_value(&self, field: &str, value: String) -> QueryError {
+        QueryError::UnknownFieldValue {
+            field: field.to_owned(),
+            value,
+            input: self.input.to_owned(),
+            span: self.span.clone().into(),
+        }
+    }
+
+    fn unsupported_depth_modifier(&self, field: &str) -> QueryError {
+        QueryError::UnsupportedDepthModifier {
+            field: field.to_owned(),
+            input: self.input.to_owned(),
+            span: self.span.clone().into(),
+        }
+    }
+
+    fn unsupported_like_operator(&self, field: &str) -> QueryError {
+        QueryError::UnsupportedLikeOperator {
+            field: field.to_owned(),
+            input: self.input.to_owned(),
+            span: self.span.clone().into(),
+        }
+    }
+}
+
 fn build_criteria_enum<T: FromStr>(
     field: &str,
     op: &ComparisonOperator,
     values: Vec<String>,
+    location: &Location,
 ) -> Result<Vec<T>, QueryError> {
     if matches!(op, ComparisonOperator::Like | ComparisonOperator::NotLike) {
-        return Err(QueryError::UnsupportedLikeOperator(field.to_owned()));
+        return Err(location.unsupported_like_operator(field));
     }
 
     let mut result = vec![];
@@ -97,57 +186,139 @@ fn build_criteria_enum<T: FromStr>(
         result.push(
             value
                 .parse()
-                .map_err(|_| QueryError::UnknownFieldValue(field.to_owned(), value))?,
+                .map_err(|_| location.unknown_field_value(field, value))?,
         );
     }
 
     Ok(result)
 }
 
-fn build_criteria(ast: Vec<AstNode>) -> Result<Criteria, QueryError> {
+fn build_criteria_bool(
+    field: &str,
+    op: &ComparisonOperator,
+    values: Vec<String>,
+    location: &Location,
+) -> Result<bool, QueryError> {
+    if matches!(op, ComparisonOperator::Like | ComparisonOperator::NotLike) {
+        return Err(location.unsupported_like_operator(field));
+    }
+
+    match values.as_slice() {
+        [value] if value == "true" => Ok(true),
+        [value] if value == "false" => Ok(false),
+        _ => Err(location.unknown_field_value(field, values.join(","))),
+    }
+}
+
+/// Split a field into its name and an optional depth modifier,
+/// where `dependentOf:2` is a depth of 2, and `dependentOf:*` is unlimited.
+fn parse_field_depth<'f>(
+    field: &'f str,
+    location: &Location,
+) -> Result<(&'f str, Option<GraphDepth>), QueryError> {
+    let Some((name, depth)) = field.split_once(':') else {
+        return Ok((field, None));
+    };
+
+    if depth == "*" {
+        return Ok((name, Some(None)));
+    }
+
+    let depth = depth
+        .parse::<usize>()
+        .map_err(|_| location.unknown_field(field))?;
+
+    Ok((name, Some(Some(depth))))
+}
+
+fn build_criteria(ast: Vec<AstNode>, input: &str) -> Result<Criteria, QueryError> {
     let mut op = None;
     let mut conditions = vec![];
 
     for node in ast {
         match node {
-            AstNode::Comparison { field, op, value } => {
-                let field = match field.as_str() {
-                    "language" => {
-                        Field::Language(build_criteria_enum::<LanguageType>(&field, &op, value)?)
-                    }
+            AstNode::Comparison {
+                field,
+                op,
+                value,
+                span,
+            } => {
+                let location = Location { input, span };
+                let (name, depth) = parse_field_depth(&field, &location)?;
+
+                if depth.is_some()
+                    && !matches!(
+                        name,
+                        "affected" | "dependencyOf" | "dependentOf" | "dependsOn"
+                    )
+                {
+                    return Err(location.unsupported_depth_modifier(name));
+                }
+
+                let field = match name {
+                    "affected" => Field::Affected {
+                        affected: build_criteria_bool(name, &op, value, &location)?,
+                        depth: depth.unwrap_or(Some(0)),
+                    },
+                    "channel" => Field::Channel(value),
+                    "dependencyOf" => Field::DependencyOf {
+                        ids: value,
+                        depth: depth.unwrap_or(Some(1)),
+                    },
+                    "dependentOf" | "dependsOn" => Field::DependentOf {
+                        ids: value,
+                        depth: depth.unwrap_or(Some(1)),
+                    },
+                    "language" => Field::Language(build_criteria_enum::<LanguageType>(
+                        name, &op, value, &location,
+                    )?),
+                    "maintainers" => Field::Maintainers(value),
+                    "owner" => Field::Owner(value),
                     "project" => Field::Project(value),
                     "projectAlias" => Field::ProjectAlias(value),
                     "projectSource" => Field::ProjectSource(value),
-                    "projectType" => {
-                        Field::ProjectType(build_criteria_enum::<ProjectType>(&field, &op, value)?)
-                    }
+                    "projectType" => Field::ProjectType(build_criteria_enum::<ProjectType>(
+                        name, &op, value, &location,
+                    )?),
                     "tag" => Field::Tag(value),
                     "task" => Field::Task(value),
+                    "taskCommand" => Field::TaskCommand(value),
+                    "taskDep" => Field::TaskDep(value),
+                    "taskEnv" => Field::TaskEnv(value),
+                    "taskInput" => Field::TaskInput(value),
+                    "taskOutput" => Field::TaskOutput(value),
                     "taskPlatform" => Field::TaskPlatform(build_criteria_enum::<PlatformType>(
-                        &field, &op, value,
+                        name, &op, value, &location,
+                    )?),
+                    "taskType" => Field::TaskType(build_criteria_enum::<TaskType>(
+                        name, &op, value, &location,
                     )?),
-                    "taskType" => {
-                        Field::TaskType(build_criteria_enum::<TaskType>(&field, &op, value)?)
-                    }
-                    _ => {
-                        return Err(QueryError::UnknownField(field));
-                    }
+                    _ => match name.split_once('.') {
+                        Some(("taskOption", option)) => Field::TaskOption {
+                            option: option.to_owned(),
+                            values: value,
+                        },
+                        _ => {
+                            return Err(location.unknown_field(name));
+                        }
+                    },
                 };
 
                 conditions.push(Condition::Field { field, op });
             }
+            // Precedence has already been applied when parsing,
+            // so each group only contains a single logical operator
             AstNode::Op { op: next_op } => {
-                if let Some(current_op) = &op {
-                    if &next_op != current_op {
-                        return Err(QueryError::LogicalOperatorMismatch);
-                    }
-                } else {
-                    op = Some(next_op);
-                }
+                op = Some(next_op);
             }
             AstNode::Group { nodes } => {
                 conditions.push(Condition::Criteria {
-                    criteria: build_criteria(nodes)?,
+                    criteria: build_criteria(nodes, input)?,
+                });
+            }
+            AstNode::NotGroup { nodes } => {
+                conditions.push(Condition::Not {
+                    criteria: build_criteria(nodes, input)?,
                 });
             }
         }
@@ -167,8 +338,28 @@ pub fn build_query<I: AsRef<str>>(input: I) -> Result<Criteria, QueryError> {
         return Err(QueryError::EmptyInput);
     }
 
-    let mut criteria =
-        build_criteria(parse_query(input).map_err(|e| QueryError::ParseFailure(e.to_string()))?)?;
+    let mut criteria = build_criteria(
+        parse_query(input).map_err(|error| {
+            let (start, end) = match error.location {
+                InputLocation::Pos(pos) => {
+                    // Highlight the entire token that failed to parse
+                    let length = input[pos..]
+                        .find(char::is_whitespace)
+                        .unwrap_or(input.len() - pos);
+
+                    (pos, pos + length)
+                }
+                InputLocation::Span(span) => span,
+            };
+
+            QueryError::ParseFailure {
+                message: error.variant.message().to_string(),
+                input: input.to_owned(),
+                span: (start, end - start).into(),
+            }
+        })?,
+        input,
+    )?;
 
     criteria.input = Some(input.to_owned());
 
@@ -4,6 +4,8 @@ use pest::{
     Parser,
 };
 use pest_derive::Parser;
+use std::mem;
+use std::ops::Range;
 
 #[derive(Parser)]
 #[grammar = "mql.pest"]
@@ -16,7 +18,7 @@ pub enum LogicalOperator {
     Or, // ||
 }
 
-#[derive(Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub enum ComparisonOperator {
     #[default]
     Equal, // =
@@ -31,6 +33,7 @@ pub enum AstNode {
         field: String,
         op: ComparisonOperator,
         value: Vec<String>,
+        span: Range<usize>,
     },
     Op {
         op: LogicalOperator,
@@ -38,11 +41,15 @@ pub enum AstNode {
     Group {
         nodes: Vec<AstNode>,
     },
+    NotGroup {
+        nodes: Vec<AstNode>,
+    },
 }
 
 fn parse_ast_node(pair: Pair<Rule>) -> Result<Option<AstNode>, Box<Error<Rule>>> {
     Ok(match pair.as_rule() {
         Rule::comparison => {
+            let span = pair.as_span();
             let mut inner = pair.into_inner();
             let field = inner.next().expect("Missing field name.");
             let op = inner.next().expect("Missing comparison operator.");
@@ -69,11 +76,22 @@ fn parse_ast_node(pair: Pair<Rule>) -> Result<Option<AstNode>, Box<Error<Rule>>>
                         .collect(),
                     _ => unreachable!(),
                 },
+                span: span.start()..span.end(),
             })
         }
         Rule::expr_group => Some(AstNode::Group {
             nodes: parse_ast(pair.into_inner())?,
         }),
+        Rule::not_group => {
+            let group = pair
+                .into_inner()
+                .find(|inner| matches!(inner.as_rule(), Rule::expr_group))
+                .expect("Missing negated group.");
+
+            Some(AstNode::NotGroup {
+                nodes: parse_ast(group.into_inner())?,
+            })
+        }
         Rule::and => Some(AstNode::Op {
             op: LogicalOperator::And,
         }),
@@ -85,6 +103,45 @@ fn parse_ast_node(pair: Pair<Rule>) -> Result<Option<AstNode>, Box<Error<Rule>>>
     })
 }
 
+fn is_op(node: &AstNode, op: LogicalOperator) -> bool {
+    matches!(node, AstNode::Op { op: node_op } if node_op == &op)
+}
+
+fn into_and_group(mut nodes: Vec<AstNode>) -> AstNode {
+    if nodes.len() == 1 {
+        nodes.remove(0)
+    } else {
+        AstNode::Group { nodes }
+    }
+}
+
+/// AND binds tighter than OR, so when both operators are used at the same level,
+/// each run of AND comparisons is wrapped in a group, and the groups are joined with OR.
+/// For example, `a && b || c` is parsed as `(a && b) || c`.
+fn apply_precedence(nodes: Vec<AstNode>) -> Vec<AstNode> {
+    let has_and = nodes.iter().any(|node| is_op(node, LogicalOperator::And));
+    let has_or = nodes.iter().any(|node| is_op(node, LogicalOperator::Or));
+
+    if !has_and || !has_or {
+        return nodes;
+    }
+
+    let mut ast = vec![];
+    let mut operands = vec![];
+
+    for node in nodes {
+        if is_op(&node, LogicalOperator::Or) {
+            ast.push(into_and_group(mem::take(&mut operands)));
+            ast.push(node);
+        } else {
+            operands.push(node);
+        }
+    }
+
+    ast.push(into_and_group(operands));
+    ast
+}
+
 fn parse_ast(pairs: Pairs<Rule>) -> Result<Vec<AstNode>, Box<Error<Rule>>> {
     let mut ast = vec![];
 
@@ -94,7 +151,7 @@ fn parse_ast(pairs: Pairs<Rule>) -> Result<Vec<AstNode>, Box<Error<Rule>>> {
         }
     }
 
-    Ok(ast)
+    Ok(apply_precedence(ast))
 }
 
 pub fn parse_query(input: &str) -> Result<Vec<AstNode>, Box<Error<Rule>>> {
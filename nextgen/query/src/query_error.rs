@@ -1,4 +1,4 @@
-use miette::Diagnostic;
+use miette::{Diagnostic, SourceSpan};
 use moon_common::{Style, Stylize};
 use starbase_utils::glob::GlobError;
 use thiserror::Error;
@@ -9,25 +9,74 @@ pub enum QueryError {
     #[error("Encountered an empty query. Did you forget to add criteria?")]
     EmptyInput,
 
-    #[diagnostic(code(query::operator::logical_mismatch))]
-    #[error("Cannot use both {} (&&) and {} (||) logical operators in the same group. Wrap in parentheses to create sub-groups.", "AND".style(Style::Symbol), "OR".style(Style::Symbol))]
-    LogicalOperatorMismatch,
-
     #[diagnostic(code(query::unknown_field))]
-    #[error("Unknown query field {}.", .0.style(Style::Id))]
-    UnknownField(String),
+    #[error("Unknown query field {}.", .field.style(Style::Id))]
+    UnknownField {
+        field: String,
+
+        #[source_code]
+        input: String,
+
+        #[label("unknown field")]
+        span: SourceSpan,
+    },
 
     #[diagnostic(code(query::unknown_field_value))]
-    #[error("Unknown query value {} for field {}.", .1.style(Style::Symbol), .0.style(Style::Id))]
-    UnknownFieldValue(String, String),
+    #[error("Unknown query value {} for field {}.", .value.style(Style::Symbol), .field.style(Style::Id))]
+    UnknownFieldValue {
+        field: String,
+        value: String,
+
+        #[source_code]
+        input: String,
+
+        #[label("unknown value")]
+        span: SourceSpan,
+    },
+
+    #[diagnostic(code(query::unknown_task_option))]
+    #[error("Unknown task option {} for field {}.", .0.style(Style::Symbol), "taskOption".style(Style::Id))]
+    UnknownTaskOption(String),
 
     #[diagnostic(code(query::operator::unsupported))]
-    #[error("Like operators (~ and !~) are not supported for field {}.", .0.style(Style::Id))]
-    UnsupportedLikeOperator(String),
+    #[error("Like operators (~ and !~) are not supported for field {}.", .field.style(Style::Id))]
+    UnsupportedLikeOperator {
+        field: String,
+
+        #[source_code]
+        input: String,
+
+        #[label("unsupported operator")]
+        span: SourceSpan,
+    },
+
+    #[diagnostic(code(query::missing_touched_files))]
+    #[error("The {} field requires touched files, which are not available in this context.", "affected".style(Style::Id))]
+    MissingTouchedFiles,
+
+    #[diagnostic(code(query::unsupported_depth))]
+    #[error("Depth modifiers (:n and :*) are not supported for field {}.", .field.style(Style::Id))]
+    UnsupportedDepthModifier {
+        field: String,
+
+        #[source_code]
+        input: String,
+
+        #[label("unsupported depth")]
+        span: SourceSpan,
+    },
 
     #[diagnostic(code(query::parse::failed))]
-    #[error("Failed to parse query:\n\n{}", .0.style(Style::MutedLight))]
-    ParseFailure(String),
+    #[error("Failed to parse query, {}.", .message)]
+    ParseFailure {
+        message: String,
+
+        #[source_code]
+        input: String,
+
+        #[label("here")]
+        span: SourceSpan,
+    },
 
     #[diagnostic(transparent)]
     #[error(transparent)]
@@ -1,5 +1,7 @@
 use moon_config::{LanguageType, PlatformType, ProjectType, TaskType};
-use moon_query::{build_query, ComparisonOperator, Condition, Criteria, Field, LogicalOperator};
+use moon_query::{
+    build_query, ComparisonOperator, Condition, Criteria, Field, LogicalOperator, QueryError,
+};
 use starbase_utils::string_vec;
 
 mod mql_build {
@@ -12,7 +14,7 @@ mod mql_build {
     }
 
     #[test]
-    #[should_panic(expected = "UnknownField(\"key\")")]
+    #[should_panic(expected = "UnknownField { field: \"key\"")]
     fn errors_unknown_field() {
         build_query("key=value").unwrap();
     }
@@ -60,9 +62,146 @@ mod mql_build {
     }
 
     #[test]
-    #[should_panic(expected = "LogicalOperatorMismatch")]
-    fn errors_when_mixing_ops() {
-        build_query("language=javascript || language!=typescript && language=ruby").unwrap();
+    fn and_binds_tighter_than_or() {
+        assert_eq!(
+            build_query("language=javascript || language!=typescript && language=ruby").unwrap(),
+            Criteria {
+                op: LogicalOperator::Or,
+                conditions: vec![
+                    Condition::Field {
+                        field: Field::Language(vec![LanguageType::JavaScript]),
+                        op: ComparisonOperator::Equal,
+                    },
+                    Condition::Criteria {
+                        criteria: Criteria {
+                            op: LogicalOperator::And,
+                            conditions: vec![
+                                Condition::Field {
+                                    field: Field::Language(vec![LanguageType::TypeScript]),
+                                    op: ComparisonOperator::NotEqual,
+                                },
+                                Condition::Field {
+                                    field: Field::Language(vec![LanguageType::Ruby]),
+                                    op: ComparisonOperator::Equal,
+                                },
+                            ],
+                            input: None,
+                        }
+                    }
+                ],
+                input: Some("language=javascript || language!=typescript && language=ruby".into())
+            }
+        );
+    }
+
+    #[test]
+    fn handles_not_group() {
+        assert_eq!(
+            build_query("task=build && !(tag=legacy || tag=deprecated)").unwrap(),
+            Criteria {
+                op: LogicalOperator::And,
+                conditions: vec![
+                    Condition::Field {
+                        field: Field::Task(string_vec!["build"]),
+                        op: ComparisonOperator::Equal,
+                    },
+                    Condition::Not {
+                        criteria: Criteria {
+                            op: LogicalOperator::Or,
+                            conditions: vec![
+                                Condition::Field {
+                                    field: Field::Tag(string_vec!["legacy"]),
+                                    op: ComparisonOperator::Equal,
+                                },
+                                Condition::Field {
+                                    field: Field::Tag(string_vec!["deprecated"]),
+                                    op: ComparisonOperator::Equal,
+                                },
+                            ],
+                            input: None,
+                        }
+                    }
+                ],
+                input: Some("task=build && !(tag=legacy || tag=deprecated)".into())
+            }
+        );
+    }
+
+    #[test]
+    fn points_to_offending_token_on_failure() {
+        let error = build_query("language=javascript & task=build").unwrap_err();
+
+        match error {
+            QueryError::ParseFailure { span, .. } => {
+                assert_eq!(span.offset(), 20);
+                assert_eq!(span.len(), 1);
+            }
+            _ => panic!("Expected a parse failure."),
+        };
+    }
+
+    #[test]
+    fn errors_point_to_the_comparison() {
+        let error = build_query("project=a && (unknown=b || tag=c)").unwrap_err();
+
+        match error {
+            QueryError::UnknownField { field, span, .. } => {
+                assert_eq!(field, "unknown");
+                assert_eq!(span.offset(), 14);
+                assert_eq!(span.len(), 9);
+            }
+            _ => panic!("Expected an unknown field error."),
+        };
+
+        let error = build_query("project=a || language~java*").unwrap_err();
+
+        match error {
+            QueryError::UnsupportedLikeOperator { span, .. } => {
+                assert_eq!(span.offset(), 13);
+                assert_eq!(span.len(), 14);
+            }
+            _ => panic!("Expected an unsupported operator error."),
+        };
+    }
+
+    mod metadata {
+        use super::*;
+
+        #[test]
+        fn owner_and_maintainers() {
+            assert_eq!(
+                build_query("owner=infra || maintainers~*@example.com").unwrap(),
+                Criteria {
+                    op: LogicalOperator::Or,
+                    conditions: vec![
+                        Condition::Field {
+                            field: Field::Owner(string_vec!["infra"]),
+                            op: ComparisonOperator::Equal,
+                        },
+                        Condition::Field {
+                            field: Field::Maintainers(string_vec!["*@example.com"]),
+                            op: ComparisonOperator::Like,
+                        }
+                    ],
+                    input: Some("owner=infra || maintainers~*@example.com".into())
+                }
+            );
+        }
+
+        #[test]
+        fn channel() {
+            assert_eq!(
+                build_query("channel=#frontend").unwrap(),
+                Criteria {
+                    op: LogicalOperator::And,
+                    conditions: vec![Condition::Field {
+                        field: Field::Channel(string_vec!["#frontend"]),
+                        op: ComparisonOperator::Equal,
+                    }],
+                    input: Some("channel=#frontend".into())
+                }
+            );
+        }
     }
 
     mod nested {
@@ -246,6 +385,150 @@ mod mql_build {
         // }
     }
 
+    mod affected {
+        use super::*;
+
+        #[test]
+        fn true_value() {
+            assert_eq!(
+                build_query("affected=true").unwrap(),
+                Criteria {
+                    op: LogicalOperator::And,
+                    conditions: vec![Condition::Field {
+                        field: Field::Affected {
+                            affected: true,
+                            depth: Some(0)
+                        },
+                        op: ComparisonOperator::Equal,
+                    }],
+                    input: Some("affected=true".into())
+                }
+            );
+        }
+
+        #[test]
+        fn with_depth() {
+            assert_eq!(
+                build_query("affected:*=false").unwrap(),
+                Criteria {
+                    op: LogicalOperator::And,
+                    conditions: vec![Condition::Field {
+                        field: Field::Affected {
+                            affected: false,
+                            depth: None
+                        },
+                        op: ComparisonOperator::Equal,
+                    }],
+                    input: Some("affected:*=false".into())
+                }
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "UnknownFieldValue { field: \"affected\", value: \"yes\"")]
+        fn invalid_value() {
+            build_query("affected=yes").unwrap();
+        }
+
+        #[test]
+        #[should_panic(expected = "UnsupportedLikeOperator { field: \"affected\"")]
+        fn errors_for_like() {
+            build_query("affected~true").unwrap();
+        }
+    }
+
+    mod dependency_of {
+        use super::*;
+
+        #[test]
+        fn direct_by_default() {
+            assert_eq!(
+                build_query("dependencyOf=[app,web]").unwrap(),
+                Criteria {
+                    op: LogicalOperator::And,
+                    conditions: vec![Condition::Field {
+                        field: Field::DependencyOf {
+                            ids: string_vec!["app", "web"],
+                            depth: Some(1)
+                        },
+                        op: ComparisonOperator::Equal,
+                    }],
+                    input: Some("dependencyOf=[app,web]".into())
+                }
+            );
+        }
+
+        #[test]
+        fn with_depth() {
+            assert_eq!(
+                build_query("dependencyOf:3~app-*").unwrap(),
+                Criteria {
+                    op: LogicalOperator::And,
+                    conditions: vec![Condition::Field {
+                        field: Field::DependencyOf {
+                            ids: string_vec!["app-*"],
+                            depth: Some(3)
+                        },
+                        op: ComparisonOperator::Like,
+                    }],
+                    input: Some("dependencyOf:3~app-*".into())
+                }
+            );
+        }
+    }
+
+    mod dependent_of {
+        use super::*;
+
+        #[test]
+        fn transitive() {
+            assert_eq!(
+                build_query("dependentOf:*=shared-utils && taskType=test").unwrap(),
+                Criteria {
+                    op: LogicalOperator::And,
+                    conditions: vec![
+                        Condition::Field {
+                            field: Field::DependentOf {
+                                ids: string_vec!["shared-utils"],
+                                depth: None
+                            },
+                            op: ComparisonOperator::Equal,
+                        },
+                        Condition::Field {
+                            field: Field::TaskType(vec![TaskType::Test]),
+                            op: ComparisonOperator::Equal,
+                        }
+                    ],
+                    input: Some("dependentOf:*=shared-utils && taskType=test".into())
+                }
+            );
+        }
+
+        #[test]
+        fn supports_depends_on() {
+            assert_eq!(
+                build_query("dependsOn!=ui").unwrap(),
+                Criteria {
+                    op: LogicalOperator::And,
+                    conditions: vec![Condition::Field {
+                        field: Field::DependentOf {
+                            ids: string_vec!["ui"],
+                            depth: Some(1)
+                        },
+                        op: ComparisonOperator::NotEqual,
+                    }],
+                    input: Some("dependsOn!=ui".into())
+                }
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "UnsupportedDepthModifier { field: \"project\"")]
+        fn errors_for_depth_on_other_fields() {
+            build_query("project:2=foo").unwrap();
+        }
+    }
+
     mod language {
         use super::*;
 
@@ -280,13 +563,13 @@ mod mql_build {
         }
 
         #[test]
-        #[should_panic(expected = "UnsupportedLikeOperator(\"language\")")]
+        #[should_panic(expected = "UnsupportedLikeOperator { field: \"language\"")]
         fn errors_for_like() {
             build_query("language~javascript").unwrap();
         }
 
         #[test]
-        #[should_panic(expected = "UnsupportedLikeOperator(\"language\")")]
+        #[should_panic(expected = "UnsupportedLikeOperator { field: \"language\"")]
         fn errors_for_not_like() {
             build_query("language!~javascript").unwrap();
         }
@@ -443,19 +726,19 @@ mod mql_build {
         }
 
         #[test]
-        #[should_panic(expected = "UnknownFieldValue(\"projectType\", \"app\")")]
+        #[should_panic(expected = "UnknownFieldValue { field: \"projectType\", value: \"app\"")]
         fn invalid_value() {
             build_query("projectType=app").unwrap();
         }
 
         #[test]
-        #[should_panic(expected = "UnsupportedLikeOperator(\"projectType\")")]
+        #[should_panic(expected = "UnsupportedLikeOperator { field: \"projectType\"")]
         fn errors_for_like() {
             build_query("projectType~library").unwrap();
         }
 
         #[test]
-        #[should_panic(expected = "UnsupportedLikeOperator(\"projectType\")")]
+        #[should_panic(expected = "UnsupportedLikeOperator { field: \"projectType\"")]
         fn errors_for_not_like() {
             build_query("projectType!~tool").unwrap();
         }
@@ -544,6 +827,98 @@ mod mql_build {
         }
     }
 
+    mod task_fields {
+        use super::*;
+
+        #[test]
+        fn command_like() {
+            assert_eq!(
+                build_query("taskCommand~{jest,vitest}").unwrap(),
+                Criteria {
+                    op: LogicalOperator::And,
+                    conditions: vec![Condition::Field {
+                        field: Field::TaskCommand(string_vec!["{jest,vitest}"]),
+                        op: ComparisonOperator::Like,
+                    }],
+                    input: Some("taskCommand~{jest,vitest}".into())
+                }
+            );
+        }
+
+        #[test]
+        fn dep_target() {
+            assert_eq!(
+                build_query("taskDep=[^:build, app:build]").unwrap(),
+                Criteria {
+                    op: LogicalOperator::And,
+                    conditions: vec![Condition::Field {
+                        field: Field::TaskDep(string_vec!["^:build", "app:build"]),
+                        op: ComparisonOperator::Equal,
+                    }],
+                    input: Some("taskDep=[^:build, app:build]".into())
+                }
+            );
+        }
+
+        #[test]
+        fn env_input_and_output() {
+            assert_eq!(
+                build_query("taskEnv=NODE_ENV || taskInput=package.json || taskOutput!~dist/**")
+                    .unwrap(),
+                Criteria {
+                    op: LogicalOperator::Or,
+                    conditions: vec![
+                        Condition::Field {
+                            field: Field::TaskEnv(string_vec!["NODE_ENV"]),
+                            op: ComparisonOperator::Equal,
+                        },
+                        Condition::Field {
+                            field: Field::TaskInput(string_vec!["package.json"]),
+                            op: ComparisonOperator::Equal,
+                        },
+                        Condition::Field {
+                            field: Field::TaskOutput(string_vec!["dist/**"]),
+                            op: ComparisonOperator::NotLike,
+                        }
+                    ],
+                    input: Some(
+                        "taskEnv=NODE_ENV || taskInput=package.json || taskOutput!~dist/**".into()
+                    )
+                }
+            );
+        }
+
+        #[test]
+        fn option() {
+            assert_eq!(
+                build_query("taskOption.persistent=true").unwrap(),
+                Criteria {
+                    op: LogicalOperator::And,
+                    conditions: vec![Condition::Field {
+                        field: Field::TaskOption {
+                            option: "persistent".into(),
+                            values: string_vec!["true"]
+                        },
+                        op: ComparisonOperator::Equal,
+                    }],
+                    input: Some("taskOption.persistent=true".into())
+                }
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "UnknownField { field: \"taskOption\"")]
+        fn errors_for_option_without_name() {
+            build_query("taskOption=cache").unwrap();
+        }
+
+        #[test]
+        #[should_panic(expected = "UnknownField { field: \"project.id\"")]
+        fn errors_for_unknown_nested_field() {
+            build_query("project.id=foo").unwrap();
+        }
+    }
+
     mod task_platform {
         use super::*;
 
@@ -578,19 +953,19 @@ mod mql_build {
         }
 
         #[test]
-        #[should_panic(expected = "UnknownFieldValue(\"taskPlatform\", \"kotlin\")")]
+        #[should_panic(expected = "UnknownFieldValue { field: \"taskPlatform\", value: \"kotlin\"")]
         fn invalid_value() {
             build_query("taskPlatform=kotlin").unwrap();
         }
 
         #[test]
-        #[should_panic(expected = "UnsupportedLikeOperator(\"taskPlatform\")")]
+        #[should_panic(expected = "UnsupportedLikeOperator { field: \"taskPlatform\"")]
         fn errors_for_like() {
             build_query("taskPlatform~node").unwrap();
         }
 
         #[test]
-        #[should_panic(expected = "UnsupportedLikeOperator(\"taskPlatform\")")]
+        #[should_panic(expected = "UnsupportedLikeOperator { field: \"taskPlatform\"")]
         fn errors_for_not_like() {
             build_query("taskPlatform!~node").unwrap();
         }
@@ -615,21 +990,86 @@ mod mql_build {
         }
 
         #[test]
-        #[should_panic(expected = "UnknownFieldValue(\"taskType\", \"kotlin\")")]
+        #[should_panic(expected = "UnknownFieldValue { field: \"taskType\", value: \"kotlin\"")]
         fn invalid_value() {
             build_query("taskType=kotlin").unwrap();
         }
 
         #[test]
-        #[should_panic(expected = "UnsupportedLikeOperator(\"taskType\")")]
+        #[should_panic(expected = "UnsupportedLikeOperator { field: \"taskType\"")]
         fn errors_for_like() {
             build_query("taskType~node").unwrap();
         }
 
         #[test]
-        #[should_panic(expected = "UnsupportedLikeOperator(\"taskType\")")]
+        #[should_panic(expected = "UnsupportedLikeOperator { field: \"taskType\"")]
         fn errors_for_not_like() {
             build_query("taskType!~node").unwrap();
         }
     }
 }
+
+mod condition_matches_list {
+    use super::*;
+
+    fn matches_list(query: &str, values: &[&str]) -> bool {
+        let criteria = build_query(query).unwrap();
+        let condition = &criteria.conditions[0];
+
+        let Condition::Field {
+            field: Field::TaskDep(haystack) | Field::TaskInput(haystack),
+            ..
+        } = condition else {
+            unreachable!();
+        };
+
+        condition
+            .matches_list(
+                haystack,
+                &values.iter().map(|v| v.to_string()).collect::<Vec<_>>(),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn equal_matches_any_value() {
+        assert!(matches_list("taskDep=b:lint", &["a:build", "b:lint"]));
+        assert!(!matches_list("taskDep=b:lint", &["a:build"]));
+        assert!(!matches_list("taskDep=b:lint", &[]));
+    }
+
+    #[test]
+    fn not_equal_matches_no_values() {
+        assert!(!matches_list("taskDep!=b:lint", &["a:build", "b:lint"]));
+        assert!(!matches_list(
+            "taskDep!=[a:build,c:test]",
+            &["a:build", "b:lint"]
+        ));
+        assert!(matches_list("taskDep!=b:lint", &["a:build", "c:test"]));
+        assert!(matches_list("taskDep!=b:lint", &[]));
+    }
+
+    #[test]
+    fn like_matches_any_value() {
+        assert!(matches_list(
+            "taskInput~src/**",
+            &["package.json", "src/index.ts"]
+        ));
+        assert!(!matches_list(
+            "taskInput~tests/**",
+            &["package.json", "src/index.ts"]
+        ));
+    }
+
+    #[test]
+    fn not_like_matches_no_values() {
+        assert!(!matches_list(
+            "taskInput!~src/**",
+            &["package.json", "src/index.ts"]
+        ));
+        assert!(matches_list(
+            "taskInput!~tests/**",
+            &["package.json", "src/index.ts"]
+        ));
+    }
+}
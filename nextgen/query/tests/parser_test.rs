@@ -41,6 +41,7 @@ mod mql_parse {
                 field: "key".into(),
                 op: ComparisonOperator::Equal,
                 value: vec!["value".into()],
+                span: 0..9,
             }],
         );
     }
@@ -53,6 +54,7 @@ mod mql_parse {
                 field: "key".into(),
                 op: ComparisonOperator::Equal,
                 value: vec!["v1".into(), "v2".into(), "v3".into()],
+                span: 0..16,
             }],
         );
     }
@@ -65,6 +67,7 @@ mod mql_parse {
                 field: "key".into(),
                 op: ComparisonOperator::NotEqual,
                 value: vec!["value".into()],
+                span: 0..10,
             }],
         );
     }
@@ -77,6 +80,7 @@ mod mql_parse {
                 field: "key".into(),
                 op: ComparisonOperator::NotEqual,
                 value: vec!["v1".into(), "v2".into(), "v3".into()],
+                span: 0..15,
             }],
         );
     }
@@ -89,6 +93,7 @@ mod mql_parse {
                 field: "key".into(),
                 op: ComparisonOperator::Like,
                 value: vec!["value".into()],
+                span: 0..9,
             }],
         );
     }
@@ -101,6 +106,7 @@ mod mql_parse {
                 field: "key".into(),
                 op: ComparisonOperator::NotLike,
                 value: vec!["value".into()],
+                span: 0..10,
             }],
         );
     }
@@ -114,6 +120,7 @@ mod mql_parse {
                     field: "k1".into(),
                     op: ComparisonOperator::Equal,
                     value: vec!["v1".into()],
+                    span: 0..5,
                 },
                 AstNode::Op {
                     op: LogicalOperator::And,
@@ -122,6 +129,7 @@ mod mql_parse {
                     field: "k2".into(),
                     op: ComparisonOperator::NotEqual,
                     value: vec!["v2".into()],
+                    span: 9..15,
                 },
                 AstNode::Op {
                     op: LogicalOperator::And,
@@ -130,6 +138,7 @@ mod mql_parse {
                     field: "k3".into(),
                     op: ComparisonOperator::Equal,
                     value: vec!["1".into(), "2".into(), "3".into()],
+                    span: 20..30,
                 }
             ],
         );
@@ -144,6 +153,7 @@ mod mql_parse {
                     field: "k1".into(),
                     op: ComparisonOperator::Equal,
                     value: vec!["v1".into()],
+                    span: 0..5,
                 },
                 AstNode::Op {
                     op: LogicalOperator::Or,
@@ -152,6 +162,7 @@ mod mql_parse {
                     field: "k2".into(),
                     op: ComparisonOperator::NotEqual,
                     value: vec!["v2".into()],
+                    span: 9..15,
                 },
                 AstNode::Op {
                     op: LogicalOperator::Or,
@@ -160,6 +171,7 @@ mod mql_parse {
                     field: "k3".into(),
                     op: ComparisonOperator::Equal,
                     value: vec!["1".into(), "2".into(), "3".into()],
+                    span: 19..29,
                 }
             ],
         );
@@ -173,6 +185,7 @@ mod mql_parse {
                 field: "key".into(),
                 op: ComparisonOperator::Equal,
                 value: vec!["value".into()],
+                span: 0..12,
             }],
         );
     }
@@ -186,6 +199,7 @@ mod mql_parse {
                     field: "k1".into(),
                     op: ComparisonOperator::Equal,
                     value: vec!["v1".into()],
+                    span: 0..5,
                 },
                 AstNode::Op {
                     op: LogicalOperator::And,
@@ -194,6 +208,7 @@ mod mql_parse {
                     field: "k2".into(),
                     op: ComparisonOperator::NotEqual,
                     value: vec!["v2".into()],
+                    span: 13..19,
                 }
             ],
         );
@@ -208,6 +223,7 @@ mod mql_parse {
                     field: "k1".into(),
                     op: ComparisonOperator::Equal,
                     value: vec!["v1".into()],
+                    span: 0..5,
                 },
                 AstNode::Op {
                     op: LogicalOperator::And,
@@ -218,6 +234,7 @@ mod mql_parse {
                             field: "k2".into(),
                             op: ComparisonOperator::NotEqual,
                             value: vec!["v2".into()],
+                            span: 10..18,
                         },
                         AstNode::Op {
                             op: LogicalOperator::Or,
@@ -226,6 +243,7 @@ mod mql_parse {
                             field: "k3".into(),
                             op: ComparisonOperator::Equal,
                             value: vec!["v3".into()],
+                            span: 22..31,
                         }
                     ]
                 }
@@ -242,6 +260,7 @@ mod mql_parse {
                     field: "k1".into(),
                     op: ComparisonOperator::Equal,
                     value: vec!["v1".into()],
+                    span: 0..5,
                 },
                 AstNode::Op {
                     op: LogicalOperator::And,
@@ -252,6 +271,7 @@ mod mql_parse {
                             field: "k2".into(),
                             op: ComparisonOperator::NotEqual,
                             value: vec!["v2".into()],
+                            span: 10..16,
                         },
                         AstNode::Op {
                             op: LogicalOperator::Or,
@@ -260,6 +280,7 @@ mod mql_parse {
                             field: "k3".into(),
                             op: ComparisonOperator::Equal,
                             value: vec!["v3".into()],
+                            span: 20..25,
                         }
                     ]
                 },
@@ -272,6 +293,7 @@ mod mql_parse {
                             field: "k4".into(),
                             op: ComparisonOperator::Equal,
                             value: vec!["v4".into()],
+                            span: 32..37,
                         },
                         AstNode::Op {
                             op: LogicalOperator::Or,
@@ -280,6 +302,7 @@ mod mql_parse {
                             field: "k5".into(),
                             op: ComparisonOperator::NotEqual,
                             value: vec!["v5".into(), "v15".into()],
+                            span: 41..53,
                         }
                     ]
                 },
@@ -296,6 +319,7 @@ mod mql_parse {
                     field: "k1".into(),
                     op: ComparisonOperator::Equal,
                     value: vec!["v1".into()],
+                    span: 0..5,
                 },
                 AstNode::Op {
                     op: LogicalOperator::And,
@@ -306,6 +330,7 @@ mod mql_parse {
                             field: "k2".into(),
                             op: ComparisonOperator::NotEqual,
                             value: vec!["v2".into()],
+                            span: 10..16,
                         },
                         AstNode::Op {
                             op: LogicalOperator::Or,
@@ -314,6 +339,7 @@ mod mql_parse {
                             field: "k3".into(),
                             op: ComparisonOperator::Equal,
                             value: vec!["v3".into()],
+                            span: 20..25,
                         },
                         AstNode::Op {
                             op: LogicalOperator::Or,
@@ -324,6 +350,7 @@ mod mql_parse {
                                     field: "k4".into(),
                                     op: ComparisonOperator::Equal,
                                     value: vec!["v4".into()],
+                                    span: 30..35,
                                 },
                                 AstNode::Op {
                                     op: LogicalOperator::And,
@@ -332,6 +359,7 @@ mod mql_parse {
                                     field: "k5".into(),
                                     op: ComparisonOperator::NotEqual,
                                     value: vec!["v5".into(), "v15".into()],
+                                    span: 40..52,
                                 }
                             ]
                         },
@@ -349,6 +377,7 @@ mod mql_parse {
                 field: "key".into(),
                 op: ComparisonOperator::Like,
                 value: vec!["value{foo,bar}".into()],
+                span: 0..18,
             }],
         );
         assert_eq!(
@@ -357,6 +386,7 @@ mod mql_parse {
                 field: "key".into(),
                 op: ComparisonOperator::NotLike,
                 value: vec!["value[a-z]?".into()],
+                span: 0..18,
             }],
         );
         assert_eq!(
@@ -365,6 +395,7 @@ mod mql_parse {
                 field: "key".into(),
                 op: ComparisonOperator::Like,
                 value: vec!["value.*".into()],
+                span: 0..11,
             }],
         );
         assert_eq!(
@@ -373,7 +404,135 @@ mod mql_parse {
                 field: "key".into(),
                 op: ComparisonOperator::NotLike,
                 value: vec!["value/**/*".into()],
+                span: 0..15,
             }],
         );
     }
+
+    #[test]
+    fn key_depth_modifier() {
+        assert_eq!(
+            parse_query("key:2=value").unwrap(),
+            vec![AstNode::Comparison {
+                field: "key:2".into(),
+                op: ComparisonOperator::Equal,
+                value: vec!["value".into()],
+                span: 0..11,
+            }],
+        );
+        assert_eq!(
+            parse_query("key:*!=value").unwrap(),
+            vec![AstNode::Comparison {
+                field: "key:*".into(),
+                op: ComparisonOperator::NotEqual,
+                value: vec!["value".into()],
+                span: 0..12,
+            }],
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_invalid_depth_modifier() {
+        parse_query("key:foo=value").unwrap();
+    }
+
+    #[test]
+    fn key_with_property() {
+        assert_eq!(
+            parse_query("key.prop=value.ext").unwrap(),
+            vec![AstNode::Comparison {
+                field: "key.prop".into(),
+                op: ComparisonOperator::Equal,
+                value: vec!["value.ext".into()],
+                span: 0..18,
+            }],
+        );
+    }
+
+    #[test]
+    fn value_with_target() {
+        assert_eq!(
+            parse_query("key=[^:build, app:test]").unwrap(),
+            vec![AstNode::Comparison {
+                field: "key".into(),
+                op: ComparisonOperator::Equal,
+                value: vec!["^:build".into(), "app:test".into()],
+                span: 0..23,
+            }],
+        );
+    }
+
+    #[test]
+    fn groups_and_before_or() {
+        assert_eq!(
+            parse_query("k1=v1 && k2=v2 || k3=v3").unwrap(),
+            vec![
+                AstNode::Group {
+                    nodes: vec![
+                        AstNode::Comparison {
+                            field: "k1".into(),
+                            op: ComparisonOperator::Equal,
+                            value: vec!["v1".into()],
+                            span: 0..5,
+                        },
+                        AstNode::Op {
+                            op: LogicalOperator::And,
+                        },
+                        AstNode::Comparison {
+                            field: "k2".into(),
+                            op: ComparisonOperator::Equal,
+                            value: vec!["v2".into()],
+                            span: 9..14,
+                        },
+                    ]
+                },
+                AstNode::Op {
+                    op: LogicalOperator::Or,
+                },
+                AstNode::Comparison {
+                    field: "k3".into(),
+                    op: ComparisonOperator::Equal,
+                    value: vec!["v3".into()],
+                    span: 18..23,
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn negates_groups() {
+        let expected = |span| {
+            vec![AstNode::NotGroup {
+                nodes: vec![AstNode::Comparison {
+                    field: "key".into(),
+                    op: ComparisonOperator::Equal,
+                    value: vec!["value".into()],
+                    span,
+                }],
+            }]
+        };
+
+        assert_eq!(parse_query("!(key=value)").unwrap(), expected(2..11));
+        assert_eq!(parse_query("NOT (key=value)").unwrap(), expected(5..14));
+    }
+
+    #[test]
+    fn doesnt_negate_keys_starting_with_not() {
+        assert_eq!(
+            parse_query("NOTE=value").unwrap(),
+            vec![AstNode::Comparison {
+                field: "NOTE".into(),
+                op: ComparisonOperator::Equal,
+                value: vec!["value".into()],
+                span: 0..10,
+            }],
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_negating_comparisons() {
+        parse_query("!key=value").unwrap();
+    }
 }
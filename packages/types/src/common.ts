@@ -1,4 +1,4 @@
-export type Platform = 'deno' | 'node' | 'system' | 'unknown';
+export type Platform = 'bun' | 'deno' | 'go' | 'node' | 'php' | 'python' | 'ruby' | 'system' | 'unknown';
 
 export type Nullable<T> = { [K in keyof T]: T[K] | null };
 
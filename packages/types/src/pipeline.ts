@@ -8,7 +8,8 @@ export type ActionStatus =
 	| 'invalid'
 	| 'passed'
 	| 'running'
-	| 'skipped';
+	| 'skipped'
+	| 'timed-out';
 
 export interface Attempt {
 	duration: Duration | null;
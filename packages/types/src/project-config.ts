@@ -1,5 +1,5 @@
 import type { Nullable, Platform } from './common';
-import type { NodeConfig, RustConfig, TypeScriptConfig } from './toolchain-config';
+import type { BunConfig, NodeConfig, RustConfig, TypeScriptConfig } from './toolchain-config';
 
 export type DependencyScope = 'development' | 'peer' | 'production';
 
@@ -29,6 +29,7 @@ export interface TaskOptionsConfig {
 	runInCI: boolean | null;
 	runFromWorkspaceRoot: boolean | null;
 	shell: boolean | null;
+	timeout: string | null;
 }
 
 export interface TaskConfig {
@@ -65,6 +66,8 @@ export interface ProjectMetadataConfig {
 	channel: string;
 }
 
+export type ProjectToolchainBunConfig = Nullable<Pick<BunConfig, 'version'>>;
+
 export type ProjectToolchainNodeConfig = Nullable<Pick<NodeConfig, 'version'>>;
 
 export type ProjectToolchainRustConfig = Nullable<Pick<RustConfig, 'version'>>;
@@ -80,6 +83,7 @@ export interface ProjectToolchainTypeScriptConfig
 }
 
 export interface ProjectToolchainConfig {
+	bun: ProjectToolchainBunConfig | null;
 	node: ProjectToolchainNodeConfig | null;
 	rust: ProjectToolchainRustConfig | null;
 	typescript: ProjectToolchainTypeScriptConfig | null;
@@ -31,6 +31,7 @@ export interface TaskOptions {
 	runInCI: boolean;
 	runFromWorkspaceRoot: boolean;
 	shell: boolean;
+	timeout: string | null;
 }
 
 export interface Task {
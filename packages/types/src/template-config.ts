@@ -38,12 +38,20 @@ export type TemplateVariable =
 
 export interface TemplateConfig {
 	description: string;
+	extends: string[];
+	postGenerate: string[];
+	registerProject: boolean;
 	title: string;
 	variables: Record<string, TemplateVariable>;
 }
 
+export type TemplateMergeStrategy = 'three-way';
+
 export interface TemplateFrontmatterConfig {
 	force: boolean | null;
+	for_each: string | null;
+	if: string | null;
+	merge: TemplateMergeStrategy | null;
 	to: string | null;
 	skip: boolean | null;
 }
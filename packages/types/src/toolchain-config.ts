@@ -1,8 +1,17 @@
+export interface BunConfig {
+	inferTasksFromScripts: boolean;
+	version: string | null;
+}
+
 export interface DenoConfig {
 	depsFile: string;
 	lockfile: string;
 }
 
+export interface GoConfig {
+	tidyOnInstall: boolean;
+}
+
 export type NodeVersionFormat =
 	| 'file'
 	| 'link'
@@ -37,6 +46,16 @@ export interface NodeConfig {
 	yarn: YarnConfig | null;
 }
 
+export type PhpConfig = Record<string, never>;
+
+export interface PythonConfig {
+	packageManager: 'pip' | 'poetry' | 'uv';
+	rootVenvOnly: boolean;
+	venvName: string;
+}
+
+export type RubyConfig = Record<string, never>;
+
 export interface RustConfig {
 	bins: string[] | null;
 	syncToolchainConfig: boolean;
@@ -55,8 +74,13 @@ export interface TypeScriptConfig {
 
 export interface ToolchainConfig {
 	extends: string | null;
+	bun: BunConfig | null;
 	deno: DenoConfig | null;
+	go: GoConfig | null;
 	node: NodeConfig | null;
+	php: PhpConfig | null;
+	python: PythonConfig | null;
+	ruby: RubyConfig | null;
 	rust: RustConfig | null;
 	typescript: TypeScriptConfig | null;
 }
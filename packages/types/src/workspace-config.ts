@@ -14,15 +14,45 @@ export interface HasherConfig {
 	warnOnMissingInputs: boolean;
 }
 
+export interface SubscriberConfig {
+	args: string[];
+	command: string;
+	events: string[];
+	timeout: number;
+}
+
 export interface NotifierConfig {
+	subscribers: SubscriberConfig[];
+	webhookEvents: string[];
+	webhookSecret: string | null;
 	webhookUrl: string | null;
 }
 
+export type OutputStoreLayout = 'archive' | 'cas';
+
+export interface OutputStoreConfig {
+	hardlink: boolean;
+	layout: OutputStoreLayout;
+}
+
+export type RemoteCacheProtocol = 'grpc' | 'http';
+
+export interface RemoteCacheConfig {
+	headers: Record<string, string>;
+	instanceName: string | null;
+	protocol: RemoteCacheProtocol;
+	token: string | null;
+	url: string | null;
+	writable: boolean;
+}
+
 export interface RunnerConfig {
 	archivableTargets: string[];
 	cacheLifetime: string;
 	inheritColorsForPipedTasks: boolean;
 	logRunningCommand: boolean;
+	outputStore: OutputStoreConfig;
+	remoteCache: RemoteCacheConfig;
 }
 
 export interface VcsConfig {
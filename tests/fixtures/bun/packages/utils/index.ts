@@ -0,0 +1 @@
+export const isBun = true;
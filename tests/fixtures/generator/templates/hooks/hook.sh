@@ -0,0 +1 @@
+echo "$MOON_TEMPLATE_NAME $MOON_VAR_NAME" >> hook-output.txt
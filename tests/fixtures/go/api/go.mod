@@ -0,0 +1,13 @@
+module example.com/acme/api
+
+go 1.20
+
+require (
+	example.com/acme/shared v0.0.0
+	github.com/google/uuid v1.3.0
+	golang.org/x/text v0.9.0 // indirect
+)
+
+require example.com/acme/legacy v1.0.0
+
+replace example.com/acme/legacy => ../utils
@@ -0,0 +1,5 @@
+module "example.com/acme/tool"
+
+go 1.20
+
+require example.com/acme/api v0.0.0
@@ -0,0 +1,3 @@
+module example.com/acme/utils
+
+go 1.20
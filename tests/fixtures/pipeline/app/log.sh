@@ -0,0 +1,6 @@
+#!/usr/bin/env bash
+
+echo "$1:start" >> ../pipeline.log
+sleep "${2:-0}"
+echo "$1:end" >> ../pipeline.log
+exit "${3:-0}"
@@ -0,0 +1,8 @@
+#!/usr/bin/env bash
+
+echo "$1" >> ../runs.log
+echo "$$" > "../$1.pid"
+
+if [ -n "$2" ]; then
+	exec sleep "$2"
+fi